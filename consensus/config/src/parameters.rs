@@ -45,6 +45,13 @@ pub struct Parameters {
     #[serde(default = "Parameters::default_dag_state_cached_rounds")]
     pub dag_state_cached_rounds: u32,
 
+    /// The number of rounds of blocks to keep in storage below the garbage collection round. Older
+    /// blocks are deleted from storage. They are not needed locally anymore, but keeping them
+    /// for a while allows peers that fell behind to fetch them while catching up.
+    /// Only applies when garbage collection is enabled by the protocol config.
+    #[serde(default = "Parameters::default_dag_storage_retention_rounds")]
+    pub dag_storage_retention_rounds: u32,
+
     // Number of authorities commit syncer fetches in parallel.
     // Both commits in a range and blocks referenced by the commits are fetched per authority.
     #[serde(default = "Parameters::default_commit_sync_parallel_fetches")]
@@ -105,6 +112,10 @@ impl Parameters {
         }
     }
 
+    pub(crate) fn default_dag_storage_retention_rounds() -> u32 {
+        50_000
+    }
+
     pub(crate) fn default_commit_sync_parallel_fetches() -> usize {
         20
     }
@@ -148,6 +159,7 @@ impl Default for Parameters {
             min_round_delay: Parameters::default_min_round_delay(),
             max_forward_time_drift: Parameters::default_max_forward_time_drift(),
             dag_state_cached_rounds: Parameters::default_dag_state_cached_rounds(),
+            dag_storage_retention_rounds: Parameters::default_dag_storage_retention_rounds(),
             max_blocks_per_fetch: Parameters::default_max_blocks_per_fetch(),
             sync_last_proposed_block_timeout: Parameters::default_sync_last_proposed_block_timeout(
             ),
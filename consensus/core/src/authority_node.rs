@@ -28,6 +28,7 @@ use crate::{
         NetworkManager,
     },
     storage::rocksdb_store::RocksDBStore,
+    store_pruner::{StorePruner, StorePrunerHandle},
     subscriber::Subscriber,
     synchronizer::{Synchronizer, SynchronizerHandle},
     transaction::{TransactionClient, TransactionConsumer, TransactionVerifier},
@@ -123,6 +124,7 @@ where
     commit_syncer: CommitSyncer<N::Client>,
     leader_timeout_handle: LeaderTimeoutTaskHandle,
     core_thread_handle: CoreThreadHandle,
+    store_pruner_handle: StorePrunerHandle,
     // Only one of broadcaster and subscriber gets created, depending on
     // if streaming is supported.
     broadcaster: Option<Broadcaster>,
@@ -192,6 +194,11 @@ where
             .unwrap();
         let store = Arc::new(RocksDBStore::new(store_path));
         let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let store_pruner_handle = StorePruner::start(
+            context.clone(),
+            store.clone(),
+            dag_state.read().store_prune_round_receiver(),
+        );
 
         let block_verifier = Arc::new(SignedBlockVerifier::new(
             context.clone(),
@@ -300,6 +307,7 @@ where
             commit_syncer,
             leader_timeout_handle,
             core_thread_handle,
+            store_pruner_handle,
             broadcaster,
             subscriber,
             network_manager,
@@ -319,6 +327,7 @@ where
         // Shutdown Core to stop block productions and broadcast.
         // When using streaming, all subscribers to broadcasted blocks stop after this.
         self.core_thread_handle.stop().await;
+        self.store_pruner_handle.stop().await;
         if let Some(mut broadcaster) = self.broadcaster.take() {
             broadcaster.stop();
         }
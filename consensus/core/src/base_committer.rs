@@ -229,6 +229,11 @@ impl BaseCommitter {
     ) -> bool {
         let mut votes_stake_aggregator = StakeAggregator::<QuorumThreshold>::new();
         for reference in potential_certificate.ancestors() {
+            // Weak links at or below the leader round can not be votes, and may point to
+            // garbage collected blocks.
+            if reference.round <= leader_block.round() {
+                continue;
+            }
             let is_vote = if let Some(is_vote) = all_votes.get(reference) {
                 *is_vote
             } else {
@@ -8,13 +8,14 @@ use std::{
     time::Instant,
 };
 
+use consensus_config::AuthorityIndex;
 use itertools::Itertools as _;
 use mysten_metrics::monitored_scope;
 use parking_lot::RwLock;
 use tracing::{debug, trace, warn};
 
 use crate::{
-    block::{BlockAPI, BlockRef, VerifiedBlock},
+    block::{BlockAPI, BlockDigest, BlockRef, VerifiedBlock, GENESIS_ROUND},
     block_verifier::BlockVerifier,
     context::Context,
     dag_state::DagState,
@@ -114,90 +115,105 @@ impl BlockManager {
                     missing_blocks.extend(ancestors_to_fetch);
                     continue;
                 }
-                TryAcceptResult::Processed => continue,
+                TryAcceptResult::Processed | TryAcceptResult::Skipped => continue,
             };
 
             // If the block is accepted, try to unsuspend its children blocks if any.
-            let unsuspended_blocks = self.try_unsuspend_children_blocks(&block);
+            let unsuspended_blocks = self.try_unsuspend_children_blocks(block.reference());
 
             // Try to verify the block and its children for timestamp, with ancestor blocks.
-            let mut blocks_to_accept: BTreeMap<BlockRef, VerifiedBlock> = BTreeMap::new();
-            let mut blocks_to_reject: BTreeMap<BlockRef, VerifiedBlock> = BTreeMap::new();
-            {
-                'block: for b in iter::once(block).chain(unsuspended_blocks) {
-                    let ancestors = self.dag_state.read().get_blocks(b.ancestors());
-                    assert_eq!(b.ancestors().len(), ancestors.len());
-                    let mut ancestor_blocks = vec![];
-                    'ancestor: for (ancestor_ref, found) in
-                        b.ancestors().iter().zip(ancestors.into_iter())
-                    {
-                        if let Some(found_block) = found {
-                            // This invariant should be guaranteed by DagState.
-                            assert_eq!(ancestor_ref, &found_block.reference());
-                            ancestor_blocks.push(found_block);
-                            continue 'ancestor;
-                        }
-                        // blocks_to_accept have not been added to DagState yet, but they
-                        // can appear in ancestors.
-                        if blocks_to_accept.contains_key(ancestor_ref) {
-                            ancestor_blocks.push(blocks_to_accept[ancestor_ref].clone());
-                            continue 'ancestor;
-                        }
-                        // If an ancestor is already rejected, reject this block as well.
-                        if blocks_to_reject.contains_key(ancestor_ref) {
-                            blocks_to_reject.insert(b.reference(), b);
-                            continue 'block;
-                        }
-                        panic!("Unsuspended block {:?} has a missing ancestor! Ancestor not found in DagState: {:?}", b, ancestor_ref);
-                    }
-                    if let Err(e) = self.block_verifier.check_ancestors(&b, &ancestor_blocks) {
-                        warn!("Block {:?} failed to verify ancestors: {}", b, e);
-                        blocks_to_reject.insert(b.reference(), b);
-                    } else {
-                        blocks_to_accept.insert(b.reference(), b);
-                    }
-                }
-            }
-            for (block_ref, block) in blocks_to_reject {
-                self.context
-                    .metrics
-                    .node_metrics
-                    .invalid_blocks
-                    .with_label_values(&[&block_ref.author.to_string(), "accept_block"])
-                    .inc();
-                warn!("Invalid block {:?} is rejected", block);
-            }
-
-            // TODO: report blocks_to_reject to peers.
-
-            // Insert the accepted blocks into DAG state so future blocks including them as
-            // ancestors do not get suspended.
-            let blocks_to_accept: Vec<_> = blocks_to_accept.into_values().collect();
-            self.dag_state
-                .write()
-                .accept_blocks(blocks_to_accept.clone());
+            let blocks_to_accept =
+                self.verify_and_accept_blocks(iter::once(block).chain(unsuspended_blocks));
 
             accepted_blocks.extend(blocks_to_accept);
         }
 
-        let metrics = &self.context.metrics.node_metrics;
-        metrics
+        self.context
+            .metrics
+            .node_metrics
             .missing_blocks_total
             .inc_by(missing_blocks.len() as u64);
-        metrics
-            .block_manager_suspended_blocks
-            .set(self.suspended_blocks.len() as i64);
-        metrics
-            .block_manager_missing_ancestors
-            .set(self.missing_ancestors.len() as i64);
-        metrics
-            .block_manager_missing_blocks
-            .set(self.missing_blocks.len() as i64);
+        self.update_stats();
 
         // Figure out the new missing blocks
         (accepted_blocks, missing_blocks)
     }
 
+    /// Verifies blocks w.r.t. their ancestors and accepts the valid ones into DagState. Blocks
+    /// must be provided in causal order, with all their ancestors either in DagState, earlier
+    /// among `blocks`, or garbage collected. Returns the accepted blocks.
+    fn verify_and_accept_blocks(
+        &mut self,
+        blocks: impl IntoIterator<Item = VerifiedBlock>,
+    ) -> Vec<VerifiedBlock> {
+        let gc_round = self.dag_state.read().gc_round();
+        let mut blocks_to_accept: BTreeMap<BlockRef, VerifiedBlock> = BTreeMap::new();
+        let mut blocks_to_reject: BTreeMap<BlockRef, VerifiedBlock> = BTreeMap::new();
+        {
+            'block: for b in blocks {
+                let ancestors = self.dag_state.read().get_blocks(b.ancestors());
+                assert_eq!(b.ancestors().len(), ancestors.len());
+                let mut ancestor_blocks = vec![];
+                'ancestor: for (ancestor_ref, found) in
+                    b.ancestors().iter().zip(ancestors.into_iter())
+                {
+                    if let Some(found_block) = found {
+                        // This invariant should be guaranteed by DagState.
+                        assert_eq!(ancestor_ref, &found_block.reference());
+                        ancestor_blocks.push(Some(found_block));
+                        continue 'ancestor;
+                    }
+                    // blocks_to_accept have not been added to DagState yet, but they
+                    // can appear in ancestors.
+                    if blocks_to_accept.contains_key(ancestor_ref) {
+                        ancestor_blocks.push(Some(blocks_to_accept[ancestor_ref].clone()));
+                        continue 'ancestor;
+                    }
+                    // If an ancestor is already rejected, reject this block as well.
+                    if blocks_to_reject.contains_key(ancestor_ref) {
+                        blocks_to_reject.insert(b.reference(), b);
+                        continue 'block;
+                    }
+                    // Garbage collected ancestors are not needed to accept the block.
+                    if ancestor_ref.round <= gc_round {
+                        ancestor_blocks.push(None);
+                        continue 'ancestor;
+                    }
+                    panic!("Unsuspended block {:?} has a missing ancestor! Ancestor not found in DagState: {:?}", b, ancestor_ref);
+                }
+                if let Err(e) = self
+                    .block_verifier
+                    .check_ancestors(&b, &ancestor_blocks, gc_round)
+                {
+                    warn!("Block {:?} failed to verify ancestors: {}", b, e);
+                    blocks_to_reject.insert(b.reference(), b);
+                } else {
+                    blocks_to_accept.insert(b.reference(), b);
+                }
+            }
+        }
+        for (block_ref, block) in blocks_to_reject {
+            self.context
+                .metrics
+                .node_metrics
+                .invalid_blocks
+                .with_label_values(&[&block_ref.author.to_string(), "accept_block"])
+                .inc();
+            warn!("Invalid block {:?} is rejected", block);
+        }
+
+        // TODO: report blocks_to_reject to peers.
+
+        // Insert the accepted blocks into DAG state so future blocks including them as
+        // ancestors do not get suspended.
+        let blocks_to_accept: Vec<_> = blocks_to_accept.into_values().collect();
+        self.dag_state
+            .write()
+            .accept_blocks(blocks_to_accept.clone());
+
+        blocks_to_accept
+    }
+
     /// Tries to accept the provided block. To accept a block its ancestors must have been already successfully accepted. If
     /// block is accepted then Some result is returned. None is returned when either the block is suspended or the block
     /// has been already accepted before.
@@ -212,15 +228,36 @@ impl BlockManager {
             return TryAcceptResult::Processed;
         }
 
+        // Blocks at or below the gc round can not be committed anymore, so there is no point
+        // in accepting them.
+        let gc_round = dag_state.gc_round();
+        if block_ref.round <= gc_round {
+            let hostname = self
+                .context
+                .committee
+                .authority(block.author())
+                .hostname
+                .as_str();
+            self.context
+                .metrics
+                .node_metrics
+                .block_manager_skipped_blocks
+                .with_label_values(&[hostname])
+                .inc();
+            self.missing_blocks.remove(&block_ref);
+            return TryAcceptResult::Skipped;
+        }
+
         let ancestors = block.ancestors();
 
-        // make sure that we have all the required ancestors in store
+        // make sure that we have all the required ancestors in store, except for the garbage
+        // collected ones.
         for (found, ancestor) in dag_state
             .contains_blocks(ancestors.to_vec())
             .into_iter()
             .zip(ancestors.iter())
         {
-            if !found {
+            if !found && ancestor.round > gc_round {
                 missing_ancestors.insert(*ancestor);
 
                 // mark the block as having missing ancestors
@@ -265,23 +302,18 @@ impl BlockManager {
 
     /// Given an accepted block `accepted_block` it attempts to accept all the suspended children blocks assuming such exist.
     /// All the unsuspended / accepted blocks are returned as a vector in causal order.
-    fn try_unsuspend_children_blocks(
-        &mut self,
-        accepted_block: &VerifiedBlock,
-    ) -> Vec<VerifiedBlock> {
+    fn try_unsuspend_children_blocks(&mut self, accepted_block: BlockRef) -> Vec<VerifiedBlock> {
         let mut unsuspended_blocks = vec![];
-        let mut to_process_blocks = vec![accepted_block.clone()];
+        let mut to_process_blocks = vec![accepted_block];
 
-        while let Some(block) = to_process_blocks.pop() {
+        while let Some(block_ref) = to_process_blocks.pop() {
             // And try to check if its direct children can be unsuspended
-            if let Some(block_refs_with_missing_deps) =
-                self.missing_ancestors.remove(&block.reference())
-            {
+            if let Some(block_refs_with_missing_deps) = self.missing_ancestors.remove(&block_ref) {
                 for r in block_refs_with_missing_deps {
                     // For each dependency try to unsuspend it. If that's successful then we add it to the queue so
                     // we can recursively try to unsuspend its children.
-                    if let Some(block) = self.try_unsuspend_block(&r, &block.reference()) {
-                        to_process_blocks.push(block.block.clone());
+                    if let Some(block) = self.try_unsuspend_block(&r, &block_ref) {
+                        to_process_blocks.push(block.block.reference());
                         unsuspended_blocks.push(block);
                     }
                 }
@@ -344,6 +376,67 @@ impl BlockManager {
         None
     }
 
+    /// Called when the gc round advances. Suspended blocks at or below the gc round are dropped,
+    /// as they can not be committed anymore. Missing ancestors at or below the gc round are not
+    /// needed anymore, so the blocks only waiting for them are unsuspended and accepted. Returns
+    /// the accepted blocks in causal order.
+    pub(crate) fn try_unsuspend_blocks_for_latest_gc_round(&mut self) -> Vec<VerifiedBlock> {
+        let _s = monitored_scope("BlockManager::try_unsuspend_blocks_for_latest_gc_round");
+
+        let gc_round = self.dag_state.read().gc_round();
+        if gc_round == GENESIS_ROUND {
+            return vec![];
+        }
+        let first_retained = BlockRef::new(gc_round + 1, AuthorityIndex::ZERO, BlockDigest::MIN);
+
+        let retained_blocks = self.suspended_blocks.split_off(&first_retained);
+        let dropped_blocks = std::mem::replace(&mut self.suspended_blocks, retained_blocks);
+        if !dropped_blocks.is_empty() {
+            debug!(
+                "Dropping suspended blocks at or below gc round {gc_round}: {}",
+                dropped_blocks.keys().map(|b| b.to_string()).join(",")
+            );
+        }
+
+        let retained_ancestors = self.missing_ancestors.split_off(&first_retained);
+        let gc_ancestors = std::mem::replace(&mut self.missing_ancestors, retained_ancestors);
+        self.missing_blocks = self.missing_blocks.split_off(&first_retained);
+
+        let mut unsuspended_blocks = vec![];
+        for (gc_ancestor, children) in gc_ancestors {
+            for child in children {
+                // Children at or below the gc round have just been dropped.
+                if !self.suspended_blocks.contains_key(&child) {
+                    continue;
+                }
+                if let Some(block) = self.try_unsuspend_block(&child, &gc_ancestor) {
+                    let hostname = self
+                        .context
+                        .committee
+                        .authority(block.block.author())
+                        .hostname
+                        .as_str();
+                    self.context
+                        .metrics
+                        .node_metrics
+                        .block_manager_gc_unsuspended_blocks
+                        .with_label_values(&[hostname])
+                        .inc();
+                    let block_ref = block.block.reference();
+                    unsuspended_blocks.push(block.block);
+                    unsuspended_blocks.extend(self.try_unsuspend_children_blocks(block_ref));
+                }
+            }
+        }
+
+        // Ancestors are accepted before their children.
+        unsuspended_blocks.sort_by_key(|b| b.round());
+        let accepted_blocks = self.verify_and_accept_blocks(unsuspended_blocks);
+
+        self.update_stats();
+        accepted_blocks
+    }
+
     /// Returns all the blocks that are currently missing and needed in order to accept suspended
     /// blocks.
     pub(crate) fn missing_blocks(&self) -> BTreeSet<BlockRef> {
@@ -374,6 +467,19 @@ impl BlockManager {
             .set(max_round.into());
     }
 
+    fn update_stats(&self) {
+        let metrics = &self.context.metrics.node_metrics;
+        metrics
+            .block_manager_suspended_blocks
+            .set(self.suspended_blocks.len() as i64);
+        metrics
+            .block_manager_missing_ancestors
+            .set(self.missing_ancestors.len() as i64);
+        metrics
+            .block_manager_missing_blocks
+            .set(self.missing_blocks.len() as i64);
+    }
+
     /// Checks if block manager is empty.
     #[cfg(test)]
     pub(crate) fn is_empty(&self) -> bool {
@@ -398,6 +504,8 @@ enum TryAcceptResult {
     // The block has been processed before and already exists in BlockManager (and is suspended) or
     // in DagState (so has been already accepted). No further processing has been done at this point.
     Processed,
+    // The block is at or below the gc round, so it has been ignored.
+    Skipped,
 }
 
 #[cfg(test)]
@@ -409,14 +517,16 @@ mod tests {
     use rand::{prelude::StdRng, seq::SliceRandom, SeedableRng};
 
     use crate::{
-        block::{BlockAPI, BlockRef, SignedBlock, VerifiedBlock},
+        block::{BlockAPI, BlockDigest, BlockRef, SignedBlock, VerifiedBlock},
         block_manager::BlockManager,
         block_verifier::{BlockVerifier, NoopBlockVerifier},
+        commit::{CommitDigest, TrustedCommit},
         context::Context,
         dag_state::DagState,
         error::{ConsensusError, ConsensusResult},
         storage::mem_store::MemStore,
         test_dag_builder::DagBuilder,
+        Round,
     };
 
     #[tokio::test]
@@ -613,7 +723,8 @@ mod tests {
         fn check_ancestors(
             &self,
             block: &VerifiedBlock,
-            _ancestors: &[VerifiedBlock],
+            _ancestors: &[Option<VerifiedBlock>],
+            _gc_round: Round,
         ) -> ConsensusResult<()> {
             if self.fail.contains(&block.reference()) {
                 Err(ConsensusError::InvalidBlockTimestamp {
@@ -687,4 +798,75 @@ mod tests {
         // Other blocks should be rejected and there should be no remaining suspended block.
         assert!(block_manager.suspended_blocks().is_empty());
     }
+
+    #[tokio::test]
+    async fn accept_blocks_with_garbage_collected_ancestors() {
+        // GIVEN
+        let (mut context, _key_pairs) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_consensus_gc_depth_for_testing(3);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+
+        let mut block_manager = BlockManager::new(
+            context.clone(),
+            dag_state.clone(),
+            Arc::new(NoopBlockVerifier),
+        );
+
+        // create a DAG of rounds 1 ~ 10
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=10).build();
+
+        // Only the blocks of rounds 5 ~ 10 are received, so they all get suspended
+        let recent_blocks = dag_builder
+            .blocks
+            .values()
+            .filter(|block| block.round() >= 5)
+            .cloned()
+            .collect::<Vec<_>>();
+        let (accepted_blocks, missing) = block_manager.try_accept_blocks(recent_blocks.clone());
+        assert!(accepted_blocks.is_empty());
+        assert_eq!(missing.len(), 4);
+        assert!(missing.iter().all(|block_ref| block_ref.round == 4));
+
+        // Nothing is garbage collected yet
+        assert!(block_manager
+            .try_unsuspend_blocks_for_latest_gc_round()
+            .is_empty());
+
+        // WHEN a leader of round 7 is committed, the gc round becomes 4
+        dag_state
+            .write()
+            .set_last_commit(TrustedCommit::new_for_test(
+                1,
+                CommitDigest::MIN,
+                0,
+                BlockRef::new(7, AuthorityIndex::new_for_test(0), BlockDigest::MIN),
+                vec![],
+            ));
+        assert_eq!(dag_state.read().gc_round(), 4);
+        let accepted_blocks = block_manager.try_unsuspend_blocks_for_latest_gc_round();
+
+        // THEN the blocks only missing garbage collected ancestors are accepted, in causal order
+        assert_eq!(accepted_blocks.len(), recent_blocks.len());
+        assert!(accepted_blocks
+            .windows(2)
+            .all(|blocks| blocks[0].round() <= blocks[1].round()));
+        assert!(block_manager.is_empty());
+
+        // AND blocks at or below the gc round are skipped
+        let old_blocks = dag_builder
+            .blocks
+            .values()
+            .filter(|block| block.round() <= 4)
+            .cloned()
+            .collect::<Vec<_>>();
+        let (accepted_blocks, missing) = block_manager.try_accept_blocks(old_blocks);
+        assert!(accepted_blocks.is_empty());
+        assert!(missing.is_empty());
+        assert!(block_manager.is_empty());
+    }
 }
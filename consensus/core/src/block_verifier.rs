@@ -5,7 +5,7 @@ use std::{collections::BTreeSet, sync::Arc};
 
 use crate::{
     block::{
-        genesis_blocks, BlockAPI, BlockRef, BlockTimestampMs, SignedBlock, VerifiedBlock,
+        genesis_blocks, BlockAPI, BlockRef, BlockTimestampMs, Round, SignedBlock, VerifiedBlock,
         GENESIS_ROUND,
     },
     context::Context,
@@ -23,10 +23,13 @@ pub(crate) trait BlockVerifier: Send + Sync + 'static {
     /// and is ready to be accepted into the DAG.
     ///
     /// Caller must make sure ancestors corresponse to block.ancestors() 1-to-1, in the same order.
+    /// Ancestors at or below `gc_round` can be missing, as they are garbage collected. They are
+    /// ignored by the checks.
     fn check_ancestors(
         &self,
         block: &VerifiedBlock,
-        ancestors: &[VerifiedBlock],
+        ancestors: &[Option<VerifiedBlock>],
+        gc_round: Round,
     ) -> ConsensusResult<()>;
 }
 
@@ -152,12 +155,22 @@ impl BlockVerifier for SignedBlockVerifier {
     fn check_ancestors(
         &self,
         block: &VerifiedBlock,
-        ancestors: &[VerifiedBlock],
+        ancestors: &[Option<VerifiedBlock>],
+        gc_round: Round,
     ) -> ConsensusResult<()> {
         assert_eq!(block.ancestors().len(), ancestors.len());
         // This checks the invariant that block timestamp >= max ancestor timestamp.
         let mut max_timestamp_ms = BlockTimestampMs::MIN;
         for (ancestor_ref, ancestor_block) in block.ancestors().iter().zip(ancestors.iter()) {
+            let Some(ancestor_block) = ancestor_block else {
+                if ancestor_ref.round > gc_round {
+                    return Err(ConsensusError::MissingAncestor {
+                        ancestor: *ancestor_ref,
+                        gc_round,
+                    });
+                }
+                continue;
+            };
             assert_eq!(ancestor_ref, &ancestor_block.reference());
             max_timestamp_ms = max_timestamp_ms.max(ancestor_block.timestamp_ms());
         }
@@ -181,7 +194,8 @@ impl BlockVerifier for NoopBlockVerifier {
     fn check_ancestors(
         &self,
         _block: &VerifiedBlock,
-        _ancestors: &[VerifiedBlock],
+        _ancestors: &[Option<VerifiedBlock>],
+        _gc_round: Round,
     ) -> ConsensusResult<()> {
         Ok(())
     }
@@ -459,10 +473,11 @@ mod test {
             let test_block = TestBlock::new(10, i as u32)
                 .set_timestamp_ms(1000 + 100 * i as BlockTimestampMs)
                 .build();
-            ancestor_blocks.push(VerifiedBlock::new_for_test(test_block));
+            ancestor_blocks.push(Some(VerifiedBlock::new_for_test(test_block)));
         }
         let ancestor_refs = ancestor_blocks
             .iter()
+            .flatten()
             .map(|block| block.reference())
             .collect::<Vec<_>>();
 
@@ -474,7 +489,7 @@ mod test {
                 .build();
             let verified_block = VerifiedBlock::new_for_test(block);
             assert!(verifier
-                .check_ancestors(&verified_block, &ancestor_blocks)
+                .check_ancestors(&verified_block, &ancestor_blocks, GENESIS_ROUND)
                 .is_ok());
         }
 
@@ -486,12 +501,33 @@ mod test {
                 .build();
             let verified_block = VerifiedBlock::new_for_test(block);
             assert!(matches!(
-                verifier.check_ancestors(&verified_block, &ancestor_blocks),
+                verifier.check_ancestors(&verified_block, &ancestor_blocks, GENESIS_ROUND),
                 Err(ConsensusError::InvalidBlockTimestamp {
                     max_timestamp_ms: _,
                     block_timestamp_ms: _
                 })
             ));
         }
+
+        // Missing ancestors are ignored when garbage collected, including their timestamps.
+        {
+            let block = TestBlock::new(11, 0)
+                .set_ancestors(ancestor_refs.clone())
+                .set_timestamp_ms(1250)
+                .build();
+            let verified_block = VerifiedBlock::new_for_test(block);
+            let mut ancestor_blocks = ancestor_blocks.clone();
+            ancestor_blocks[3] = None;
+            assert!(matches!(
+                verifier.check_ancestors(&verified_block, &ancestor_blocks, 9),
+                Err(ConsensusError::MissingAncestor {
+                    ancestor: _,
+                    gc_round: 9
+                })
+            ));
+            assert!(verifier
+                .check_ancestors(&verified_block, &ancestor_blocks, 10)
+                .is_ok());
+        }
     }
 }
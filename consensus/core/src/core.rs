@@ -244,6 +244,22 @@ impl Core {
 
             self.try_commit()?;
 
+            // Commits may have advanced the gc round, accept the blocks that were only missing
+            // ancestors which are now garbage collected.
+            let gc_accepted_blocks = self
+                .block_manager
+                .try_unsuspend_blocks_for_latest_gc_round();
+            if !gc_accepted_blocks.is_empty() {
+                debug!(
+                    "Accepted blocks after garbage collection: {}",
+                    gc_accepted_blocks
+                        .iter()
+                        .map(|b| b.reference().to_string())
+                        .join(",")
+                );
+                self.add_accepted_blocks(gc_accepted_blocks);
+            }
+
             // Try to propose now since there are new blocks accepted.
             self.try_propose(false)?;
         }
@@ -635,23 +651,28 @@ impl Core {
     /// Retrieves the next ancestors to propose to form a block at `clock_round` round.
     fn ancestors_to_propose(&mut self, clock_round: Round) -> Vec<VerifiedBlock> {
         // Now take the ancestors before the clock_round (excluded) for each authority.
-        let ancestors = self
-            .dag_state
-            .read()
-            .get_last_cached_block_per_authority(clock_round);
+        let (ancestors, gc_round) = {
+            let dag_state = self.dag_state.read();
+            (
+                dag_state.get_last_cached_block_per_authority(clock_round),
+                dag_state.gc_round(),
+            )
+        };
         assert_eq!(
             ancestors.len(),
             self.context.committee.size(),
             "Fatal error, number of returned ancestors don't match committee size."
         );
 
-        // Propose only ancestors of higher rounds than what has already been proposed.
+        // Propose only ancestors of higher rounds than what has already been proposed, and above
+        // the gc round as garbage collected blocks can not be committed anymore.
         // And always include own last proposed block first among ancestors.
         let ancestors = iter::once(self.last_proposed_block.clone())
             .chain(
                 ancestors
                     .into_iter()
                     .filter(|block| block.author() != self.context.own_index)
+                    .filter(|block| block.round() > gc_round)
                     .flat_map(|block| {
                         if let Some(last_block_ref) = self.last_included_ancestors[block.author()] {
                             return (last_block_ref.round < block.round()).then_some(block);
@@ -11,6 +11,7 @@ use std::{
 
 use consensus_config::AuthorityIndex;
 use itertools::Itertools as _;
+use tokio::sync::watch;
 use tracing::{debug, error};
 
 use crate::{
@@ -28,6 +29,9 @@ use crate::{
     storage::{Store, WriteBatch},
 };
 
+/// The minimum number of rounds to prune from storage at once.
+const STORE_PRUNE_BATCH_ROUNDS: Round = 1000;
+
 /// DagState provides the API to write and read accepted blocks from the DAG.
 /// Only uncommitted and last committed blocks are cached in memory.
 /// The rest of blocks are stored on disk.
@@ -84,6 +88,9 @@ pub(crate) struct DagState {
 
     // The number of cached rounds
     cached_rounds: Round,
+
+    // Blocks of rounds below this one are deleted from storage by the StorePruner.
+    store_prune_round: watch::Sender<Round>,
 }
 
 impl DagState {
@@ -161,6 +168,7 @@ impl DagState {
             unscored_committed_subdags,
             store,
             cached_rounds,
+            store_prune_round: watch::channel(GENESIS_ROUND).0,
         };
 
         for (i, round) in last_committed_rounds.into_iter().enumerate() {
@@ -629,6 +637,12 @@ impl DagState {
                 .set((*round).into());
         }
 
+        self.context
+            .metrics
+            .node_metrics
+            .dag_state_gc_round
+            .set(self.gc_round() as i64);
+
         self.pending_commit_votes.push_back(commit.reference());
         self.commits_to_write.push(commit);
     }
@@ -787,6 +801,40 @@ impl DagState {
             .dag_state_recent_blocks
             .set(self.recent_blocks.len() as i64);
         metrics.dag_state_recent_refs.set(total_recent_refs as i64);
+
+        self.prune_store();
+    }
+
+    /// Has the StorePruner delete the blocks that are `dag_storage_retention_rounds` below the gc
+    /// round from storage. Storage is pruned in batches of rounds, as deleting compacts the pruned
+    /// ranges.
+    fn prune_store(&mut self) {
+        let gc_round = self.gc_round();
+        if gc_round == GENESIS_ROUND {
+            return;
+        }
+        let prune_round =
+            gc_round.saturating_sub(self.context.parameters.dag_storage_retention_rounds);
+        if prune_round < *self.store_prune_round.borrow() + STORE_PRUNE_BATCH_ROUNDS {
+            return;
+        }
+        self.store_prune_round.send_replace(prune_round);
+    }
+
+    /// Receives the rounds below which blocks can be deleted from storage.
+    pub(crate) fn store_prune_round_receiver(&self) -> watch::Receiver<Round> {
+        self.store_prune_round.subscribe()
+    }
+
+    /// The round at or below which blocks are garbage collected. Those blocks are not committed
+    /// anymore, and are not needed to accept new blocks, so they can be ignored when missing.
+    /// Returns `GENESIS_ROUND` when garbage collection is disabled.
+    pub(crate) fn gc_round(&self) -> Round {
+        let gc_depth = self.context.protocol_config.gc_depth();
+        if gc_depth == 0 {
+            return GENESIS_ROUND;
+        }
+        self.last_commit_round().saturating_sub(gc_depth)
     }
 
     /// Detects and returns the blocks of the round that forms the last quorum. The method will return
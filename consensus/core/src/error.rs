@@ -108,6 +108,9 @@ pub enum ConsensusError {
         block_timestamp_ms: u64,
     },
 
+    #[error("Ancestor {ancestor} is missing but is above the gc round {gc_round}")]
+    MissingAncestor { ancestor: BlockRef, gc_round: Round },
+
     #[error("No available authority to fetch commits")]
     NoAvailableAuthorityToFetchCommits,
 
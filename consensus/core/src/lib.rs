@@ -25,6 +25,7 @@ mod metrics;
 mod network;
 mod stake_aggregator;
 mod storage;
+mod store_pruner;
 mod subscriber;
 mod synchronizer;
 mod threshold_clock;
@@ -46,6 +46,7 @@ impl Linearizer {
         let last_commit_digest = dag_state.last_commit_digest();
         let last_commit_timestamp_ms = dag_state.last_commit_timestamp_ms();
         let last_committed_rounds = dag_state.last_committed_rounds();
+        let gc_round = dag_state.gc_round();
 
         let mut to_commit = Vec::new();
         let mut committed = HashSet::new();
@@ -64,10 +65,12 @@ impl Linearizer {
                         .iter()
                         .copied()
                         .filter(|ancestor| {
-                            // We skip the block if we already committed it or we reached a
-                            // round that we already committed.
+                            // We skip the block if we already committed it, we reached a
+                            // round that we already committed, or the block is garbage
+                            // collected.
                             !committed.contains(ancestor)
                                 && last_committed_rounds[ancestor.author] < ancestor.round
+                                && gc_round < ancestor.round
                         })
                         .collect::<Vec<_>>(),
                 )
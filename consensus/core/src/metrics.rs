@@ -118,6 +118,10 @@ pub(crate) struct NodeMetrics {
     pub(crate) dag_state_recent_refs: IntGauge,
     pub(crate) dag_state_store_read_count: IntCounterVec,
     pub(crate) dag_state_store_write_count: IntCounter,
+    pub(crate) dag_state_gc_round: IntGauge,
+    pub(crate) dag_state_store_pruned_round: IntGauge,
+    pub(crate) block_manager_gc_unsuspended_blocks: IntCounterVec,
+    pub(crate) block_manager_skipped_blocks: IntCounterVec,
     pub(crate) fetch_blocks_scheduler_inflight: IntGauge,
     pub(crate) synchronizer_fetched_blocks_by_peer: IntCounterVec,
     pub(crate) synchronizer_fetched_blocks_by_authority: IntCounterVec,
@@ -286,6 +290,28 @@ impl NodeMetrics {
                 "Number of times DagState needs to write to store",
                 registry,
             ).unwrap(),
+            dag_state_gc_round: register_int_gauge_with_registry!(
+                "dag_state_gc_round",
+                "The round at or below which blocks are garbage collected",
+                registry,
+            ).unwrap(),
+            dag_state_store_pruned_round: register_int_gauge_with_registry!(
+                "dag_state_store_pruned_round",
+                "The round below which blocks have been deleted from storage",
+                registry,
+            ).unwrap(),
+            block_manager_gc_unsuspended_blocks: register_int_counter_vec_with_registry!(
+                "block_manager_gc_unsuspended_blocks",
+                "The number of blocks unsuspended because their missing ancestors got garbage collected, per authority",
+                &["authority"],
+                registry,
+            ).unwrap(),
+            block_manager_skipped_blocks: register_int_counter_vec_with_registry!(
+                "block_manager_skipped_blocks",
+                "The number of blocks skipped by the block manager because they are at or below the gc round, per authority",
+                &["authority"],
+                registry,
+            ).unwrap(),
             fetch_blocks_scheduler_inflight: register_int_gauge_with_registry!(
                 "fetch_blocks_scheduler_inflight",
                 "Designates whether the synchronizer scheduler task to fetch blocks is currently running",
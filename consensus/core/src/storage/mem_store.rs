@@ -211,4 +211,18 @@ impl Store for MemStore {
             .last_key_value()
             .map(|(k, v)| (CommitRef::new(k.0, k.1), v.clone())))
     }
+
+    fn prune_blocks(&self, before_round: Round) -> ConsensusResult<()> {
+        let mut inner = self.inner.write();
+        inner
+            .blocks
+            .retain(|(round, _, _), _| *round >= before_round);
+        inner
+            .digests_by_authorities
+            .retain(|(_, round, _)| *round >= before_round);
+        inner
+            .commit_votes
+            .retain(|(_, _, block_ref)| block_ref.round >= before_round);
+        Ok(())
+    }
 }
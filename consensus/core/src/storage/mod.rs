@@ -58,6 +58,10 @@ pub(crate) trait Store: Send + Sync {
 
     /// Reads the last commit info, written atomically with the last commit.
     fn read_last_commit_info(&self) -> ConsensusResult<Option<(CommitRef, CommitInfo)>>;
+
+    /// Deletes blocks of rounds < `before_round`, along with the commit votes they carry.
+    /// Commits are kept.
+    fn prune_blocks(&self, before_round: Round) -> ConsensusResult<()>;
 }
 
 /// Represents data to be written to the store together atomically.
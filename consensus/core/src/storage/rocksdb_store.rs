@@ -1,7 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::VecDeque, ops::Bound::Included, time::Duration};
+use std::{
+    collections::VecDeque,
+    ops::Bound::{Excluded, Included, Unbounded},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use consensus_config::AuthorityIndex;
@@ -296,4 +300,59 @@ impl Store for RocksDBStore {
         let (key, commit_info) = result.map_err(ConsensusError::RocksDBFailure)?;
         Ok(Some((CommitRef::new(key.0, key.1), commit_info)))
     }
+
+    fn prune_blocks(&self, before_round: Round) -> ConsensusResult<()> {
+        let mut batch = self.blocks.batch();
+
+        let blocks_range = (
+            (Round::MIN, AuthorityIndex::MIN, BlockDigest::MIN),
+            (before_round, AuthorityIndex::MIN, BlockDigest::MIN),
+        );
+        batch.schedule_delete_range(&self.blocks, &blocks_range.0, &blocks_range.1)?;
+
+        // The secondary index is ordered by authority first, so it is pruned per authority.
+        let mut digests_ranges = vec![];
+        let mut start = Included((AuthorityIndex::MIN, Round::MIN, BlockDigest::MIN));
+        while let Some(kv) = self
+            .digests_by_authorities
+            .safe_range_iter((start, Unbounded))
+            .next()
+        {
+            let ((author, _, _), _) = kv?;
+            let range = (
+                (author, Round::MIN, BlockDigest::MIN),
+                (author, before_round, BlockDigest::MIN),
+            );
+            batch.schedule_delete_range(&self.digests_by_authorities, &range.0, &range.1)?;
+            digests_ranges.push(range);
+            start = Excluded((author, Round::MAX, BlockDigest::MAX));
+        }
+
+        // Votes are included in blocks shortly after the commits they vote for, so they are
+        // roughly ordered by round as well. Stop at the first vote to keep, the votes left behind
+        // are pruned on a later call.
+        let mut votes = vec![];
+        for kv in self.commit_votes.safe_iter() {
+            let (vote, _) = kv?;
+            if vote.2.round >= before_round {
+                break;
+            }
+            votes.push(vote);
+        }
+        batch.delete_batch(&self.commit_votes, votes.iter())?;
+
+        batch.write()?;
+
+        // Compact the deleted ranges right away to reclaim the space, and to stop serving the
+        // deleted blocks.
+        self.blocks
+            .compact_range(&blocks_range.0, &blocks_range.1)?;
+        for (start, end) in digests_ranges {
+            self.digests_by_authorities.compact_range(&start, &end)?;
+        }
+        if let (Some(first), Some(last)) = (votes.first(), votes.last()) {
+            self.commit_votes.compact_range(first, last)?;
+        }
+        Ok(())
+    }
 }
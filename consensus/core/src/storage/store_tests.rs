@@ -8,7 +8,7 @@ use tempfile::TempDir;
 use super::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store, WriteBatch};
 use crate::{
     block::{BlockAPI, BlockDigest, BlockRef, Slot, TestBlock, VerifiedBlock},
-    commit::{CommitDigest, TrustedCommit},
+    commit::{CommitDigest, CommitRef, TrustedCommit},
 };
 
 /// Test fixture for store tests. Wraps around various store implementations.
@@ -297,3 +297,70 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn prune_blocks(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    // Blocks of rounds 1 ~ 6 for authorities 0 ~ 3, each voting for the commit of the previous
+    // round.
+    let written_blocks: Vec<VerifiedBlock> = (1..=6)
+        .flat_map(|round| {
+            (0..4).map(move |author| {
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(round, author)
+                        .set_commit_votes(vec![CommitRef::new(round - 1, CommitDigest::MIN)])
+                        .build(),
+                )
+            })
+        })
+        .collect();
+    store
+        .write(WriteBatch::default().blocks(written_blocks.clone()))
+        .unwrap();
+
+    store.prune_blocks(4).unwrap();
+
+    let refs = written_blocks
+        .iter()
+        .map(|block| block.reference())
+        .collect::<Vec<_>>();
+    let read_blocks = store.read_blocks(&refs).unwrap();
+    for (block, read_block) in written_blocks.iter().zip(read_blocks) {
+        if block.round() < 4 {
+            assert!(read_block.is_none(), "{:?}", read_block);
+        } else {
+            assert_eq!(read_block.as_ref(), Some(block));
+        }
+    }
+    let contained = store.contains_blocks(&refs).unwrap();
+    assert_eq!(
+        contained,
+        written_blocks
+            .iter()
+            .map(|block| block.round() >= 4)
+            .collect::<Vec<_>>()
+    );
+
+    // The secondary index is pruned for every authority.
+    for author in 0..4 {
+        let author = AuthorityIndex::new_for_test(author);
+        let scanned_blocks = store.scan_blocks_by_author(author, 0).unwrap();
+        assert_eq!(
+            scanned_blocks
+                .iter()
+                .map(|block| block.round())
+                .collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        assert!(!store.contains_block_at_slot(Slot::new(3, author)).unwrap());
+        assert!(store.contains_block_at_slot(Slot::new(4, author)).unwrap());
+    }
+
+    // Votes carried by the pruned blocks are gone.
+    assert!(store.read_commit_votes(2).unwrap().is_empty());
+    assert_eq!(store.read_commit_votes(3).unwrap().len(), 4);
+}
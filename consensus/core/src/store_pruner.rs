@@ -0,0 +1,169 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::block::{Round, GENESIS_ROUND};
+use crate::context::Context;
+use crate::storage::Store;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot::{Receiver, Sender};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+/// How long to wait before pruning again after a failure.
+const PRUNE_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+pub(crate) struct StorePrunerHandle {
+    handle: JoinHandle<()>,
+    stop: Sender<()>,
+}
+
+impl StorePrunerHandle {
+    pub async fn stop(self) {
+        self.stop.send(()).ok();
+        self.handle.await.ok();
+    }
+}
+
+/// Deletes old blocks from storage in the background, so that DagState does not hold its lock
+/// while they are deleted and the deleted ranges are compacted.
+pub(crate) struct StorePruner {
+    context: Arc<Context>,
+    store: Arc<dyn Store>,
+    prune_round_receiver: watch::Receiver<Round>,
+    stop: Receiver<()>,
+}
+
+impl StorePruner {
+    /// Starts pruning blocks of rounds below the rounds sent through `prune_round_receiver`.
+    pub fn start(
+        context: Arc<Context>,
+        store: Arc<dyn Store>,
+        prune_round_receiver: watch::Receiver<Round>,
+    ) -> StorePrunerHandle {
+        let (stop_sender, stop) = tokio::sync::oneshot::channel();
+        let mut me = Self {
+            context,
+            store,
+            prune_round_receiver,
+            stop,
+        };
+        let handle = tokio::spawn(async move { me.run().await });
+
+        StorePrunerHandle {
+            handle,
+            stop: stop_sender,
+        }
+    }
+
+    async fn run(&mut self) {
+        let mut pruned_round = GENESIS_ROUND;
+
+        loop {
+            let prune_round = *self.prune_round_receiver.borrow_and_update();
+            if prune_round > pruned_round {
+                debug!("Pruning blocks of rounds < {prune_round} from storage.");
+                let store = self.store.clone();
+                let result =
+                    tokio::task::spawn_blocking(move || store.prune_blocks(prune_round)).await;
+
+                match result {
+                    Ok(Ok(())) => {
+                        pruned_round = prune_round;
+                        self.context
+                            .metrics
+                            .node_metrics
+                            .dag_state_store_pruned_round
+                            .set(prune_round as i64);
+                    }
+                    // Retry later, with the latest prune round by then
+                    Ok(Err(err)) => {
+                        warn!(
+                            "Failed to prune storage below round {prune_round}, retrying: {err:?}"
+                        );
+                        tokio::select! {
+                            _ = sleep(PRUNE_RETRY_DELAY) => continue,
+                            _ = &mut self.stop => {
+                                debug!("Stop signal has been received, now shutting down");
+                                return;
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        warn!("Pruning task failed, now shutting down: {err:?}");
+                        return;
+                    }
+                }
+            }
+
+            tokio::select! {
+                result = self.prune_round_receiver.changed() => {
+                    if result.is_err() {
+                        debug!("Prune round sender has been dropped, now shutting down");
+                        return;
+                    }
+                },
+                _ = &mut self.stop => {
+                    debug!("Stop signal has been received, now shutting down");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::watch;
+    use tokio::time::sleep;
+
+    use crate::block::{BlockAPI, TestBlock, VerifiedBlock};
+    use crate::context::Context;
+    use crate::storage::{mem_store::MemStore, Store, WriteBatch};
+    use crate::store_pruner::StorePruner;
+
+    #[tokio::test]
+    async fn prune_in_background() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+
+        let blocks: Vec<VerifiedBlock> = (1..=6)
+            .flat_map(|round| {
+                (0..4).map(move |author| {
+                    VerifiedBlock::new_for_test(TestBlock::new(round, author).build())
+                })
+            })
+            .collect();
+        store
+            .write(WriteBatch::default().blocks(blocks.clone()))
+            .unwrap();
+        let refs = blocks
+            .iter()
+            .map(|block| block.reference())
+            .collect::<Vec<_>>();
+
+        let (prune_round_sender, prune_round_receiver) = watch::channel(0);
+        let handle = StorePruner::start(context, store.clone(), prune_round_receiver);
+
+        prune_round_sender.send_replace(4);
+
+        let expected = blocks
+            .iter()
+            .map(|block| block.round() >= 4)
+            .collect::<Vec<_>>();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while store.contains_blocks(&refs).unwrap() != expected {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("blocks were not pruned");
+
+        handle.stop().await;
+    }
+}
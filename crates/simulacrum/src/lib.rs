@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use fastcrypto::traits::Signer;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use rand::rngs::OsRng;
 use sui_config::{genesis, transaction_deny_config::TransactionDenyConfig};
 use sui_protocol_config::ProtocolVersion;
@@ -604,6 +604,38 @@ impl<T: Send + Sync, V: store::SimulatorStore + Send + Sync> RestStateReader for
     ) -> sui_types::storage::error::Result<Option<sui_types::storage::CoinInfo>> {
         todo!()
     }
+
+    fn account_balances_iter(
+        &self,
+        _owner: SuiAddress,
+        _cursor: Option<TypeTag>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::AccountBalanceInfo> + '_>,
+    > {
+        todo!()
+    }
+
+    fn event_by_type_iter(
+        &self,
+        _event_type: &StructTag,
+        _start: sui_types::storage::EventPosition,
+        _descending: bool,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::RestEventInfo> + '_>,
+    > {
+        todo!()
+    }
+
+    fn event_by_sender_iter(
+        &self,
+        _sender: SuiAddress,
+        _start: sui_types::storage::EventPosition,
+        _descending: bool,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::RestEventInfo> + '_>,
+    > {
+        todo!()
+    }
 }
 
 impl Simulacrum {
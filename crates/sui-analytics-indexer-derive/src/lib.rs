@@ -9,7 +9,7 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields};
 pub fn schema_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let struct_name = &input.ident;
-    let (schema, getter_implementation) = match &input.data {
+    let (schema, getter_implementation, field_types) = match &input.data {
         Data::Struct(data_struct) => match &data_struct.fields {
             Fields::Named(fields) => {
                 let (schema_iter, getter_iter): (Vec<_>, Vec<_>) = fields
@@ -27,7 +27,8 @@ pub fn schema_derive(input: TokenStream) -> TokenStream {
                         )
                     })
                     .unzip();
-                (schema_iter.join(", "), getter_iter.join("\n"))
+                let field_types: Vec<_> = fields.named.iter().map(|field| &field.ty).collect();
+                (schema_iter.join(", "), getter_iter.join("\n"), field_types)
             }
             _ => panic!("not supported struct for parquet serialization"),
         },
@@ -42,6 +43,10 @@ pub fn schema_derive(input: TokenStream) -> TokenStream {
                 vec![#schema_tokens]
             }
 
+            fn schema_types() -> Vec<ParquetType> {
+                vec![#(<#field_types as ParquetColumn>::TYPE),*]
+            }
+
             fn get_column(&self, idx: usize) -> ParquetValue {
                 #getter_implementation_tokens
                 panic!("not supported column {:?}", idx);
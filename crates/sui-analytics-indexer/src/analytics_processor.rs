@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::fs;
+use std::future::Future;
 use std::ops::Range;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -12,8 +13,8 @@ use anyhow::Result;
 use object_store::path::Path;
 use object_store::DynObjectStore;
 use serde::Serialize;
-use tokio::sync::{mpsc, oneshot, Mutex};
-use tracing::{error, info};
+use tokio::sync::{mpsc, oneshot, watch, Mutex};
+use tracing::{error, info, warn};
 
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_data_ingestion_core::Worker;
@@ -23,10 +24,11 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
 use crate::handlers::AnalyticsHandler;
+use crate::table_manifest::{TableColumn, TableManifest};
 use crate::writers::AnalyticsWriter;
 use crate::{
-    join_paths, AnalyticsIndexerConfig, FileMetadata, MaxCheckpointReader, ParquetSchema,
-    EPOCH_DIR_PREFIX,
+    join_paths, AnalyticsIndexerConfig, FileFormat, FileMetadata, FileType, MaxCheckpointReader,
+    ParquetSchema, EPOCH_DIR_PREFIX,
 };
 
 struct State<S: Serialize + ParquetSchema> {
@@ -35,6 +37,8 @@ struct State<S: Serialize + ParquetSchema> {
     last_commit_instant: Instant,
     num_checkpoint_iterations: u64,
     writer: Box<dyn AnalyticsWriter<S>>,
+    /// End (exclusive) of the checkpoint range of the last file handed over for upload.
+    last_cut_checkpoint: Option<CheckpointSequenceNumber>,
 }
 
 pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
@@ -43,6 +47,8 @@ pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
     metrics: AnalyticsMetrics,
     config: AnalyticsIndexerConfig,
     sender: mpsc::Sender<FileMetadata>,
+    /// End (exclusive) of the checkpoint range of the last file uploaded to the remote store.
+    uploaded_receiver: watch::Receiver<CheckpointSequenceNumber>,
     #[allow(dead_code)]
     kill_sender: oneshot::Sender<()>,
     #[allow(dead_code)]
@@ -50,6 +56,8 @@ pub struct AnalyticsProcessor<S: Serialize + ParquetSchema> {
 }
 
 const CHECK_FILE_SIZE_ITERATION_CYCLE: u64 = 50;
+/// How long to wait before retrying a failed upload or table manifest update.
+const SYNC_RETRY_DELAY: Duration = Duration::from_secs(5);
 
 #[async_trait::async_trait]
 impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
@@ -96,6 +104,23 @@ impl<S: Serialize + ParquetSchema + 'static> Worker for AnalyticsProcessor<S> {
             .checked_add(1)
             .context("Checkpoint sequence num overflow")?;
         state.num_checkpoint_iterations += 1;
+
+        if self.config.ending_checkpoint_seq_num == Some(checkpoint_num) {
+            // This is the last checkpoint of a backfill, so flush the remaining rows and wait for
+            // all files to be uploaded, before reporting the checkpoint as processed.
+            self.cut(&mut state).await?;
+            self.reset(&mut state)?;
+            if let Some(last_cut_checkpoint) = state.last_cut_checkpoint {
+                info!(
+                    "Waiting for {} files up to checkpoint {checkpoint_num} to be uploaded",
+                    self.name()
+                );
+                self.uploaded_receiver
+                    .clone()
+                    .wait_for(|uploaded| *uploaded >= last_cut_checkpoint)
+                    .await?;
+            }
+        }
         Ok(())
     }
 }
@@ -118,15 +143,23 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         let remote_object_store = config.remote_store_config.make()?;
         let (kill_sender, kill_receiver) = oneshot::channel::<()>();
         let (sender, receiver) = mpsc::channel::<FileMetadata>(100);
+        let (uploaded_sender, uploaded_receiver) = watch::channel(next_checkpoint_seq_num);
         let name: String = handler.name().parse()?;
         let checkpoint_dir = config.checkpoint_dir.clone();
         let cloned_metrics = metrics.clone();
+        let table_columns = config
+            .write_table_manifest
+            .then(TableManifest::columns::<S>);
         tokio::task::spawn(Self::start_syncing_with_remote(
             remote_object_store,
             local_object_store.clone(),
             checkpoint_dir,
             config.remote_store_path_prefix.clone(),
+            config.file_type,
+            config.file_format,
+            table_columns,
             receiver,
+            uploaded_sender,
             kill_receiver,
             cloned_metrics,
             name.clone(),
@@ -144,12 +177,14 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             last_commit_instant: Instant::now(),
             num_checkpoint_iterations: 0,
             writer,
+            last_cut_checkpoint: None,
         };
         Ok(Self {
             handler,
             state: Mutex::new(state),
             kill_sender,
             sender,
+            uploaded_receiver,
             max_checkpoint_sender,
             metrics,
             config,
@@ -171,6 +206,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                 state.current_checkpoint_range.clone(),
             );
             self.sender.send(file_metadata).await?;
+            state.last_cut_checkpoint = Some(state.current_checkpoint_range.end);
             tokio::task::yield_now().await;
         }
         Ok(())
@@ -221,11 +257,33 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         local_object_store: Arc<DynObjectStore>,
         local_staging_root_dir: PathBuf,
         remote_store_path_prefix: Option<Path>,
+        file_type: FileType,
+        file_format: FileFormat,
+        table_columns: Option<Vec<TableColumn>>,
         mut file_recv: mpsc::Receiver<FileMetadata>,
+        uploaded_sender: watch::Sender<CheckpointSequenceNumber>,
         mut recv: oneshot::Receiver<()>,
         metrics: AnalyticsMetrics,
         name: String,
     ) -> Result<()> {
+        // Files uploaded before the manifest was updated, e.g. because of a crash, are only
+        // found by listing the remote store
+        if let Some(table_columns) = &table_columns {
+            let reconciled =
+                Self::retry_until_stopped(&mut recv, "reconcile table manifest", || {
+                    Self::reconcile_table_manifest(
+                        &remote_object_store,
+                        remote_store_path_prefix.clone(),
+                        file_type,
+                        file_format,
+                        table_columns,
+                    )
+                })
+                .await;
+            if !reconciled {
+                return Ok(());
+            }
+        }
         loop {
             tokio::select! {
                 _ = &mut recv => break,
@@ -233,16 +291,36 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                     if let Some(file_metadata) = file {
                         info!("Received {name} file with checkpoints: {:?}", &file_metadata.checkpoint_seq_range);
                         let checkpoint_seq_num = file_metadata.checkpoint_seq_range.end;
-                        Self::sync_file_to_remote(
+                        let synced = Self::retry_until_stopped(&mut recv, "sync file to remote", || {
+                            Self::sync_file_to_remote(
                                 local_staging_root_dir.clone(),
                                 file_metadata.file_path(),
                                 remote_store_path_prefix.clone(),
                                 local_object_store.clone(),
-                                remote_object_store.clone()
+                                remote_object_store.clone(),
                             )
-                            .await
-                            .expect("Syncing checkpoint should not fail");
+                        })
+                        .await;
+                        if !synced {
+                            break;
+                        }
+                        // The watermark only advances once the file is in the manifest
+                        if let Some(table_columns) = &table_columns {
+                            let updated = Self::retry_until_stopped(&mut recv, "update table manifest", || {
+                                Self::update_table_manifest(
+                                    &remote_object_store,
+                                    remote_store_path_prefix.clone(),
+                                    &file_metadata,
+                                    table_columns,
+                                )
+                            })
+                            .await;
+                            if !updated {
+                                break;
+                            }
+                        }
                         metrics.last_uploaded_checkpoint.with_label_values(&[&name]).set(checkpoint_seq_num as i64);
+                        uploaded_sender.send_replace(checkpoint_seq_num);
                     } else {
                         info!("Terminating upload sync loop");
                         break;
@@ -253,6 +331,29 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         Ok(())
     }
 
+    /// Runs `operation` until it succeeds, waiting `SYNC_RETRY_DELAY` after each failure. Returns
+    /// false if the stop signal is received first.
+    async fn retry_until_stopped<F, Fut>(
+        stop: &mut oneshot::Receiver<()>,
+        description: &str,
+        mut operation: F,
+    ) -> bool
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        loop {
+            match operation().await {
+                Ok(()) => return true,
+                Err(err) => warn!("Failed to {description}, retrying: {err:?}"),
+            }
+            tokio::select! {
+                _ = &mut *stop => return false,
+                _ = tokio::time::sleep(SYNC_RETRY_DELAY) => {}
+            }
+        }
+    }
+
     async fn setup_max_checkpoint_metrics_updates(
         max_checkpoint_reader: Box<dyn MaxCheckpointReader>,
         analytics_metrics: AnalyticsMetrics,
@@ -290,7 +391,42 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         let remote_dest = join_paths(prefix, &path);
         info!("Syncing file to remote: {:?}", &remote_dest);
         copy_file(&path, &remote_dest, &from, &to).await?;
-        fs::remove_file(path_to_filesystem(dir, &path)?)?;
+        // The file is uploaded, so failing to clean it up must not make the upload be retried
+        if let Err(err) = fs::remove_file(path_to_filesystem(dir, &path)?) {
+            warn!("Failed to remove synced file {path}: {err}");
+        }
+        Ok(())
+    }
+
+    async fn update_table_manifest(
+        remote_object_store: &Arc<DynObjectStore>,
+        prefix: Option<Path>,
+        file_metadata: &FileMetadata,
+        table_columns: &[TableColumn],
+    ) -> Result<()> {
+        let path = TableManifest::path(file_metadata.file_type, prefix);
+        let mut manifest =
+            TableManifest::read(remote_object_store, &path, file_metadata.file_type).await?;
+        let schema_version = manifest.schema_version(table_columns);
+        manifest.add_file(file_metadata, schema_version)?;
+        manifest.write(remote_object_store, &path).await
+    }
+
+    async fn reconcile_table_manifest(
+        remote_object_store: &Arc<DynObjectStore>,
+        prefix: Option<Path>,
+        file_type: FileType,
+        file_format: FileFormat,
+        table_columns: &[TableColumn],
+    ) -> Result<()> {
+        let path = TableManifest::path(file_type, prefix.clone());
+        let mut manifest = TableManifest::read(remote_object_store, &path, file_type).await?;
+        let files =
+            TableManifest::list_files(remote_object_store, prefix, file_type, file_format).await?;
+        if manifest.add_missing_files(&files, table_columns)? {
+            info!("Adding files missing from the {file_type:?} table manifest");
+            manifest.write(remote_object_store, &path).await?;
+        }
         Ok(())
     }
 }
@@ -44,6 +44,7 @@ use crate::tables::{
     MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
     TransactionObjectEntry, WrappedObjectEntry,
 };
+use crate::writers::arrow_writer::ArrowIpcWriter;
 use crate::writers::csv_writer::CSVWriter;
 use crate::writers::parquet_writer::ParquetWriter;
 use crate::writers::AnalyticsWriter;
@@ -53,6 +54,8 @@ pub mod analytics_processor;
 pub mod errors;
 mod handlers;
 mod package_store;
+pub mod query;
+pub mod table_manifest;
 pub mod tables;
 mod writers;
 
@@ -68,98 +71,170 @@ const DYNAMIC_FIELD_PREFIX: &str = "dynamic_field";
 
 const WRAPPED_OBJECT_PREFIX: &str = "wrapped_object";
 
-#[derive(Parser, Clone, Debug)]
-#[clap(
-    name = "Sui Analytics Indexer",
-    about = "Indexer service to upload data for the analytics pipeline.",
-    rename_all = "kebab-case"
-)]
+#[derive(Args, Clone, Debug)]
+#[clap(rename_all = "kebab-case")]
 pub struct AnalyticsIndexerConfig {
     /// The url of the checkpoint client to connect to.
     #[clap(long)]
     pub rest_url: String,
     /// The url of the metrics client to connect to.
-    #[clap(long, default_value = "127.0.0.1", global = true)]
+    #[clap(long, default_value = "127.0.0.1")]
     pub client_metric_host: String,
     /// The port of the metrics client to connect to.
-    #[clap(long, default_value = "8081", global = true)]
+    #[clap(long, default_value = "8081")]
     pub client_metric_port: u16,
     /// Directory to contain the temporary files for checkpoint entries.
-    #[clap(long, global = true, default_value = "/tmp")]
+    #[clap(long, default_value = "/tmp")]
     pub checkpoint_dir: PathBuf,
     /// Number of checkpoints to process before uploading to the datastore.
-    #[clap(long, default_value = "10000", global = true)]
+    #[clap(long, default_value = "10000")]
     pub checkpoint_interval: u64,
     /// Maximum file size in mb before uploading to the datastore.
-    #[clap(long, default_value = "100", global = true)]
+    #[clap(long, default_value = "100")]
     pub max_file_size_mb: u64,
     /// Checkpoint sequence number to start the download from
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub starting_checkpoint_seq_num: Option<u64>,
+    /// Last checkpoint sequence number to process (inclusive). When set, the indexer backfills the
+    /// checkpoints up to and including this one, uploads the remaining data, and exits. Separate
+    /// ranges can be backfilled in parallel by separate indexers with distinct checkpoint dirs.
+    #[clap(long, default_value = None)]
+    pub ending_checkpoint_seq_num: Option<u64>,
     /// Time to process in seconds before uploading to the datastore.
-    #[clap(long, default_value = "600", global = true)]
+    #[clap(long, default_value = "600")]
     pub time_interval_s: u64,
     // Remote object store where data gets written to
     #[command(flatten)]
     pub remote_store_config: ObjectStoreConfig,
     // Remote object store path prefix to use while writing
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub remote_store_path_prefix: Option<Path>,
     // File format to store data in i.e. csv, parquet, etc
-    #[clap(long, value_enum, default_value = "csv", global = true)]
+    #[clap(long, value_enum, default_value = "csv")]
     pub file_format: FileFormat,
     // Type of data to write i.e. checkpoint, object, transaction, etc
-    #[clap(long, value_enum, long, global = true)]
+    #[clap(long, value_enum, long)]
     pub file_type: FileType,
-    #[clap(
-        long,
-        default_value = "https://checkpoints.mainnet.sui.io",
-        global = true
-    )]
+    #[clap(long, default_value = "https://checkpoints.mainnet.sui.io")]
     pub remote_store_url: String,
     // Directory to contain the package cache for pipelines
-    #[clap(
-        long,
-        value_enum,
-        long,
-        global = true,
-        default_value = "/opt/sui/db/package_cache"
-    )]
+    #[clap(long, value_enum, long, default_value = "/opt/sui/db/package_cache")]
     pub package_cache_path: PathBuf,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub bq_service_account_key_file: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub bq_project_id: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub bq_dataset_id: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub bq_table_id: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub bq_checkpoint_col_id: Option<String>,
-    #[clap(long, global = true)]
+    #[clap(long)]
     pub report_bq_max_table_checkpoint: bool,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_account_identifier: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_warehouse: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_database: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_schema: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_username: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_role: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_password: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_table_id: Option<String>,
-    #[clap(long, default_value = None, global = true)]
+    #[clap(long, default_value = None)]
     pub sf_checkpoint_col_id: Option<String>,
-    #[clap(long, global = true)]
+    #[clap(long)]
     pub report_sf_max_table_checkpoint: bool,
+    /// Maintain a table manifest listing the uploaded files along with their schema versions, so
+    /// query engines can attach the remote directory of the file type as a table. The manifest is
+    /// rewritten after each upload, so only one indexer per file type should enable it.
+    #[clap(long)]
+    pub write_table_manifest: bool,
 }
 
+/// Run a SQL query against the indexer output synced to a local directory. The directory of each
+/// file type is registered as a table named after it, e.g. `checkpoints`, `transactions`,
+/// `events`, `move_call` or `objects`.
+#[derive(Args, Clone, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct QueryArgs {
+    /// Local directory laid out like the remote store, i.e. without the remote store path prefix.
+    #[clap(long)]
+    pub path: PathBuf,
+    /// Format of the files to query.
+    #[clap(long, value_enum, default_value = "csv")]
+    pub file_format: FileFormat,
+    /// SQL query to run, e.g. "SELECT package, COUNT(*) AS calls FROM move_call GROUP BY package
+    /// ORDER BY calls DESC LIMIT 10".
+    pub sql: String,
+}
+
+/// Command line of the indexer. Without a subcommand it indexes checkpoints into files of a file
+/// type and uploads them to the remote store, e.g.
+/// `sui-analytics-indexer --rest-url <url> --file-type checkpoint`, while the `query` subcommand
+/// runs SQL against the uploaded files, e.g.
+/// `sui-analytics-indexer query --path <dir> --file-format parquet "<sql>"`.
+#[derive(Clone, Debug)]
+pub enum AnalyticsCommand {
+    Index(AnalyticsIndexerConfig),
+    Query(QueryArgs),
+}
+
+// Implemented by hand because the derive can't make the flattened indexer config optional while
+// it flattens the remote store config itself.
+impl CommandFactory for AnalyticsCommand {
+    fn command() -> Command {
+        AnalyticsIndexerConfig::augment_args(
+            Command::new("sui-analytics-indexer")
+                .about("Indexer service to upload data for the analytics pipeline."),
+        )
+        .subcommand(QueryArgs::augment_args(Command::new("query")))
+        .args_conflicts_with_subcommands(true)
+        .subcommand_negates_reqs(true)
+    }
+
+    fn command_for_update() -> Command {
+        Self::command()
+    }
+}
+
+impl FromArgMatches for AnalyticsCommand {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        Self::from_arg_matches_mut(&mut matches.clone())
+    }
+
+    fn from_arg_matches_mut(matches: &mut ArgMatches) -> Result<Self, clap::Error> {
+        match matches.remove_subcommand() {
+            Some((name, mut matches)) if name == "query" => {
+                QueryArgs::from_arg_matches_mut(&mut matches).map(Self::Query)
+            }
+            Some((name, _)) => Err(clap::Error::raw(
+                clap::error::ErrorKind::InvalidSubcommand,
+                format!("Unknown subcommand: {name}"),
+            )),
+            None => AnalyticsIndexerConfig::from_arg_matches_mut(matches).map(Self::Index),
+        }
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+        self.update_from_arg_matches_mut(&mut matches.clone())
+    }
+
+    fn update_from_arg_matches_mut(&mut self, matches: &mut ArgMatches) -> Result<(), clap::Error> {
+        *self = Self::from_arg_matches_mut(matches)?;
+        Ok(())
+    }
+}
+
+impl Parser for AnalyticsCommand {}
+
 #[async_trait::async_trait]
 pub trait MaxCheckpointReader: Send + Sync + 'static {
     async fn max_checkpoint(&self) -> Result<i64>;
@@ -292,6 +367,7 @@ impl MaxCheckpointReader for NoOpCheckpointReader {
 pub enum FileFormat {
     CSV = 0,
     PARQUET = 1,
+    ARROW = 2,
 }
 
 impl FileFormat {
@@ -299,6 +375,7 @@ impl FileFormat {
         match self {
             FileFormat::CSV => "csv",
             FileFormat::PARQUET => "parquet",
+            FileFormat::ARROW => "arrow",
         }
     }
 }
@@ -448,9 +525,53 @@ impl From<Option<DynamicFieldType>> for ParquetValue {
     }
 }
 
+/// Type of a column, matching the `ParquetValue` variant its values are stored as.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ParquetType {
+    U64,
+    Str,
+    Bool,
+    I64,
+    OptionU64,
+    OptionStr,
+}
+
+/// Maps the type of a table entry field to the type of its column.
+pub trait ParquetColumn {
+    const TYPE: ParquetType;
+}
+
+macro_rules! impl_parquet_column {
+    ($($type:ty => $parquet_type:ident),* $(,)?) => {
+        $(
+            impl ParquetColumn for $type {
+                const TYPE: ParquetType = ParquetType::$parquet_type;
+            }
+        )*
+    };
+}
+
+impl_parquet_column!(
+    u64 => U64,
+    i64 => I64,
+    String => Str,
+    bool => Bool,
+    Option<u64> => OptionU64,
+    Option<String> => OptionStr,
+    OwnerType => Str,
+    Option<OwnerType> => OptionStr,
+    ObjectStatus => Str,
+    Option<ObjectStatus> => OptionStr,
+    Option<InputObjectKind> => OptionStr,
+    DynamicFieldType => Str,
+    Option<DynamicFieldType> => OptionStr,
+);
+
 pub trait ParquetSchema {
     fn schema() -> Vec<String>;
 
+    fn schema_types() -> Vec<ParquetType>;
+
     fn get_column(&self, idx: usize) -> ParquetValue;
 }
 
@@ -873,6 +994,11 @@ pub fn make_writer<S: Serialize + ParquetSchema>(
             file_type,
             starting_checkpoint_seq_num,
         )?),
+        FileFormat::ARROW => Box::new(ArrowIpcWriter::new(
+            &config.checkpoint_dir,
+            file_type,
+            starting_checkpoint_seq_num,
+        )?),
     })
 }
 
@@ -920,3 +1046,48 @@ pub fn join_paths(base: Option<Path>, child: &Path) -> Path {
     })
     .unwrap_or(child.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnalyticsCommand, FileFormat, FileType};
+    use clap::{CommandFactory, Parser};
+
+    #[test]
+    fn test_parse_command() {
+        AnalyticsCommand::command().debug_assert();
+
+        let command = AnalyticsCommand::try_parse_from([
+            "sui-analytics-indexer",
+            "--rest-url",
+            "http://localhost:9000",
+            "--file-type",
+            "move-call",
+            "--file-format",
+            "parquet",
+        ])
+        .unwrap();
+        let AnalyticsCommand::Index(config) = command else {
+            panic!("Expected the indexer config, got {command:?}");
+        };
+        assert_eq!(config.rest_url, "http://localhost:9000");
+        assert_eq!(config.file_type, FileType::MoveCall);
+        assert_eq!(config.file_format, FileFormat::PARQUET);
+
+        let command = AnalyticsCommand::try_parse_from([
+            "sui-analytics-indexer",
+            "query",
+            "--path",
+            "/tmp/analytics",
+            "SELECT COUNT(*) FROM move_call",
+        ])
+        .unwrap();
+        let AnalyticsCommand::Query(args) = command else {
+            panic!("Expected a query, got {command:?}");
+        };
+        assert_eq!(args.path.to_str(), Some("/tmp/analytics"));
+        assert_eq!(args.file_format, FileFormat::CSV);
+        assert_eq!(args.sql, "SELECT COUNT(*) FROM move_call");
+
+        assert!(AnalyticsCommand::try_parse_from(["sui-analytics-indexer"]).is_err());
+    }
+}
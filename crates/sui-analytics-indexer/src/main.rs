@@ -6,10 +6,11 @@ use clap::*;
 use prometheus::Registry;
 use sui_analytics_indexer::{
     analytics_metrics::AnalyticsMetrics, errors::AnalyticsIndexerError, make_analytics_processor,
-    AnalyticsIndexerConfig,
+    query::run_query, AnalyticsCommand, AnalyticsIndexerConfig,
 };
-use sui_data_ingestion_core::setup_single_workflow;
+use sui_data_ingestion_core::{setup_range_workflow, setup_single_workflow};
 use tokio::signal;
+use tokio::sync::oneshot;
 use tracing::info;
 
 #[tokio::main]
@@ -18,7 +19,13 @@ async fn main() -> Result<()> {
         .with_env()
         .init();
 
-    let config = AnalyticsIndexerConfig::parse();
+    match AnalyticsCommand::parse() {
+        AnalyticsCommand::Index(config) => run_indexer(config).await,
+        AnalyticsCommand::Query(args) => run_query(&args.path, args.file_format, &args.sql).await,
+    }
+}
+
+async fn run_indexer(config: AnalyticsIndexerConfig) -> Result<()> {
     info!("Parsed config: {:#?}", config);
     let registry_service = mysten_metrics::start_prometheus_server(
         format!(
@@ -32,14 +39,35 @@ async fn main() -> Result<()> {
     mysten_metrics::init_metrics(&registry);
     let metrics = AnalyticsMetrics::new(&registry);
     let remote_store_url = config.remote_store_url.clone();
+    let ending_checkpoint = config.ending_checkpoint_seq_num;
     let processor = make_analytics_processor(config, metrics)
         .await
         .map_err(|e| AnalyticsIndexerError::GenericError(e.to_string()))?;
     let watermark = processor.last_committed_checkpoint().unwrap_or_default() + 1;
 
-    let (executor, exit_sender) =
-        setup_single_workflow(processor, remote_store_url, watermark, 1, None).await?;
+    if let Some(ending_checkpoint) = ending_checkpoint {
+        info!("Backfilling checkpoints {watermark} to {ending_checkpoint}");
+        let (executor, exit_sender) = setup_range_workflow(
+            processor,
+            remote_store_url,
+            watermark..=ending_checkpoint,
+            1,
+            1,
+            None,
+        )
+        .await?;
+        exit_on_ctrl_c(exit_sender);
+        executor.await?;
+    } else {
+        let (executor, exit_sender) =
+            setup_single_workflow(processor, remote_store_url, watermark, 1, None).await?;
+        exit_on_ctrl_c(exit_sender);
+        executor.await?;
+    }
+    Ok(())
+}
 
+fn exit_on_ctrl_c(exit_sender: oneshot::Sender<()>) {
     tokio::spawn(async {
         signal::ctrl_c()
             .await
@@ -48,6 +76,4 @@ async fn main() -> Result<()> {
             .send(())
             .expect("Failed to gracefully process shutdown");
     });
-    executor.await?;
-    Ok(())
 }
@@ -0,0 +1,186 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::prelude::{ArrowReadOptions, CsvReadOptions, ParquetReadOptions, SessionContext};
+use strum::IntoEnumIterator;
+use tracing::info;
+
+use sui_storage::object_store::util::path_to_filesystem;
+
+use crate::table_manifest::{TableColumn, TableManifest};
+use crate::tables::{
+    CheckpointEntry, DynamicFieldEntry, EventEntry, MoveCallEntry, MovePackageEntry, ObjectEntry,
+    TransactionEntry, TransactionObjectEntry, WrappedObjectEntry,
+};
+use crate::{FileFormat, FileType, ParquetType};
+
+/// Runs `sql` against the files of the given format under `path`, a local directory laid out like
+/// the remote store the indexer uploads to, and prints the result.
+pub async fn run_query(path: &Path, file_format: FileFormat, sql: &str) -> Result<()> {
+    let ctx = register_tables(path, file_format).await?;
+    ctx.sql(sql).await?.show().await?;
+    Ok(())
+}
+
+/// Registers the directory of every file type found under `path` as a table named after the
+/// directory, e.g. `checkpoints`, `transactions` or `move_call`.
+pub async fn register_tables(path: &Path, file_format: FileFormat) -> Result<SessionContext> {
+    let ctx = SessionContext::new();
+    let mut num_tables = 0;
+    for file_type in FileType::iter() {
+        let table_name = file_type.dir_prefix().to_string();
+        let table_dir = path_to_filesystem(path.to_path_buf(), &file_type.dir_prefix())?;
+        if !table_dir.is_dir() {
+            continue;
+        }
+        let table_path = format!(
+            "{}/",
+            table_dir
+                .to_str()
+                .ok_or(anyhow!("Invalid table path: {}", table_dir.display()))?
+        );
+        match file_format {
+            FileFormat::CSV => {
+                // csv files are written without headers, so their schema comes from the table
+                // entries they were written from
+                let schema = csv_schema(&table_columns(file_type));
+                let options = CsvReadOptions::new()
+                    .has_header(false)
+                    .delimiter(b'|')
+                    .schema(&schema);
+                ctx.register_csv(&table_name, &table_path, options).await?
+            }
+            FileFormat::PARQUET => {
+                ctx.register_parquet(&table_name, &table_path, ParquetReadOptions::default())
+                    .await?
+            }
+            FileFormat::ARROW => {
+                ctx.register_arrow(&table_name, &table_path, ArrowReadOptions::default())
+                    .await?
+            }
+        }
+        info!("Registered table {table_name} from {table_path}");
+        num_tables += 1;
+    }
+    if num_tables == 0 {
+        return Err(anyhow!(
+            "No {} tables found in {}",
+            file_format.file_suffix(),
+            path.display()
+        ));
+    }
+    Ok(ctx)
+}
+
+fn table_columns(file_type: FileType) -> Vec<TableColumn> {
+    match file_type {
+        FileType::Checkpoint => TableManifest::columns::<CheckpointEntry>(),
+        FileType::Object => TableManifest::columns::<ObjectEntry>(),
+        FileType::Transaction => TableManifest::columns::<TransactionEntry>(),
+        FileType::TransactionObjects => TableManifest::columns::<TransactionObjectEntry>(),
+        FileType::Event => TableManifest::columns::<EventEntry>(),
+        FileType::MoveCall => TableManifest::columns::<MoveCallEntry>(),
+        FileType::MovePackage => TableManifest::columns::<MovePackageEntry>(),
+        FileType::DynamicField => TableManifest::columns::<DynamicFieldEntry>(),
+        FileType::WrappedObject => TableManifest::columns::<WrappedObjectEntry>(),
+    }
+}
+
+fn csv_schema(columns: &[TableColumn]) -> Schema {
+    let fields: Vec<_> = columns
+        .iter()
+        .map(|column| {
+            let data_type = match column.column_type {
+                ParquetType::U64 | ParquetType::OptionU64 => DataType::UInt64,
+                ParquetType::I64 => DataType::Int64,
+                ParquetType::Bool => DataType::Boolean,
+                ParquetType::Str | ParquetType::OptionStr => DataType::Utf8,
+            };
+            // empty csv fields are read as nulls whatever the column type
+            Field::new(&column.name, data_type, true)
+        })
+        .collect();
+    Schema::new(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::register_tables;
+    use crate::tables::MoveCallEntry;
+    use crate::writers::arrow_writer::ArrowIpcWriter;
+    use crate::writers::csv_writer::CSVWriter;
+    use crate::writers::parquet_writer::ParquetWriter;
+    use crate::writers::AnalyticsWriter;
+    use crate::{FileFormat, FileType};
+    use datafusion::arrow::array::{Int64Array, StringArray};
+    use tempfile::tempdir;
+
+    fn move_call(checkpoint: u64, package: &str) -> MoveCallEntry {
+        MoveCallEntry {
+            transaction_digest: format!("digest_{checkpoint}"),
+            checkpoint,
+            epoch: 0,
+            timestamp_ms: checkpoint * 1000,
+            package: package.to_string(),
+            module: "module".to_string(),
+            function: "function".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_move_calls() -> anyhow::Result<()> {
+        for file_format in [FileFormat::CSV, FileFormat::PARQUET, FileFormat::ARROW] {
+            let dir = tempdir()?;
+            let mut writer: Box<dyn AnalyticsWriter<MoveCallEntry>> = match file_format {
+                FileFormat::CSV => Box::new(CSVWriter::new(dir.path(), FileType::MoveCall, 0)?),
+                FileFormat::PARQUET => {
+                    Box::new(ParquetWriter::new(dir.path(), FileType::MoveCall, 0)?)
+                }
+                FileFormat::ARROW => {
+                    Box::new(ArrowIpcWriter::new(dir.path(), FileType::MoveCall, 0)?)
+                }
+            };
+            writer.write(&[
+                move_call(0, "0x2"),
+                move_call(1, "0x3"),
+                move_call(2, "0x2"),
+            ])?;
+            writer.flush(3)?;
+            writer.reset(0, 3)?;
+            writer.write(&[move_call(3, "0x2")])?;
+            writer.flush(4)?;
+
+            let ctx = register_tables(dir.path(), file_format).await?;
+            let batches = ctx
+                .sql(
+                    "SELECT package, COUNT(*) AS calls FROM move_call \
+                     GROUP BY package ORDER BY calls DESC",
+                )
+                .await?
+                .collect()
+                .await?;
+            let mut rows = vec![];
+            for batch in batches {
+                let packages = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .unwrap();
+                let calls = batch
+                    .column(1)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                for i in 0..batch.num_rows() {
+                    rows.push((packages.value(i).to_string(), calls.value(i)));
+                }
+            }
+            assert_eq!(rows, vec![("0x2".to_string(), 3), ("0x3".to_string(), 1)]);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use serde::{Deserialize, Serialize};
+
+use sui_storage::object_store::util::{exists, find_all_dirs_with_epoch_prefix, get, put};
+
+use crate::{
+    join_paths, FileFormat, FileMetadata, FileType, ParquetSchema, ParquetType, EPOCH_DIR_PREFIX,
+};
+
+pub const TABLE_MANIFEST_FILENAME: &str = "_table_manifest.json";
+const TABLE_MANIFEST_FORMAT_VERSION: u64 = 1;
+/// Files are partitioned by epoch, through their `epoch_<num>` directory.
+const EPOCH_PARTITION_COLUMN: &str = "epoch";
+
+/// Table level metadata of the files written for a file type, kept next to their epoch
+/// directories so query engines can attach the directory as a table instead of listing it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TableManifest {
+    pub format_version: u64,
+    pub file_type: FileType,
+    pub partition_columns: Vec<String>,
+    /// Every schema files of the table were written with, oldest first.
+    pub schemas: Vec<TableSchema>,
+    /// Files of the table ordered by checkpoint range.
+    pub files: Vec<TableFile>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TableSchema {
+    pub schema_version: u64,
+    pub columns: Vec<TableColumn>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TableColumn {
+    pub name: String,
+    pub column_type: ParquetType,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TableFile {
+    /// Path of the file relative to the table directory.
+    pub path: String,
+    pub file_format: FileFormat,
+    pub epoch: u64,
+    pub checkpoint_seq_range: Range<u64>,
+    pub schema_version: u64,
+}
+
+impl TableManifest {
+    pub fn new(file_type: FileType) -> Self {
+        Self {
+            format_version: TABLE_MANIFEST_FORMAT_VERSION,
+            file_type,
+            partition_columns: vec![EPOCH_PARTITION_COLUMN.to_string()],
+            schemas: vec![],
+            files: vec![],
+        }
+    }
+
+    /// Location of the manifest of the table of `file_type` in the remote store.
+    pub fn path(file_type: FileType, prefix: Option<Path>) -> Path {
+        join_paths(
+            prefix,
+            &file_type.dir_prefix().child(TABLE_MANIFEST_FILENAME),
+        )
+    }
+
+    /// Columns of the table of entries `S`.
+    pub fn columns<S: ParquetSchema>() -> Vec<TableColumn> {
+        S::schema()
+            .into_iter()
+            .zip(S::schema_types())
+            .map(|(name, column_type)| TableColumn { name, column_type })
+            .collect()
+    }
+
+    /// Returns the version of the schema with the given columns, adding it as the latest schema
+    /// if it differs from the current one.
+    pub fn schema_version(&mut self, columns: &[TableColumn]) -> u64 {
+        if let Some(schema) = self.schemas.last() {
+            if schema.columns == columns {
+                return schema.schema_version;
+            }
+        }
+        let schema_version = self
+            .schemas
+            .last()
+            .map_or(0, |schema| schema.schema_version + 1);
+        self.schemas.push(TableSchema {
+            schema_version,
+            columns: columns.to_vec(),
+        });
+        schema_version
+    }
+
+    /// Records a file written with the given schema version, replacing any previous record of
+    /// the same file.
+    pub fn add_file(&mut self, file_metadata: &FileMetadata, schema_version: u64) -> Result<()> {
+        if file_metadata.file_type != self.file_type {
+            return Err(anyhow!(
+                "Cannot add {:?} file to {:?} table",
+                file_metadata.file_type,
+                self.file_type
+            ));
+        }
+        let path = Self::file_path(file_metadata);
+        self.files.retain(|file| file.path != path);
+        self.files.push(TableFile {
+            path,
+            file_format: file_metadata.file_format,
+            epoch: file_metadata.epoch_num,
+            checkpoint_seq_range: file_metadata.checkpoint_seq_range.clone(),
+            schema_version,
+        });
+        self.files.sort_by_key(|file| {
+            (
+                file.checkpoint_seq_range.start,
+                file.checkpoint_seq_range.end,
+            )
+        });
+        Ok(())
+    }
+
+    /// Records the files the manifest does not know of yet as written with the given columns.
+    /// Returns whether any file was added.
+    pub fn add_missing_files(
+        &mut self,
+        files: &[FileMetadata],
+        columns: &[TableColumn],
+    ) -> Result<bool> {
+        let missing: Vec<_> = files
+            .iter()
+            .filter(|file_metadata| {
+                let path = Self::file_path(file_metadata);
+                !self.files.iter().any(|file| file.path == path)
+            })
+            .collect();
+        if missing.is_empty() {
+            return Ok(false);
+        }
+        let schema_version = self.schema_version(columns);
+        for file_metadata in missing {
+            self.add_file(file_metadata, schema_version)?;
+        }
+        Ok(true)
+    }
+
+    /// Path of a file relative to the table directory.
+    fn file_path(file_metadata: &FileMetadata) -> String {
+        let range = &file_metadata.checkpoint_seq_range;
+        format!(
+            "{}{}/{}_{}.{}",
+            EPOCH_DIR_PREFIX,
+            file_metadata.epoch_num,
+            range.start,
+            range.end,
+            file_metadata.file_format.file_suffix()
+        )
+    }
+
+    /// Lists the files of the table of `file_type` in `file_format` found in the remote store.
+    pub async fn list_files(
+        store: &Arc<DynObjectStore>,
+        prefix: Option<Path>,
+        file_type: FileType,
+        file_format: FileFormat,
+    ) -> Result<Vec<FileMetadata>> {
+        let table_dir = join_paths(prefix, &file_type.dir_prefix());
+        let mut files = vec![];
+        for (epoch, epoch_dir) in find_all_dirs_with_epoch_prefix(store, Some(&table_dir)).await? {
+            let entries = store.list_with_delimiter(Some(&epoch_dir)).await?;
+            for entry in entries.objects {
+                let Some((start, end)) = entry
+                    .location
+                    .filename()
+                    .and_then(|filename| filename.strip_suffix(file_format.file_suffix()))
+                    .and_then(|stem| stem.strip_suffix('.'))
+                    .and_then(|range| range.split_once('_'))
+                else {
+                    continue;
+                };
+                let (Ok(start), Ok(end)) = (start.parse::<u64>(), end.parse::<u64>()) else {
+                    continue;
+                };
+                files.push(FileMetadata::new(file_type, file_format, epoch, start..end));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Reads the manifest at `path`, or returns an empty one if the table has no manifest yet.
+    pub async fn read(
+        store: &Arc<DynObjectStore>,
+        path: &Path,
+        file_type: FileType,
+    ) -> Result<Self> {
+        if !exists(store, path).await {
+            return Ok(Self::new(file_type));
+        }
+        let bytes = get(store, path).await?;
+        let manifest: Self = serde_json::from_slice(&bytes)
+            .map_err(|err| anyhow!("Error parsing table manifest {path}: {err}"))?;
+        if manifest.file_type != file_type {
+            return Err(anyhow!(
+                "Table manifest {path} is for {:?} files",
+                manifest.file_type
+            ));
+        }
+        Ok(manifest)
+    }
+
+    pub async fn write(&self, store: &Arc<DynObjectStore>, path: &Path) -> Result<()> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        put(store, path, Bytes::from(bytes)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::table_manifest::{TableColumn, TableManifest};
+    use crate::{FileFormat, FileMetadata, FileType, ParquetType};
+
+    fn column(name: &str, column_type: ParquetType) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            column_type,
+        }
+    }
+
+    #[test]
+    fn test_table_manifest_schema_evolution() -> anyhow::Result<()> {
+        let mut manifest = TableManifest::new(FileType::Checkpoint);
+        let columns = vec![column("sequence_number", ParquetType::U64)];
+        let schema_version = manifest.schema_version(&columns);
+        assert_eq!(schema_version, 0);
+        assert_eq!(manifest.schema_version(&columns), 0);
+        manifest.add_file(
+            &FileMetadata::new(FileType::Checkpoint, FileFormat::ARROW, 0, 10..20),
+            schema_version,
+        )?;
+        manifest.add_file(
+            &FileMetadata::new(FileType::Checkpoint, FileFormat::ARROW, 0, 0..10),
+            schema_version,
+        )?;
+
+        // A new column starts a new schema version, while files are recorded once
+        let mut columns = columns;
+        columns.push(column("digest", ParquetType::OptionStr));
+        let schema_version = manifest.schema_version(&columns);
+        assert_eq!(schema_version, 1);
+        manifest.add_file(
+            &FileMetadata::new(FileType::Checkpoint, FileFormat::ARROW, 1, 20..30),
+            schema_version,
+        )?;
+        manifest.add_file(
+            &FileMetadata::new(FileType::Checkpoint, FileFormat::ARROW, 1, 20..30),
+            schema_version,
+        )?;
+
+        assert_eq!(manifest.schemas.len(), 2);
+        let files: Vec<_> = manifest
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.schema_version))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("epoch_0/0_10.arrow", 0),
+                ("epoch_0/10_20.arrow", 0),
+                ("epoch_1/20_30.arrow", 1),
+            ]
+        );
+        assert!(manifest
+            .add_file(
+                &FileMetadata::new(FileType::Object, FileFormat::ARROW, 1, 30..40),
+                schema_version,
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_manifest_add_missing_files() -> anyhow::Result<()> {
+        let mut manifest = TableManifest::new(FileType::Checkpoint);
+        let columns = vec![column("sequence_number", ParquetType::U64)];
+        let files = vec![
+            FileMetadata::new(FileType::Checkpoint, FileFormat::ARROW, 0, 0..10),
+            FileMetadata::new(FileType::Checkpoint, FileFormat::ARROW, 0, 10..20),
+        ];
+        assert!(!manifest.add_missing_files(&[], &columns)?);
+        assert!(manifest.schemas.is_empty());
+        manifest.add_file(&files[0], manifest.schema_version(&columns))?;
+
+        // Files already recorded keep the schema version they were recorded with
+        let mut new_columns = columns.clone();
+        new_columns.push(column("digest", ParquetType::OptionStr));
+        assert!(manifest.add_missing_files(&files, &new_columns)?);
+        assert!(!manifest.add_missing_files(&files, &new_columns)?);
+        let files: Vec<_> = manifest
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.schema_version))
+            .collect();
+        assert_eq!(
+            files,
+            vec![("epoch_0/0_10.arrow", 0), ("epoch_0/10_20.arrow", 1)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::writers::to_record_batch;
+use crate::{AnalyticsWriter, FileFormat, FileType};
+use crate::{ParquetSchema, ParquetValue};
+use anyhow::{anyhow, Result};
+use arrow::ipc::writer::FileWriter;
+use serde::Serialize;
+use std::fs::File;
+use std::fs::{create_dir_all, remove_file};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use sui_storage::object_store::util::path_to_filesystem;
+use sui_types::base_types::EpochId;
+
+// Save table entries to arrow IPC files.
+pub(crate) struct ArrowIpcWriter {
+    root_dir_path: PathBuf,
+    file_type: FileType,
+    epoch: EpochId,
+    checkpoint_range: Range<u64>,
+    data: Vec<Vec<ParquetValue>>,
+}
+
+impl ArrowIpcWriter {
+    pub(crate) fn new(
+        root_dir_path: &Path,
+        file_type: FileType,
+        start_checkpoint_seq_num: u64,
+    ) -> Result<Self> {
+        let checkpoint_range = start_checkpoint_seq_num..u64::MAX;
+        Ok(Self {
+            root_dir_path: root_dir_path.to_path_buf(),
+            file_type,
+            epoch: 0,
+            checkpoint_range,
+            data: vec![],
+        })
+    }
+
+    fn file(&self) -> Result<File> {
+        let file_path = path_to_filesystem(
+            self.root_dir_path.clone(),
+            &self
+                .file_type
+                .file_path(FileFormat::ARROW, self.epoch, self.checkpoint_range.clone()),
+        )?;
+        create_dir_all(file_path.parent().ok_or(anyhow!("Bad directory path"))?)?;
+        if file_path.exists() {
+            remove_file(&file_path)?;
+        }
+        Ok(File::create(&file_path)?)
+    }
+}
+
+impl<S: Serialize + ParquetSchema> AnalyticsWriter<S> for ArrowIpcWriter {
+    fn file_format(&self) -> Result<FileFormat> {
+        Ok(FileFormat::ARROW)
+    }
+
+    fn write(&mut self, rows: &[S]) -> Result<()> {
+        for row in rows {
+            for col_idx in 0..S::schema().len() {
+                if col_idx == self.data.len() {
+                    self.data.push(vec![]);
+                }
+                self.data[col_idx].push(row.get_column(col_idx));
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, end_checkpoint_seq_num: u64) -> Result<bool> {
+        if self.data.is_empty() {
+            return Ok(false);
+        }
+        self.checkpoint_range.end = end_checkpoint_seq_num;
+        let batch = to_record_batch::<S>(std::mem::take(&mut self.data))?;
+
+        let mut writer = FileWriter::try_new(self.file()?, &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(true)
+    }
+
+    fn reset(&mut self, epoch_num: EpochId, start_checkpoint_seq_num: u64) -> Result<()> {
+        self.checkpoint_range.start = start_checkpoint_seq_num;
+        self.checkpoint_range.end = u64::MAX;
+        self.epoch = epoch_num;
+        self.data = vec![];
+        Ok(())
+    }
+
+    fn file_size(&self) -> Result<Option<u64>> {
+        // like the parquet writer, rows are only serialized into a file when flush is invoked
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tables::MoveCallEntry;
+    use crate::writers::arrow_writer::ArrowIpcWriter;
+    use crate::writers::AnalyticsWriter;
+    use crate::{FileFormat, FileType, ParquetSchema};
+    use arrow::array::{StringArray, UInt64Array};
+    use arrow::ipc::reader::FileReader;
+    use std::fs::File;
+    use sui_storage::object_store::util::path_to_filesystem;
+    use tempfile::tempdir;
+
+    fn move_call(checkpoint: u64) -> MoveCallEntry {
+        MoveCallEntry {
+            transaction_digest: format!("digest_{checkpoint}"),
+            checkpoint,
+            epoch: 1,
+            timestamp_ms: checkpoint * 1000,
+            package: "0x2".to_string(),
+            module: "module".to_string(),
+            function: "function".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_arrow_ipc_writer() -> anyhow::Result<()> {
+        let dir = tempdir()?;
+        let mut writer = ArrowIpcWriter::new(dir.path(), FileType::MoveCall, 10)?;
+        assert!(!AnalyticsWriter::<MoveCallEntry>::flush(&mut writer, 10)?);
+
+        AnalyticsWriter::<MoveCallEntry>::reset(&mut writer, 1, 10)?;
+        writer.write(&[move_call(10), move_call(11)])?;
+        assert!(AnalyticsWriter::<MoveCallEntry>::flush(&mut writer, 12)?);
+
+        let file_path = path_to_filesystem(
+            dir.path().to_path_buf(),
+            &FileType::MoveCall.file_path(FileFormat::ARROW, 1, 10..12),
+        )?;
+        let reader = FileReader::try_new(File::open(file_path)?, None)?;
+        let columns: Vec<_> = reader
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        assert_eq!(columns, MoveCallEntry::schema());
+        let batches = reader.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        let checkpoints = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(checkpoints.values().to_vec(), vec![10, 11]);
+        let digests = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(digests.value(1), "digest_11");
+
+        // Rows are not carried over to the next file
+        AnalyticsWriter::<MoveCallEntry>::reset(&mut writer, 1, 12)?;
+        assert!(!AnalyticsWriter::<MoveCallEntry>::flush(&mut writer, 12)?);
+        Ok(())
+    }
+}
@@ -65,6 +65,10 @@ pub struct NodeConfig {
     #[serde(default)]
     pub enable_experimental_rest_api: bool,
 
+    /// Serve the BCS encoded gRPC read api alongside JSON-RPC, on `json_rpc_address`.
+    #[serde(default)]
+    pub enable_grpc_read_api: bool,
+
     #[serde(default = "default_metrics_address")]
     pub metrics_address: SocketAddr,
     #[serde(default = "default_admin_interface_port")]
@@ -142,6 +146,12 @@ pub struct NodeConfig {
     #[serde(default)]
     pub certificate_deny_config: CertificateDenyConfig,
 
+    /// Optional YAML file holding `transaction-deny-config` and/or `certificate-deny-config`
+    /// overrides. The node watches this file and applies changes without a restart; removing
+    /// the file reverts to the configs above.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny_config_path: Option<PathBuf>,
+
     #[serde(default)]
     pub state_debug_dump_config: StateDebugDumpConfig,
 
@@ -729,6 +739,13 @@ pub struct StateSnapshotConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object_store_config: Option<ObjectStoreConfig>,
     pub concurrency: usize,
+    /// If set, a full snapshot is only written for epochs that are a multiple of this interval,
+    /// and the snapshots of the epochs in between are incremental, each recording the changes
+    /// since the previous epoch. The db checkpoint of an epoch is then kept, and its upload
+    /// delayed, until the snapshot of the next epoch is written. Only full snapshots are written
+    /// if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_snapshot_interval: Option<u64>,
 }
 
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
@@ -6,6 +6,7 @@ use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::execution_cache::TransactionCacheRead;
 use crate::rest_index::RestIndexStore;
 use crate::transaction_outputs::TransactionOutputs;
+use crate::transaction_signing_filter::TransactionSigningFilter;
 use crate::verify_indexes::verify_indexes;
 use anyhow::anyhow;
 use arc_swap::{ArcSwap, Guard};
@@ -42,7 +43,9 @@ use std::{
     sync::Arc,
     vec,
 };
+use sui_config::certificate_deny_config::CertificateDenyConfig;
 use sui_config::node::{AuthorityOverloadConfig, StateDebugDumpConfig};
+use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_config::NodeConfig;
 use sui_types::crypto::RandomnessRound;
 use sui_types::execution_status::ExecutionStatus;
@@ -109,6 +112,7 @@ use sui_types::messages_grpc::{
 };
 use sui_types::metrics::{BytecodeVerifierMetrics, LimitsMetrics};
 use sui_types::object::{MoveObject, Owner, PastObjectRead, OBJECT_START_VERSION};
+use sui_types::signature::GenericSignature;
 use sui_types::storage::{
     BackingPackageStore, BackingStore, ObjectKey, ObjectOrTombstone, ObjectStore, WriteKind,
 };
@@ -290,6 +294,9 @@ pub struct AuthorityMetrics {
 
     pub authenticator_state_update_failed: IntCounter,
 
+    /// Count of transactions rejected by the transaction deny config, by rule
+    pub transaction_denied_by_rule: IntCounterVec,
+
     /// Count of zklogin signatures
     pub zklogin_sig_count: IntCounter,
     /// Count of multisig signatures
@@ -701,6 +708,13 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            transaction_denied_by_rule: register_int_counter_vec_with_registry!(
+                "transaction_denied_by_rule",
+                "Number of transactions rejected by the transaction deny config, by rule",
+                &["rule"],
+                registry,
+            )
+            .unwrap(),
             zklogin_sig_count: register_int_counter_with_registry!(
                 "zklogin_sig_count",
                 "Count of zkLogin signatures",
@@ -781,6 +795,10 @@ pub struct AuthorityState {
 
     pub config: NodeConfig,
 
+    /// Deny configs applied to transactions and certificates, which may differ from
+    /// the ones in `config` as they can be updated at runtime.
+    transaction_signing_filter: Arc<TransactionSigningFilter>,
+
     /// Current overload status in this authority. Updated periodically.
     pub overload_info: AuthorityOverloadInfo,
 }
@@ -800,6 +818,52 @@ impl AuthorityState {
         !self.is_validator(epoch_store)
     }
 
+    pub fn transaction_signing_filter(&self) -> &Arc<TransactionSigningFilter> {
+        &self.transaction_signing_filter
+    }
+
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.transaction_signing_filter.transaction_deny_config()
+    }
+
+    pub fn certificate_deny_config(&self) -> Arc<CertificateDenyConfig> {
+        self.transaction_signing_filter.certificate_deny_config()
+    }
+
+    /// Checks the transaction against the current transaction deny config, recording
+    /// which rule rejected it, if any.
+    fn check_transaction_deny_config(
+        &self,
+        tx_data: &TransactionData,
+        tx_signatures: &[GenericSignature],
+        input_object_kinds: &[InputObjectKind],
+        receiving_objects: &[ObjectRef],
+    ) -> SuiResult {
+        let deny_config = self.transaction_deny_config();
+        sui_transaction_checks::deny::check_transaction_deny_rules(
+            tx_data,
+            tx_signatures,
+            input_object_kinds,
+            receiving_objects,
+            &deny_config,
+            self.get_backing_package_store().as_ref(),
+        )
+        .map_err(|err| {
+            if let Some(rule) = err.rule() {
+                debug!(
+                    sender = ?tx_data.sender(),
+                    %rule,
+                    "Transaction rejected by deny config"
+                );
+                self.metrics
+                    .transaction_denied_by_rule
+                    .with_label_values(&[rule.as_str()])
+                    .inc();
+            }
+            err.into()
+        })
+    }
+
     pub fn committee_store(&self) -> &Arc<CommitteeStore> {
         &self.committee_store
     }
@@ -836,13 +900,11 @@ impl AuthorityState {
         // Note: the deny checks may do redundant package loads but:
         // - they only load packages when there is an active package deny map
         // - the loads are cached anyway
-        sui_transaction_checks::deny::check_transaction_for_signing(
+        self.check_transaction_deny_config(
             tx_data,
             transaction.tx_signatures(),
             &input_object_kinds,
             &receiving_objects_refs,
-            &self.config.transaction_deny_config,
-            self.get_backing_package_store().as_ref(),
         )?;
 
         let (input_objects, receiving_objects) = self
@@ -1567,7 +1629,7 @@ impl AuthorityState {
                 self.config
                     .expensive_safety_check_config
                     .enable_deep_per_tx_sui_conservation_check(),
-                self.config.certificate_deny_config.certificate_deny_set(),
+                self.certificate_deny_config().certificate_deny_set(),
                 &epoch_store.epoch_start_config().epoch_data().epoch_id(),
                 epoch_store
                     .epoch_start_config()
@@ -1623,7 +1685,33 @@ impl AuthorityState {
         Option<ObjectID>,
     )> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
-        if !self.is_fullnode(&epoch_store) {
+        self.check_dry_exec_supported(&epoch_store, &transaction)?;
+
+        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest)
+            .await
+    }
+
+    /// Executes `transaction` against the latest state without committing its results, returning
+    /// the temporary store holding its input and written objects and events, its effects and the
+    /// id of the mock gas coin used if the transaction has no gas payment.
+    pub async fn simulate_transaction(
+        &self,
+        transaction: TransactionData,
+    ) -> SuiResult<(InnerTemporaryStore, TransactionEffects, Option<ObjectID>)> {
+        let epoch_store = self.load_epoch_store_one_call_per_task();
+        self.check_dry_exec_supported(&epoch_store, &transaction)?;
+
+        let transaction_digest = TransactionDigest::new(default_hash(&transaction));
+        self.dry_exec_transaction_to_effects(&epoch_store, &transaction, transaction_digest)
+            .await
+    }
+
+    fn check_dry_exec_supported(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        transaction: &TransactionData,
+    ) -> SuiResult {
+        if !self.is_fullnode(epoch_store) {
             return Err(SuiError::UnsupportedFeatureError {
                 error: "dry-exec is only supported on fullnodes".to_string(),
             });
@@ -1635,8 +1723,7 @@ impl AuthorityState {
             });
         }
 
-        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest)
-            .await
+        Ok(())
     }
 
     pub async fn dry_exec_transaction_for_benchmark(
@@ -1665,19 +1752,93 @@ impl AuthorityState {
         TransactionEffects,
         Option<ObjectID>,
     )> {
+        let (inner_temp_store, effects, mock_gas) = self
+            .dry_exec_transaction_to_effects(epoch_store, &transaction, transaction_digest)
+            .await?;
+        let tx_digest = *effects.transaction_digest();
+
+        let module_cache =
+            TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
+
+        let mut layout_resolver =
+            epoch_store
+                .executor()
+                .type_layout_resolver(Box::new(PackageStoreWithFallback::new(
+                    &inner_temp_store,
+                    self.get_backing_package_store(),
+                )));
+        // Returning empty vector here because we recalculate changes in the rpc layer.
+        let object_changes = Vec::new();
+
+        // Returning empty vector here because we recalculate changes in the rpc layer.
+        let balance_changes = Vec::new();
+
+        let written_with_kind = effects
+            .created()
+            .into_iter()
+            .map(|(oref, _)| (oref, WriteKind::Create))
+            .chain(
+                effects
+                    .unwrapped()
+                    .into_iter()
+                    .map(|(oref, _)| (oref, WriteKind::Unwrap)),
+            )
+            .chain(
+                effects
+                    .mutated()
+                    .into_iter()
+                    .map(|(oref, _)| (oref, WriteKind::Mutate)),
+            )
+            .map(|(oref, kind)| {
+                let obj = inner_temp_store.written.get(&oref.0).unwrap();
+                // TODO: Avoid clones.
+                (oref.0, (oref, obj.clone(), kind))
+            })
+            .collect();
+
+        Ok((
+            DryRunTransactionBlockResponse {
+                input: SuiTransactionBlockData::try_from(transaction, &module_cache).map_err(
+                    |e| SuiError::TransactionSerializationError {
+                        error: format!(
+                            "Failed to convert transaction to SuiTransactionBlockData: {}",
+                            e
+                        ),
+                    },
+                )?, // TODO: replace the underlying try_from to SuiError. This one goes deep
+                effects: effects.clone().try_into()?,
+                events: SuiTransactionBlockEvents::try_from(
+                    inner_temp_store.events.clone(),
+                    tx_digest,
+                    None,
+                    layout_resolver.as_mut(),
+                )?,
+                object_changes,
+                balance_changes,
+            },
+            written_with_kind,
+            effects,
+            mock_gas,
+        ))
+    }
+
+    async fn dry_exec_transaction_to_effects(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        transaction: &TransactionData,
+        transaction_digest: TransactionDigest,
+    ) -> SuiResult<(InnerTemporaryStore, TransactionEffects, Option<ObjectID>)> {
         // Cheap validity checks for a transaction, including input size limits.
         transaction.validity_check_no_gas_check(epoch_store.protocol_config())?;
 
         let input_object_kinds = transaction.input_objects()?;
         let receiving_object_refs = transaction.receiving_objects();
 
-        sui_transaction_checks::deny::check_transaction_for_signing(
-            &transaction,
+        self.check_transaction_deny_config(
+            transaction,
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.config.transaction_deny_config,
-            self.get_backing_package_store().as_ref(),
         )?;
 
         let (input_objects, receiving_objects) = self
@@ -1711,7 +1872,7 @@ impl AuthorityState {
                 sui_transaction_checks::check_transaction_input_with_given_gas(
                     epoch_store.protocol_config(),
                     epoch_store.reference_gas_price(),
-                    &transaction,
+                    transaction,
                     input_objects,
                     receiving_objects,
                     gas_object,
@@ -1724,7 +1885,7 @@ impl AuthorityState {
                 sui_transaction_checks::check_transaction_input(
                     epoch_store.protocol_config(),
                     epoch_store.reference_gas_price(),
-                    &transaction,
+                    transaction,
                     input_objects,
                     &receiving_objects,
                     &self.metrics.bytecode_verifier_metrics,
@@ -1747,7 +1908,7 @@ impl AuthorityState {
                 protocol_config,
                 self.metrics.limits_metrics.clone(),
                 expensive_checks,
-                self.config.certificate_deny_config.certificate_deny_set(),
+                self.certificate_deny_config().certificate_deny_set(),
                 &epoch_store.epoch_start_config().epoch_data().epoch_id(),
                 epoch_store
                     .epoch_start_config()
@@ -1760,71 +1921,8 @@ impl AuthorityState {
                 signer,
                 transaction_digest,
             );
-        let tx_digest = *effects.transaction_digest();
-
-        let module_cache =
-            TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
-
-        let mut layout_resolver =
-            epoch_store
-                .executor()
-                .type_layout_resolver(Box::new(PackageStoreWithFallback::new(
-                    &inner_temp_store,
-                    self.get_backing_package_store(),
-                )));
-        // Returning empty vector here because we recalculate changes in the rpc layer.
-        let object_changes = Vec::new();
-
-        // Returning empty vector here because we recalculate changes in the rpc layer.
-        let balance_changes = Vec::new();
-
-        let written_with_kind = effects
-            .created()
-            .into_iter()
-            .map(|(oref, _)| (oref, WriteKind::Create))
-            .chain(
-                effects
-                    .unwrapped()
-                    .into_iter()
-                    .map(|(oref, _)| (oref, WriteKind::Unwrap)),
-            )
-            .chain(
-                effects
-                    .mutated()
-                    .into_iter()
-                    .map(|(oref, _)| (oref, WriteKind::Mutate)),
-            )
-            .map(|(oref, kind)| {
-                let obj = inner_temp_store.written.get(&oref.0).unwrap();
-                // TODO: Avoid clones.
-                (oref.0, (oref, obj.clone(), kind))
-            })
-            .collect();
 
-        Ok((
-            DryRunTransactionBlockResponse {
-                input: SuiTransactionBlockData::try_from(transaction, &module_cache).map_err(
-                    |e| SuiError::TransactionSerializationError {
-                        error: format!(
-                            "Failed to convert transaction to SuiTransactionBlockData: {}",
-                            e
-                        ),
-                    },
-                )?, // TODO: replace the underlying try_from to SuiError. This one goes deep
-                effects: effects.clone().try_into()?,
-                events: SuiTransactionBlockEvents::try_from(
-                    inner_temp_store.events.clone(),
-                    tx_digest,
-                    None,
-                    layout_resolver.as_mut(),
-                )?,
-                object_changes,
-                balance_changes,
-            },
-            written_with_kind,
-            effects,
-            mock_gas,
-        ))
+        Ok((inner_temp_store, effects, mock_gas))
     }
 
     /// The object ID for gas can be any object ID, even for an uncreated object
@@ -1890,13 +1988,11 @@ impl AuthorityState {
         let input_object_kinds = transaction.input_objects()?;
         let receiving_object_refs = transaction.receiving_objects();
 
-        sui_transaction_checks::deny::check_transaction_for_signing(
+        self.check_transaction_deny_config(
             &transaction,
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.config.transaction_deny_config,
-            self.get_backing_package_store().as_ref(),
         )?;
 
         let (mut input_objects, receiving_objects) = self
@@ -1988,7 +2084,7 @@ impl AuthorityState {
             protocol_config,
             self.metrics.limits_metrics.clone(),
             /* expensive checks */ false,
-            self.config.certificate_deny_config.certificate_deny_set(),
+            self.certificate_deny_config().certificate_deny_set(),
             &epoch_store.epoch_start_config().epoch_data().epoch_id(),
             epoch_store
                 .epoch_start_config()
@@ -2655,6 +2751,10 @@ impl AuthorityState {
             _pruner,
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
+            transaction_signing_filter: Arc::new(TransactionSigningFilter::new(
+                config.transaction_deny_config.clone(),
+                config.certificate_deny_config.clone(),
+            )),
             config,
             overload_info: AuthorityOverloadInfo::default(),
         });
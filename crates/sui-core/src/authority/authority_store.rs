@@ -777,6 +777,27 @@ impl AuthorityStore {
         Ok(())
     }
 
+    /// Removes objects restored from an earlier snapshot that are no longer live, along with
+    /// their live object markers. Indirect objects are left in place.
+    pub fn bulk_remove_live_objects(
+        perpetual_db: &AuthorityPerpetualTables,
+        object_refs: impl Iterator<Item = ObjectRef>,
+    ) -> SuiResult<()> {
+        let mut batch = perpetual_db.objects.batch();
+        for object_ref in object_refs {
+            batch.delete_batch(
+                &perpetual_db.objects,
+                iter::once(ObjectKey::from(object_ref)),
+            )?;
+            batch.delete_batch(
+                &perpetual_db.live_owned_object_markers,
+                iter::once(object_ref),
+            )?;
+        }
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn set_epoch_start_configuration(
         &self,
         epoch_start_configuration: &EpochStartConfiguration,
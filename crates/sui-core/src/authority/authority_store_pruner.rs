@@ -22,8 +22,10 @@ use sui_config::node::AuthorityStorePruningConfig;
 use sui_storage::mutex_table::RwLockTable;
 use sui_types::base_types::SequenceNumber;
 use sui_types::committee::EpochId;
+use sui_types::digests::TransactionDigest;
 use sui_types::effects::TransactionEffects;
 use sui_types::effects::TransactionEffectsAPI;
+use sui_types::event::Event;
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointDigest, CheckpointSequenceNumber,
@@ -228,6 +230,27 @@ impl AuthorityStorePruner {
         Ok(())
     }
 
+    /// The events emitted by the transactions of `effects_to_prune`, which the rest index needs to
+    /// prune its event indexes.
+    fn events_to_prune(
+        perpetual_db: &AuthorityPerpetualTables,
+        effects_to_prune: &[TransactionEffects],
+    ) -> Result<HashMap<TransactionDigest, Vec<Event>>, TypedStoreError> {
+        let mut events_to_prune = HashMap::new();
+        for effects in effects_to_prune {
+            let Some(event_digest) = effects.events_digest() else {
+                continue;
+            };
+            let events = perpetual_db
+                .events
+                .safe_range_iter((*event_digest, 0)..=(*event_digest, usize::MAX))
+                .map(|entry| entry.map(|(_, event)| event))
+                .collect::<Result<Vec<_>, _>>()?;
+            events_to_prune.insert(*effects.transaction_digest(), events);
+        }
+        Ok(events_to_prune)
+    }
+
     fn prune_checkpoints(
         perpetual_db: &Arc<AuthorityPerpetualTables>,
         checkpoint_db: &Arc<CheckpointStore>,
@@ -296,7 +319,8 @@ impl AuthorityStorePruner {
         )?;
 
         if let Some(rest_index) = rest_index {
-            rest_index.prune(&checkpoint_content_to_prune)?;
+            let events_to_prune = Self::events_to_prune(perpetual_db, effects_to_prune)?;
+            rest_index.prune(&checkpoint_content_to_prune, &events_to_prune)?;
         }
         perpetual_batch.write()?;
         checkpoints_batch.write()?;
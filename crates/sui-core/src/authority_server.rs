@@ -307,19 +307,38 @@ impl ValidatorService {
         traffic_controller_metrics: TrafficControllerMetrics,
         policy_config: Option<PolicyConfig>,
         firewall_config: Option<RemoteFirewallConfig>,
+    ) -> Self {
+        let traffic_controller = policy_config.map(|policy| {
+            Arc::new(TrafficController::spawn(
+                policy,
+                traffic_controller_metrics,
+                firewall_config,
+            ))
+        });
+        Self::new_with_traffic_controller(
+            state,
+            consensus_adapter,
+            validator_metrics,
+            traffic_controller,
+        )
+    }
+
+    /// Creates a service sharing an already running traffic controller, so
+    /// that its policy can be reconfigured by the owner of the controller.
+    pub fn new_with_traffic_controller(
+        state: Arc<AuthorityState>,
+        consensus_adapter: Arc<ConsensusAdapter>,
+        validator_metrics: Arc<ValidatorServiceMetrics>,
+        traffic_controller: Option<Arc<TrafficController>>,
     ) -> Self {
         Self {
             state,
             consensus_adapter,
             metrics: validator_metrics,
-            traffic_controller: policy_config.clone().map(|policy| {
-                Arc::new(TrafficController::spawn(
-                    policy,
-                    traffic_controller_metrics,
-                    firewall_config,
-                ))
-            }),
-            client_id_source: policy_config.map(|policy| policy.client_id_source),
+            client_id_source: traffic_controller
+                .as_ref()
+                .map(|controller| controller.client_id_source().clone()),
+            traffic_controller,
         }
     }
 
@@ -920,9 +939,16 @@ impl ValidatorService {
         Ok((tonic::Response::new(response), Weight::one()))
     }
 
-    async fn handle_traffic_req(&self, client: Option<IpAddr>) -> Result<(), tonic::Status> {
+    async fn handle_traffic_req(
+        &self,
+        client: Option<IpAddr>,
+        endpoint: &str,
+    ) -> Result<(), tonic::Status> {
         if let Some(traffic_controller) = &self.traffic_controller {
-            if !traffic_controller.check(&client, &None).await {
+            if !traffic_controller
+                .check_method(&client, &None, endpoint)
+                .await
+            {
                 // Entity in blocklist
                 Err(tonic::Status::from_error(SuiError::TooManyRequests.into()))
             } else {
@@ -936,6 +962,7 @@ impl ValidatorService {
     fn handle_traffic_resp<T>(
         &self,
         client: Option<IpAddr>,
+        endpoint: &str,
         wrapped_response: WrappedServiceResponse<T>,
     ) -> Result<tonic::Response<T>, tonic::Status> {
         let (error, spam_weight, unwrapped_response) = match wrapped_response {
@@ -948,6 +975,7 @@ impl ValidatorService {
         };
 
         if let Some(traffic_controller) = self.traffic_controller.clone() {
+            let spam_weight = traffic_controller.spam_weight(endpoint, spam_weight);
             traffic_controller.tally(TrafficTally {
                 direct: client,
                 through_fullnode: None,
@@ -1055,12 +1083,16 @@ macro_rules! handle_with_decoration {
             }
         };
 
+        // endpoint name as used in per-method traffic control policies,
+        // e.g. `handle_certificate_v2`
+        let endpoint = stringify!($func_name).trim_end_matches("_impl");
+
         // check if either IP is blocked, in which case return early
-        $self.handle_traffic_req(client.clone()).await?;
+        $self.handle_traffic_req(client.clone(), endpoint).await?;
 
         // handle traffic tallying
         let wrapped_response = $self.$func_name($request).await;
-        $self.handle_traffic_resp(client, wrapped_response)
+        $self.handle_traffic_resp(client, endpoint, wrapped_response)
     }};
 }
 
@@ -148,6 +148,8 @@ pub struct CheckpointExecutor {
     accumulator: Arc<StateAccumulator>,
     config: CheckpointExecutorConfig,
     metrics: Arc<CheckpointExecutorMetrics>,
+    /// Notified of the sequence number of every checkpoint once it has been executed.
+    executed_checkpoint_sender: Option<broadcast::Sender<CheckpointSequenceNumber>>,
 }
 
 impl CheckpointExecutor {
@@ -158,6 +160,7 @@ impl CheckpointExecutor {
         accumulator: Arc<StateAccumulator>,
         config: CheckpointExecutorConfig,
         metrics: Arc<CheckpointExecutorMetrics>,
+        executed_checkpoint_sender: Option<broadcast::Sender<CheckpointSequenceNumber>>,
     ) -> Self {
         Self {
             mailbox,
@@ -169,6 +172,7 @@ impl CheckpointExecutor {
             accumulator,
             config,
             metrics,
+            executed_checkpoint_sender,
         }
     }
 
@@ -185,6 +189,7 @@ impl CheckpointExecutor {
             accumulator,
             Default::default(),
             CheckpointExecutorMetrics::new_for_tests(),
+            None,
         )
     }
 
@@ -412,6 +417,11 @@ impl CheckpointExecutor {
             .last_executed_checkpoint_timestamp_ms
             .set(checkpoint.timestamp_ms as i64);
         checkpoint.report_checkpoint_age_ms(&self.metrics.last_executed_checkpoint_age_ms);
+
+        if let Some(sender) = &self.executed_checkpoint_sender {
+            // Sending only fails when nobody is subscribed
+            let _ = sender.send(seq);
+        }
     }
 
     /// Post processing and plumbing after we executed a checkpoint. This function is guaranteed
@@ -74,4 +74,4 @@ mod type_param_tests;
 pub mod signature_verifier;
 
 pub mod runtime;
-mod transaction_signing_filter;
+pub mod transaction_signing_filter;
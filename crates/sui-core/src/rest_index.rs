@@ -5,10 +5,13 @@ use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use crate::authority::authority_store_tables::LiveObject;
 use crate::authority::AuthorityStore;
 use crate::checkpoints::CheckpointStore;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
 use move_core_types::language_storage::TypeTag;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -23,11 +26,14 @@ use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::SuiAddress;
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldType};
-use sui_types::messages_checkpoint::CheckpointContents;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::event::Event;
+use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSequenceNumber};
 use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::BackingPackageStore;
+use sui_types::storage::EventPosition;
 use sui_types::type_resolver::LayoutResolver;
 use tracing::{debug, info};
 use typed_store::rocks::{DBMap, MetricConf};
@@ -36,7 +42,7 @@ use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::TypedStoreError;
 use typed_store_derive::DBMapUtils;
 
-const CURRENT_DB_VERSION: u64 = 0;
+const CURRENT_DB_VERSION: u64 = 3;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct MetadataInfo {
@@ -108,6 +114,115 @@ pub struct TransactionInfo {
     pub checkpoint: u64,
 }
 
+/// A coin owned by an address. Keyed by coin object, rather than holding a running total per
+/// coin type, so that indexing a checkpoint again overwrites its entries instead of counting them
+/// twice.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BalanceKey {
+    pub owner: SuiAddress,
+    pub coin_type: TypeTag,
+    pub object_id: ObjectID,
+}
+
+impl BalanceKey {
+    /// The key of `object` in the balance index, if it is a coin owned by an address
+    fn from_coin(object: &Object) -> Option<(Self, BalanceIndexInfo)> {
+        let Owner::AddressOwner(owner) = object.owner else {
+            return None;
+        };
+        let coin_type = object.coin_type_maybe()?;
+        let key = Self {
+            owner,
+            coin_type,
+            object_id: object.id(),
+        };
+        let info = BalanceIndexInfo {
+            balance: object.get_coin_value_unsafe(),
+        };
+        Some((key, info))
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct BalanceIndexInfo {
+    pub balance: u64,
+}
+
+/// The sum of the coins of a type owned by an address.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BalanceSummary {
+    pub owner: SuiAddress,
+    pub coin_type: TypeTag,
+    pub balance: u64,
+    pub coin_object_count: u64,
+}
+
+/// Events of a type, regardless of its type parameters, in the order they were emitted in.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct EventTypeIndexKey {
+    pub address: AccountAddress,
+    pub module: Identifier,
+    pub name: Identifier,
+    pub position: EventPosition,
+}
+
+impl EventTypeIndexKey {
+    fn new(event_type: &StructTag, position: EventPosition) -> Self {
+        Self {
+            address: event_type.address,
+            module: event_type.module.clone(),
+            name: event_type.name.clone(),
+            position,
+        }
+    }
+}
+
+/// Events emitted by transactions sent by an address, in the order they were emitted in.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct EventSenderIndexKey {
+    pub sender: SuiAddress,
+    pub position: EventPosition,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct EventIndexInfo {
+    // the position of the event is a part of the Key
+    pub transaction_digest: TransactionDigest,
+    pub timestamp_ms: u64,
+    pub sender: SuiAddress,
+    pub type_: StructTag,
+}
+
+/// The entries of the event indexes for the events emitted by a transaction.
+fn event_index_entries<'a>(
+    checkpoint: CheckpointSequenceNumber,
+    timestamp_ms: u64,
+    transaction: usize,
+    transaction_digest: TransactionDigest,
+    events: &'a [Event],
+) -> impl Iterator<Item = (EventTypeIndexKey, EventSenderIndexKey, EventIndexInfo)> + 'a {
+    events.iter().enumerate().map(move |(event_seq, event)| {
+        let position = EventPosition {
+            checkpoint,
+            transaction: transaction as u64,
+            event: event_seq as u64,
+        };
+        (
+            EventTypeIndexKey::new(&event.type_, position),
+            EventSenderIndexKey {
+                sender: event.sender,
+                position,
+            },
+            EventIndexInfo {
+                transaction_digest,
+                timestamp_ms,
+                sender: event.sender,
+                type_: event.type_.clone(),
+            },
+        )
+    })
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct CoinIndexKey {
     coin_type: StructTag,
@@ -171,6 +286,27 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of coin balances.
+    ///
+    /// Allows looking up the total balance of each coin type owned by a specific user account, by
+    /// summing the values of its coins. Bounded in size by the live object set, as every entry is
+    /// a coin.
+    balance: DBMap<BalanceKey, BalanceIndexInfo>,
+
+    /// An index of events by their type, ignoring type parameters.
+    ///
+    /// Allows an efficient iterator to list the events of a type in the order they were emitted
+    /// in. Only contains entries for transactions which have yet to be pruned from the main
+    /// database.
+    event_by_type: DBMap<EventTypeIndexKey, EventIndexInfo>,
+
+    /// An index of events by the sender of the transaction that emitted them.
+    ///
+    /// Allows an efficient iterator to list the events emitted by the transactions of a specific
+    /// user account. Only contains entries for transactions which have yet to be pruned from the
+    /// main database.
+    event_by_sender: DBMap<EventSenderIndexKey, EventIndexInfo>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -240,9 +376,43 @@ impl IndexStoreTables {
                     checkpoint: checkpoint.sequence_number,
                 };
 
-                self.transactions
-                    .multi_insert(contents.iter().map(|digests| (digests.transaction, info)))
-                    .map_err(StorageError::from)
+                let mut batch = self.transactions.batch();
+                batch.insert_batch(
+                    &self.transactions,
+                    contents.iter().map(|digests| (digests.transaction, info)),
+                )?;
+
+                for (index, digests) in contents.iter().enumerate() {
+                    let events_digest = authority_store
+                        .perpetual_tables
+                        .effects
+                        .get(&digests.effects)?
+                        .ok_or_else(|| {
+                            StorageError::missing(format!(
+                                "missing effects for transaction {}",
+                                digests.transaction
+                            ))
+                        })?
+                        .events_digest()
+                        .copied();
+                    let Some(events) = events_digest
+                        .map(|digest| authority_store.get_events(&digest))
+                        .transpose()?
+                        .flatten()
+                    else {
+                        continue;
+                    };
+                    self.insert_events(
+                        &mut batch,
+                        checkpoint.sequence_number,
+                        checkpoint.timestamp_ms,
+                        index,
+                        digests.transaction,
+                        &events.data,
+                    )?;
+                }
+
+                batch.write().map_err(StorageError::from)
             })?;
 
             info!(
@@ -342,6 +512,11 @@ impl IndexStoreTables {
                     let owner_key = OwnerIndexKey::new(owner, object.id());
                     let owner_info = OwnerIndexInfo::new(&object);
                     batch.insert_batch(&self.owner, [(owner_key, owner_info)])?;
+
+                    // Balance Index
+                    if let Some((balance_key, balance_info)) = BalanceKey::from_coin(&object) {
+                        batch.insert_batch(&self.balance, [(balance_key, balance_info)])?;
+                    }
                 }
 
                 // Dynamic Field Index
@@ -383,16 +558,61 @@ impl IndexStoreTables {
         }
 
         batch.write()?;
+
+        Ok(())
+    }
+
+    fn insert_events(
+        &self,
+        batch: &mut typed_store::rocks::DBBatch,
+        checkpoint: CheckpointSequenceNumber,
+        timestamp_ms: u64,
+        transaction: usize,
+        transaction_digest: TransactionDigest,
+        events: &[Event],
+    ) -> Result<(), TypedStoreError> {
+        for (type_key, sender_key, info) in event_index_entries(
+            checkpoint,
+            timestamp_ms,
+            transaction,
+            transaction_digest,
+            events,
+        ) {
+            batch.insert_batch(&self.event_by_type, [(type_key, info.clone())])?;
+            batch.insert_batch(&self.event_by_sender, [(sender_key, info)])?;
+        }
         Ok(())
     }
 
     /// Prune data from this Index
+    ///
+    /// `events_to_prune` are the events emitted by the transactions in
+    /// `checkpoint_contents_to_prune`, which are needed to find their entries in the event
+    /// indexes.
     fn prune(
         &self,
         checkpoint_contents_to_prune: &[CheckpointContents],
+        events_to_prune: &HashMap<TransactionDigest, Vec<Event>>,
     ) -> Result<(), TypedStoreError> {
         let mut batch = self.transactions.batch();
 
+        for contents in checkpoint_contents_to_prune {
+            for (index, digests) in contents.iter().enumerate() {
+                let Some(events) = events_to_prune.get(&digests.transaction) else {
+                    continue;
+                };
+                let Some(info) = self.transactions.get(&digests.transaction)? else {
+                    continue;
+                };
+                for (type_key, sender_key, _) in
+                    event_index_entries(info.checkpoint, 0, index, digests.transaction, events)
+                {
+                    batch.delete_batch(&self.event_by_type, [type_key])?;
+                    batch.delete_batch(&self.event_by_sender, [sender_key])?;
+                }
+            }
+        }
+
         let transactions_to_prune = checkpoint_contents_to_prune
             .iter()
             .flat_map(|contents| contents.iter().map(|digests| digests.transaction));
@@ -430,6 +650,20 @@ impl IndexStoreTables {
             )?;
         }
 
+        // event indexes
+        for (index, tx) in checkpoint.transactions.iter().enumerate() {
+            if let Some(events) = &tx.events {
+                self.insert_events(
+                    &mut batch,
+                    checkpoint.checkpoint_summary.sequence_number,
+                    checkpoint.checkpoint_summary.timestamp_ms,
+                    index,
+                    *tx.transaction.digest(),
+                    &events.data,
+                )?;
+            }
+        }
+
         // object indexes
         {
             let mut coin_index = HashMap::new();
@@ -437,6 +671,9 @@ impl IndexStoreTables {
             for tx in &checkpoint.transactions {
                 // determine changes from removed objects
                 for removed_object in tx.removed_objects() {
+                    if let Some((balance_key, _)) = BalanceKey::from_coin(removed_object) {
+                        batch.delete_batch(&self.balance, [balance_key])?;
+                    }
                     match removed_object.owner() {
                         Owner::AddressOwner(address) => {
                             let owner_key = OwnerIndexKey::new(*address, removed_object.id());
@@ -455,6 +692,12 @@ impl IndexStoreTables {
                 // determine changes from changed objects
                 for (object, old_object) in tx.changed_objects() {
                     if let Some(old_object) = old_object {
+                        // Deleted before the new version is inserted, which has the same key
+                        // unless the coin changed owners
+                        if let Some((balance_key, _)) = BalanceKey::from_coin(old_object) {
+                            batch.delete_batch(&self.balance, [balance_key])?;
+                        }
+
                         if old_object.owner() != object.owner() {
                             match old_object.owner() {
                                 Owner::AddressOwner(address) => {
@@ -474,6 +717,9 @@ impl IndexStoreTables {
                         }
                     }
 
+                    if let Some((balance_key, balance_info)) = BalanceKey::from_coin(object) {
+                        batch.insert_batch(&self.balance, [(balance_key, balance_info)])?;
+                    }
                     match object.owner() {
                         Owner::AddressOwner(owner) => {
                             let owner_key = OwnerIndexKey::new(*owner, object.id());
@@ -580,6 +826,95 @@ impl IndexStoreTables {
         };
         self.coin.get(&key)
     }
+
+    fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<impl Iterator<Item = BalanceSummary> + '_, TypedStoreError> {
+        // Type tags have no maximum value to use as an upper bound, so iterate from the first
+        // entry of the owner until reaching the entries of the next one.
+        let lower_bound = BalanceKey {
+            owner,
+            coin_type: cursor.unwrap_or(TypeTag::Bool),
+            object_id: ObjectID::ZERO,
+        };
+        let mut coins = self
+            .balance
+            .iter_with_bounds(Some(lower_bound), None)
+            .take_while(move |(key, _)| key.owner == owner)
+            .peekable();
+
+        // The coins of a type are next to each other, so they are summed up as they are read
+        let iter = std::iter::from_fn(move || {
+            let (key, info) = coins.next()?;
+            let mut summary = BalanceSummary {
+                owner: key.owner,
+                coin_type: key.coin_type,
+                balance: info.balance,
+                coin_object_count: 1,
+            };
+            while let Some((_, info)) = coins.next_if(|(key, _)| key.coin_type == summary.coin_type)
+            {
+                summary.balance = summary.balance.saturating_add(info.balance);
+                summary.coin_object_count += 1;
+            }
+            Some(summary)
+        });
+
+        Ok(iter)
+    }
+
+    fn event_by_type_iter(
+        &self,
+        event_type: &StructTag,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = (EventTypeIndexKey, EventIndexInfo)> + '_>, TypedStoreError>
+    {
+        let key = |position| EventTypeIndexKey::new(event_type, position);
+        Ok(event_index_iter(
+            &self.event_by_type,
+            key(start),
+            key,
+            descending,
+        ))
+    }
+
+    fn event_by_sender_iter(
+        &self,
+        sender: SuiAddress,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = (EventSenderIndexKey, EventIndexInfo)> + '_>, TypedStoreError>
+    {
+        let key = |position| EventSenderIndexKey { sender, position };
+        Ok(event_index_iter(
+            &self.event_by_sender,
+            key(start),
+            key,
+            descending,
+        ))
+    }
+}
+
+/// Iterates over the entries of an event index that share the prefix of `key`, from `start` and in
+/// the order the events were emitted in, or the reverse order if `descending`.
+fn event_index_iter<'a, K: Serialize + DeserializeOwned + 'a>(
+    index: &'a DBMap<K, EventIndexInfo>,
+    start: K,
+    key: impl Fn(EventPosition) -> K,
+    descending: bool,
+) -> Box<dyn Iterator<Item = (K, EventIndexInfo)> + 'a> {
+    if descending {
+        let iter = index
+            .range_iter(key(EventPosition::MIN)..=start)
+            .skip_to_last()
+            .reverse();
+        Box::new(iter)
+    } else {
+        Box::new(index.range_iter(start..=key(EventPosition::MAX)))
+    }
 }
 
 pub struct RestIndexStore {
@@ -635,8 +970,10 @@ impl RestIndexStore {
     pub fn prune(
         &self,
         checkpoint_contents_to_prune: &[CheckpointContents],
+        events_to_prune: &HashMap<TransactionDigest, Vec<Event>>,
     ) -> Result<(), TypedStoreError> {
-        self.tables.prune(checkpoint_contents_to_prune)
+        self.tables
+            .prune(checkpoint_contents_to_prune, events_to_prune)
     }
 
     pub fn index_checkpoint(
@@ -677,6 +1014,35 @@ impl RestIndexStore {
     ) -> Result<Option<CoinIndexInfo>, TypedStoreError> {
         self.tables.get_coin_info(coin_type)
     }
+
+    pub fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<impl Iterator<Item = BalanceSummary> + '_, TypedStoreError> {
+        self.tables.balance_iter(owner, cursor)
+    }
+
+    pub fn event_by_type_iter(
+        &self,
+        event_type: &StructTag,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = (EventTypeIndexKey, EventIndexInfo)> + '_>, TypedStoreError>
+    {
+        self.tables
+            .event_by_type_iter(event_type, start, descending)
+    }
+
+    pub fn event_by_sender_iter(
+        &self,
+        sender: SuiAddress,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = (EventSenderIndexKey, EventIndexInfo)> + '_>, TypedStoreError>
+    {
+        self.tables.event_by_sender_iter(sender, start, descending)
+    }
 }
 
 fn try_create_dynamic_field_info(
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use sui_types::base_types::ObjectID;
@@ -22,10 +23,13 @@ use sui_types::messages_checkpoint::VerifiedCheckpointContents;
 use sui_types::object::Object;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::error::Result;
+use sui_types::storage::AccountBalanceInfo;
 use sui_types::storage::AccountOwnedObjectInfo;
 use sui_types::storage::CoinInfo;
+use sui_types::storage::EventPosition;
 use sui_types::storage::ObjectStore;
 use sui_types::storage::RestDynamicFieldInfo;
+use sui_types::storage::RestEventInfo;
 use sui_types::storage::RestStateReader;
 use sui_types::storage::WriteStore;
 use sui_types::storage::{ObjectKey, ReadStore};
@@ -36,9 +40,13 @@ use crate::authority::AuthorityState;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
+use crate::rest_index::BalanceSummary;
 use crate::rest_index::CoinIndexInfo;
 use crate::rest_index::DynamicFieldIndexInfo;
 use crate::rest_index::DynamicFieldKey;
+use crate::rest_index::EventIndexInfo;
+use crate::rest_index::EventSenderIndexKey;
+use crate::rest_index::EventTypeIndexKey;
 use crate::rest_index::OwnerIndexInfo;
 use crate::rest_index::OwnerIndexKey;
 use crate::rest_index::RestIndexStore;
@@ -589,4 +597,72 @@ impl RestStateReader for RestReadStore {
             )
             .pipe(Ok)
     }
+
+    fn account_balances_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<Box<dyn Iterator<Item = AccountBalanceInfo> + '_>> {
+        let iter = self.index()?.balance_iter(owner, cursor)?.map(
+            |BalanceSummary {
+                 owner,
+                 coin_type,
+                 balance,
+                 coin_object_count,
+             }| AccountBalanceInfo {
+                owner,
+                coin_type,
+                balance,
+                coin_object_count,
+            },
+        );
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn event_by_type_iter(
+        &self,
+        event_type: &StructTag,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = RestEventInfo> + '_>> {
+        let iter = self
+            .index()?
+            .event_by_type_iter(event_type, start, descending)?
+            .map(|(EventTypeIndexKey { position, .. }, info)| rest_event_info(position, info));
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn event_by_sender_iter(
+        &self,
+        sender: SuiAddress,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = RestEventInfo> + '_>> {
+        let iter = self
+            .index()?
+            .event_by_sender_iter(sender, start, descending)?
+            .map(|(EventSenderIndexKey { position, .. }, info)| rest_event_info(position, info));
+
+        Ok(Box::new(iter) as _)
+    }
+}
+
+fn rest_event_info(
+    position: EventPosition,
+    EventIndexInfo {
+        transaction_digest,
+        timestamp_ms,
+        sender,
+        type_,
+    }: EventIndexInfo,
+) -> RestEventInfo {
+    RestEventInfo {
+        position,
+        transaction_digest,
+        timestamp_ms,
+        sender,
+        type_,
+    }
 }
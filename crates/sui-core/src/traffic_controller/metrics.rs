@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
-    Registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge, Registry,
 };
 
 #[derive(Clone)]
@@ -19,6 +19,10 @@ pub struct TrafficControllerMetrics {
     pub tally_handled: IntCounter,
     pub error_tally_handled: IntCounter,
     pub deadmans_switch_enabled: IntGauge,
+    pub requests_blocked_by_rule: IntCounterVec,
+    pub allowlisted_requests: IntCounter,
+    pub rate_limit_buckets: IntGauge,
+    pub policy_reloads: IntCounter,
 }
 
 impl TrafficControllerMetrics {
@@ -93,6 +97,32 @@ impl TrafficControllerMetrics {
                 registry
             )
             .unwrap(),
+            requests_blocked_by_rule: register_int_counter_vec_with_registry!(
+                "traffic_control_requests_blocked_by_rule",
+                "Number of requests that traffic control decided to block, by rule \
+                    and rate limited method. Includes requests let through in dry run mode",
+                &["rule", "method"],
+                registry
+            )
+            .unwrap(),
+            allowlisted_requests: register_int_counter_with_registry!(
+                "traffic_control_allowlisted_requests",
+                "Number of requests that bypassed traffic control due to the allowlist",
+                registry
+            )
+            .unwrap(),
+            rate_limit_buckets: register_int_gauge_with_registry!(
+                "traffic_control_rate_limit_buckets",
+                "Number of per-client, per-method rate limit token buckets being tracked",
+                registry
+            )
+            .unwrap(),
+            policy_reloads: register_int_counter_with_registry!(
+                "traffic_control_policy_reloads",
+                "Number of times the traffic control policy config was reloaded",
+                registry
+            )
+            .unwrap(),
         }
     }
 
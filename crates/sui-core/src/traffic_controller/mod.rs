@@ -6,26 +6,28 @@ pub mod nodefw_client;
 pub mod nodefw_test_server;
 pub mod policies;
 
+use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use fs::File;
 use prometheus::IntGauge;
 use std::fs;
 use std::net::{IpAddr, Ipv4Addr};
 use std::ops::Add;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use self::metrics::TrafficControllerMetrics;
 use crate::traffic_controller::nodefw_client::{BlockAddress, BlockAddresses, NodeFWClient};
 use crate::traffic_controller::policies::{
-    Policy, PolicyResponse, TrafficControlPolicy, TrafficTally,
+    BlockReason, Policy, PolicyResponse, RequestPolicy, TrafficControlPolicy, TrafficTally,
 };
 use mysten_metrics::spawn_monitored_task;
 use rand::Rng;
 use std::fmt::Debug;
 use std::time::{Duration, Instant, SystemTime};
-use sui_types::traffic_control::{PolicyConfig, RemoteFirewallConfig, Weight};
-use tokio::sync::mpsc;
+use sui_types::traffic_control::{ClientIdSource, PolicyConfig, RemoteFirewallConfig, Weight};
 use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, info, warn};
 
 type Blocklist = Arc<DashMap<IpAddr, SystemTime>>;
@@ -41,7 +43,10 @@ pub struct TrafficController {
     tally_channel: mpsc::Sender<TrafficTally>,
     blocklists: Blocklists,
     metrics: Arc<TrafficControllerMetrics>,
-    dry_run_mode: bool,
+    dry_run_mode: Arc<AtomicBool>,
+    request_policy: Arc<ArcSwap<RequestPolicy>>,
+    policy_config: Arc<watch::Sender<PolicyConfig>>,
+    client_id_source: ClientIdSource,
 }
 
 impl Debug for TrafficController {
@@ -84,6 +89,7 @@ impl TrafficController {
             .deadmans_switch_enabled
             .set(mem_drainfile_present as i64);
 
+        let (policy_config_tx, policy_config_rx) = watch::channel(policy_config.clone());
         let ret = Self {
             tally_channel: tx,
             blocklists: Blocklists {
@@ -91,15 +97,21 @@ impl TrafficController {
                 proxied_clients: Arc::new(DashMap::new()),
             },
             metrics: metrics.clone(),
-            dry_run_mode: policy_config.dry_run,
+            dry_run_mode: Arc::new(AtomicBool::new(policy_config.dry_run)),
+            request_policy: Arc::new(ArcSwap::from_pointee(RequestPolicy::new(
+                policy_config.clone(),
+            ))),
+            policy_config: Arc::new(policy_config_tx),
+            client_id_source: policy_config.client_id_source.clone(),
         };
         let tally_loop_blocklists = ret.blocklists.clone();
         let clear_loop_blocklists = ret.blocklists.clone();
+        let clear_loop_request_policy = ret.request_policy.clone();
         let tally_loop_metrics = metrics.clone();
         let clear_loop_metrics = metrics.clone();
         spawn_monitored_task!(run_tally_loop(
             rx,
-            policy_config,
+            policy_config_rx,
             fw_config,
             tally_loop_blocklists,
             tally_loop_metrics,
@@ -107,11 +119,53 @@ impl TrafficController {
         ));
         spawn_monitored_task!(run_clear_blocklists_loop(
             clear_loop_blocklists,
+            clear_loop_request_policy,
             clear_loop_metrics,
         ));
         ret
     }
 
+    /// Replaces the policy config of a running controller. The allowlist,
+    /// denylist, method policies and dry run mode take effect immediately
+    /// (resetting all rate limit buckets), and the spam and error policies
+    /// are rebuilt by the tally loop. `client_id_source` and
+    /// `channel_capacity` are fixed at spawn time and are not updated.
+    pub fn update_policy_config(&self, mut policy_config: PolicyConfig) {
+        let current = self.policy_config.borrow().clone();
+        if current.channel_capacity != policy_config.channel_capacity {
+            warn!("Ignoring channel-capacity change in traffic control policy reload");
+            policy_config.channel_capacity = current.channel_capacity;
+        }
+        if std::mem::discriminant(&current.client_id_source)
+            != std::mem::discriminant(&policy_config.client_id_source)
+        {
+            warn!("Ignoring client-id-source change in traffic control policy reload");
+            policy_config.client_id_source = current.client_id_source;
+        }
+        self.dry_run_mode
+            .store(policy_config.dry_run, Ordering::Relaxed);
+        self.request_policy
+            .store(Arc::new(RequestPolicy::new(policy_config.clone())));
+        self.metrics.rate_limit_buckets.set(0);
+        self.policy_config.send_replace(policy_config);
+        self.metrics.policy_reloads.inc();
+        info!("Traffic control policy config reloaded");
+    }
+
+    pub fn policy_config(&self) -> PolicyConfig {
+        self.policy_config.borrow().clone()
+    }
+
+    pub fn client_id_source(&self) -> &ClientIdSource {
+        &self.client_id_source
+    }
+
+    /// Returns the weight with which a request to `method` should be tallied
+    /// by the spam policy, taking per-method overrides into account.
+    pub fn spam_weight(&self, method: &str, default: Weight) -> Weight {
+        self.request_policy.load().spam_weight(method, default)
+    }
+
     pub fn spawn_for_test(
         policy_config: PolicyConfig,
         fw_config: Option<RemoteFirewallConfig>,
@@ -143,24 +197,90 @@ impl TrafficController {
 
     /// Handle check with dry-run mode considered
     pub async fn check(&self, client: &Option<IpAddr>, proxied_client: &Option<IpAddr>) -> bool {
-        match (
-            self.check_impl(client, proxied_client).await,
-            self.dry_run_mode(),
-        ) {
-            // check succeeded
-            (true, _) => true,
-            // check failed while in dry-run mode
-            (false, true) => {
-                debug!(
-                    "Dry run mode: Blocked request from client {:?}, proxied client: {:?}",
-                    client, proxied_client
-                );
-                self.metrics.num_dry_run_blocked_requests.inc();
-                true
+        self.check_request(client, proxied_client, None).await
+    }
+
+    /// Same as `check`, but additionally applies the rate limit configured
+    /// for `method`, which is either a JSON-RPC method or a validator gRPC
+    /// endpoint name.
+    pub async fn check_method(
+        &self,
+        client: &Option<IpAddr>,
+        proxied_client: &Option<IpAddr>,
+        method: &str,
+    ) -> bool {
+        self.check_request(client, proxied_client, Some(method))
+            .await
+    }
+
+    async fn check_request(
+        &self,
+        client: &Option<IpAddr>,
+        proxied_client: &Option<IpAddr>,
+        method: Option<&str>,
+    ) -> bool {
+        let reason = match self.check_rules(client, proxied_client, method).await {
+            Ok(()) => return true,
+            Err(reason) => reason,
+        };
+        // Only label with the method if it is configured, as otherwise the
+        // label cardinality would be controlled by clients
+        let method_label = match method {
+            Some(method) if reason == BlockReason::RateLimit => method,
+            _ => "",
+        };
+        self.metrics
+            .requests_blocked_by_rule
+            .with_label_values(&[reason.as_str(), method_label])
+            .inc();
+        if self.dry_run_mode() {
+            debug!(
+                "Dry run mode: Blocked request from client {:?}, proxied client: {:?}, rule: {}",
+                client,
+                proxied_client,
+                reason.as_str(),
+            );
+            self.metrics.num_dry_run_blocked_requests.inc();
+            true
+        } else {
+            false
+        }
+    }
+
+    async fn check_rules(
+        &self,
+        client: &Option<IpAddr>,
+        proxied_client: &Option<IpAddr>,
+        method: Option<&str>,
+    ) -> Result<(), BlockReason> {
+        let request_policy = self.request_policy.load_full();
+        // The proxied client, if known, is the end user on whose behalf the
+        // request is made, and is therefore the one allowlisted and rate limited
+        let end_client = proxied_client.or(*client);
+        if let Some(end_client) = end_client {
+            if request_policy.is_allowlisted(&end_client) {
+                self.metrics.allowlisted_requests.inc();
+                return Ok(());
             }
-            // check failed
-            (false, false) => false,
         }
+        if client
+            .iter()
+            .chain(proxied_client.iter())
+            .any(|ip| request_policy.is_denylisted(ip))
+        {
+            return Err(BlockReason::Denylist);
+        }
+        if !self.check_impl(client, proxied_client).await {
+            return Err(BlockReason::Blocklist);
+        }
+        if let (Some(method), Some(end_client)) = (method, end_client) {
+            if request_policy.is_rate_limited_method(method)
+                && !request_policy.check_rate_limit(method, end_client)
+            {
+                return Err(BlockReason::RateLimit);
+            }
+        }
+        Ok(())
     }
 
     /// Returns true if the connection is allowed, false if it is blocked
@@ -185,7 +305,7 @@ impl TrafficController {
     }
 
     pub fn dry_run_mode(&self) -> bool {
-        self.dry_run_mode
+        self.dry_run_mode.load(Ordering::Relaxed)
     }
 
     async fn check_and_clear_blocklist(
@@ -221,10 +341,20 @@ impl TrafficController {
 /// IPs in the blocklist for clients that are added, then once blocked,
 /// never checked again. This function runs periodically to clear out any
 /// such stale IPs. This also ensures that the blocklist length metric
-/// accurately reflects TTL.
-async fn run_clear_blocklists_loop(blocklists: Blocklists, metrics: Arc<TrafficControllerMetrics>) {
+/// accurately reflects TTL. Rate limit buckets that have refilled are
+/// dropped here for the same reason.
+async fn run_clear_blocklists_loop(
+    blocklists: Blocklists,
+    request_policy: Arc<ArcSwap<RequestPolicy>>,
+    metrics: Arc<TrafficControllerMetrics>,
+) {
     loop {
         tokio::time::sleep(Duration::from_secs(3)).await;
+        let current_policy = request_policy.load_full();
+        current_policy.clear_full_buckets();
+        metrics
+            .rate_limit_buckets
+            .set(current_policy.num_buckets() as i64);
         let now = SystemTime::now();
         blocklists.clients.retain(|_, expiration| now < *expiration);
         blocklists
@@ -241,12 +371,13 @@ async fn run_clear_blocklists_loop(blocklists: Blocklists, metrics: Arc<TrafficC
 
 async fn run_tally_loop(
     mut receiver: mpsc::Receiver<TrafficTally>,
-    policy_config: PolicyConfig,
+    mut policy_config_updates: watch::Receiver<PolicyConfig>,
     fw_config: Option<RemoteFirewallConfig>,
     blocklists: Blocklists,
     metrics: Arc<TrafficControllerMetrics>,
     mut mem_drainfile_present: bool,
 ) {
+    let mut policy_config = policy_config_updates.borrow_and_update().clone();
     let mut spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
     let mut error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
     let spam_blocklists = Arc::new(blocklists.clone());
@@ -300,6 +431,11 @@ async fn run_tally_loop(
                     }
                 }
             }
+            Ok(()) = policy_config_updates.changed() => {
+                policy_config = policy_config_updates.borrow_and_update().clone();
+                spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
+                error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
+            }
             // Dead man's switch - if we suspect something is sinking all traffic to node, disable nodefw
             _ = tokio::time::sleep(tokio::time::Duration::from_secs(timeout)) => {
                 if let Some(fw_config) = &fw_config {
@@ -5,6 +5,7 @@
 use std::{collections::HashMap, net::IpAddr, sync::Arc};
 
 use count_min_sketch::CountMinSketch32;
+use dashmap::DashMap;
 use mysten_metrics::spawn_monitored_task;
 use parking_lot::RwLock;
 use std::collections::VecDeque;
@@ -12,7 +13,9 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::time::Duration;
 use std::time::{Instant, SystemTime};
-use sui_types::traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, Weight};
+use sui_types::traffic_control::{
+    FreqThresholdConfig, PolicyConfig, PolicyType, TokenBucketConfig, Weight,
+};
 use tracing::info;
 
 /// The type of request client.
@@ -390,13 +393,154 @@ impl TestPanicOnInvocationPolicy {
     }
 }
 
+/// Rule that caused a request to be blocked at check time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockReason {
+    Denylist,
+    Blocklist,
+    RateLimit,
+}
+
+impl BlockReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockReason::Denylist => "denylist",
+            BlockReason::Blocklist => "blocklist",
+            BlockReason::RateLimit => "rate_limit",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn full(config: &TokenBucketConfig) -> Self {
+        Self {
+            tokens: config.capacity as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, config: &TokenBucketConfig) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * config.refill_per_sec as f64).min(config.capacity as f64);
+        self.last_refill = now;
+    }
+
+    fn try_acquire(&mut self, config: &TokenBucketConfig) -> bool {
+        self.refill(config);
+        let cost = config.cost as f64;
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Policy evaluated synchronously for every request before it is served,
+/// as opposed to the tally policies above which only observe traffic after
+/// the fact. Applies the CIDR allowlist and denylist as well as per-method
+/// token bucket rate limits from the `PolicyConfig`.
+pub struct RequestPolicy {
+    config: PolicyConfig,
+    buckets: DashMap<(String, IpAddr), TokenBucket>,
+}
+
+impl RequestPolicy {
+    pub fn new(config: PolicyConfig) -> Self {
+        Self {
+            config,
+            buckets: DashMap::new(),
+        }
+    }
+
+    pub fn is_allowlisted(&self, client: &IpAddr) -> bool {
+        self.config
+            .allowlist
+            .iter()
+            .any(|network| network.contains(*client))
+    }
+
+    pub fn is_denylisted(&self, client: &IpAddr) -> bool {
+        self.config
+            .denylist
+            .iter()
+            .any(|network| network.contains(*client))
+    }
+
+    /// Returns true if `method` is configured with a rate limit, in which
+    /// case it is safe to use as a metric label.
+    pub fn is_rate_limited_method(&self, method: &str) -> bool {
+        self.config
+            .method_policies
+            .get(method)
+            .is_some_and(|policy| policy.rate_limit.is_some())
+    }
+
+    /// Withdraws tokens for a call to `method` by `client`. Returns false if
+    /// the client has exhausted its bucket for this method.
+    pub fn check_rate_limit(&self, method: &str, client: IpAddr) -> bool {
+        let Some(rate_limit) = self
+            .config
+            .method_policies
+            .get(method)
+            .and_then(|policy| policy.rate_limit.as_ref())
+        else {
+            return true;
+        };
+        self.buckets
+            .entry((method.to_string(), client))
+            .or_insert_with(|| TokenBucket::full(rate_limit))
+            .try_acquire(rate_limit)
+    }
+
+    /// Returns the configured spam weight for `method`, falling back to
+    /// `default` if none is set.
+    pub fn spam_weight(&self, method: &str, default: Weight) -> Weight {
+        self.config
+            .method_policies
+            .get(method)
+            .and_then(|policy| policy.spam_weight.clone())
+            .unwrap_or(default)
+    }
+
+    /// Drops buckets that have refilled to capacity, as these are
+    /// indistinguishable from a freshly created bucket.
+    pub fn clear_full_buckets(&self) {
+        let method_policies = &self.config.method_policies;
+        self.buckets.retain(|(method, _), bucket| {
+            let Some(rate_limit) = method_policies
+                .get(method)
+                .and_then(|policy| policy.rate_limit.as_ref())
+            else {
+                return false;
+            };
+            bucket.refill(rate_limit);
+            bucket.tokens < rate_limit.capacity as f64
+        });
+    }
+
+    pub fn num_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::net::{IpAddr, Ipv4Addr};
     use sui_macros::sim_test;
     use sui_types::traffic_control::{
-        DEFAULT_SKETCH_CAPACITY, DEFAULT_SKETCH_PROBABILITY, DEFAULT_SKETCH_TOLERANCE,
+        MethodPolicyConfig, DEFAULT_SKETCH_CAPACITY, DEFAULT_SKETCH_PROBABILITY,
+        DEFAULT_SKETCH_TOLERANCE,
     };
 
     #[sim_test]
@@ -527,4 +671,65 @@ mod tests {
             "Memory estimate {mem_estimate} for traffic sketch exceeds 128MB."
         );
     }
+
+    #[sim_test]
+    async fn test_request_policy() {
+        let policy = RequestPolicy::new(PolicyConfig {
+            allowlist: vec!["10.0.0.0/8".parse().unwrap()],
+            denylist: vec![
+                "10.1.0.0/16".parse().unwrap(),
+                "192.168.1.7/32".parse().unwrap(),
+            ],
+            method_policies: [(
+                "suix_getOwnedObjects".to_string(),
+                MethodPolicyConfig {
+                    rate_limit: Some(TokenBucketConfig {
+                        capacity: 4,
+                        refill_per_sec: 1,
+                        cost: 2,
+                    }),
+                    spam_weight: Some(Weight::zero()),
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        });
+
+        let allowed = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        let denied = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 7));
+        let other = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 8));
+        assert!(policy.is_allowlisted(&allowed));
+        assert!(policy.is_denylisted(&allowed));
+        assert!(policy.is_denylisted(&denied));
+        assert!(!policy.is_allowlisted(&other));
+        assert!(!policy.is_denylisted(&other));
+
+        // bucket of 4 tokens allows two calls of cost 2
+        assert!(policy.check_rate_limit("suix_getOwnedObjects", other));
+        assert!(policy.check_rate_limit("suix_getOwnedObjects", other));
+        assert!(!policy.check_rate_limit("suix_getOwnedObjects", other));
+        // buckets are tracked per client and per method
+        assert!(policy.check_rate_limit("suix_getOwnedObjects", denied));
+        assert!(policy.check_rate_limit("sui_getObject", other));
+        assert!(!policy.is_rate_limited_method("sui_getObject"));
+
+        // refilled after 2 seconds
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert!(policy.check_rate_limit("suix_getOwnedObjects", other));
+        assert!(!policy.check_rate_limit("suix_getOwnedObjects", other));
+
+        // bucket of `denied` is full again and is cleared
+        policy.clear_full_buckets();
+        assert_eq!(policy.num_buckets(), 1);
+
+        assert_eq!(
+            policy.spam_weight("suix_getOwnedObjects", Weight::one()),
+            Weight::zero()
+        );
+        assert_eq!(
+            policy.spam_weight("sui_getObject", Weight::one()),
+            Weight::one()
+        );
+    }
 }
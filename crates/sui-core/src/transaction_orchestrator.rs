@@ -822,4 +822,27 @@ where
     > {
         self.execute_transaction_v3(request, client_addr).await
     }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: sui_types::transaction::TransactionData,
+    ) -> Result<sui_rest_api::SimulateTransactionResult, SuiError> {
+        let (inner_temp_store, effects, mock_gas_id) = self
+            .validator_state
+            .simulate_transaction(transaction)
+            .await?;
+
+        let events = effects
+            .events_digest()
+            .is_some()
+            .then_some(inner_temp_store.events);
+
+        Ok(sui_rest_api::SimulateTransactionResult {
+            effects,
+            events,
+            input_objects: inner_temp_store.input_objects,
+            output_objects: inner_temp_store.written,
+            mock_gas_id,
+        })
+    }
 }
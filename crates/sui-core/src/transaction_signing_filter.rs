@@ -1,6 +1,151 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use arc_swap::ArcSwap;
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use sui_config::certificate_deny_config::CertificateDenyConfig;
+use sui_config::transaction_deny_config::TransactionDenyConfig;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
 #[cfg(test)]
 #[path = "unit_tests/transaction_deny_tests.rs"]
 mod transaction_deny_tests;
+
+/// Contents of the deny config file watched by the node. A config that is omitted
+/// from the file keeps the value it has in the `NodeConfig`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DenyConfigFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_deny_config: Option<TransactionDenyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate_deny_config: Option<CertificateDenyConfig>,
+}
+
+/// Holds the deny configs consulted when signing and executing transactions.
+/// Both start out as the values in the `NodeConfig` and can be swapped atomically
+/// at runtime, so that a package, address or object can be blocked during an
+/// incident without restarting the node.
+pub struct TransactionSigningFilter {
+    transaction_deny_config: ArcSwap<TransactionDenyConfig>,
+    certificate_deny_config: ArcSwap<CertificateDenyConfig>,
+    node_transaction_deny_config: TransactionDenyConfig,
+    node_certificate_deny_config: CertificateDenyConfig,
+}
+
+impl TransactionSigningFilter {
+    pub fn new(
+        transaction_deny_config: TransactionDenyConfig,
+        certificate_deny_config: CertificateDenyConfig,
+    ) -> Self {
+        Self {
+            transaction_deny_config: ArcSwap::from_pointee(transaction_deny_config.clone()),
+            certificate_deny_config: ArcSwap::from_pointee(certificate_deny_config.clone()),
+            node_transaction_deny_config: transaction_deny_config,
+            node_certificate_deny_config: certificate_deny_config,
+        }
+    }
+
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.transaction_deny_config.load_full()
+    }
+
+    pub fn certificate_deny_config(&self) -> Arc<CertificateDenyConfig> {
+        self.certificate_deny_config.load_full()
+    }
+
+    pub fn set_transaction_deny_config(&self, config: TransactionDenyConfig) {
+        info!(?config, "Updating transaction deny config");
+        self.transaction_deny_config.store(Arc::new(config));
+    }
+
+    pub fn set_certificate_deny_config(&self, config: CertificateDenyConfig) {
+        info!(?config, "Updating certificate deny config");
+        self.certificate_deny_config.store(Arc::new(config));
+    }
+
+    /// Applies the deny configs in the file at `path`. If the file does not exist,
+    /// both configs are reset to their `NodeConfig` values. If the file cannot be
+    /// parsed the current configs are left untouched.
+    pub fn reload_from_file(&self, path: &Path) -> anyhow::Result<()> {
+        let file = if path.exists() {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read deny config file {:?}", path))?;
+            serde_yaml::from_str::<DenyConfigFile>(&contents)
+                .with_context(|| format!("failed to parse deny config file {:?}", path))?
+        } else {
+            info!(
+                "Deny config file {:?} not found, using deny configs from node config",
+                path
+            );
+            DenyConfigFile::default()
+        };
+        self.set_transaction_deny_config(
+            file.transaction_deny_config
+                .unwrap_or_else(|| self.node_transaction_deny_config.clone()),
+        );
+        self.set_certificate_deny_config(
+            file.certificate_deny_config
+                .unwrap_or_else(|| self.node_certificate_deny_config.clone()),
+        );
+        Ok(())
+    }
+
+    /// Loads the deny config file at `path` and reloads it every time it changes,
+    /// calling `on_reload` after each successful reload. Runs forever unless the
+    /// watcher fails to start.
+    pub async fn watch_file(
+        self: Arc<Self>,
+        path: PathBuf,
+        on_reload: impl Fn(&TransactionSigningFilter) + Send + 'static,
+    ) {
+        let (tx, mut rx) = mpsc::channel(1);
+        let file_name = path.file_name().map(|name| name.to_owned());
+        let event_handler = move |res: notify::Result<notify::Event>| match res {
+            Ok(event) => {
+                if event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == file_name.as_deref())
+                {
+                    // A full channel already has a pending reload
+                    let _ = tx.try_send(());
+                }
+            }
+            Err(err) => warn!("Deny config file watch error: {:?}", err),
+        };
+        let mut watcher = match notify::recommended_watcher(event_handler) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                error!("Failed to create deny config file watcher: {:?}", err);
+                return;
+            }
+        };
+        // Watch the parent directory rather than the file itself, so that the file
+        // can be created, deleted, or replaced atomically by a rename.
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if let Err(err) = watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            error!("Failed to watch deny config directory {:?}: {:?}", dir, err);
+            return;
+        }
+
+        loop {
+            match self.reload_from_file(&path) {
+                Ok(()) => on_reload(&self),
+                Err(err) => error!("Failed to reload deny config: {:?}", err),
+            }
+            if rx.recv().await.is_none() {
+                return;
+            }
+        }
+    }
+}
@@ -475,3 +475,70 @@ async fn test_certificate_deny() {
         }
     ));
 }
+
+#[tokio::test]
+async fn test_deny_config_reload() {
+    let (network_config, state) = setup_test(TransactionDenyConfig::default()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let denied_by_address = || {
+        state
+            .metrics
+            .transaction_denied_by_rule
+            .with_label_values(&["address-deny-list"])
+            .get()
+    };
+
+    // Deny the sender at runtime, without rebuilding the authority state.
+    state
+        .transaction_signing_filter()
+        .set_transaction_deny_config(
+            TransactionDenyConfigBuilder::new()
+                .add_denied_address(accounts[0].0)
+                .build(),
+        );
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+    assert_eq!(denied_by_address(), 1);
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+
+    // Reload from a deny config file.
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("deny-config.yaml");
+    std::fs::write(
+        &path,
+        format!(
+            "transaction-deny-config:\n  address-deny-list:\n    - \"{}\"\n",
+            accounts[2].0
+        ),
+    )
+    .unwrap();
+    state
+        .transaction_signing_filter()
+        .reload_from_file(&path)
+        .unwrap();
+    assert_denied(&transfer_with_account(&accounts[2], &accounts[2], &state).await);
+    assert_eq!(denied_by_address(), 2);
+    // The file replaces the previous runtime override.
+    assert!(transfer_with_account(&accounts[0], &accounts[0], &state)
+        .await
+        .is_ok());
+
+    // A malformed file leaves the current config in place.
+    std::fs::write(&path, "transaction-deny-config: [").unwrap();
+    assert!(state
+        .transaction_signing_filter()
+        .reload_from_file(&path)
+        .is_err());
+    assert_denied(&transfer_with_account(&accounts[2], &accounts[2], &state).await);
+
+    // Removing the file reverts to the node config.
+    std::fs::remove_file(&path).unwrap();
+    state
+        .transaction_signing_filter()
+        .reload_from_file(&path)
+        .unwrap();
+    assert!(transfer_with_account(&accounts[2], &accounts[2], &state)
+        .await
+        .is_ok());
+}
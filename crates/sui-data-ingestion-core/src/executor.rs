@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::filter::CheckpointFilter;
 use crate::progress_store::{
     ExecutorProgress, ProgressStore, ProgressStoreWrapper, ShimProgressStore,
 };
@@ -12,8 +13,10 @@ use anyhow::Result;
 use futures::Future;
 use mysten_metrics::spawn_monitored_task;
 use prometheus::Registry;
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Arc;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tokio::sync::mpsc;
@@ -21,9 +24,15 @@ use tokio::sync::oneshot;
 
 pub const MAX_CHECKPOINTS_IN_PROGRESS: usize = 10000;
 
+struct PoolSender {
+    task_name: String,
+    filter: Option<CheckpointFilter>,
+    sender: mpsc::Sender<CheckpointData>,
+}
+
 pub struct IndexerExecutor<P> {
     pools: Vec<Pin<Box<dyn Future<Output = ()> + Send>>>,
-    pool_senders: Vec<mpsc::Sender<CheckpointData>>,
+    pool_senders: Vec<PoolSender>,
     progress_store: ProgressStoreWrapper<P>,
     pool_progress_sender: mpsc::Sender<(String, CheckpointSequenceNumber)>,
     pool_progress_receiver: mpsc::Receiver<(String, CheckpointSequenceNumber)>,
@@ -48,16 +57,22 @@ impl<P: ProgressStore> IndexerExecutor<P> {
     pub async fn register<W: Worker + 'static>(&mut self, pool: WorkerPool<W>) -> Result<()> {
         let checkpoint_number = self.progress_store.load(pool.task_name.clone()).await?;
         let (sender, receiver) = mpsc::channel(MAX_CHECKPOINTS_IN_PROGRESS);
+        let pool_sender = PoolSender {
+            task_name: pool.task_name.clone(),
+            filter: pool.filter.clone(),
+            sender,
+        };
         self.pools.push(Box::pin(pool.run(
             checkpoint_number,
             receiver,
             self.pool_progress_sender.clone(),
         )));
-        self.pool_senders.push(sender);
+        self.pool_senders.push(pool_sender);
         Ok(())
     }
 
-    /// Main executor loop
+    /// Main executor loop. Runs until `exit_receiver` fires, or, if the reader has an upper limit,
+    /// until every worker pool has processed all checkpoints up to and including that limit.
     pub async fn run(
         mut self,
         path: PathBuf,
@@ -67,6 +82,12 @@ impl<P: ProgressStore> IndexerExecutor<P> {
         mut exit_receiver: oneshot::Receiver<()>,
     ) -> Result<ExecutorProgress> {
         let mut reader_checkpoint_number = self.progress_store.min_watermark()?;
+        let upper_limit = reader_options.upper_limit;
+        let filters = self
+            .pool_senders
+            .iter()
+            .map(|pool| pool.filter.clone())
+            .collect();
         let (checkpoint_reader, mut checkpoint_recv, gc_sender, _exit_sender) =
             CheckpointReader::initialize(
                 path,
@@ -74,12 +95,14 @@ impl<P: ProgressStore> IndexerExecutor<P> {
                 remote_store_url,
                 remote_store_options,
                 reader_options,
+                filters,
             );
         spawn_monitored_task!(checkpoint_reader.run());
 
-        for pool in std::mem::take(&mut self.pools) {
-            spawn_monitored_task!(pool);
-        }
+        let pool_handles: Vec<_> = std::mem::take(&mut self.pools)
+            .into_iter()
+            .map(|pool| spawn_monitored_task!(pool))
+            .collect();
         loop {
             tokio::select! {
                 _ = &mut exit_receiver => break,
@@ -91,14 +114,39 @@ impl<P: ProgressStore> IndexerExecutor<P> {
                         reader_checkpoint_number = seq_number;
                     }
                     self.metrics.data_ingestion_checkpoint.with_label_values(&[&task_name]).set(sequence_number as i64);
+                    if upper_limit.is_some_and(|limit| seq_number > limit) {
+                        break;
+                    }
                 }
                 Some(checkpoint) = checkpoint_recv.recv() => {
-                    for sender in &self.pool_senders {
-                        sender.send(checkpoint.clone()).await?;
+                    for (index, pool) in self.pool_senders.iter().enumerate() {
+                        let (checkpoint, stats) = checkpoint.for_pool(index, pool.filter.as_ref());
+                        if let Some(stats) = stats {
+                            self.metrics
+                                .data_ingestion_filtered_transactions
+                                .with_label_values(&[&pool.task_name])
+                                .inc_by(stats.transactions_dropped);
+                            self.metrics
+                                .data_ingestion_trimmed_objects
+                                .with_label_values(&[&pool.task_name])
+                                .inc_by(stats.objects_dropped);
+                            self.metrics
+                                .data_ingestion_trimmed_events
+                                .with_label_values(&[&pool.task_name])
+                                .inc_by(stats.events_dropped);
+                        }
+                        pool.sender.send(checkpoint).await?;
                     }
                 }
             }
         }
+
+        // Closing the pools' channels signals them to shut down.
+        self.pool_senders.clear();
+        self.pool_progress_receiver.close();
+        for handle in pool_handles {
+            handle.await?;
+        }
         Ok(self.progress_store.stats())
     }
 }
@@ -130,3 +178,88 @@ pub async fn setup_single_workflow<W: Worker + 'static>(
         exit_sender,
     ))
 }
+
+/// Sets up a workflow that processes the checkpoints in `range` (inclusive), instead of following
+/// the tip of the chain, which is useful for backfills. The range is split into `num_partitions`
+/// contiguous sub-ranges, each read and processed by its own executor and worker pool (all sharing
+/// `worker`) in parallel. The returned future resolves once every checkpoint in the range has been
+/// processed, or after the exit signal is sent and all partitions have shut down.
+pub async fn setup_range_workflow<W: Worker + 'static>(
+    worker: W,
+    remote_store_url: String,
+    range: RangeInclusive<CheckpointSequenceNumber>,
+    num_partitions: usize,
+    concurrency: usize,
+    reader_options: Option<ReaderOptions>,
+) -> Result<(
+    impl Future<Output = Result<Vec<ExecutorProgress>>>,
+    oneshot::Sender<()>,
+)> {
+    let (exit_sender, exit_receiver) = oneshot::channel();
+    let worker = Arc::new(worker);
+    let reader_options = reader_options.unwrap_or_default();
+    let mut executors = vec![];
+    let mut partition_exit_senders = vec![];
+    for partition in split_range(range, num_partitions) {
+        let (start, end) = partition.into_inner();
+        let (partition_exit_sender, partition_exit_receiver) = oneshot::channel();
+        let metrics = DataIngestionMetrics::new(&Registry::new());
+        let mut executor = IndexerExecutor::new(ShimProgressStore(start), 1, metrics);
+        let worker_pool = WorkerPool::new(
+            worker.clone(),
+            format!("workflow-{start}-{end}"),
+            concurrency,
+        );
+        executor.register(worker_pool).await?;
+        executors.push(executor.run(
+            tempfile::tempdir()?.into_path(),
+            Some(remote_store_url.clone()),
+            vec![],
+            ReaderOptions {
+                upper_limit: Some(end),
+                ..reader_options.clone()
+            },
+            partition_exit_receiver,
+        ));
+        partition_exit_senders.push(partition_exit_sender);
+    }
+    Ok((
+        async move {
+            let executors = futures::future::try_join_all(executors);
+            tokio::pin!(executors);
+            tokio::select! {
+                result = &mut executors => result,
+                Ok(()) = exit_receiver => {
+                    for sender in partition_exit_senders {
+                        let _ = sender.send(());
+                    }
+                    executors.await
+                }
+            }
+        },
+        exit_sender,
+    ))
+}
+
+/// Splits `range` into at most `num_partitions` contiguous, non-empty sub-ranges of (roughly) equal
+/// size.
+pub(crate) fn split_range(
+    range: RangeInclusive<CheckpointSequenceNumber>,
+    num_partitions: usize,
+) -> Vec<RangeInclusive<CheckpointSequenceNumber>> {
+    if range.is_empty() {
+        return vec![];
+    }
+    let (start, end) = range.into_inner();
+    let len = end - start + 1;
+    let num_partitions = (num_partitions.max(1) as u64).min(len);
+    let (size, remainder) = (len / num_partitions, len % num_partitions);
+    let mut partitions = vec![];
+    let mut partition_start = start;
+    for i in 0..num_partitions {
+        let partition_len = size + u64::from(i < remainder);
+        partitions.push(partition_start..=partition_start + partition_len - 1);
+        partition_start += partition_len;
+    }
+    partitions
+}
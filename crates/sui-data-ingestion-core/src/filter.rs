@@ -0,0 +1,299 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::object::Object;
+use sui_types::transaction::TransactionDataAPI;
+
+/// Declarative filter over the transactions in a checkpoint, applied by the executor before a
+/// checkpoint is dispatched to a worker pool, so that workers only receive (and the executor only
+/// copies) the data they are interested in. The reader matches each checkpoint against every
+/// pool's filter as soon as it is deserialized, and releases the data no pool is interested in
+/// before the checkpoint is queued for the executor.
+///
+/// A transaction is kept if it matches any of the configured criteria, or if no criteria are
+/// configured. Checkpoints themselves are never dropped, only trimmed, so that pool watermarks keep
+/// advancing even when no transaction in a checkpoint is of interest.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckpointFilter {
+    /// Keep transactions that call into one of these packages, or emit events defined in or
+    /// emitted from one of these packages.
+    pub packages: Vec<ObjectID>,
+    /// Keep transactions that emit events of one of these types. Types without type parameters
+    /// match all instantiations of a generic type.
+    pub event_types: Vec<StructTag>,
+    /// Keep transactions sent by one of these addresses.
+    pub senders: Vec<SuiAddress>,
+    /// Keep transactions that read or write objects of one of these types. Types without type
+    /// parameters match all instantiations of a generic type.
+    pub object_types: Vec<StructTag>,
+    /// Remove the input and output objects from the transactions that are kept.
+    pub drop_objects: bool,
+    /// Remove the events from the transactions that are kept.
+    pub drop_events: bool,
+}
+
+/// How much of a checkpoint a `CheckpointFilter` removed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FilterStats {
+    pub transactions_dropped: u64,
+    pub objects_dropped: u64,
+    pub events_dropped: u64,
+}
+
+impl std::ops::AddAssign for FilterStats {
+    fn add_assign(&mut self, other: Self) {
+        self.transactions_dropped += other.transactions_dropped;
+        self.objects_dropped += other.objects_dropped;
+        self.events_dropped += other.events_dropped;
+    }
+}
+
+impl CheckpointFilter {
+    /// Returns a copy of `checkpoint` that only contains the transactions (and parts of
+    /// transactions) selected by this filter, along with statistics on what was removed.
+    pub fn apply(&self, checkpoint: &CheckpointData) -> (CheckpointData, FilterStats) {
+        let mut stats = FilterStats::default();
+        let mut transactions = vec![];
+        for transaction in &checkpoint.transactions {
+            if self.matches(transaction) {
+                transactions.push(self.project(transaction, &mut stats));
+            } else {
+                stats.transactions_dropped += 1;
+            }
+        }
+
+        let checkpoint = CheckpointData {
+            checkpoint_summary: checkpoint.checkpoint_summary.clone(),
+            checkpoint_contents: checkpoint.checkpoint_contents.clone(),
+            transactions,
+        };
+        (checkpoint, stats)
+    }
+
+    /// Whether this filter selects every transaction, because it has no criteria.
+    fn selects_all(&self) -> bool {
+        self.packages.is_empty()
+            && self.event_types.is_empty()
+            && self.senders.is_empty()
+            && self.object_types.is_empty()
+    }
+
+    /// Whether this filter selects `transaction`.
+    pub fn matches(&self, transaction: &CheckpointTransaction) -> bool {
+        if self.selects_all() {
+            return true;
+        }
+
+        let data = transaction.transaction.transaction_data();
+        if self.senders.contains(&data.sender()) {
+            return true;
+        }
+
+        if data
+            .move_calls()
+            .into_iter()
+            .any(|(package, _, _)| self.packages.contains(package))
+        {
+            return true;
+        }
+
+        let events = transaction.events.iter().flat_map(|events| &events.data);
+        for event in events {
+            if self.packages.contains(&event.package_id)
+                || self.packages.contains(&event.type_.address.into())
+                || self
+                    .event_types
+                    .iter()
+                    .any(|f| type_matches(f, &event.type_))
+            {
+                return true;
+            }
+        }
+
+        if !self.object_types.is_empty() {
+            let objects = transaction
+                .input_objects
+                .iter()
+                .chain(&transaction.output_objects);
+            for object in objects {
+                if let Some(tag) = object.struct_tag() {
+                    if self.object_types.iter().any(|f| type_matches(f, &tag)) {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Copies the parts of `transaction` that are kept by this filter.
+    pub(crate) fn project(
+        &self,
+        transaction: &CheckpointTransaction,
+        stats: &mut FilterStats,
+    ) -> CheckpointTransaction {
+        let objects = |objects: &Vec<Object>, stats: &mut FilterStats| {
+            if self.drop_objects {
+                stats.objects_dropped += objects.len() as u64;
+                vec![]
+            } else {
+                objects.clone()
+            }
+        };
+
+        let events = if self.drop_events {
+            if let Some(events) = &transaction.events {
+                stats.events_dropped += events.data.len() as u64;
+            }
+            None
+        } else {
+            transaction.events.clone()
+        };
+
+        CheckpointTransaction {
+            transaction: transaction.transaction.clone(),
+            effects: transaction.effects.clone(),
+            events,
+            input_objects: objects(&transaction.input_objects, stats),
+            output_objects: objects(&transaction.output_objects, stats),
+        }
+    }
+}
+
+/// A checkpoint as read by the reader, along with the transactions each worker pool's filter
+/// selected. Transactions are matched against every filter before the data that no pool receives
+/// is removed, so a pool can match on objects or events that it then drops.
+pub(crate) struct FilteredCheckpoint {
+    pub(crate) checkpoint: CheckpointData,
+    /// By pool, in registration order, the selection of its filter, or `None` for a pool without
+    /// a filter.
+    selections: Vec<Option<Selection>>,
+}
+
+struct Selection {
+    /// Whether each transaction of the checkpoint is selected.
+    selected: Vec<bool>,
+    /// What the filter removes from the checkpoint as read.
+    stats: FilterStats,
+}
+
+impl FilteredCheckpoint {
+    /// Matches `checkpoint` against the filter of each worker pool, where `None` stands for a pool
+    /// without a filter, then removes the transactions, objects and events that no pool receives.
+    pub(crate) fn new(filters: &[Option<CheckpointFilter>], checkpoint: CheckpointData) -> Self {
+        let mut selections: Vec<_> = filters
+            .iter()
+            .map(|filter| {
+                let filter = filter.as_ref()?;
+                let mut stats = FilterStats::default();
+                let selected = checkpoint
+                    .transactions
+                    .iter()
+                    .map(|transaction| {
+                        let selected = filter.matches(transaction);
+                        if !selected {
+                            stats.transactions_dropped += 1;
+                            return false;
+                        }
+                        if filter.drop_objects {
+                            stats.objects_dropped += (transaction.input_objects.len()
+                                + transaction.output_objects.len())
+                                as u64;
+                        }
+                        if filter.drop_events {
+                            if let Some(events) = &transaction.events {
+                                stats.events_dropped += events.data.len() as u64;
+                            }
+                        }
+                        true
+                    })
+                    .collect();
+                Some(Selection { selected, stats })
+            })
+            .collect();
+
+        // A pool without a filter receives whole checkpoints.
+        if selections.iter().any(Option::is_none) {
+            return Self {
+                checkpoint,
+                selections,
+            };
+        }
+
+        let mut checkpoint = checkpoint;
+        let mut kept = vec![];
+        let transactions = std::mem::take(&mut checkpoint.transactions);
+        for (i, mut transaction) in transactions.into_iter().enumerate() {
+            let receivers: Vec<_> = filters
+                .iter()
+                .zip(&selections)
+                .filter_map(|(filter, selection)| {
+                    let selection = selection.as_ref()?;
+                    selection.selected[i].then_some(filter.as_ref()?)
+                })
+                .collect();
+            kept.push(!receivers.is_empty());
+            if receivers.is_empty() {
+                continue;
+            }
+            if receivers.iter().all(|filter| filter.drop_objects) {
+                transaction.input_objects.clear();
+                transaction.output_objects.clear();
+            }
+            if receivers.iter().all(|filter| filter.drop_events) {
+                transaction.events = None;
+            }
+            checkpoint.transactions.push(transaction);
+        }
+        for selection in selections.iter_mut().flatten() {
+            let mut kept = kept.iter();
+            selection.selected.retain(|_| *kept.next().unwrap());
+        }
+
+        Self {
+            checkpoint,
+            selections,
+        }
+    }
+
+    /// The checkpoint to dispatch to the pool registered at `index` with `filter`, along with what
+    /// its filter removed, if it has one.
+    pub(crate) fn for_pool(
+        &self,
+        index: usize,
+        filter: Option<&CheckpointFilter>,
+    ) -> (CheckpointData, Option<FilterStats>) {
+        let (Some(filter), Some(Some(selection))) = (filter, self.selections.get(index)) else {
+            return (self.checkpoint.clone(), None);
+        };
+        let transactions = self
+            .checkpoint
+            .transactions
+            .iter()
+            .zip(&selection.selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(transaction, _)| filter.project(transaction, &mut FilterStats::default()))
+            .collect();
+        let checkpoint = CheckpointData {
+            checkpoint_summary: self.checkpoint.checkpoint_summary.clone(),
+            checkpoint_contents: self.checkpoint.checkpoint_contents.clone(),
+            transactions,
+        };
+        (checkpoint, Some(selection.stats))
+    }
+}
+
+/// Whether `tag` is matched by `filter`: They must refer to the same type, and if `filter` has type
+/// parameters, then they must be the same as `tag`'s type parameters.
+pub(crate) fn type_matches(filter: &StructTag, tag: &StructTag) -> bool {
+    filter.address == tag.address
+        && filter.module == tag.module
+        && filter.name == tag.name
+        && (filter.type_params.is_empty() || filter.type_params == tag.type_params)
+}
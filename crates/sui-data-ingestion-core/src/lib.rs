@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod executor;
+mod filter;
 mod metrics;
 mod progress_store;
 mod reader;
@@ -12,10 +13,16 @@ mod worker_pool;
 
 use anyhow::Result;
 use async_trait::async_trait;
-pub use executor::{setup_single_workflow, IndexerExecutor, MAX_CHECKPOINTS_IN_PROGRESS};
+pub use executor::{
+    setup_range_workflow, setup_single_workflow, IndexerExecutor, MAX_CHECKPOINTS_IN_PROGRESS,
+};
+pub use filter::{CheckpointFilter, FilterStats};
 pub use metrics::DataIngestionMetrics;
+#[cfg(feature = "postgres")]
+pub use progress_store::PostgresProgressStore;
 pub use progress_store::{FileProgressStore, ProgressStore, ShimProgressStore};
 pub use reader::ReaderOptions;
+use std::sync::Arc;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 pub use util::create_remote_store_client;
@@ -39,3 +46,23 @@ pub trait Worker: Send + Sync {
         Ok(())
     }
 }
+
+/// Allows a single worker to be shared between several worker pools, e.g. to process multiple
+/// checkpoint ranges in parallel.
+#[async_trait]
+impl<W: Worker + ?Sized> Worker for Arc<W> {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        self.as_ref().process_checkpoint(checkpoint).await
+    }
+
+    async fn save_progress(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<CheckpointSequenceNumber> {
+        self.as_ref().save_progress(sequence_number).await
+    }
+
+    fn preprocess_hook(&self, checkpoint: CheckpointData) -> Result<()> {
+        self.as_ref().preprocess_hook(checkpoint)
+    }
+}
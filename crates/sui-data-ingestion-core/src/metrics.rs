@@ -1,11 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use prometheus::{register_int_gauge_vec_with_registry, IntGaugeVec, Registry};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
+    IntGaugeVec, Registry,
+};
 
 #[derive(Clone)]
 pub struct DataIngestionMetrics {
     pub data_ingestion_checkpoint: IntGaugeVec,
+    pub data_ingestion_filtered_transactions: IntCounterVec,
+    pub data_ingestion_trimmed_objects: IntCounterVec,
+    pub data_ingestion_trimmed_events: IntCounterVec,
 }
 
 impl DataIngestionMetrics {
@@ -18,6 +24,27 @@ impl DataIngestionMetrics {
                 registry,
             )
             .unwrap(),
+            data_ingestion_filtered_transactions: register_int_counter_vec_with_registry!(
+                "data_ingestion_filtered_transactions",
+                "Number of transactions removed by a worker pool's checkpoint filter.",
+                &["task"],
+                registry,
+            )
+            .unwrap(),
+            data_ingestion_trimmed_objects: register_int_counter_vec_with_registry!(
+                "data_ingestion_trimmed_objects",
+                "Number of input and output objects removed by a worker pool's checkpoint filter.",
+                &["task"],
+                registry,
+            )
+            .unwrap(),
+            data_ingestion_trimmed_events: register_int_counter_vec_with_registry!(
+                "data_ingestion_trimmed_events",
+                "Number of events removed by a worker pool's checkpoint filter.",
+                &["task"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -6,7 +6,11 @@ use async_trait::async_trait;
 use std::collections::HashMap;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 mod file;
+#[cfg(feature = "postgres")]
+mod postgres;
 pub use file::FileProgressStore;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresProgressStore;
 
 pub type ExecutorProgress = HashMap<String, CheckpointSequenceNumber>;
 
@@ -0,0 +1,250 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::progress_store::ProgressStore;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use diesel::pg::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sql_types::{BigInt, Double, Text};
+use diesel::{QueryableByName, RunQueryDsl};
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::info;
+
+type PgPool = Pool<ConnectionManager<PgConnection>>;
+
+const DEFAULT_LEASE_DURATION: Duration = Duration::from_secs(60);
+
+const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS ingestion_progress (
+        task_name           TEXT        PRIMARY KEY,
+        checkpoint          BIGINT      NOT NULL,
+        lease_holder        TEXT,
+        lease_expires_at    TIMESTAMPTZ
+    )";
+
+const INSERT_TASK: &str = "
+    INSERT INTO ingestion_progress (task_name, checkpoint)
+    VALUES ($1, 0)
+    ON CONFLICT (task_name) DO NOTHING";
+
+/// Takes (or renews) the lease on a task, provided nobody else holds an unexpired lease on it.
+const ACQUIRE_LEASE: &str = "
+    UPDATE ingestion_progress
+    SET lease_holder = $2, lease_expires_at = now() + make_interval(secs => $3)
+    WHERE task_name = $1
+      AND (lease_holder IS NULL OR lease_holder = $2 OR lease_expires_at < now())
+    RETURNING checkpoint";
+
+/// Advances the watermark of a task, provided the caller still holds its lease. The watermark
+/// never moves backwards.
+const ADVANCE_WATERMARK: &str = "
+    UPDATE ingestion_progress
+    SET checkpoint = GREATEST(checkpoint, $3), lease_expires_at = now() + make_interval(secs => $4)
+    WHERE task_name = $1 AND lease_holder = $2
+    RETURNING checkpoint";
+
+#[derive(QueryableByName)]
+struct Watermark {
+    #[diesel(sql_type = BigInt)]
+    checkpoint: i64,
+}
+
+/// Progress store backed by a Postgres table, which allows several `IndexerExecutor` replicas to
+/// share the same pipelines.
+///
+/// Each task is guarded by a lease: only the replica holding a task's lease can advance its
+/// watermark, and a replica trying to load a task whose lease is held elsewhere waits until that
+/// lease expires. This supports a hot/standby setup, where the standby blocks while registering its
+/// worker pools until the active replica stops renewing its leases. Leases are renewed on every
+/// load and save, so `lease_duration` should comfortably exceed the time between checkpoints.
+///
+/// A replica that discovers its lease has been taken over errors out instead of saving progress, so
+/// that it stops processing checkpoints that the new lease holder is responsible for.
+pub struct PostgresProgressStore {
+    pool: PgPool,
+    replica_id: String,
+    lease_duration: Duration,
+    leased_tasks: HashSet<String>,
+}
+
+impl PostgresProgressStore {
+    pub async fn new(database_url: &str, replica_id: Option<String>) -> Result<Self> {
+        let manager = ConnectionManager::<PgConnection>::new(database_url);
+        let pool = Pool::builder().max_size(2).build(manager)?;
+        let store = Self {
+            pool,
+            replica_id: replica_id.unwrap_or_else(default_replica_id),
+            lease_duration: DEFAULT_LEASE_DURATION,
+            leased_tasks: HashSet::new(),
+        };
+        store
+            .execute(|conn| {
+                diesel::sql_query(CREATE_TABLE).execute(conn)?;
+                Ok(())
+            })
+            .await?;
+        Ok(store)
+    }
+
+    pub fn with_lease_duration(mut self, lease_duration: Duration) -> Self {
+        self.lease_duration = lease_duration;
+        self
+    }
+
+    pub fn replica_id(&self) -> &str {
+        &self.replica_id
+    }
+
+    async fn execute<T, F>(&self, query: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut PgConnection) -> Result<T> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || query(&mut pool.get()?)).await?
+    }
+
+    /// Tries to take or renew the lease on `task_name`, returning the task's watermark if it
+    /// succeeded, and `None` if another replica holds the lease.
+    async fn try_acquire(&self, task_name: String) -> Result<Option<CheckpointSequenceNumber>> {
+        let replica_id = self.replica_id.clone();
+        let lease_secs = self.lease_duration.as_secs_f64();
+        let rows = self
+            .execute(move |conn| {
+                diesel::sql_query(INSERT_TASK)
+                    .bind::<Text, _>(&task_name)
+                    .execute(conn)?;
+                Ok(diesel::sql_query(ACQUIRE_LEASE)
+                    .bind::<Text, _>(&task_name)
+                    .bind::<Text, _>(&replica_id)
+                    .bind::<Double, _>(lease_secs)
+                    .load::<Watermark>(conn)?)
+            })
+            .await?;
+        Ok(rows
+            .first()
+            .map(|w| w.checkpoint as CheckpointSequenceNumber))
+    }
+}
+
+#[async_trait]
+impl ProgressStore for PostgresProgressStore {
+    async fn load(&mut self, task_name: String) -> Result<CheckpointSequenceNumber> {
+        loop {
+            if let Some(checkpoint) = self.try_acquire(task_name.clone()).await? {
+                if self.leased_tasks.insert(task_name.clone()) {
+                    info!(
+                        "Replica {} acquired lease for task {task_name} at checkpoint {checkpoint}",
+                        self.replica_id
+                    );
+                }
+                return Ok(checkpoint);
+            }
+            if self.leased_tasks.contains(&task_name) {
+                bail!(
+                    "Replica {} lost its lease for task {task_name} to another replica",
+                    self.replica_id
+                );
+            }
+            info!(
+                "Replica {} waiting for lease on task {task_name}",
+                self.replica_id
+            );
+            tokio::time::sleep(self.lease_duration / 2).await;
+        }
+    }
+
+    async fn save(
+        &mut self,
+        task_name: String,
+        checkpoint_number: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        let replica_id = self.replica_id.clone();
+        let lease_secs = self.lease_duration.as_secs_f64();
+        let name = task_name.clone();
+        let rows = self
+            .execute(move |conn| {
+                Ok(diesel::sql_query(ADVANCE_WATERMARK)
+                    .bind::<Text, _>(&name)
+                    .bind::<Text, _>(&replica_id)
+                    .bind::<BigInt, _>(checkpoint_number as i64)
+                    .bind::<Double, _>(lease_secs)
+                    .load::<Watermark>(conn)?)
+            })
+            .await?;
+        if rows.is_empty() {
+            bail!(
+                "Replica {} no longer holds the lease for task {task_name}, refusing to save \
+                 checkpoint {checkpoint_number}",
+                self.replica_id
+            );
+        }
+        Ok(())
+    }
+}
+
+fn default_replica_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    format!("replica-{}-{nanos}", std::process::id())
+}
+
+#[cfg(all(test, feature = "pg_integration"))]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn database_url() -> String {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        format!("postgres://postgres:{pw}@{pg_host}:{pg_port}")
+    }
+
+    fn task_name(test: &str) -> String {
+        format!("{test}-{}", default_replica_id())
+    }
+
+    #[tokio::test]
+    async fn watermark_only_moves_forward() -> Result<()> {
+        let task = task_name("forward");
+        let mut store = PostgresProgressStore::new(&database_url(), None).await?;
+        assert_eq!(store.load(task.clone()).await?, 0);
+        store.save(task.clone(), 10).await?;
+        store.save(task.clone(), 5).await?;
+        assert_eq!(store.load(task).await?, 10);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn standby_waits_for_lease() -> Result<()> {
+        let task = task_name("standby");
+        let lease = Duration::from_millis(500);
+        let mut active = PostgresProgressStore::new(&database_url(), Some("active".into()))
+            .await?
+            .with_lease_duration(lease);
+        let mut standby = PostgresProgressStore::new(&database_url(), Some("standby".into()))
+            .await?
+            .with_lease_duration(lease);
+
+        active.load(task.clone()).await?;
+        active.save(task.clone(), 7).await?;
+
+        // The standby only gets the lease once the active replica stops renewing it, and resumes
+        // from the active replica's watermark.
+        assert!(standby.try_acquire(task.clone()).await?.is_none());
+        assert_eq!(standby.load(task.clone()).await?, 7);
+
+        // The active replica may no longer make progress.
+        assert!(active.save(task.clone(), 8).await.is_err());
+        assert!(active.load(task.clone()).await.is_err());
+        standby.save(task.clone(), 8).await?;
+        assert_eq!(standby.load(task).await?, 8);
+        Ok(())
+    }
+}
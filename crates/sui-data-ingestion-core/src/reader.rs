@@ -3,6 +3,7 @@
 
 use crate::create_remote_store_client;
 use crate::executor::MAX_CHECKPOINTS_IN_PROGRESS;
+use crate::filter::{CheckpointFilter, FilteredCheckpoint};
 use anyhow::Result;
 use backoff::backoff::Backoff;
 use futures::StreamExt;
@@ -35,12 +36,15 @@ pub struct CheckpointReader {
     remote_store_options: Vec<(String, String)>,
     current_checkpoint_number: CheckpointSequenceNumber,
     last_pruned_watermark: CheckpointSequenceNumber,
-    checkpoint_sender: mpsc::Sender<CheckpointData>,
+    checkpoint_sender: mpsc::Sender<FilteredCheckpoint>,
     processed_receiver: mpsc::Receiver<CheckpointSequenceNumber>,
     remote_fetcher_receiver: Option<mpsc::Receiver<Result<(CheckpointData, usize)>>>,
     exit_receiver: oneshot::Receiver<()>,
     options: ReaderOptions,
     data_limiter: DataLimiter,
+    /// Filters of the worker pools, in registration order, which checkpoints are matched against
+    /// as they are read, to remove the data that no pool is interested in.
+    filters: Vec<Option<CheckpointFilter>>,
 }
 
 #[derive(Clone)]
@@ -49,6 +53,12 @@ pub struct ReaderOptions {
     pub timeout_secs: u64,
     pub batch_size: usize,
     pub data_limit: usize,
+    /// Last checkpoint to read (inclusive). The reader stops making progress once it has read this
+    /// checkpoint, instead of following the tip of the chain.
+    pub upper_limit: Option<CheckpointSequenceNumber>,
+    /// Subscribe to the checkpoints pushed by a full node instead of requesting them one by one.
+    /// Requires the remote store to be the REST API of the node, i.e. its url ends with `/rest`.
+    pub rest_subscription: bool,
 }
 
 impl Default for ReaderOptions {
@@ -58,6 +68,8 @@ impl Default for ReaderOptions {
             timeout_secs: 5,
             batch_size: 100,
             data_limit: 0,
+            upper_limit: None,
+            rest_subscription: false,
         }
     }
 }
@@ -67,6 +79,11 @@ enum RemoteStore {
     Rest(sui_rest_api::Client),
 }
 
+/// Whether `url` points at the REST API of a full node rather than at an object store.
+fn is_rest_url(url: &str) -> bool {
+    url.ends_with("/rest")
+}
+
 impl CheckpointReader {
     /// Represents a single iteration of the reader.
     /// Reads files in a local directory, validates them, and forwards `CheckpointData` to the executor.
@@ -76,7 +93,9 @@ impl CheckpointReader {
             let entry = entry?;
             let filename = entry.file_name();
             if let Some(sequence_number) = Self::checkpoint_number_from_file_path(&filename) {
-                if sequence_number >= self.current_checkpoint_number {
+                if sequence_number >= self.current_checkpoint_number
+                    && !self.exceeds_upper_limit(sequence_number)
+                {
                     files.push((sequence_number, entry.path()));
                 }
             }
@@ -97,6 +116,13 @@ impl CheckpointReader {
     fn exceeds_capacity(&self, checkpoint_number: CheckpointSequenceNumber) -> bool {
         ((MAX_CHECKPOINTS_IN_PROGRESS as u64 + self.last_pruned_watermark) <= checkpoint_number)
             || self.data_limiter.exceeds()
+            || self.exceeds_upper_limit(checkpoint_number)
+    }
+
+    fn exceeds_upper_limit(&self, checkpoint_number: CheckpointSequenceNumber) -> bool {
+        self.options
+            .upper_limit
+            .is_some_and(|limit| checkpoint_number > limit)
     }
 
     async fn remote_fetch_checkpoint_internal(
@@ -147,15 +173,62 @@ impl CheckpointReader {
         }
     }
 
+    /// Forwards the checkpoints pushed by the checkpoint subscription endpoint of a full node,
+    /// until `end_checkpoint` (exclusive) or until the subscription fails.
+    async fn forward_subscription(
+        client: &sui_rest_api::Client,
+        start_checkpoint: CheckpointSequenceNumber,
+        end_checkpoint: CheckpointSequenceNumber,
+        sender: &mpsc::Sender<Result<(CheckpointData, usize)>>,
+    ) {
+        let checkpoints = match client.subscribe_checkpoints(start_checkpoint).await {
+            Ok(checkpoints) => checkpoints,
+            Err(err) => {
+                let _ = sender.send(Err(err)).await;
+                return;
+            }
+        };
+        let mut checkpoints = std::pin::pin!(checkpoints);
+
+        while let Some(checkpoint) = checkpoints.next().await {
+            let checkpoint = checkpoint.and_then(|checkpoint| {
+                let size = bcs::serialized_size(&checkpoint)?;
+                Ok((checkpoint, size))
+            });
+            let done = checkpoint.as_ref().map_or(true, |(checkpoint, _)| {
+                checkpoint
+                    .checkpoint_summary
+                    .sequence_number
+                    .saturating_add(1)
+                    >= end_checkpoint
+            });
+            if sender.send(checkpoint).await.is_err() {
+                info!("remote reader dropped");
+                return;
+            }
+            if done {
+                return;
+            }
+        }
+        let _ = sender
+            .send(Err(anyhow::anyhow!("checkpoint subscription ended")))
+            .await;
+    }
+
     fn start_remote_fetcher(&mut self) -> mpsc::Receiver<Result<(CheckpointData, usize)>> {
         let batch_size = self.options.batch_size;
+        let rest_subscription = self.options.rest_subscription;
         let start_checkpoint = self.current_checkpoint_number;
+        let end_checkpoint = self
+            .options
+            .upper_limit
+            .map_or(u64::MAX, |limit| limit.saturating_add(1));
         let (sender, receiver) = mpsc::channel(batch_size);
         let url = self
             .remote_store_url
             .clone()
             .expect("remote store url must be set");
-        let store = if url.ends_with("/rest") {
+        let store = if is_rest_url(&url) {
             RemoteStore::Rest(sui_rest_api::Client::new(url))
         } else {
             let object_store = create_remote_store_client(
@@ -168,7 +241,12 @@ impl CheckpointReader {
         };
 
         spawn_monitored_task!(async move {
-            let mut checkpoint_stream = (start_checkpoint..u64::MAX)
+            if let (RemoteStore::Rest(client), true) = (&store, rest_subscription) {
+                Self::forward_subscription(client, start_checkpoint, end_checkpoint, &sender).await;
+                return;
+            }
+
+            let mut checkpoint_stream = (start_checkpoint..end_checkpoint)
                 .map(|checkpoint_number| Self::remote_fetch_checkpoint(&store, checkpoint_number))
                 .pipe(futures::stream::iter)
                 .buffered(batch_size);
@@ -211,6 +289,9 @@ impl CheckpointReader {
     }
 
     async fn sync(&mut self) -> Result<()> {
+        if self.exceeds_upper_limit(self.current_checkpoint_number) {
+            return Ok(());
+        }
         let backoff = backoff::ExponentialBackoff::default();
         let mut checkpoints = backoff::future::retry(backoff, || async {
             self.read_local_files().await.map_err(|err| {
@@ -247,6 +328,7 @@ impl CheckpointReader {
                 checkpoint.checkpoint_summary.sequence_number,
                 self.current_checkpoint_number
             );
+            let checkpoint = FilteredCheckpoint::new(&self.filters, checkpoint);
             self.checkpoint_sender.send(checkpoint).await?;
             self.current_checkpoint_number += 1;
         }
@@ -283,9 +365,10 @@ impl CheckpointReader {
         remote_store_url: Option<String>,
         remote_store_options: Vec<(String, String)>,
         options: ReaderOptions,
+        filters: Vec<Option<CheckpointFilter>>,
     ) -> (
         Self,
-        mpsc::Receiver<CheckpointData>,
+        mpsc::Receiver<FilteredCheckpoint>,
         mpsc::Sender<CheckpointSequenceNumber>,
         oneshot::Sender<()>,
     ) {
@@ -304,11 +387,21 @@ impl CheckpointReader {
             exit_receiver,
             data_limiter: DataLimiter::new(options.data_limit),
             options,
+            filters,
         };
         (reader, checkpoint_recv, processed_sender, exit_sender)
     }
 
     pub async fn run(mut self) -> Result<()> {
+        if self.options.rest_subscription
+            && !self.remote_store_url.as_deref().is_some_and(is_rest_url)
+        {
+            anyhow::bail!(
+                "checkpoint subscriptions require the remote store url to be the REST API of a full node, ending with `/rest`, got {:?}",
+                self.remote_store_url
+            );
+        }
+
         let (inotify_sender, mut inotify_recv) = mpsc::channel(1);
         std::fs::create_dir_all(self.path.clone()).expect("failed to create a directory");
         let mut watcher = notify::recommended_watcher(move |res| {
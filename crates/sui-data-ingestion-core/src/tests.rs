@@ -1,26 +1,39 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::executor::split_range;
+use crate::filter::{type_matches, FilterStats, FilteredCheckpoint};
 use crate::progress_store::ExecutorProgress;
-use crate::{DataIngestionMetrics, FileProgressStore, IndexerExecutor, WorkerPool};
+use crate::{
+    CheckpointFilter, DataIngestionMetrics, FileProgressStore, IndexerExecutor, WorkerPool,
+};
 use crate::{ReaderOptions, Worker};
 use anyhow::Result;
 use async_trait::async_trait;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
 use prometheus::Registry;
 use rand::prelude::StdRng;
 use rand::SeedableRng;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use sui_protocol_config::ProtocolConfig;
 use sui_storage::blob::{Blob, BlobEncoding};
-use sui_types::crypto::KeypairTraits;
-use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::base_types::{random_object_ref, ObjectID, SuiAddress};
+use sui_types::crypto::{get_account_key_pair, AccountKeyPair, KeypairTraits};
+use sui_types::effects::{TransactionEffects, TransactionEvents};
+use sui_types::event::Event;
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
 use sui_types::gas::GasCostSummary;
 use sui_types::messages_checkpoint::{
     CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
     SignedCheckpointSummary,
 };
-use sui_types::utils::make_committee_key;
+use sui_types::object::Object;
+use sui_types::transaction::TransactionData;
+use sui_types::utils::{make_committee_key, to_sender_signed_transaction};
 use tempfile::NamedTempFile;
 use tokio::sync::oneshot;
 
@@ -105,6 +118,339 @@ async fn basic_flow() {
     assert_eq!(result.unwrap().get("test"), Some(&20));
 }
 
+#[tokio::test]
+async fn bounded_flow() {
+    let mut bundle = create_executor_bundle();
+    add_worker_pool(&mut bundle.executor, TestWorker, 5)
+        .await
+        .unwrap();
+    let path = temp_dir();
+    for checkpoint_number in 0..20 {
+        let bytes = mock_checkpoint_data_bytes(checkpoint_number);
+        std::fs::write(path.join(format!("{}.chk", checkpoint_number)), bytes).unwrap();
+    }
+    let options = ReaderOptions {
+        tick_interal_ms: 10,
+        batch_size: 1,
+        upper_limit: Some(9),
+        ..Default::default()
+    };
+    // The executor exits on its own once the upper limit is processed, without an exit signal.
+    let (_sender, recv) = oneshot::channel();
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        bundle.executor.run(path, None, vec![], options, recv),
+    )
+    .await
+    .expect("bounded executor should exit once the range is processed");
+    assert_eq!(result.unwrap().get("test"), Some(&10));
+}
+
+#[tokio::test]
+async fn filtered_flow() {
+    let mut bundle = create_executor_bundle();
+    // A filter that matches nothing still lets the pool's watermark advance.
+    let filter = CheckpointFilter {
+        senders: vec![SuiAddress::random_for_testing_only()],
+        drop_objects: true,
+        ..Default::default()
+    };
+    let worker_pool = WorkerPool::new(TestWorker, "test".to_string(), 5).with_filter(filter);
+    bundle.executor.register(worker_pool).await.unwrap();
+    let path = temp_dir();
+    for checkpoint_number in 0..20 {
+        let bytes = mock_checkpoint_data_bytes(checkpoint_number);
+        std::fs::write(path.join(format!("{}.chk", checkpoint_number)), bytes).unwrap();
+    }
+    let result = run(bundle.executor, Some(path), Some(Duration::from_secs(1))).await;
+    assert_eq!(result.unwrap().get("test"), Some(&20));
+}
+
+#[derive(Clone, Default)]
+struct RecordingWorker(Arc<Mutex<Vec<CheckpointTransaction>>>);
+
+#[async_trait]
+impl Worker for RecordingWorker {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        self.0.lock().unwrap().extend(checkpoint.transactions);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn filtered_flow_with_projections() {
+    let (transactions, [by_object_type, by_event_type]) = filtered_transactions();
+    let mut bundle = create_executor_bundle();
+    let objects_worker = RecordingWorker::default();
+    let events_worker = RecordingWorker::default();
+    for (name, worker, filter) in [
+        ("objects", objects_worker.clone(), by_object_type),
+        ("events", events_worker.clone(), by_event_type),
+    ] {
+        let worker_pool = WorkerPool::new(worker, name.to_string(), 1).with_filter(filter);
+        bundle.executor.register(worker_pool).await.unwrap();
+    }
+    let path = temp_dir();
+    for checkpoint_number in 0..2 {
+        let checkpoint_transactions = if checkpoint_number == 0 {
+            transactions.clone()
+        } else {
+            vec![]
+        };
+        let bytes = Blob::encode(
+            &mock_checkpoint_data(checkpoint_number, checkpoint_transactions),
+            BlobEncoding::Bcs,
+        )
+        .unwrap()
+        .to_bytes();
+        std::fs::write(path.join(format!("{}.chk", checkpoint_number)), bytes).unwrap();
+    }
+    let result = run(bundle.executor, Some(path), Some(Duration::from_secs(1)))
+        .await
+        .unwrap();
+    assert_eq!(result.get("objects"), Some(&2));
+    assert_eq!(result.get("events"), Some(&2));
+
+    let received = objects_worker.0.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].transaction, transactions[0].transaction);
+    assert!(received[0].input_objects.is_empty());
+    let received = events_worker.0.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].transaction, transactions[1].transaction);
+    assert!(received[0].events.is_none());
+}
+
+#[test]
+fn filter_type_matching() {
+    let tag = |s: &str| StructTag::from_str(s).unwrap();
+    let coin = tag("0x2::coin::Coin");
+    let sui_coin = tag("0x2::coin::Coin<0x2::sui::SUI>");
+    let other_coin = tag("0x2::coin::Coin<0x3::foo::FOO>");
+
+    assert!(type_matches(&coin, &sui_coin));
+    assert!(type_matches(&coin, &other_coin));
+    assert!(type_matches(&sui_coin, &sui_coin));
+    assert!(!type_matches(&sui_coin, &other_coin));
+    assert!(!type_matches(
+        &coin,
+        &tag("0x2::balance::Balance<0x2::sui::SUI>")
+    ));
+}
+
+fn mock_transaction(
+    sender: SuiAddress,
+    key: &AccountKeyPair,
+    package: ObjectID,
+    events: Vec<Event>,
+) -> CheckpointTransaction {
+    let data = TransactionData::new_move_call(
+        sender,
+        package,
+        Identifier::new("module").unwrap(),
+        Identifier::new("function").unwrap(),
+        vec![],
+        random_object_ref(),
+        vec![],
+        1_000_000,
+        1,
+    )
+    .unwrap();
+    CheckpointTransaction {
+        transaction: to_sender_signed_transaction(data, key),
+        effects: TransactionEffects::default(),
+        events: (!events.is_empty()).then_some(TransactionEvents { data: events }),
+        input_objects: vec![Object::with_owner_for_testing(sender)],
+        output_objects: vec![Object::with_owner_for_testing(sender)],
+    }
+}
+
+fn mock_event(package: ObjectID, type_: &str) -> Event {
+    Event::new(
+        &package.into(),
+        &Identifier::new("module").unwrap(),
+        SuiAddress::ZERO,
+        StructTag::from_str(type_).unwrap(),
+        vec![],
+    )
+}
+
+#[test]
+fn filter_transactions() {
+    let (alice, alice_key) = get_account_key_pair();
+    let (bob, bob_key) = get_account_key_pair();
+    let package = ObjectID::random();
+    let other_package = ObjectID::random();
+    let event_type = format!("{package}::module::Minted");
+
+    let transactions = [
+        mock_transaction(alice, &alice_key, other_package, vec![]),
+        mock_transaction(
+            bob,
+            &bob_key,
+            package,
+            vec![mock_event(package, &event_type)],
+        ),
+        mock_transaction(
+            bob,
+            &bob_key,
+            other_package,
+            vec![mock_event(
+                other_package,
+                "0x2::coin::CoinCreated<0x2::sui::SUI>",
+            )],
+        ),
+    ];
+    let selected = |filter: &CheckpointFilter| -> Vec<bool> {
+        transactions.iter().map(|tx| filter.matches(tx)).collect()
+    };
+
+    assert_eq!(
+        selected(&CheckpointFilter::default()),
+        vec![true, true, true]
+    );
+    let by_sender = CheckpointFilter {
+        senders: vec![alice],
+        ..Default::default()
+    };
+    assert_eq!(selected(&by_sender), vec![true, false, false]);
+    let by_package = CheckpointFilter {
+        packages: vec![package],
+        ..Default::default()
+    };
+    assert_eq!(selected(&by_package), vec![false, true, false]);
+    let by_event_type = CheckpointFilter {
+        event_types: vec![StructTag::from_str("0x2::coin::CoinCreated").unwrap()],
+        ..Default::default()
+    };
+    assert_eq!(selected(&by_event_type), vec![false, false, true]);
+    // All mock transactions read and write gas coins.
+    let by_object_type = CheckpointFilter {
+        object_types: vec![StructTag::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap()],
+        ..Default::default()
+    };
+    assert_eq!(selected(&by_object_type), vec![true, true, true]);
+
+    let projection = CheckpointFilter {
+        drop_objects: true,
+        drop_events: true,
+        ..Default::default()
+    };
+    let mut stats = FilterStats::default();
+    let projected = projection.project(&transactions[1], &mut stats);
+    assert!(projected.input_objects.is_empty());
+    assert!(projected.output_objects.is_empty());
+    assert!(projected.events.is_none());
+    assert_eq!(projected.transaction, transactions[1].transaction);
+    assert_eq!(
+        stats,
+        FilterStats {
+            transactions_dropped: 0,
+            objects_dropped: 2,
+            events_dropped: 1,
+        }
+    );
+}
+
+/// Two transactions, along with the filters of two pools that each select one of them, the first
+/// by its objects and the second by its events.
+fn filtered_transactions() -> (Vec<CheckpointTransaction>, [CheckpointFilter; 2]) {
+    let (alice, alice_key) = get_account_key_pair();
+    let (bob, bob_key) = get_account_key_pair();
+    let package = ObjectID::random();
+    let event_type = format!("{package}::module::Minted");
+
+    let with_objects = mock_transaction(alice, &alice_key, package, vec![]);
+    let mut with_events = mock_transaction(
+        bob,
+        &bob_key,
+        package,
+        vec![mock_event(package, &event_type)],
+    );
+    with_events.input_objects.clear();
+    with_events.output_objects.clear();
+
+    // Each pool drops the data it matches on.
+    let by_object_type = CheckpointFilter {
+        object_types: vec![StructTag::from_str("0x2::coin::Coin").unwrap()],
+        drop_objects: true,
+        ..Default::default()
+    };
+    let by_event_type = CheckpointFilter {
+        event_types: vec![StructTag::from_str(&event_type).unwrap()],
+        drop_events: true,
+        ..Default::default()
+    };
+    (
+        vec![with_objects, with_events],
+        [by_object_type, by_event_type],
+    )
+}
+
+#[test]
+fn filter_checkpoint_for_pools() {
+    let (transactions, [by_object_type, by_event_type]) = filtered_transactions();
+    let checkpoint = mock_checkpoint_data(0, transactions.clone());
+
+    let filters = [Some(by_object_type.clone()), Some(by_event_type.clone())];
+    let filtered = FilteredCheckpoint::new(&filters, checkpoint.clone());
+    // No pool receives the objects or events of the transactions, once they are matched.
+    assert_eq!(filtered.checkpoint.transactions.len(), 2);
+    assert!(filtered
+        .checkpoint
+        .transactions
+        .iter()
+        .all(|tx| tx.input_objects.is_empty() && tx.events.is_none()));
+
+    let (objects_pool, stats) = filtered.for_pool(0, Some(&by_object_type));
+    assert_eq!(objects_pool.transactions.len(), 1);
+    assert_eq!(
+        objects_pool.transactions[0].transaction,
+        transactions[0].transaction
+    );
+    assert_eq!(
+        stats,
+        Some(FilterStats {
+            transactions_dropped: 1,
+            objects_dropped: 2,
+            events_dropped: 0,
+        })
+    );
+    let (events_pool, stats) = filtered.for_pool(1, Some(&by_event_type));
+    assert_eq!(events_pool.transactions.len(), 1);
+    assert_eq!(
+        events_pool.transactions[0].transaction,
+        transactions[1].transaction
+    );
+    assert_eq!(
+        stats,
+        Some(FilterStats {
+            transactions_dropped: 1,
+            objects_dropped: 0,
+            events_dropped: 1,
+        })
+    );
+
+    // A pool without a filter receives whole checkpoints.
+    let filtered = FilteredCheckpoint::new(&[Some(by_object_type.clone()), None], checkpoint);
+    let (whole, stats) = filtered.for_pool(1, None);
+    assert_eq!(whole.transactions.len(), 2);
+    assert!(!whole.transactions[0].input_objects.is_empty());
+    assert!(whole.transactions[1].events.is_some());
+    assert!(stats.is_none());
+    let (objects_pool, _) = filtered.for_pool(0, Some(&by_object_type));
+    assert_eq!(objects_pool.transactions.len(), 1);
+    assert!(objects_pool.transactions[0].input_objects.is_empty());
+}
+
+#[test]
+fn split_checkpoint_ranges() {
+    assert_eq!(split_range(0..=9, 3), vec![0..=3, 4..=6, 7..=9]);
+    assert_eq!(split_range(5..=6, 4), vec![5..=5, 6..=6]);
+    assert_eq!(split_range(5..=5, 0), vec![5..=5]);
+    assert!(split_range(6..=5, 2).is_empty());
+}
+
 fn temp_dir() -> std::path::PathBuf {
     tempfile::tempdir()
         .expect("Failed to open temporary directory")
@@ -133,6 +479,15 @@ const RNG_SEED: [u8; 32] = [
 ];
 
 fn mock_checkpoint_data_bytes(seq_number: CheckpointSequenceNumber) -> Vec<u8> {
+    Blob::encode(&mock_checkpoint_data(seq_number, vec![]), BlobEncoding::Bcs)
+        .unwrap()
+        .to_bytes()
+}
+
+fn mock_checkpoint_data(
+    seq_number: CheckpointSequenceNumber,
+    transactions: Vec<CheckpointTransaction>,
+) -> CheckpointData {
     let mut rng = StdRng::from_seed(RNG_SEED);
     let (keys, committee) = make_committee_key(&mut rng);
     let contents = CheckpointContents::new_with_digests_only_for_tests(vec![]);
@@ -157,13 +512,10 @@ fn mock_checkpoint_data_bytes(seq_number: CheckpointSequenceNumber) -> Vec<u8> {
         })
         .collect();
 
-    let checkpoint_data = CheckpointData {
+    CheckpointData {
         checkpoint_summary: CertifiedCheckpointSummary::new(summary, sign_infos, &committee)
             .unwrap(),
         checkpoint_contents: contents,
-        transactions: vec![],
-    };
-    Blob::encode(&checkpoint_data, BlobEncoding::Bcs)
-        .unwrap()
-        .to_bytes()
+        transactions,
+    }
 }
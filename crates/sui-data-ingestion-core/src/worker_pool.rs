@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::executor::MAX_CHECKPOINTS_IN_PROGRESS;
+use crate::filter::CheckpointFilter;
 use crate::Worker;
 use mysten_metrics::spawn_monitored_task;
 use std::collections::{BTreeSet, HashMap, VecDeque};
@@ -17,6 +18,7 @@ pub struct WorkerPool<W: Worker> {
     pub task_name: String,
     concurrency: usize,
     worker: Arc<W>,
+    pub(crate) filter: Option<CheckpointFilter>,
 }
 
 impl<W: Worker + 'static> WorkerPool<W> {
@@ -25,8 +27,16 @@ impl<W: Worker + 'static> WorkerPool<W> {
             task_name,
             concurrency,
             worker: Arc::new(worker),
+            filter: None,
         }
     }
+
+    /// Only dispatches the parts of each checkpoint selected by `filter` to this pool's workers.
+    pub fn with_filter(mut self, filter: CheckpointFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     pub async fn run(
         self,
         mut current_checkpoint_number: CheckpointSequenceNumber,
@@ -123,7 +133,12 @@ impl<W: Worker + 'static> WorkerPool<W> {
                         }
                     }
                 }
-                Some(checkpoint) = checkpoint_receiver.recv() => {
+                maybe_checkpoint = checkpoint_receiver.recv() => {
+                    let Some(checkpoint) = maybe_checkpoint else {
+                        // The executor dropping the checkpoint channel is a sign we need to exit
+                        // this loop.
+                        break;
+                    };
                     let sequence_number = checkpoint.checkpoint_summary.sequence_number;
                     if sequence_number < current_checkpoint_number {
                         continue;
@@ -6,12 +6,13 @@ use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 use sui_data_ingestion::{
     ArchivalConfig, ArchivalWorker, BlobTaskConfig, BlobWorker, DynamoDBProgressStore,
     KVStoreTaskConfig, KVStoreWorker,
 };
 use sui_data_ingestion_core::{DataIngestionMetrics, ReaderOptions};
-use sui_data_ingestion_core::{IndexerExecutor, WorkerPool};
+use sui_data_ingestion_core::{IndexerExecutor, PostgresProgressStore, ProgressStore, WorkerPool};
 use tokio::signal;
 use tokio::sync::oneshot;
 
@@ -31,15 +32,32 @@ struct TaskConfig {
     concurrency: usize,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+enum ProgressStoreConfig {
+    DynamoDB(DynamoDBProgressStoreConfig),
+    Postgres(PostgresProgressStoreConfig),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
-struct ProgressStoreConfig {
+struct DynamoDBProgressStoreConfig {
     pub aws_access_key_id: String,
     pub aws_secret_access_key: String,
     pub aws_region: String,
     pub table_name: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct PostgresProgressStoreConfig {
+    pub database_url: String,
+    /// Identifies this replica when holding task leases. Generated at startup if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replica_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lease_duration_secs: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct IndexerConfig {
     path: PathBuf,
@@ -51,6 +69,9 @@ struct IndexerConfig {
     remote_store_options: Vec<(String, String)>,
     #[serde(default = "default_remote_read_batch_size")]
     remote_read_batch_size: usize,
+    /// Subscribe to the checkpoint stream of a full node when `remote_store_url` is its REST API
+    #[serde(default)]
+    remote_rest_subscription: bool,
     #[serde(default = "default_metrics_host")]
     metrics_host: String,
     #[serde(default = "default_metrics_port")]
@@ -107,13 +128,35 @@ async fn main() -> Result<()> {
     mysten_metrics::init_metrics(&registry);
     let metrics = DataIngestionMetrics::new(&registry);
 
-    let progress_store = DynamoDBProgressStore::new(
-        &config.progress_store.aws_access_key_id,
-        &config.progress_store.aws_secret_access_key,
-        config.progress_store.aws_region,
-        config.progress_store.table_name,
-    )
-    .await;
+    match config.progress_store.clone() {
+        ProgressStoreConfig::DynamoDB(store_config) => {
+            let progress_store = DynamoDBProgressStore::new(
+                &store_config.aws_access_key_id,
+                &store_config.aws_secret_access_key,
+                store_config.aws_region,
+                store_config.table_name,
+            )
+            .await;
+            run_executor(progress_store, config, metrics, exit_receiver).await
+        }
+        ProgressStoreConfig::Postgres(store_config) => {
+            let mut progress_store =
+                PostgresProgressStore::new(&store_config.database_url, store_config.replica_id)
+                    .await?;
+            if let Some(secs) = store_config.lease_duration_secs {
+                progress_store = progress_store.with_lease_duration(Duration::from_secs(secs));
+            }
+            run_executor(progress_store, config, metrics, exit_receiver).await
+        }
+    }
+}
+
+async fn run_executor<P: ProgressStore>(
+    progress_store: P,
+    config: IndexerConfig,
+    metrics: DataIngestionMetrics,
+    exit_receiver: oneshot::Receiver<()>,
+) -> Result<()> {
     let mut executor = IndexerExecutor::new(progress_store, config.tasks.len(), metrics);
     for task_config in config.tasks {
         match task_config.task {
@@ -145,6 +188,7 @@ async fn main() -> Result<()> {
     }
     let reader_options = ReaderOptions {
         batch_size: config.remote_read_batch_size,
+        rest_subscription: config.remote_rest_subscription,
         ..Default::default()
     };
     executor
@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_sdk::grpc::{ObjectResponse, TransactionBlockOptions};
+use sui_sdk::SuiClientBuilder;
+use sui_test_transaction_builder::make_transfer_sui_transaction;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::gas_coin::GAS;
+use test_cluster::TestClusterBuilder;
+
+#[sim_test]
+async fn test_grpc_read_api() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = SuiClientBuilder::default()
+        .build_grpc(test_cluster.rpc_url())
+        .await
+        .unwrap();
+
+    let recipient = SuiAddress::random_for_testing_only();
+    let txn = make_transfer_sui_transaction(&test_cluster.wallet, Some(recipient), Some(9)).await;
+    let digest = *txn.digest();
+    test_cluster.execute_transaction(txn).await;
+
+    let transaction = client
+        .get_transaction_block(digest, TransactionBlockOptions::full())
+        .await
+        .unwrap();
+    assert_eq!(transaction.digest, digest);
+    assert_eq!(transaction.transaction.unwrap().digest(), &digest);
+    let native_effects = transaction.effects.unwrap();
+    assert_eq!(native_effects.transaction_digest(), &digest);
+    assert_eq!(transaction.events.unwrap().data.len(), 0);
+
+    // Only the requested parts are returned
+    let transaction = client
+        .get_transaction_block(digest, TransactionBlockOptions::default())
+        .await
+        .unwrap();
+    assert!(transaction.transaction.is_none());
+    assert!(transaction.effects.is_none());
+    assert!(transaction.events.is_none());
+
+    let unknown = TransactionDigest::random();
+    assert!(client
+        .get_transaction_block(unknown, TransactionBlockOptions::default())
+        .await
+        .is_err());
+    let transactions = client
+        .multi_get_transaction_blocks(vec![digest, unknown], TransactionBlockOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0].as_ref().unwrap().digest, digest);
+    assert!(transactions[1].is_none());
+
+    // The recipient's new coin matches the fullnode's copy
+    let (created, _) = native_effects.created()[0];
+    let expected = test_cluster
+        .get_object_from_fullnode_store(&created.0)
+        .await
+        .unwrap();
+    match client.get_object(created.0).await.unwrap() {
+        ObjectResponse::Exists(object) => assert_eq!(object, expected),
+        other => panic!("Unexpected response {other:?}"),
+    }
+    let objects = client
+        .multi_get_objects(vec![created.0, ObjectID::ZERO])
+        .await
+        .unwrap();
+    assert!(matches!(objects[0], ObjectResponse::Exists(_)));
+    assert!(matches!(objects[1], ObjectResponse::NotExists(_)));
+
+    let balance = client.get_balance(recipient, None).await.unwrap();
+    assert_eq!(balance.coin_type, GAS::type_().to_string());
+    assert_eq!(balance.total_balance, 9);
+    assert_eq!(balance.coin_object_count, 1);
+    assert_eq!(
+        client.get_all_balances(recipient).await.unwrap(),
+        vec![balance]
+    );
+
+    let coins = client.get_coins(recipient, None, None, None).await.unwrap();
+    assert_eq!(coins.coins.len(), 1);
+    assert_eq!(coins.coins[0].object_ref, created);
+    assert_eq!(coins.coins[0].balance, 9);
+    assert!(!coins.has_next_page);
+}
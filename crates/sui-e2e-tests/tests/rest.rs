@@ -1,14 +1,26 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use futures::StreamExt;
+use std::future::Future;
+use std::time::Duration;
 use sui_macros::sim_test;
 use sui_rest_api::client::BalanceChange;
 use sui_rest_api::Client;
-use sui_rest_api::ExecuteTransactionQueryParameters;
-use sui_test_transaction_builder::make_transfer_sui_transaction;
-use sui_types::base_types::SuiAddress;
-use sui_types::effects::TransactionEffectsAPI;
-use sui_types::transaction::TransactionDataAPI;
+use sui_rest_api::{
+    DynamicFieldType, ExecuteTransactionQueryParameters, ListAccountBalancesQueryParameters,
+    ListDynamicFieldsQueryParameters, ListEventsQueryParameters,
+    SimulateTransactionQueryParameters,
+};
+use sui_test_transaction_builder::{
+    create_devnet_nft, make_transfer_sui_transaction, publish_nfts_package,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
 use test_cluster::TestClusterBuilder;
 
 #[sim_test]
@@ -55,3 +67,287 @@ async fn execute_transaction_transfer() {
 
     assert_eq!(actual, expected);
 }
+
+/// The rest indexes are only updated once the fullnode has executed a checkpoint, so poll `f`
+/// until it returns a value.
+async fn wait_for<T, F, Fut>(mut f: F) -> T
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            if let Some(value) = f().await {
+                return value;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the rest indexes to catch up")
+}
+
+#[sim_test]
+async fn list_events() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let client = &Client::new(format!("{}/v2", test_cluster.rpc_url()));
+
+    let (package_id, _, _) = publish_nfts_package(&test_cluster.wallet).await;
+    let (sender, _, digest) = create_devnet_nft(&test_cluster.wallet, package_id).await;
+    let event_type = format!("{package_id}::devnet_nft::MintNFTEvent");
+
+    let event_type = &event_type;
+    let events = wait_for(|| async move {
+        let page = client
+            .list_events(&ListEventsQueryParameters {
+                event_type: Some(event_type.clone()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        (!page.entries.is_empty()).then(|| page.entries)
+    })
+    .await;
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        TransactionDigest::from(events[0].transaction_digest),
+        digest
+    );
+
+    // The same events are returned when also filtering on their sender
+    let page = client
+        .list_events(&ListEventsQueryParameters {
+            event_type: Some(event_type.clone()),
+            sender: Some(sender.into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(
+        page.entries[0].transaction_digest,
+        events[0].transaction_digest
+    );
+
+    // The events of the sender include them
+    let page = client
+        .list_events(&ListEventsQueryParameters {
+            sender: Some(sender.into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(page
+        .entries
+        .iter()
+        .any(|event| event.transaction_digest == events[0].transaction_digest));
+
+    // but not when filtering on some other sender
+    let page = client
+        .list_events(&ListEventsQueryParameters {
+            event_type: Some(event_type.clone()),
+            sender: Some(SuiAddress::random_for_testing_only().into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(page.entries.is_empty());
+}
+
+#[sim_test]
+async fn list_account_balances() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let client = &Client::new(format!("{}/v2", test_cluster.rpc_url()));
+    let address = SuiAddress::random_for_testing_only();
+    let amount = 9;
+
+    for _ in 0..2 {
+        let txn =
+            make_transfer_sui_transaction(&test_cluster.wallet, Some(address), Some(amount)).await;
+        test_cluster.execute_transaction(txn).await;
+    }
+
+    let balances = wait_for(|| async move {
+        let page = client
+            .list_account_balances(address, &ListAccountBalancesQueryParameters::default())
+            .await
+            .unwrap();
+        page.entries
+            .first()
+            .map_or(false, |balance| balance.coin_object_count == 2)
+            .then(|| page.entries)
+    })
+    .await;
+
+    assert_eq!(balances.len(), 1);
+    assert_eq!(
+        sui_types::TypeTag::from(balances[0].coin_type.clone()),
+        sui_types::gas_coin::GAS::type_tag()
+    );
+    assert_eq!(balances[0].balance, 2 * amount);
+}
+
+#[sim_test]
+async fn list_dynamic_fields() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let client = &Client::new(format!("{}/v2", test_cluster.rpc_url()));
+
+    // The system state object keeps its inner state in a dynamic field
+    let fields = wait_for(|| async move {
+        let page = client
+            .list_dynamic_fields(
+                SUI_SYSTEM_STATE_OBJECT_ID,
+                &ListDynamicFieldsQueryParameters::default(),
+            )
+            .await
+            .unwrap();
+        (!page.entries.is_empty()).then(|| page.entries)
+    })
+    .await;
+
+    for field in &fields {
+        assert_eq!(ObjectID::from(field.parent), SUI_SYSTEM_STATE_OBJECT_ID);
+        assert_eq!(field.dynamic_field_type, DynamicFieldType::Field);
+        assert!(field.dynamic_object_id.is_none());
+    }
+}
+
+#[sim_test]
+async fn subscribe_checkpoints() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let client = Client::new(format!("{}/v2", test_cluster.rpc_url()));
+
+    let start = client
+        .get_latest_checkpoint()
+        .await
+        .unwrap()
+        .sequence_number;
+    let checkpoints = client.subscribe_checkpoints(start).await.unwrap();
+    let mut checkpoints = std::pin::pin!(checkpoints);
+
+    let txn = make_transfer_sui_transaction(&test_cluster.wallet, None, None).await;
+    let digest = *txn.digest();
+    test_cluster.execute_transaction(txn).await;
+
+    // Checkpoints are pushed in order, without gaps, until one includes the transaction
+    let mut expected = start;
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let checkpoint = checkpoints.next().await.unwrap().unwrap();
+            assert_eq!(checkpoint.checkpoint_summary.sequence_number, expected);
+            expected += 1;
+
+            if checkpoint
+                .transactions
+                .iter()
+                .any(|tx| *tx.transaction.digest() == digest)
+            {
+                break;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for the transaction to be checkpointed");
+}
+
+#[sim_test]
+async fn simulate_transaction() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let client = Client::new(format!("{}/v2", test_cluster.rpc_url()));
+    let wallet = &test_cluster.wallet;
+    let sender = test_cluster.get_address_0();
+    let recipient = SuiAddress::random_for_testing_only();
+    let gas = wallet
+        .get_one_gas_object_owned_by_address(sender)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_price = wallet.get_reference_gas_price().await.unwrap();
+    let transfer = |budget| {
+        TransactionData::new_transfer_sui(recipient, sender, Some(9), gas, budget, gas_price)
+    };
+
+    // Without a budget, simulating the transaction estimates one
+    let response = client
+        .simulate_transaction(
+            &SimulateTransactionQueryParameters {
+                object_changes: true,
+                estimate_gas_budget: true,
+                ..Default::default()
+            },
+            &transfer(0),
+        )
+        .await
+        .unwrap();
+    let effects = TransactionEffects::from(response.effects);
+    assert!(effects.status().is_ok());
+
+    // The object changes are the ones of the effects
+    let mut expected = effects
+        .object_changes()
+        .into_iter()
+        .map(|change| {
+            (
+                change.id,
+                change.input_version.map(|version| version.value()),
+                change.output_version.map(|version| version.value()),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut actual = response
+        .object_changes
+        .unwrap()
+        .into_iter()
+        .map(|change| {
+            (
+                ObjectID::from(change.object_id),
+                change.input_version,
+                change.output_version,
+            )
+        })
+        .collect::<Vec<_>>();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+
+    // and a transaction with the estimated budget executes successfully
+    let budget = response.estimated_gas_budget.unwrap();
+    let txn = wallet.sign_transaction(&transfer(budget));
+    let response = client
+        .execute_transaction(&ExecuteTransactionQueryParameters::default(), &txn)
+        .await
+        .unwrap();
+    assert!(response.effects.status().is_ok());
+
+    // A budget covering the computation but not the storage of the transaction runs out of gas
+    let gas = wallet
+        .get_one_gas_object_owned_by_address(sender)
+        .await
+        .unwrap()
+        .unwrap();
+    let budget = effects.gas_cost_summary().computation_cost;
+    let transaction =
+        TransactionData::new_transfer_sui(recipient, sender, Some(9), gas, budget, gas_price);
+    let response = client
+        .simulate_transaction(&SimulateTransactionQueryParameters::default(), &transaction)
+        .await
+        .unwrap();
+    let effects = TransactionEffects::from(response.effects);
+    assert!(
+        matches!(
+            effects.status(),
+            ExecutionStatus::Failure {
+                error: ExecutionFailureStatus::InsufficientGas,
+                ..
+            }
+        ),
+        "{:?}",
+        effects.status()
+    );
+}
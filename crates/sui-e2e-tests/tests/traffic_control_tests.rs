@@ -26,7 +26,8 @@ use sui_test_transaction_builder::batch_make_transfer_transactions;
 use sui_types::{
     quorum_driver_types::ExecuteTransactionRequestType,
     traffic_control::{
-        FreqThresholdConfig, PolicyConfig, PolicyType, RemoteFirewallConfig, Weight,
+        FreqThresholdConfig, MethodPolicyConfig, PolicyConfig, PolicyType, RemoteFirewallConfig,
+        TokenBucketConfig, Weight,
     },
 };
 use test_cluster::{TestCluster, TestClusterBuilder};
@@ -317,6 +318,70 @@ async fn test_fullnode_traffic_control_spam_blocked() -> Result<(), anyhow::Erro
     panic!("Expected spam policy to trigger within {txn_count} requests");
 }
 
+#[tokio::test]
+async fn test_fullnode_traffic_control_method_rate_limit_reload() -> Result<(), anyhow::Error> {
+    let policy_config = PolicyConfig {
+        dry_run: false,
+        ..Default::default()
+    };
+    let test_cluster = TestClusterBuilder::new()
+        .with_fullnode_policy_config(Some(policy_config.clone()))
+        .build()
+        .await;
+    let traffic_controller = test_cluster
+        .fullnode_handle
+        .sui_node
+        .with(|node| node.traffic_controller())
+        .expect("Expected traffic controller on fullnode");
+    let jsonrpc_client = &test_cluster.fullnode_handle.rpc_client;
+
+    // Rate limit a single method to 3 requests per client, without refill
+    let mut rate_limited = policy_config.clone();
+    rate_limited.method_policies.insert(
+        "sui_getChainIdentifier".to_string(),
+        MethodPolicyConfig {
+            rate_limit: Some(TokenBucketConfig {
+                capacity: 3,
+                refill_per_sec: 0,
+                cost: 1,
+            }),
+            spam_weight: None,
+        },
+    );
+    traffic_controller.update_policy_config(rate_limited.clone());
+
+    for _ in 0..3 {
+        let response: RpcResult<String> = jsonrpc_client
+            .request("sui_getChainIdentifier", rpc_params![])
+            .await;
+        assert!(response.is_ok(), "Expected request within rate limit");
+    }
+    let response: RpcResult<String> = jsonrpc_client
+        .request("sui_getChainIdentifier", rpc_params![])
+        .await;
+    assert!(
+        response
+            .unwrap_err()
+            .to_string()
+            .contains("Too many requests"),
+        "Expected rate limit to trigger"
+    );
+    // Other methods are not affected
+    let response: RpcResult<String> = jsonrpc_client
+        .request("sui_getTotalTransactionBlocks", rpc_params![])
+        .await;
+    assert!(response.is_ok());
+
+    // Allowlisting the client on reload lifts the rate limit
+    rate_limited.allowlist = vec!["127.0.0.0/8".parse().unwrap()];
+    traffic_controller.update_policy_config(rate_limited);
+    let response: RpcResult<String> = jsonrpc_client
+        .request("sui_getChainIdentifier", rpc_params![])
+        .await;
+    assert!(response.is_ok(), "Expected allowlisted request to succeed");
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_validator_traffic_control_error_delegated() -> Result<(), anyhow::Error> {
     let n = 5;
@@ -29,15 +29,17 @@ use crate::{
     },
     server::version::{check_version_middleware, set_version_middleware},
     types::query::{Query, SuiGraphQLSchema},
+    types::subscription::Subscription,
 };
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
-use async_graphql::EmptySubscription;
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
 use axum::extract::FromRef;
 use axum::extract::{
     connect_info::IntoMakeServiceWithConnectInfo, ConnectInfo, Query as AxumQuery, State,
+    WebSocketUpgrade,
 };
 use axum::http::{HeaderMap, StatusCode};
 use axum::middleware::{self};
@@ -144,7 +146,7 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
     resolver: Option<PackageResolver>,
@@ -217,17 +219,18 @@ impl ServerBuilder {
         self
     }
 
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
-    /// Prepares the components of the server to be run. Finalizes the graphql schema, and expects
-    /// the `Db` and `Router` to have been initialized.
+    /// Prepares the components of the server to be run. Expects the `Db` and `Router` to have been
+    /// initialized. The graphql schema is finalized by the caller, once it has been extended with
+    /// data from background tasks that depend on these components.
     fn build_components(
         self,
     ) -> (
         String,
-        Schema<Query, Mutation, EmptySubscription>,
+        SchemaBuilder<Query, Mutation, Subscription>,
         Db,
         PackageResolver,
         Router,
@@ -242,7 +245,7 @@ impl ServerBuilder {
         } = self;
         (
             address,
-            schema.finish(),
+            schema,
             db_reader.expect("DB reader not initialized"),
             resolver.expect("Package resolver not initialized"),
             router.expect("Router not initialized"),
@@ -256,6 +259,8 @@ impl ServerBuilder {
                 .route("/:version", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
                 .route("/graphql/:version", post(graphql_handler))
+                .route("/subscriptions", get(subscription_handler))
+                .route("/graphql/subscriptions", get(subscription_handler))
                 .route("/health", get(health_check))
                 .with_state(self.state.clone())
                 .route_layer(CallbackLayer::new(MetricsMakeCallbackHandler {
@@ -303,8 +308,8 @@ impl ServerBuilder {
         info!("Access control allow origin set to: {acl:?}");
 
         let cors = CorsLayer::new()
-            // Allow `POST` when accessing the resource
-            .allow_methods([Method::POST])
+            // Allow `POST` when accessing the resource, and `GET` to upgrade to a websocket
+            .allow_methods([Method::GET, Method::POST])
             // Allow requests from any origin
             .allow_origin(acl)
             .allow_headers([
@@ -328,6 +333,9 @@ impl ServerBuilder {
             state.cancellation_token.clone(),
         );
 
+        // Subscriptions are driven by updates to the checkpoint watermark.
+        let schema = schema.data(watermark_task.checkpoint_receiver()).finish();
+
         let system_package_task = SystemPackageTask::new(
             resolver,
             watermark_task.epoch_receiver(),
@@ -493,8 +501,8 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -537,6 +545,29 @@ async fn graphql_handler(
     (extensions, result.into())
 }
 
+/// Entry point for graphql subscriptions, served over a websocket. Operations on the connection
+/// are stamped with the watermark as of when the connection was established, while the streams
+/// themselves follow the watermark as it advances.
+async fn subscription_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(schema): Extension<SuiGraphQLSchema>,
+    Extension(watermark_lock): Extension<WatermarkLock>,
+    protocol: GraphQLProtocol,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let watermark = Watermark::new(watermark_lock).await;
+    ws.protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            let mut data = async_graphql::Data::default();
+            data.insert(Uuid::new_v4());
+            data.insert(addr);
+            data.insert(watermark);
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
 #[derive(Clone)]
 struct MetricsMakeCallbackHandler {
     metrics: Metrics,
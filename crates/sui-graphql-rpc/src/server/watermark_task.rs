@@ -25,10 +25,17 @@ pub(crate) struct WatermarkTask {
     cancel: CancellationToken,
     sender: watch::Sender<u64>,
     receiver: watch::Receiver<u64>,
+    checkpoint_sender: watch::Sender<u64>,
+    checkpoint_receiver: watch::Receiver<u64>,
 }
 
 pub(crate) type WatermarkLock = Arc<RwLock<Watermark>>;
 
+/// Receiver for the checkpoint high watermark, added to the schema's context so that subscriptions
+/// can wake up whenever new checkpoints become available to query.
+#[derive(Clone)]
+pub(crate) struct CheckpointReceiver(pub watch::Receiver<u64>);
+
 /// Watermark used by GraphQL queries to ensure cross-query consistency and flag epoch-boundary
 /// changes.
 #[derive(Clone, Copy, Default)]
@@ -50,6 +57,7 @@ impl WatermarkTask {
         cancel: CancellationToken,
     ) -> Self {
         let (sender, receiver) = watch::channel(0);
+        let (checkpoint_sender, checkpoint_receiver) = watch::channel(0);
 
         Self {
             watermark: Default::default(),
@@ -59,6 +67,8 @@ impl WatermarkTask {
             cancel,
             sender,
             receiver,
+            checkpoint_sender,
+            checkpoint_receiver,
         }
     }
 
@@ -81,13 +91,19 @@ impl WatermarkTask {
                     };
 
                     // Write the watermark as follows to limit how long we hold the lock
-                    let prev_epoch = {
+                    let (prev_checkpoint, prev_epoch) = {
                         let mut w = self.watermark.write().await;
-                        w.checkpoint = checkpoint;
                         w.checkpoint_timestamp_ms = checkpoint_timestamp_ms;
-                        mem::replace(&mut w.epoch, epoch)
+                        (
+                            mem::replace(&mut w.checkpoint, checkpoint),
+                            mem::replace(&mut w.epoch, epoch),
+                        )
                     };
 
+                    if checkpoint > prev_checkpoint {
+                        self.checkpoint_sender.send(checkpoint).unwrap();
+                    }
+
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
@@ -104,6 +120,11 @@ impl WatermarkTask {
     pub(crate) fn epoch_receiver(&self) -> watch::Receiver<u64> {
         self.receiver.clone()
     }
+
+    /// Receiver for subscribing to changes in the checkpoint high watermark.
+    pub(crate) fn checkpoint_receiver(&self) -> CheckpointReceiver {
+        CheckpointReceiver(self.checkpoint_receiver.clone())
+    }
 }
 
 impl Watermark {
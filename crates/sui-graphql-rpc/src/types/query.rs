@@ -29,6 +29,7 @@ use super::{
     object::{self, Object, ObjectFilter},
     owner::Owner,
     protocol_config::ProtocolConfigs,
+    subscription::Subscription,
     sui_address::SuiAddress,
     suins_registration::Domain,
     transaction_block::{self, TransactionBlock, TransactionBlockFilter},
@@ -43,7 +44,7 @@ use crate::types::zklogin_verify_signature::ZkLoginVerifyResult;
 use crate::{config::ServiceConfig, error::Error, mutation::Mutation};
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl Query {
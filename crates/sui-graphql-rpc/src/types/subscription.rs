@@ -0,0 +1,217 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+
+use async_graphql::connection::{Connection, CursorType};
+use async_graphql::*;
+use futures::stream::{self, Stream, StreamExt};
+use tokio::sync::watch;
+
+use super::cursor::Page;
+use super::event::{self, Event, EventFilter};
+use super::transaction_block::{self, TransactionBlock, TransactionBlockFilter};
+use crate::config::ServiceConfig;
+use crate::data::Db;
+use crate::error::Error;
+use crate::server::watermark_task::CheckpointReceiver;
+
+pub(crate) struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Stream events as they are indexed, optionally narrowed down by `filter`.
+    ///
+    /// The stream starts at the checkpoint high watermark at the time of subscribing, and pushes
+    /// the matching events from each subsequent checkpoint, in order, as soon as that checkpoint
+    /// becomes available to query.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+        let CheckpointReceiver(receiver) = ctx.data::<CheckpointReceiver>()?.clone();
+        let filter = filter.unwrap_or_default();
+
+        Ok(subscribe(
+            config,
+            receiver,
+            move |after, checkpoint_viewed_at, page: Page<event::Cursor>| {
+                let db = db.clone();
+                let filter = filter.clone();
+                async move {
+                    Event::paginate_after_checkpoint(
+                        &db,
+                        page,
+                        filter,
+                        Some(after),
+                        checkpoint_viewed_at,
+                    )
+                    .await
+                }
+            },
+        ))
+    }
+
+    /// Stream transaction blocks as they are indexed, optionally narrowed down by `filter`.
+    ///
+    /// The stream starts at the checkpoint high watermark at the time of subscribing, and pushes
+    /// the matching transaction blocks from each subsequent checkpoint, in order, as soon as that
+    /// checkpoint becomes available to query.
+    async fn transaction_blocks(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+        let CheckpointReceiver(receiver) = ctx.data::<CheckpointReceiver>()?.clone();
+        let filter = filter.unwrap_or_default();
+
+        Ok(subscribe(
+            config,
+            receiver,
+            move |after, checkpoint_viewed_at, page: Page<transaction_block::Cursor>| {
+                let db = db.clone();
+
+                // Restrict the filter to the newly indexed checkpoints. If that is not possible,
+                // the user's filter excludes all of them, and there is nothing to report.
+                let filter = filter.clone().intersect(TransactionBlockFilter {
+                    after_checkpoint: Some(after),
+                    ..Default::default()
+                });
+
+                async move {
+                    let Some(filter) = filter else {
+                        return Ok(Connection::new(false, false));
+                    };
+
+                    TransactionBlock::paginate(&db, page, filter, checkpoint_viewed_at).await
+                }
+            },
+        ))
+    }
+}
+
+/// Turn updates to the checkpoint high watermark into a stream of results. Each time the watermark
+/// advances, `paginate` is called with the previous watermark (exclusive) and the new one
+/// (inclusive), a page (of the maximum allowed size) at a time, and the results of each page are
+/// forwarded in order before the next page is fetched, so at most one page is buffered per
+/// subscription.
+///
+/// If the watermark task has not read the watermark yet when subscribing, the stream starts at
+/// the first watermark it reads, rather than replaying every checkpoint since genesis.
+///
+/// The stream ends when the watermark task shuts down, or after forwarding the first error
+/// encountered while fetching, at which point the client is expected to re-subscribe.
+fn subscribe<C, T, F, Fut>(
+    config: ServiceConfig,
+    mut receiver: watch::Receiver<u64>,
+    paginate: F,
+) -> impl Stream<Item = Result<T>>
+where
+    C: CursorType,
+    C::Error: std::fmt::Display,
+    F: Fn(u64, u64, Page<C>) -> Fut + Clone,
+    Fut: Future<Output = Result<Connection<String, T>, Error>>,
+{
+    let after = *receiver.borrow_and_update();
+    let state = SubscriptionState {
+        receiver,
+        after,
+        pending: None,
+    };
+
+    stream::unfold(Some(state), move |state| {
+        let config = config.clone();
+        let paginate = paginate.clone();
+        async move {
+            let mut state = state?;
+            let (checkpoint_viewed_at, cursor) = match state.pending.take() {
+                Some(pending) => pending,
+                None => loop {
+                    state.receiver.changed().await.ok()?;
+                    let checkpoint_viewed_at = *state.receiver.borrow_and_update();
+                    // The watermark channel starts at 0, before the watermark is first read.
+                    if state.after == 0 {
+                        state.after = checkpoint_viewed_at;
+                    } else if checkpoint_viewed_at > state.after {
+                        break (checkpoint_viewed_at, None);
+                    }
+                },
+            };
+
+            match next_page(
+                &config,
+                &paginate,
+                state.after,
+                checkpoint_viewed_at,
+                cursor,
+            )
+            .await
+            {
+                Ok((results, next_cursor)) => {
+                    match next_cursor {
+                        // More results to fetch for this range of checkpoints.
+                        Some(cursor) => state.pending = Some((checkpoint_viewed_at, Some(cursor))),
+                        None => state.after = checkpoint_viewed_at,
+                    }
+                    Some((results.into_iter().map(Ok).collect(), Some(state)))
+                }
+                Err(e) => Some((vec![Err(e.extend())], None)),
+            }
+        }
+    })
+    .flat_map(stream::iter)
+}
+
+struct SubscriptionState<C> {
+    receiver: watch::Receiver<u64>,
+    /// Watermark up to which (inclusive) all results have been forwarded.
+    after: u64,
+    /// Watermark and cursor of the next page to fetch, if the last one was not the final page for
+    /// its range of checkpoints.
+    pending: Option<(u64, Option<C>)>,
+}
+
+/// Fetch a single page of results after `cursor`, returning the cursor to continue from if there
+/// are more pages.
+async fn next_page<C, T, F, Fut>(
+    config: &ServiceConfig,
+    paginate: &F,
+    after: u64,
+    checkpoint_viewed_at: u64,
+    cursor: Option<C>,
+) -> Result<(Vec<T>, Option<C>), Error>
+where
+    C: CursorType,
+    C::Error: std::fmt::Display,
+    F: Fn(u64, u64, Page<C>) -> Fut,
+    Fut: Future<Output = Result<Connection<String, T>, Error>>,
+{
+    let page = Page::from_params(
+        config,
+        Some(config.limits.max_page_size),
+        cursor,
+        None,
+        None,
+    )
+    .map_err(|e| Error::Internal(format!("Failed to create page: {}", e.message)))?;
+
+    let conn = paginate(after, checkpoint_viewed_at, page).await?;
+    let next_cursor = match conn.edges.last() {
+        Some(last) if conn.has_next_page => {
+            Some(C::decode_cursor(&last.cursor).map_err(|e| {
+                Error::Internal(format!("Failed to decode subscription cursor: {e}"))
+            })?)
+        }
+        _ => None,
+    };
+
+    Ok((
+        conn.edges.into_iter().map(|edge| edge.node).collect(),
+        next_cursor,
+    ))
+}
@@ -4,6 +4,7 @@
 #[cfg(feature = "pg_integration")]
 mod tests {
     use fastcrypto::encoding::{Base64, Encoding};
+    use futures::{SinkExt, StreamExt};
     use rand::rngs::StdRng;
     use rand::SeedableRng;
     use serde_json::json;
@@ -16,6 +17,7 @@ mod tests {
     use sui_graphql_rpc::config::ConnectionConfig;
     use sui_graphql_rpc::test_infra::cluster::ExecutorCluster;
     use sui_graphql_rpc::test_infra::cluster::DEFAULT_INTERNAL_DATA_SOURCE_PORT;
+    use sui_test_transaction_builder::{create_devnet_nft, publish_nfts_package};
     use sui_types::digests::ChainIdentifier;
     use sui_types::gas_coin::GAS;
     use sui_types::transaction::CallArg;
@@ -25,7 +27,11 @@ mod tests {
     use sui_types::SUI_FRAMEWORK_ADDRESS;
     use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
     use tempfile::tempdir;
-    use tokio::time::sleep;
+    use tokio::time::{sleep, timeout};
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::http::HeaderValue;
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 
     async fn prep_cluster() -> (ConnectionConfig, ExecutorCluster) {
         let rng = StdRng::from_seed([12; 32]);
@@ -897,4 +903,123 @@ mod tests {
             .await;
         test_health_check_impl().await;
     }
+
+    /// Opens a subscription over the `graphql-transport-ws` protocol, returning the socket once
+    /// the server has acknowledged it.
+    async fn open_subscription(
+        url: &str,
+        query: &str,
+    ) -> WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>> {
+        let mut request = url.into_client_request().unwrap();
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            HeaderValue::from_static("graphql-transport-ws"),
+        );
+        let (mut ws, _) = connect_async(request).await.unwrap();
+
+        ws.send(Message::Text(
+            json!({ "type": "connection_init" }).to_string(),
+        ))
+        .await
+        .unwrap();
+        let ack = next_message(&mut ws).await;
+        assert_eq!(ack["type"], "connection_ack");
+
+        ws.send(Message::Text(
+            json!({ "id": "1", "type": "subscribe", "payload": { "query": query } }).to_string(),
+        ))
+        .await
+        .unwrap();
+        ws
+    }
+
+    /// Waits for the next protocol message, skipping over pings and other control frames.
+    async fn next_message(
+        ws: &mut WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
+    ) -> serde_json::Value {
+        loop {
+            let message = timeout(Duration::from_secs(60), ws.next())
+                .await
+                .expect("Timed out waiting for subscription message")
+                .expect("Subscription closed")
+                .unwrap();
+            if let Message::Text(text) = message {
+                let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if value["type"] != "ping" {
+                    return value;
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_subscriptions() {
+        let _guard = telemetry_subscribers::TelemetryConfig::new()
+            .with_env()
+            .init();
+
+        let (host, port) = ("127.0.0.1", 8000);
+        let connection_config =
+            ConnectionConfig::new(Some(port), Some(host.to_string()), None, None, None, None);
+        let cluster =
+            sui_graphql_rpc::test_infra::cluster::start_cluster(connection_config, None).await;
+        cluster
+            .wait_for_checkpoint_catchup(0, Duration::from_secs(10))
+            .await;
+
+        let wallet = &cluster.validator_fullnode_handle.wallet;
+        let (package_id, _, _) = publish_nfts_package(wallet).await;
+
+        // Subscribe to the package's mints only, so that the publish and any system transactions
+        // are filtered out of the streams.
+        let url = format!("ws://{host}:{port}/subscriptions");
+        let mut events = open_subscription(
+            &url,
+            &format!(
+                r#"subscription {{
+                    events(filter: {{ emittingModule: "{package_id}::devnet_nft" }}) {{
+                        type {{ repr }}
+                        json
+                    }}
+                }}"#
+            ),
+        )
+        .await;
+        let mut transactions = open_subscription(
+            &url,
+            &format!(
+                r#"subscription {{
+                    transactionBlocks(filter: {{ function: "{package_id}::devnet_nft::mint" }}) {{
+                        digest
+                    }}
+                }}"#
+            ),
+        )
+        .await;
+
+        let mut minted = vec![];
+        for _ in 0..3 {
+            let (_, object_id, digest) = create_devnet_nft(wallet, package_id).await;
+            minted.push((object_id, digest));
+        }
+
+        for (object_id, digest) in &minted {
+            let message = next_message(&mut transactions).await;
+            assert_eq!(message["type"], "next", "{message}");
+            assert_eq!(
+                message["payload"]["data"]["transactionBlocks"]["digest"],
+                digest.to_string()
+            );
+
+            let message = next_message(&mut events).await;
+            assert_eq!(message["type"], "next", "{message}");
+            let event = &message["payload"]["data"]["events"];
+            assert_eq!(
+                event["type"]["repr"],
+                format!("{package_id}::devnet_nft::MintNFTEvent")
+            );
+            assert_eq!(event["json"]["object_id"], object_id.to_string());
+        }
+    }
 }
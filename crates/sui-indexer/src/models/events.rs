@@ -71,7 +71,7 @@ pub struct StoredEvent {
 #[cfg(feature = "postgres-feature")]
 pub type SendersType = Vec<Option<Vec<u8>>>;
 
-#[cfg(feature = "postgres-feature")]
+#[cfg(feature = "mysql-feature")]
 #[cfg(not(feature = "postgres-feature"))]
 pub type SendersType = serde_json::Value;
 
@@ -36,6 +36,7 @@ pub mod diesel_macro {
             #[cfg(feature = "mysql-feature")]
             #[cfg(not(feature = "postgres-feature"))]
             {
+                use diesel::Connection;
                 let mut pool_conn = get_pool_connection($pool)?;
                 pool_conn
                     .as_any_mut()
@@ -82,7 +82,8 @@ const PG_DB_COMMIT_SLEEP_DURATION: Duration = Duration::from_secs(3600);
 
 // with rn = 1, we only select the latest version of each object,
 // so that we don't have to update the same object multiple times.
-const UPDATE_OBJECTS_SNAPSHOT_QUERY: &str = r"
+const UPDATE_OBJECTS_SNAPSHOT_QUERY: &str = if cfg!(feature = "postgres-feature") {
+    r"
 INSERT INTO objects_snapshot (object_id, object_version, object_status, object_digest, checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, df_name, df_object_type, df_object_id)
 SELECT object_id, object_version, object_status, object_digest, checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, df_name, df_object_type, df_object_id
 FROM (
@@ -110,7 +111,38 @@ SET object_version = EXCLUDED.object_version,
     df_name = EXCLUDED.df_name,
     df_object_type = EXCLUDED.df_object_type,
     df_object_id = EXCLUDED.df_object_id;
-";
+"
+} else {
+    r"
+INSERT INTO objects_snapshot (object_id, object_version, object_status, object_digest, checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, df_name, df_object_type, df_object_id)
+SELECT object_id, object_version, object_status, object_digest, checkpoint_sequence_number, owner_type, owner_id, object_type, object_type_package, object_type_module, object_type_name, serialized_object, coin_type, coin_balance, df_kind, df_name, df_object_type, df_object_id
+FROM (
+    SELECT *,
+           ROW_NUMBER() OVER (PARTITION BY object_id ORDER BY object_version DESC) as rn
+    FROM objects_history
+    WHERE checkpoint_sequence_number >= ? AND checkpoint_sequence_number < ?
+) as subquery
+WHERE rn = 1
+ON DUPLICATE KEY UPDATE
+    object_version = VALUES(object_version),
+    object_status = VALUES(object_status),
+    object_digest = VALUES(object_digest),
+    checkpoint_sequence_number = VALUES(checkpoint_sequence_number),
+    owner_type = VALUES(owner_type),
+    owner_id = VALUES(owner_id),
+    object_type = VALUES(object_type),
+    object_type_package = VALUES(object_type_package),
+    object_type_module = VALUES(object_type_module),
+    object_type_name = VALUES(object_type_name),
+    serialized_object = VALUES(serialized_object),
+    coin_type = VALUES(coin_type),
+    coin_balance = VALUES(coin_balance),
+    df_kind = VALUES(df_kind),
+    df_name = VALUES(df_name),
+    df_object_type = VALUES(df_object_type),
+    df_object_id = VALUES(df_object_id);
+"
+};
 
 #[derive(Clone)]
 pub struct PgIndexerStoreConfig {
@@ -486,17 +518,20 @@ impl<T: R2D2Connection + 'static> PgIndexerStore<T> {
     }
 
     fn update_objects_snapshot(&self, start_cp: u64, end_cp: u64) -> Result<(), IndexerError> {
-        let work_mem_gb = std::env::var("INDEXER_PG_WORK_MEM")
-            .unwrap_or_else(|_e| "16".to_string())
-            .parse::<i64>()
-            .unwrap();
-        let pg_work_mem_query_string = format!("SET work_mem = '{}GB'", work_mem_gb);
-        let pg_work_mem_query = pg_work_mem_query_string.as_str();
-        transactional_blocking_with_retry!(
-            &self.blocking_cp,
-            |conn| { RunQueryDsl::execute(diesel::sql_query(pg_work_mem_query), conn,) },
-            PG_DB_COMMIT_SLEEP_DURATION
-        )?;
+        #[cfg(feature = "postgres-feature")]
+        {
+            let work_mem_gb = std::env::var("INDEXER_PG_WORK_MEM")
+                .unwrap_or_else(|_e| "16".to_string())
+                .parse::<i64>()
+                .unwrap();
+            let pg_work_mem_query_string = format!("SET work_mem = '{}GB'", work_mem_gb);
+            let pg_work_mem_query = pg_work_mem_query_string.as_str();
+            transactional_blocking_with_retry!(
+                &self.blocking_cp,
+                |conn| { RunQueryDsl::execute(diesel::sql_query(pg_work_mem_query), conn,) },
+                PG_DB_COMMIT_SLEEP_DURATION
+            )?;
+        }
 
         transactional_blocking_with_retry!(
             &self.blocking_cp,
@@ -31,8 +31,8 @@ GROUP BY table_name;
 } else if cfg!(feature = "mysql-feature") && cfg!(not(feature = "postgres-feature")) {
     r"
 SELECT TABLE_NAME AS table_name,
-       MIN(CAST(SUBSTRING_INDEX(PARTITION_NAME, '_', -1) AS UNSIGNED)) AS first_partition,
-       MAX(CAST(SUBSTRING_INDEX(PARTITION_NAME, '_', -1) AS UNSIGNED)) AS last_partition
+       MIN(CAST(SUBSTRING_INDEX(PARTITION_NAME, '_', -1) AS SIGNED)) AS first_partition,
+       MAX(CAST(SUBSTRING_INDEX(PARTITION_NAME, '_', -1) AS SIGNED)) AS last_partition
 FROM information_schema.PARTITIONS
 WHERE TABLE_SCHEMA = DATABASE()
 AND PARTITION_NAME IS NOT NULL
@@ -182,9 +182,7 @@ impl<T: R2D2Connection> PgPartitionManager<T> {
                         |conn| {
                             RunQueryDsl::execute(
                                 diesel::sql_query(format!(
-                                    "ALTER TABLE {} DROP PARTITION partition_{}",
-                                    table.clone(),
-                                    epoch
+                                    "ALTER TABLE {table} DROP PARTITION {table}_partition_{epoch}"
                                 )),
                                 conn,
                             )
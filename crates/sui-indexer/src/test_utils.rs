@@ -21,6 +21,13 @@ use crate::indexer::Indexer;
 use crate::store::PgIndexerStore;
 use crate::{IndexerConfig, IndexerMetrics};
 
+/// Database that always exists, to connect to while creating or dropping test databases.
+const DEFAULT_DB_NAME: &str = if cfg!(feature = "postgres-feature") {
+    "postgres"
+} else {
+    "mysql"
+};
+
 pub enum ReaderWriterConfig {
     Reader { reader_mode_rpc_url: String },
     Writer { snapshot_config: SnapshotLagConfig },
@@ -70,12 +77,7 @@ pub async fn start_test_indexer_impl<T: R2D2Connection>(
     info!("Setting DB_POOL_SIZE to 10");
     std::env::set_var("DB_POOL_SIZE", "10");
 
-    let db_url = db_url.unwrap_or_else(|| {
-        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
-        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
-        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
-        format!("postgres://postgres:{pw}@{pg_host}:{pg_port}")
-    });
+    let db_url = db_url.unwrap_or_else(default_db_url);
 
     let pool_config = ConnectionPoolConfig::default();
 
@@ -101,7 +103,7 @@ pub async fn start_test_indexer_impl<T: R2D2Connection>(
 
     if let Some(new_database) = new_database {
         // Switch to default to create a new database
-        let (default_db_url, _) = replace_db_name(parsed_url.expose_secret(), "postgres");
+        let (default_db_url, _) = replace_db_name(parsed_url.expose_secret(), DEFAULT_DB_NAME);
 
         // Open in default mode
         let blocking_pool =
@@ -164,6 +166,20 @@ pub async fn start_test_indexer_impl<T: R2D2Connection>(
     (store, handle)
 }
 
+fn default_db_url() -> String {
+    if cfg!(feature = "postgres-feature") {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        format!("postgres://postgres:{pw}@{pg_host}:{pg_port}")
+    } else {
+        let mysql_host = env::var("MYSQL_HOST").unwrap_or_else(|_| "localhost".into());
+        let mysql_port = env::var("MYSQL_PORT").unwrap_or_else(|_| "3306".into());
+        let pw = env::var("MYSQL_PASSWORD").unwrap_or_else(|_| "mysqlpw".into());
+        format!("mysql://root:{pw}@{mysql_host}:{mysql_port}")
+    }
+}
+
 fn replace_db_name(db_url: &str, new_db_name: &str) -> (String, String) {
     let pos = db_url.rfind('/').expect("Unable to find / in db_url");
     let old_db_name = &db_url[pos + 1..];
@@ -175,18 +191,23 @@ fn replace_db_name(db_url: &str, new_db_name: &str) -> (String, String) {
 }
 
 pub async fn force_delete_database<T: R2D2Connection + 'static>(db_url: String) {
-    // Replace the database name with the default `postgres`, which should be the last string after `/`
-    // This is necessary because you can't drop a database while being connected to it.
-    // Hence switch to the default `postgres` database to drop the active database.
-    let (default_db_url, db_name) = replace_db_name(&db_url, "postgres");
+    // Replace the database name with the default one (`postgres` or `mysql`), which should be the
+    // last string after `/`. This is necessary because you can't drop a database while being
+    // connected to it. Hence switch to the default database to drop the active database.
+    let (default_db_url, db_name) = replace_db_name(&db_url, DEFAULT_DB_NAME);
     let pool_config = ConnectionPoolConfig::default();
 
     let blocking_pool =
         new_connection_pool_with_config::<T>(&default_db_url, Some(5), pool_config).unwrap();
+    let drop_database = if cfg!(feature = "postgres-feature") {
+        format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", db_name)
+    } else {
+        format!("DROP DATABASE IF EXISTS {}", db_name)
+    };
     blocking_pool
         .get()
         .unwrap()
-        .batch_execute(&format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", db_name))
+        .batch_execute(&drop_database)
         .unwrap();
 }
 
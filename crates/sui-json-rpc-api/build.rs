@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    build_read_service();
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+#[cfg(feature = "grpc")]
+fn build_read_service() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    let out_dir = if std::env::var("DUMP_GENERATED_GRPC").is_ok() {
+        std::path::PathBuf::from("")
+    } else {
+        std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap())
+    };
+    println!("cargo:rerun-if-env-changed=DUMP_GENERATED_GRPC");
+
+    let codec_path = "mysten_network::codec::BcsCodec";
+    let method = |name: &str, route_name: &str, input_type: &str, output_type: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route_name)
+            .input_type(format!("crate::grpc::{input_type}"))
+            .output_type(format!("crate::grpc::{output_type}"))
+            .codec_path(codec_path)
+            .build()
+    };
+
+    let read_service = Service::builder()
+        .name("ReadService")
+        .package("sui.rpc")
+        .comment("The BCS encoded read API of a fullnode")
+        .method(method(
+            "get_object",
+            "GetObject",
+            "GetObjectRequest",
+            "ObjectResponse",
+        ))
+        .method(method(
+            "multi_get_objects",
+            "MultiGetObjects",
+            "MultiGetObjectsRequest",
+            "MultiGetObjectsResponse",
+        ))
+        .method(method(
+            "get_transaction_block",
+            "GetTransactionBlock",
+            "GetTransactionBlockRequest",
+            "TransactionBlockResponse",
+        ))
+        .method(method(
+            "multi_get_transaction_blocks",
+            "MultiGetTransactionBlocks",
+            "MultiGetTransactionBlocksRequest",
+            "MultiGetTransactionBlocksResponse",
+        ))
+        .method(method(
+            "get_balance",
+            "GetBalance",
+            "GetBalanceRequest",
+            "Balance",
+        ))
+        .method(method(
+            "get_all_balances",
+            "GetAllBalances",
+            "GetAllBalancesRequest",
+            "GetAllBalancesResponse",
+        ))
+        .method(method(
+            "get_coins",
+            "GetCoins",
+            "GetCoinsRequest",
+            "GetCoinsResponse",
+        ))
+        .build();
+
+    Builder::new().out_dir(&out_dir).compile(&[read_service]);
+}
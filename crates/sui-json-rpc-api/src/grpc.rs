@@ -0,0 +1,145 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A binary alternative to the JSON-RPC read API for high-volume readers. Requests and responses
+//! are BCS encoded and carry the native Sui types rather than their JSON representations, so
+//! neither end pays for JSON encoding. The service is served on the same address as the JSON-RPC
+//! server.
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::effects::{TransactionEffects, TransactionEvents};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use sui_types::transaction::Transaction;
+
+mod read_service {
+    include!(concat!(env!("OUT_DIR"), "/sui.rpc.ReadService.rs"));
+}
+
+pub use read_service::{
+    read_service_client::ReadServiceClient,
+    read_service_server::{ReadService, ReadServiceServer},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetObjectRequest {
+    pub object_id: ObjectID,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiGetObjectsRequest {
+    pub object_ids: Vec<ObjectID>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ObjectResponse {
+    /// The latest version of the object.
+    Exists(Object),
+    NotExists(ObjectID),
+    /// The object was deleted or wrapped, as of the given reference.
+    Deleted(ObjectRef),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiGetObjectsResponse {
+    /// One entry per requested object, in request order.
+    pub objects: Vec<ObjectResponse>,
+}
+
+/// Selects the parts of a transaction block to return. The checkpoint and timestamp are
+/// always returned when known.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TransactionBlockOptions {
+    pub show_input: bool,
+    pub show_effects: bool,
+    pub show_events: bool,
+}
+
+impl TransactionBlockOptions {
+    pub fn full() -> Self {
+        Self {
+            show_input: true,
+            show_effects: true,
+            show_events: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetTransactionBlockRequest {
+    pub digest: TransactionDigest,
+    pub options: TransactionBlockOptions,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiGetTransactionBlocksRequest {
+    pub digests: Vec<TransactionDigest>,
+    pub options: TransactionBlockOptions,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionBlockResponse {
+    pub digest: TransactionDigest,
+    pub transaction: Option<Transaction>,
+    pub effects: Option<TransactionEffects>,
+    pub events: Option<TransactionEvents>,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    pub timestamp_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiGetTransactionBlocksResponse {
+    /// One entry per requested digest, in request order. `None` if the transaction is not known
+    /// to the node.
+    pub transactions: Vec<Option<TransactionBlockResponse>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetBalanceRequest {
+    pub owner: SuiAddress,
+    /// Defaults to `0x2::sui::SUI` if not specified.
+    pub coin_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetAllBalancesRequest {
+    pub owner: SuiAddress,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Balance {
+    pub coin_type: String,
+    pub coin_object_count: u64,
+    pub total_balance: u128,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetAllBalancesResponse {
+    pub balances: Vec<Balance>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetCoinsRequest {
+    pub owner: SuiAddress,
+    /// Defaults to `0x2::sui::SUI` if not specified.
+    pub coin_type: Option<String>,
+    /// The object ID to start after, taken from `next_cursor` of the previous page.
+    pub cursor: Option<ObjectID>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coin {
+    pub coin_type: String,
+    pub object_ref: ObjectRef,
+    pub balance: u64,
+    pub previous_transaction: TransactionDigest,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetCoinsResponse {
+    pub coins: Vec<Coin>,
+    pub next_cursor: Option<ObjectID>,
+    pub has_next_page: bool,
+}
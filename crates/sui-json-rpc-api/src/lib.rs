@@ -40,6 +40,8 @@ mod bridge;
 mod coin;
 mod extended;
 mod governance;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 mod indexer;
 mod move_utils;
 mod read;
@@ -4,7 +4,6 @@
 use std::net::IpAddr;
 use std::time::SystemTime;
 use std::{net::SocketAddr, sync::Arc};
-use sui_types::traffic_control::RemoteFirewallConfig;
 
 use axum::extract::{ConnectInfo, Json, State};
 use futures::StreamExt;
@@ -19,18 +18,16 @@ use jsonrpsee::types::error::{ErrorCode, BATCHES_NOT_SUPPORTED_CODE, BATCHES_NOT
 use jsonrpsee::types::{ErrorObject, Id, InvalidRequest, Params, Request};
 use jsonrpsee::{core::server::rpc_module::Methods, server::logger::Logger};
 use serde_json::value::RawValue;
-use sui_core::traffic_controller::{
-    metrics::TrafficControllerMetrics, policies::TrafficTally, TrafficController,
-};
+use sui_core::traffic_controller::{policies::TrafficTally, TrafficController};
 use sui_types::traffic_control::ClientIdSource;
-use sui_types::traffic_control::{PolicyConfig, Weight};
+use sui_types::traffic_control::Weight;
 use tracing::error;
 
 use crate::routing_layer::RpcRouter;
 use sui_json_rpc_api::CLIENT_TARGET_API_VERSION_HEADER;
 
 pub const MAX_RESPONSE_SIZE: u32 = 2 << 30;
-const TOO_MANY_REQUESTS_MSG: &str = "Too many requests";
+pub(crate) const TOO_MANY_REQUESTS_MSG: &str = "Too many requests";
 
 #[derive(Clone, Debug)]
 pub struct JsonRpcService<L> {
@@ -50,23 +47,17 @@ impl<L> JsonRpcService<L> {
         methods: Methods,
         rpc_router: RpcRouter,
         logger: L,
-        remote_fw_config: Option<RemoteFirewallConfig>,
-        policy_config: Option<PolicyConfig>,
-        traffic_controller_metrics: TrafficControllerMetrics,
+        traffic_controller: Option<Arc<TrafficController>>,
     ) -> Self {
         Self {
             methods,
             rpc_router,
             logger,
             id_provider: Arc::new(RandomIntegerIdProvider),
-            traffic_controller: policy_config.clone().map(|policy| {
-                Arc::new(TrafficController::spawn(
-                    policy,
-                    traffic_controller_metrics,
-                    remote_fw_config,
-                ))
-            }),
-            client_id_source: policy_config.map(|policy| policy.client_id_source),
+            client_id_source: traffic_controller
+                .as_ref()
+                .map(|controller| controller.client_id_source().clone()),
+            traffic_controller,
         }
     }
 }
@@ -158,9 +149,10 @@ async fn process_raw_request<L: Logger>(
     };
     if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
         // check if either IP is blocked, in which case return early
+        let method = request.method.to_string();
         if let Some(traffic_controller) = &service.traffic_controller {
             if let Err(blocked_response) =
-                handle_traffic_req(traffic_controller.clone(), &client).await
+                handle_traffic_req(traffic_controller.clone(), &client, &method).await
             {
                 return blocked_response;
             }
@@ -169,7 +161,7 @@ async fn process_raw_request<L: Logger>(
         // handle response tallying
         let response = process_request(request, api_version, service.call_data()).await;
         if let Some(traffic_controller) = &service.traffic_controller {
-            handle_traffic_resp(traffic_controller.clone(), client, &response);
+            handle_traffic_resp(traffic_controller.clone(), client, &method, &response);
         }
 
         response
@@ -187,8 +179,9 @@ async fn process_raw_request<L: Logger>(
 async fn handle_traffic_req(
     traffic_controller: Arc<TrafficController>,
     client: &Option<IpAddr>,
+    method: &str,
 ) -> Result<(), MethodResponse> {
-    if !traffic_controller.check(client, &None).await {
+    if !traffic_controller.check_method(client, &None, method).await {
         // Entity in blocklist
         let err_obj =
             ErrorObject::borrowed(ErrorCode::ServerIsBusy.code(), &TOO_MANY_REQUESTS_MSG, None);
@@ -201,21 +194,23 @@ async fn handle_traffic_req(
 fn handle_traffic_resp(
     traffic_controller: Arc<TrafficController>,
     client: Option<IpAddr>,
+    method: &str,
     response: &MethodResponse,
 ) {
     let error = response.error_code.map(ErrorCode::from);
+    let spam_weight = traffic_controller.spam_weight(method, Weight::one());
     traffic_controller.tally(TrafficTally {
         direct: client,
         through_fullnode: None,
         error_weight: error.map(normalize).unwrap_or(Weight::zero()),
-        // For now, count everything as spam with equal weight
+        // By default, count everything as spam with equal weight
         // on the rpc node side, including gas-charging endpoints
         // such as `sui_executeTransactionBlock`, as this can enable
         // node operators who wish to rate limit their transcation
         // traffic and incentivize high volume clients to choose a
-        // suitable rpc provider (or run their own). Later we may want
-        // to provide a weight distribution based on the method being called.
-        spam_weight: Weight::one(),
+        // suitable rpc provider (or run their own). Operators may
+        // override the weight per method in the policy config.
+        spam_weight,
         timestamp: SystemTime::now(),
     });
 }
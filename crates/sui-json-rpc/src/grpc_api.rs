@@ -0,0 +1,304 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use axum::extract::ConnectInfo;
+use futures::future::join_all;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::CallError;
+use sui_core::traffic_controller::{policies::TrafficTally, TrafficController};
+use sui_json_rpc_api::grpc::{
+    Balance, Coin, GetAllBalancesRequest, GetAllBalancesResponse, GetBalanceRequest,
+    GetCoinsRequest, GetCoinsResponse, GetObjectRequest, GetTransactionBlockRequest,
+    MultiGetObjectsRequest, MultiGetObjectsResponse, MultiGetTransactionBlocksRequest,
+    MultiGetTransactionBlocksResponse, ObjectResponse, ReadService, ReadServiceServer,
+    TransactionBlockResponse,
+};
+use sui_json_rpc_api::{CoinReadApiServer, QUERY_MAX_RESULT_LIMIT};
+use sui_types::base_types::ObjectID;
+use sui_types::object::ObjectRead;
+use sui_types::traffic_control::{ClientIdSource, Weight};
+use tonic::server::NamedService;
+use tonic::{Code, Request, Response, Status};
+use tracing::{error, instrument};
+
+use crate::axum_router::TOO_MANY_REQUESTS_MSG;
+use crate::coin_api::CoinReadApi;
+use crate::error::{Error, SuiRpcInputError};
+use crate::read_api::ReadApi;
+
+/// Serves the BCS encoded gRPC read service, the binary counterpart of the `ReadApi` and
+/// `CoinReadApi` JSON-RPC modules, on top of the same implementations.
+pub struct GrpcReadApi {
+    read_api: ReadApi,
+    coin_read_api: CoinReadApi,
+    traffic_controller: Option<Arc<TrafficController>>,
+}
+
+impl GrpcReadApi {
+    pub fn new(read_api: ReadApi, coin_read_api: CoinReadApi) -> Self {
+        Self {
+            read_api,
+            coin_read_api,
+            traffic_controller: None,
+        }
+    }
+
+    /// Subjects requests to the same traffic controller as the JSON-RPC server. Requests are
+    /// checked and tallied under the name of the equivalent JSON-RPC method (e.g.
+    /// `sui_getObject`), so per-method policies apply to both servers alike.
+    pub fn with_traffic_controller(mut self, traffic_controller: Arc<TrafficController>) -> Self {
+        self.traffic_controller = Some(traffic_controller);
+        self
+    }
+
+    /// Returns a router serving the read service under `/sui.rpc.ReadService/`, to be merged
+    /// with the JSON-RPC router. The JSON-RPC server accepts HTTP/2 without TLS, which is what
+    /// gRPC clients connect with.
+    pub fn into_router(self) -> axum::Router {
+        axum::Router::new().route_service(
+            &format!("/{}/*rest", ReadServiceServer::<Self>::NAME),
+            ReadServiceServer::new(self),
+        )
+    }
+
+    async fn get_object_response(&self, object_id: ObjectID) -> Result<ObjectResponse, Error> {
+        Ok(match self.read_api.get_object_read(object_id).await? {
+            ObjectRead::Exists(_, object, _) => ObjectResponse::Exists(object),
+            ObjectRead::NotExists(object_id) => ObjectResponse::NotExists(object_id),
+            ObjectRead::Deleted(object_ref) => ObjectResponse::Deleted(object_ref),
+        })
+    }
+
+    /// Serves `request` with `handler`, after checking the client against the traffic controller
+    /// (if any), and tallies the outcome afterwards, mirroring the JSON-RPC request handling.
+    async fn traffic_controlled<T, R, F, Fut>(
+        &self,
+        method: &str,
+        request: Request<T>,
+        handler: F,
+    ) -> Result<Response<R>, Status>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<R, Status>>,
+    {
+        let Some(traffic_controller) = &self.traffic_controller else {
+            return handler(request.into_inner()).await.map(Response::new);
+        };
+
+        let client = match traffic_controller.client_id_source() {
+            ClientIdSource::SocketAddr => request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip()),
+            ClientIdSource::XForwardedFor => {
+                error!(
+                    "X-Forwarded-For client ID source not yet supported on the gRPC \
+                    read service. Skipping traffic controller request handling.",
+                );
+                None
+            }
+        };
+
+        if !traffic_controller
+            .check_method(&client, &None, method)
+            .await
+        {
+            return Err(Status::resource_exhausted(TOO_MANY_REQUESTS_MSG));
+        }
+
+        let response = handler(request.into_inner()).await;
+        traffic_controller.tally(TrafficTally {
+            direct: client,
+            through_fullnode: None,
+            error_weight: match &response {
+                Err(status) if status.code() == Code::InvalidArgument => Weight::one(),
+                _ => Weight::zero(),
+            },
+            spam_weight: traffic_controller.spam_weight(method, Weight::one()),
+            timestamp: SystemTime::now(),
+        });
+        response.map(Response::new)
+    }
+}
+
+#[tonic::async_trait]
+impl ReadService for GrpcReadApi {
+    #[instrument(skip(self))]
+    async fn get_object(
+        &self,
+        request: Request<GetObjectRequest>,
+    ) -> Result<Response<ObjectResponse>, Status> {
+        self.traffic_controlled("sui_getObject", request, |request| async move {
+            self.get_object_response(request.object_id)
+                .await
+                .map_err(to_status)
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn multi_get_objects(
+        &self,
+        request: Request<MultiGetObjectsRequest>,
+    ) -> Result<Response<MultiGetObjectsResponse>, Status> {
+        self.traffic_controlled("sui_multiGetObjects", request, |request| async move {
+            let object_ids = request.object_ids;
+            if object_ids.len() > *QUERY_MAX_RESULT_LIMIT {
+                return Err(to_status(Error::from(SuiRpcInputError::SizeLimitExceeded(
+                    QUERY_MAX_RESULT_LIMIT.to_string(),
+                ))));
+            }
+            let metrics = &self.read_api.metrics;
+            metrics.get_objects_limit.report(object_ids.len() as u64);
+
+            let objects = join_all(
+                object_ids
+                    .into_iter()
+                    .map(|object_id| self.get_object_response(object_id)),
+            )
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(to_status)?;
+
+            metrics.get_objects_result_size.report(objects.len() as u64);
+            metrics
+                .get_objects_result_size_total
+                .inc_by(objects.len() as u64);
+            Ok(MultiGetObjectsResponse { objects })
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_transaction_block(
+        &self,
+        request: Request<GetTransactionBlockRequest>,
+    ) -> Result<Response<TransactionBlockResponse>, Status> {
+        self.traffic_controlled("sui_getTransactionBlock", request, |request| async move {
+            let GetTransactionBlockRequest { digest, options } = request;
+            self.read_api
+                .multi_get_transaction_blocks_bcs(vec![digest], &options)
+                .await
+                .map_err(to_status)?
+                .pop()
+                .flatten()
+                .ok_or_else(|| Status::not_found(format!("Could not find transaction {digest}")))
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn multi_get_transaction_blocks(
+        &self,
+        request: Request<MultiGetTransactionBlocksRequest>,
+    ) -> Result<Response<MultiGetTransactionBlocksResponse>, Status> {
+        self.traffic_controlled(
+            "sui_multiGetTransactionBlocks",
+            request,
+            |request| async move {
+                let MultiGetTransactionBlocksRequest { digests, options } = request;
+                let transactions = self
+                    .read_api
+                    .multi_get_transaction_blocks_bcs(digests, &options)
+                    .await
+                    .map_err(to_status)?;
+                Ok(MultiGetTransactionBlocksResponse { transactions })
+            },
+        )
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_balance(
+        &self,
+        request: Request<GetBalanceRequest>,
+    ) -> Result<Response<Balance>, Status> {
+        self.traffic_controlled("suix_getBalance", request, |request| async move {
+            let GetBalanceRequest { owner, coin_type } = request;
+            let balance = self
+                .coin_read_api
+                .get_balance(owner, coin_type)
+                .await
+                .map_err(to_status)?;
+            Ok(to_balance(balance))
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_all_balances(
+        &self,
+        request: Request<GetAllBalancesRequest>,
+    ) -> Result<Response<GetAllBalancesResponse>, Status> {
+        self.traffic_controlled("suix_getAllBalances", request, |request| async move {
+            let balances = self
+                .coin_read_api
+                .get_all_balances(request.owner)
+                .await
+                .map_err(to_status)?
+                .into_iter()
+                .map(to_balance)
+                .collect();
+            Ok(GetAllBalancesResponse { balances })
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_coins(
+        &self,
+        request: Request<GetCoinsRequest>,
+    ) -> Result<Response<GetCoinsResponse>, Status> {
+        self.traffic_controlled("suix_getCoins", request, |request| async move {
+            let GetCoinsRequest {
+                owner,
+                coin_type,
+                cursor,
+                limit,
+            } = request;
+            let page = self
+                .coin_read_api
+                .get_coins(owner, coin_type, cursor, limit.map(|limit| limit as usize))
+                .await
+                .map_err(to_status)?;
+            Ok(GetCoinsResponse {
+                coins: page
+                    .data
+                    .into_iter()
+                    .map(|coin| Coin {
+                        object_ref: coin.object_ref(),
+                        coin_type: coin.coin_type,
+                        balance: coin.balance,
+                        previous_transaction: coin.previous_transaction,
+                    })
+                    .collect(),
+                next_cursor: page.next_cursor,
+                has_next_page: page.has_next_page,
+            })
+        })
+        .await
+    }
+}
+
+fn to_balance(balance: sui_json_rpc_types::Balance) -> Balance {
+    Balance {
+        coin_type: balance.coin_type,
+        coin_object_count: balance.coin_object_count as u64,
+        total_balance: balance.total_balance,
+    }
+}
+
+/// Maps errors the same way as the JSON-RPC server does: invalid params are the client's fault,
+/// everything else is an internal error.
+fn to_status(err: impl Into<RpcError>) -> Status {
+    match err.into() {
+        RpcError::Call(CallError::InvalidParams(err)) => Status::invalid_argument(err.to_string()),
+        err => Status::internal(err.to_string()),
+    }
+}
@@ -4,6 +4,7 @@
 use std::env;
 use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use hyper::header::HeaderName;
 use hyper::header::HeaderValue;
@@ -13,6 +14,7 @@ use hyper::Request;
 use jsonrpsee::RpcModule;
 use prometheus::Registry;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
+use sui_core::traffic_controller::TrafficController;
 use sui_types::traffic_control::PolicyConfig;
 use sui_types::traffic_control::RemoteFirewallConfig;
 use tokio::runtime::Handle;
@@ -40,6 +42,7 @@ pub mod bridge_api;
 pub mod coin_api;
 pub mod error;
 pub mod governance_api;
+pub mod grpc_api;
 pub mod indexer_api;
 pub mod logger;
 mod metrics;
@@ -61,6 +64,7 @@ pub struct JsonRpcServerBuilder {
     registry: Registry,
     policy_config: Option<PolicyConfig>,
     firewall_config: Option<RemoteFirewallConfig>,
+    traffic_controller: Option<Arc<TrafficController>>,
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -89,9 +93,17 @@ impl JsonRpcServerBuilder {
             registry: prometheus_registry.clone(),
             policy_config,
             firewall_config,
+            traffic_controller: None,
         }
     }
 
+    /// Use an already running traffic controller rather than spawning one
+    /// from the policy config, so that its policy can be reconfigured by the
+    /// owner of the controller.
+    pub fn set_traffic_controller(&mut self, traffic_controller: Arc<TrafficController>) {
+        self.traffic_controller = Some(traffic_controller);
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -174,7 +186,16 @@ impl JsonRpcServerBuilder {
         let methods_names = module.method_names().collect::<Vec<_>>();
 
         let metrics_logger = MetricsLogger::new(&self.registry, &methods_names);
-        let traffic_controller_metrics = TrafficControllerMetrics::new(&self.registry);
+        let traffic_controller = match &self.traffic_controller {
+            Some(traffic_controller) => Some(traffic_controller.clone()),
+            None => self.policy_config.clone().map(|policy| {
+                Arc::new(TrafficController::spawn(
+                    policy,
+                    TrafficControllerMetrics::new(&self.registry),
+                    self.firewall_config.clone(),
+                ))
+            }),
+        };
 
         let middleware = tower::ServiceBuilder::new()
             .layer(Self::trace_layer())
@@ -184,9 +205,7 @@ impl JsonRpcServerBuilder {
             module.into(),
             rpc_router,
             metrics_logger,
-            self.firewall_config.clone(),
-            self.policy_config.clone(),
-            traffic_controller_metrics,
+            traffic_controller,
         );
 
         let mut router = axum::Router::new();
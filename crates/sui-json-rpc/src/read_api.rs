@@ -19,6 +19,7 @@ use tracing::{debug, error, info, instrument, trace, warn};
 
 use mysten_metrics::spawn_monitored_task;
 use sui_core::authority::AuthorityState;
+use sui_json_rpc_api::grpc::{TransactionBlockOptions, TransactionBlockResponse};
 use sui_json_rpc_api::{
     validate_limit, JsonRpcMetrics, ReadApiOpenRpc, ReadApiServer, QUERY_MAX_RESULT_LIMIT,
     QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
@@ -474,6 +475,124 @@ impl ReadApi {
 
         Ok(converted_tx_block_resps)
     }
+
+    pub(crate) async fn get_object_read(&self, object_id: ObjectID) -> Result<ObjectRead, Error> {
+        let state = self.state.clone();
+        spawn_monitored_task!(async move {
+            state.get_object_read(&object_id).map_err(|e| {
+                warn!(?object_id, "Failed to get object: {:?}", e);
+                Error::from(e)
+            })
+        })
+        .await
+        .map_err(Error::from)?
+    }
+
+    /// Fetches transaction blocks without converting them to their JSON representation, for the
+    /// BCS encoded gRPC read service. Transactions unknown to this node are returned as `None`.
+    pub(crate) async fn multi_get_transaction_blocks_bcs(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: &TransactionBlockOptions,
+    ) -> Result<Vec<Option<TransactionBlockResponse>>, Error> {
+        if digests.len() > *QUERY_MAX_RESULT_LIMIT {
+            Err(SuiRpcInputError::SizeLimitExceeded(
+                QUERY_MAX_RESULT_LIMIT.to_string(),
+            ))?
+        }
+        self.metrics
+            .get_tx_blocks_limit
+            .report(digests.len() as u64);
+
+        let effects_digests: &[TransactionDigest] = if options.show_effects || options.show_events {
+            &digests
+        } else {
+            &[]
+        };
+        let (transactions, effects, _) = self
+            .transaction_kv_store
+            .multi_get(&digests, effects_digests, &[])
+            .await
+            .tap_err(|err| debug!(?digests, "Failed to multi get transactions: {:?}", err))?;
+
+        let mut events = vec![None; digests.len()];
+        if options.show_events {
+            // Only fetch events for transactions that emitted any. As in the JSON-RPC api, other
+            // executed transactions get an empty list.
+            let mut indices = vec![];
+            let mut event_digests = vec![];
+            for (i, fx) in effects.iter().enumerate() {
+                match fx.as_ref().map(|fx| fx.events_digest()) {
+                    Some(Some(event_digest)) => {
+                        indices.push(i);
+                        event_digests.push(*event_digest);
+                    }
+                    Some(None) => events[i] = Some(TransactionEvents::default()),
+                    None => {}
+                }
+            }
+            let fetched = self
+                .transaction_kv_store
+                .multi_get_events(&event_digests)
+                .await?;
+            for (i, tx_events) in indices.into_iter().zip(fetched) {
+                events[i] = tx_events;
+            }
+        }
+
+        let checkpoints = self
+            .transaction_kv_store
+            .multi_get_transaction_checkpoint(&digests)
+            .await?;
+        let unique_checkpoints = checkpoints
+            .iter()
+            .flatten()
+            .copied()
+            .unique()
+            .collect::<Vec<CheckpointSequenceNumber>>();
+        let checkpoint_to_timestamp: HashMap<_, _> = unique_checkpoints
+            .iter()
+            .copied()
+            .zip(
+                self.transaction_kv_store
+                    .multi_get_checkpoints_summaries(&unique_checkpoints)
+                    .await?
+                    .into_iter()
+                    .map(|summary| summary.map(|summary| summary.timestamp_ms)),
+            )
+            .collect();
+
+        let mut effects = effects.into_iter();
+        let mut events = events.into_iter();
+        let responses = digests
+            .into_iter()
+            .zip(transactions)
+            .zip(checkpoints)
+            .map(|((digest, transaction), checkpoint)| {
+                let effects = effects.next().flatten();
+                let events = events.next().flatten();
+                let transaction = transaction?;
+                Some(TransactionBlockResponse {
+                    digest,
+                    transaction: options.show_input.then_some(transaction),
+                    effects: effects.filter(|_| options.show_effects),
+                    events,
+                    checkpoint,
+                    timestamp_ms: checkpoint
+                        .and_then(|checkpoint| checkpoint_to_timestamp.get(&checkpoint).copied())
+                        .flatten(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        self.metrics
+            .get_tx_blocks_result_size
+            .report(responses.len() as u64);
+        self.metrics
+            .get_tx_blocks_result_size_total
+            .inc_by(responses.len() as u64);
+        Ok(responses)
+    }
 }
 
 #[async_trait]
@@ -485,15 +604,7 @@ impl ReadApiServer for ReadApi {
         options: Option<SuiObjectDataOptions>,
     ) -> RpcResult<SuiObjectResponse> {
         with_tracing!(async move {
-            let state = self.state.clone();
-            let object_read = spawn_monitored_task!(async move {
-                state.get_object_read(&object_id).map_err(|e| {
-                    warn!(?object_id, "Failed to get object: {:?}", e);
-                    Error::from(e)
-                })
-            })
-            .await
-            .map_err(Error::from)??;
+            let object_read = self.get_object_read(object_id).await?;
             let options = options.unwrap_or_default();
 
             match object_read {
@@ -0,0 +1,257 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passphrase based encryption for keystore files.
+//!
+//! A 256-bit key is derived from the passphrase with scrypt, and every private key is sealed
+//! separately with AES-256-GCM under a fresh nonce, using its public key as associated data. Public
+//! keys are stored in the clear, so that addresses can be listed without the passphrase. A sealed
+//! known value lets a wrong passphrase be detected even when the keystore holds no keys.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, bail, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sui_types::crypto::{EncodeDecodeBase64, PublicKey, SuiKeyPair};
+use zeroize::Zeroizing;
+
+/// Name of the environment variable holding the passphrase of an encrypted keystore, for
+/// non-interactive use (e.g. in CI).
+pub const SUI_KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+const FORMAT_VERSION: u8 = 1;
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const SALT_LENGTH: usize = 32;
+
+const VERIFIER_PLAINTEXT: &[u8] = b"sui keystore";
+const VERIFIER_AAD: &[u8] = b"verifier";
+
+/// On-disk representation of an encrypted keystore.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedKeystore {
+    pub version: u8,
+    pub kdf: KdfParams,
+    pub verifier: Sealed,
+    pub keys: Vec<EncryptedKey>,
+}
+
+/// Parameters of the scrypt key derivation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    /// Base64 encoded salt.
+    pub salt: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedKey {
+    /// Base64 encoded `flag || public key`.
+    pub public_key: String,
+    #[serde(flatten)]
+    pub sealed: Sealed,
+}
+
+/// Output of AES-256-GCM, with Base64 encoded fields.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sealed {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Key derived from a keystore's passphrase, used to seal and open its private keys.
+pub struct KeystoreCipher {
+    kdf: KdfParams,
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+impl KdfParams {
+    /// scrypt's recommended cost parameters, with a fresh salt.
+    fn generate() -> Self {
+        let params = scrypt::Params::recommended();
+        let mut salt = [0u8; SALT_LENGTH];
+        thread_rng().fill_bytes(&mut salt);
+        Self {
+            log_n: params.log_n(),
+            r: params.r(),
+            p: params.p(),
+            salt: Base64::encode(salt),
+        }
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LENGTH]>, anyhow::Error> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p)
+            .map_err(|e| anyhow!("Invalid keystore key derivation parameters: {e}"))?;
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!("Invalid keystore salt: {e}"))?;
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key[..])
+            .map_err(|e| anyhow!("Cannot derive keystore key: {e}"))?;
+        Ok(key)
+    }
+}
+
+impl KeystoreCipher {
+    /// Derive a new key from `passphrase`, with a fresh salt.
+    pub fn new(passphrase: &str) -> Result<Self, anyhow::Error> {
+        Self::with_kdf(passphrase, KdfParams::generate())
+    }
+
+    fn with_kdf(passphrase: &str, kdf: KdfParams) -> Result<Self, anyhow::Error> {
+        let key = kdf.derive_key(passphrase)?;
+        Ok(Self { kdf, key })
+    }
+
+    /// Derive the key of `keystore` from `passphrase`, and use it to open all of its private keys.
+    /// Fails if the passphrase is wrong or the keystore has been tampered with.
+    pub fn open(
+        keystore: &EncryptedKeystore,
+        passphrase: &str,
+    ) -> Result<(Self, Vec<SuiKeyPair>), anyhow::Error> {
+        if keystore.version != FORMAT_VERSION {
+            bail!(
+                "Unsupported encrypted keystore version {}, expected {FORMAT_VERSION}",
+                keystore.version
+            );
+        }
+
+        let cipher = Self::with_kdf(passphrase, keystore.kdf.clone())?;
+        let verifier = cipher
+            .open_sealed(&keystore.verifier, VERIFIER_AAD)
+            .map_err(|_| anyhow!("Incorrect keystore passphrase"))?;
+        if verifier.as_slice() != VERIFIER_PLAINTEXT {
+            bail!("Incorrect keystore passphrase");
+        }
+
+        let keys = keystore
+            .keys
+            .iter()
+            .map(|key| {
+                let bytes = cipher
+                    .open_sealed(&key.sealed, key.public_key.as_bytes())
+                    .with_context(|| format!("Cannot decrypt key {}", key.public_key))?;
+                let keypair = SuiKeyPair::from_bytes(&bytes)
+                    .map_err(|e| anyhow!("Invalid key {}: {e}", key.public_key))?;
+                if keypair.public().encode_base64() != key.public_key {
+                    bail!("Key does not match its public key {}", key.public_key);
+                }
+                Ok(keypair)
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+        Ok((cipher, keys))
+    }
+
+    /// Seal `keys` into a new encrypted keystore.
+    pub fn seal<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a SuiKeyPair>,
+    ) -> Result<EncryptedKeystore, anyhow::Error> {
+        let keys = keys
+            .into_iter()
+            .map(|keypair| {
+                let public_key = keypair.public().encode_base64();
+                let bytes = Zeroizing::new(keypair.to_bytes());
+                let sealed = self.seal_bytes(&bytes, public_key.as_bytes())?;
+                Ok(EncryptedKey { public_key, sealed })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+        Ok(EncryptedKeystore {
+            version: FORMAT_VERSION,
+            kdf: self.kdf.clone(),
+            verifier: self.seal_bytes(VERIFIER_PLAINTEXT, VERIFIER_AAD)?,
+            keys,
+        })
+    }
+
+    fn seal_bytes(&self, plaintext: &[u8], aad: &[u8]) -> Result<Sealed, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = self
+            .aead()?
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|e| anyhow!("Cannot encrypt key: {e}"))?;
+        Ok(Sealed {
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    fn open_sealed(
+        &self,
+        sealed: &Sealed,
+        aad: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        let nonce = Base64::decode(&sealed.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        if nonce.len() != NONCE_LENGTH {
+            bail!("Invalid nonce length {}", nonce.len());
+        }
+        let ciphertext =
+            Base64::decode(&sealed.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+        let plaintext = self
+            .aead()?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|e| anyhow!("Cannot decrypt key: {e}"))?;
+        Ok(Zeroizing::new(plaintext))
+    }
+
+    fn aead(&self) -> Result<Aes256Gcm, anyhow::Error> {
+        Aes256Gcm::new_from_slice(&self.key[..]).map_err(|e| anyhow!("Invalid key: {e}"))
+    }
+}
+
+impl EncryptedKeystore {
+    /// Public keys of the keys in this keystore, which are available without the passphrase.
+    pub fn public_keys(&self) -> Result<Vec<PublicKey>, anyhow::Error> {
+        self.keys
+            .iter()
+            .map(|key| {
+                PublicKey::decode_base64(&key.public_key)
+                    .map_err(|e| anyhow!("Invalid public key {}: {e}", key.public_key))
+            })
+            .collect()
+    }
+}
+
+/// Read the passphrase of the keystore at `path` from the environment, falling back to prompting
+/// for it on the terminal.
+pub fn read_passphrase(path: &std::path::Path) -> Result<String, anyhow::Error> {
+    if let Ok(passphrase) = std::env::var(SUI_KEYSTORE_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password(format!(
+        "Enter passphrase for keystore {}: ",
+        path.display()
+    ))
+    .context("Cannot read keystore passphrase")
+}
+
+/// Prompt for a new passphrase on the terminal, asking for it twice to catch typos.
+pub fn read_new_passphrase() -> Result<String, anyhow::Error> {
+    let passphrase = rpassword::prompt_password("Enter new keystore passphrase: ")
+        .context("Cannot read keystore passphrase")?;
+    if passphrase.is_empty() {
+        bail!("Keystore passphrase cannot be empty");
+    }
+    let confirmation = rpassword::prompt_password("Confirm new keystore passphrase: ")
+        .context("Cannot read keystore passphrase")?;
+    if passphrase != confirmation {
+        bail!("Passphrases do not match");
+    }
+    Ok(passphrase)
+}
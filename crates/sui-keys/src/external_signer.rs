@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client side of the protocol used to talk to signers that hold keys outside of this process,
+//! e.g. in an HSM or a signing daemon.
+//!
+//! Each exchange is a single [`SignerRequest`] followed by a single [`SignerResponse`], both
+//! encoded as one line of JSON. Byte strings (public keys, messages, signatures) are Base64
+//! encoded, and public keys and signatures use the same `flag || bytes` encoding as the rest of
+//! Sui. Depending on the [`SignerEndpoint`], each exchange either happens over a fresh connection
+//! to a Unix socket, or over the stdin/stdout of a freshly spawned program.
+
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::Signature;
+
+/// How long to wait for a signing daemon to respond. This is generous to leave room for signers
+/// that ask a human to confirm each request.
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Where to find an external signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignerEndpoint {
+    /// A signing daemon listening on a Unix socket. Each request is sent over a new connection.
+    UnixSocket(PathBuf),
+    /// A program that is run once per request, reading the request from its stdin and writing
+    /// the response to its stdout.
+    Command {
+        program: PathBuf,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the keys the signer can sign with.
+    Keys,
+    /// Sign the Blake2b256 digest of `intent_message`, the BCS serialized `IntentMessage`. The
+    /// message is sent in full so that the signer can inspect (or display) what it is signing.
+    SignSecure {
+        address: SuiAddress,
+        intent_message: String,
+    },
+    /// Sign `message` as-is.
+    SignHashed {
+        address: SuiAddress,
+        message: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SignerResponse {
+    Keys { keys: Vec<SignerKey> },
+    Signature { signature: Signature },
+    Error { error: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerKey {
+    /// Base64 encoded `flag || public key`.
+    pub public_key: String,
+    /// Alias to use for the key, if the signer has a name for it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+impl SignerEndpoint {
+    /// Send `request` to the signer, and wait for its response. Error responses from the signer
+    /// are turned into errors.
+    pub fn call(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut request = serde_json::to_vec(request)?;
+        request.push(b'\n');
+
+        let mut response = String::new();
+        match self {
+            SignerEndpoint::UnixSocket(path) => unix_socket_call(path, &request, &mut response)?,
+            SignerEndpoint::Command { program, args } => {
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .spawn()
+                    .with_context(|| format!("Cannot run external signer {}", program.display()))?;
+
+                let mut stdin = child.stdin.take().expect("stdin is piped");
+                stdin.write_all(&request)?;
+                drop(stdin);
+
+                let output = child.wait_with_output()?;
+                if !output.status.success() {
+                    bail!(
+                        "External signer {} exited with {}",
+                        program.display(),
+                        output.status
+                    );
+                }
+                response = String::from_utf8(output.stdout)
+                    .context("External signer returned a non UTF-8 response")?;
+            }
+        }
+
+        let response: SignerResponse = serde_json::from_str(response.trim())
+            .with_context(|| format!("Invalid response from external signer {self}"))?;
+        if let SignerResponse::Error { error } = response {
+            bail!("External signer {self} failed: {error}");
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(unix)]
+fn unix_socket_call(
+    path: &Path,
+    request: &[u8],
+    response: &mut String,
+) -> Result<(), anyhow::Error> {
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(path)
+        .with_context(|| format!("Cannot connect to external signer at {}", path.display()))?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.write_all(request)?;
+    BufReader::new(stream).read_line(response)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn unix_socket_call(
+    path: &Path,
+    _request: &[u8],
+    _response: &mut String,
+) -> Result<(), anyhow::Error> {
+    Err(anyhow!(
+        "Cannot connect to external signer at {}: Unix sockets are not supported on this platform",
+        path.display()
+    ))
+}
+
+impl Display for SignerEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignerEndpoint::UnixSocket(path) => write!(f, "unix:{}", path.display()),
+            SignerEndpoint::Command { program, args } => {
+                write!(f, "{}", program.display())?;
+                for arg in args {
+                    write!(f, " {arg}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl SignerResponse {
+    pub fn into_keys(self) -> Result<Vec<SignerKey>, anyhow::Error> {
+        match self {
+            SignerResponse::Keys { keys } => Ok(keys),
+            _ => Err(anyhow!("Expected a list of keys from external signer")),
+        }
+    }
+
+    pub fn into_signature(self) -> Result<Signature, anyhow::Error> {
+        match self {
+            SignerResponse::Signature { signature } => Ok(signature),
+            _ => Err(anyhow!("Expected a signature from external signer")),
+        }
+    }
+}
@@ -1,11 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encryption::{read_passphrase, EncryptedKeystore, KeystoreCipher};
+use crate::external_signer::{SignerEndpoint, SignerRequest};
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::VerifyingKey;
 use rand::{rngs::StdRng, SeedableRng};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -15,12 +19,14 @@ use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write as _};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    SuiSignature, SuiSignatureInner,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -28,6 +34,7 @@ use sui_types::crypto::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -150,6 +157,11 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Keystore Signer : {}", external.endpoint)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -165,6 +177,38 @@ pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     aliases: BTreeMap<SuiAddress, Alias>,
     path: Option<PathBuf>,
+    encryption: Option<Encryption>,
+    /// Reads the passphrase of a locked keystore the first time a private key is needed, if set.
+    /// Otherwise, using a private key of a locked keystore fails.
+    unlock_on_use: Option<PassphraseReader>,
+}
+
+/// Reads the passphrase of the keystore at the given path, e.g. by prompting for it.
+pub type PassphraseReader = Box<dyn Fn(&Path) -> Result<String, anyhow::Error> + Send + Sync>;
+
+/// Encryption state of a keystore whose keys are encrypted at rest.
+enum Encryption {
+    /// The passphrase has not been provided yet, so only the public keys are available, unless
+    /// the keystore is unlocked on use.
+    Locked {
+        file: EncryptedKeystore,
+        public_keys: BTreeMap<SuiAddress, PublicKey>,
+        /// Keys decrypted on first use, once the right passphrase has been provided.
+        opened: OnceLock<OpenedKeystore>,
+    },
+    /// Keys have been decrypted, and are re-encrypted with `cipher` when saved.
+    Unlocked(KeystoreCipher),
+}
+
+type OpenedKeystore = (KeystoreCipher, BTreeMap<SuiAddress, SuiKeyPair>);
+
+/// Contents of a keystore file, which holds either plaintext Base64 encoded keys, or encrypted
+/// keys.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeystoreFile {
+    Plain(Vec<String>),
+    Encrypted(EncryptedKeystore),
 }
 
 impl Serialize for FileBasedKeystore {
@@ -197,9 +241,14 @@ impl AccountKeystore for FileBasedKeystore {
     fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
         Ok(Signature::new_hashed(
             msg,
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.private_keys()
+                .map_err(|e| signature::Error::from_source(e.to_string()))?
+                .get(address)
+                .ok_or_else(|| {
+                    signature::Error::from_source(format!(
+                        "Cannot find key for address: [{address}]"
+                    ))
+                })?,
         ))
     }
     fn sign_secure<T>(
@@ -213,13 +262,19 @@ impl AccountKeystore for FileBasedKeystore {
     {
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.private_keys()
+                .map_err(|e| signature::Error::from_source(e.to_string()))?
+                .get(address)
+                .ok_or_else(|| {
+                    signature::Error::from_source(format!(
+                        "Cannot find key for address: [{address}]"
+                    ))
+                })?,
         ))
     }
 
     fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
         let address: SuiAddress = (&keypair.public()).into();
         let alias = self.create_alias(alias)?;
         self.aliases.insert(
@@ -249,7 +304,10 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn keys(&self) -> Vec<PublicKey> {
-        self.keys.values().map(|key| key.public()).collect()
+        match &self.encryption {
+            Some(Encryption::Locked { public_keys, .. }) => public_keys.values().cloned().collect(),
+            _ => self.keys.values().map(|key| key.public()).collect(),
+        }
     }
 
     /// This function returns an error if the provided alias already exists. If the alias
@@ -289,7 +347,7 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
-        match self.keys.get(address) {
+        match self.private_keys()?.get(address) {
             Some(key) => Ok(key),
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
         }
@@ -310,24 +368,48 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
+        let file = if path.exists() {
             let reader =
                 BufReader::new(File::open(path).with_context(|| {
                     format!("Cannot open the keystore file: {}", path.display())
                 })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
+            serde_json::from_reader(reader).with_context(|| {
                 format!("Cannot deserialize the keystore file: {}", path.display(),)
-            })?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (SuiAddress::from(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+            })?
         } else {
-            BTreeMap::new()
+            KeystoreFile::Plain(vec![])
+        };
+
+        let (keys, encryption) = match file {
+            KeystoreFile::Plain(kp_strings) => {
+                let keys = kp_strings
+                    .iter()
+                    .map(|kpstr| {
+                        let key = SuiKeyPair::decode_base64(kpstr);
+                        key.map(|k| (SuiAddress::from(&k.public()), k))
+                    })
+                    .collect::<Result<BTreeMap<_, _>, _>>()
+                    .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?;
+                (keys, None)
+            }
+            KeystoreFile::Encrypted(file) => {
+                let public_keys = file
+                    .public_keys()
+                    .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+                    .into_iter()
+                    .map(|pk| (SuiAddress::from(&pk), pk))
+                    .collect();
+                let encryption = Encryption::Locked {
+                    file,
+                    public_keys,
+                    opened: OnceLock::new(),
+                };
+                (BTreeMap::new(), Some(encryption))
+            }
+        };
+        let public_keys: BTreeMap<SuiAddress, PublicKey> = match &encryption {
+            Some(Encryption::Locked { public_keys, .. }) => public_keys.clone(),
+            _ => keys.iter().map(|(a, k)| (*a, k.public())).collect(),
         };
 
         // check aliases
@@ -363,15 +445,15 @@ impl FileBasedKeystore {
                         e
                     )
                 })?
-        } else if keys.is_empty() {
+        } else if public_keys.is_empty() {
             BTreeMap::new()
         } else {
-            let names: Vec<String> = random_names(HashSet::new(), keys.len());
-            let aliases = keys
+            let names: Vec<String> = random_names(HashSet::new(), public_keys.len());
+            let aliases = public_keys
                 .iter()
                 .zip(names)
-                .map(|((sui_address, skp), alias)| {
-                    let public_key_base64 = skp.public().encode_base64();
+                .map(|((sui_address, pk), alias)| {
+                    let public_key_base64 = pk.encode_base64();
                     (
                         *sui_address,
                         Alias {
@@ -396,9 +478,119 @@ impl FileBasedKeystore {
             keys,
             aliases,
             path: Some(path.to_path_buf()),
+            encryption,
+            unlock_on_use: None,
         })
     }
 
+    /// Whether the keys in this keystore are encrypted at rest.
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Whether this keystore is encrypted, and has not been unlocked yet. Only public keys and
+    /// aliases are available while the keystore is locked.
+    pub fn is_locked(&self) -> bool {
+        matches!(
+            &self.encryption,
+            Some(Encryption::Locked { opened, .. }) if opened.get().is_none()
+        )
+    }
+
+    /// Decrypt the keys of a locked keystore with `passphrase`. Does nothing if the keystore is
+    /// not locked.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        let Some(Encryption::Locked { file, .. }) = &self.encryption else {
+            return Ok(());
+        };
+        let (cipher, keys) = KeystoreCipher::open(file, passphrase)?;
+        self.keys = key_map(keys);
+        self.encryption = Some(Encryption::Unlocked(cipher));
+        Ok(())
+    }
+
+    /// Unlock a locked keystore, with the passphrase from the `SUI_KEYSTORE_PASSPHRASE`
+    /// environment variable if it is set, or otherwise prompting for it on the terminal.
+    pub fn unlock_interactive(&mut self) -> Result<(), anyhow::Error> {
+        if !self.is_locked() {
+            return Ok(());
+        }
+        let passphrase = read_passphrase(self.path.as_deref().unwrap_or(Path::new("")))?;
+        self.unlock(&passphrase)
+    }
+
+    /// Unlock a locked keystore interactively (see `unlock_interactive`) the first time one of
+    /// its private keys is needed, rather than failing. This lets commands that only need public
+    /// information run without a passphrase.
+    pub fn unlock_on_use(&mut self) {
+        self.unlock_on_use_with(read_passphrase);
+    }
+
+    /// Like `unlock_on_use`, with the passphrase read by `read_passphrase`. A wrong passphrase
+    /// fails the operation that needed the keys, and the passphrase is read again the next time.
+    pub fn unlock_on_use_with(
+        &mut self,
+        read_passphrase: impl Fn(&Path) -> Result<String, anyhow::Error> + Send + Sync + 'static,
+    ) {
+        self.unlock_on_use = Some(Box::new(read_passphrase));
+    }
+
+    /// Encrypt the keys in this keystore with `passphrase` from now on, replacing the current
+    /// passphrase if the keystore is already encrypted, and save it. The keystore must be
+    /// unlocked.
+    pub fn set_passphrase(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
+        self.encryption = Some(Encryption::Unlocked(KeystoreCipher::new(passphrase)?));
+        self.save_keystore()
+    }
+
+    /// Store the keys in this keystore in plaintext from now on, and save it. The keystore must
+    /// be unlocked.
+    pub fn remove_passphrase(&mut self) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
+        self.encryption = None;
+        self.save_keystore()
+    }
+
+    /// The private keys of this keystore, decrypting them first if it is locked and unlocked on
+    /// use.
+    fn private_keys(&self) -> Result<&BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+        let Some(Encryption::Locked { file, opened, .. }) = &self.encryption else {
+            return Ok(&self.keys);
+        };
+        if let Some((_, keys)) = opened.get() {
+            return Ok(keys);
+        }
+        let Some(read_passphrase) = &self.unlock_on_use else {
+            return Err(self.locked_error());
+        };
+        let path = self.path.as_deref().unwrap_or(Path::new(""));
+        let (cipher, keys) = read_passphrase(path)
+            .and_then(|passphrase| KeystoreCipher::open(file, &passphrase))
+            .map_err(|e| anyhow!("Cannot unlock keystore {}: {e}", path.display()))?;
+        Ok(&opened.get_or_init(|| (cipher, key_map(keys))).1)
+    }
+
+    /// Make sure the private keys of this keystore are available before modifying it, unlocking
+    /// it if it is unlocked on use.
+    fn ensure_unlocked(&mut self) -> Result<(), anyhow::Error> {
+        self.private_keys()?;
+        if let Some(Encryption::Locked { opened, .. }) = &mut self.encryption {
+            if let Some((cipher, keys)) = opened.take() {
+                self.keys = keys;
+                self.encryption = Some(Encryption::Unlocked(cipher));
+            }
+        }
+        Ok(())
+    }
+
+    fn locked_error(&self) -> anyhow::Error {
+        anyhow!(
+            "Keystore {} is encrypted and has not been unlocked",
+            self.path.as_deref().unwrap_or(Path::new("")).display()
+        )
+    }
+
     pub fn set_path(&mut self, path: &Path) {
         self.path = Some(path.to_path_buf());
     }
@@ -422,6 +614,21 @@ impl FileBasedKeystore {
     }
 
     pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
+        match &self.encryption {
+            None => {}
+            Some(Encryption::Locked { .. }) => return Err(self.locked_error()),
+            Some(Encryption::Unlocked(cipher)) => {
+                if let Some(path) = &self.path {
+                    let store = serde_json::to_string_pretty(&cipher.seal(self.keys.values())?)
+                        .with_context(|| {
+                            format!("Cannot serialize keystore to file: {}", path.display())
+                        })?;
+                    write_atomically(path, store.as_bytes())?;
+                }
+                return Ok(());
+            }
+        }
+
         eprintln!(
             "Keys saved as Base64 with 33 bytes `flag || privkey` ($BASE64_STR). 
         To see Bech32 format encoding, use `sui keytool export $SUI_ADDRESS` where 
@@ -437,7 +644,7 @@ impl FileBasedKeystore {
                     .collect::<Vec<_>>(),
             )
             .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            fs::write(path, store)?;
+            write_atomically(path, store.as_bytes())?;
         }
         Ok(())
     }
@@ -605,6 +812,325 @@ impl InMemKeystore {
     }
 }
 
+/// Keystore whose private keys are held by an external signer (e.g. an HSM or a signing daemon),
+/// which is asked to sign on the keystore's behalf. See [`crate::external_signer`] for the
+/// protocol used to talk to the signer.
+///
+/// The set of keys is fetched from the signer the first time it is needed, so that loading a
+/// config that uses this keystore does not require the signer to be up. Failed fetches are not
+/// cached, so a signer that was briefly unreachable is asked again the next time. The signer is in charge of
+/// managing the keys: they cannot be added to or exported from this keystore. Signatures returned
+/// by the signer are verified against the expected public key before they are handed out.
+pub struct ExternalKeystore {
+    endpoint: SignerEndpoint,
+    /// The signer's keys, once they have been fetched successfully.
+    keys: OnceLock<SignerKeys>,
+}
+
+struct SignerKeys {
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.endpoint.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ExternalKeystore::new(SignerEndpoint::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl ExternalKeystore {
+    pub fn new(endpoint: SignerEndpoint) -> Self {
+        Self {
+            endpoint,
+            keys: OnceLock::new(),
+        }
+    }
+
+    pub fn endpoint(&self) -> &SignerEndpoint {
+        &self.endpoint
+    }
+
+    /// The signer's keys, fetched from the signer until a fetch succeeds.
+    fn signer_keys(&self) -> Result<&SignerKeys, anyhow::Error> {
+        if let Some(keys) = self.keys.get() {
+            return Ok(keys);
+        }
+        let keys = SignerKeys::fetch(&self.endpoint).map_err(|e| {
+            anyhow!(
+                "Cannot list the keys of external signer {}: {e}",
+                self.endpoint
+            )
+        })?;
+        // Another thread may have fetched the keys in the meantime, in which case they are kept.
+        Ok(self.keys.get_or_init(|| keys))
+    }
+
+    /// Like `signer_keys`, for the methods of `AccountKeystore` that cannot fail: A signer that
+    /// cannot be reached has no keys.
+    fn signer_keys_or_none(&self) -> Option<&SignerKeys> {
+        self.signer_keys().ok()
+    }
+
+    /// Ask the signer to respond to `request` on behalf of `address`, and check that the
+    /// signature it returns was produced by `address`'s key.
+    fn sign(
+        &self,
+        address: &SuiAddress,
+        request: SignerRequest,
+    ) -> Result<Signature, signature::Error> {
+        let public_key = self
+            .signer_keys()
+            .map_err(|e| signature::Error::from_source(e.to_string()))?
+            .keys
+            .get(address)
+            .ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })?;
+
+        let signature = self
+            .endpoint
+            .call(&request)
+            .and_then(|response| response.into_signature())
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+
+        if signature.scheme() != public_key.scheme()
+            || signature.public_key_bytes() != public_key.as_ref()
+        {
+            return Err(signature::Error::from_source(format!(
+                "External signer {} signed with the wrong key for address: [{address}]",
+                self.endpoint
+            )));
+        }
+        Ok(signature)
+    }
+
+    fn invalid_signature(&self, e: impl Display) -> signature::Error {
+        signature::Error::from_source(format!(
+            "External signer {} returned an invalid signature: {e}",
+            self.endpoint
+        ))
+    }
+}
+
+impl SignerKeys {
+    fn fetch(endpoint: &SignerEndpoint) -> Result<Self, anyhow::Error> {
+        let signer_keys = endpoint.call(&SignerRequest::Keys)?.into_keys()?;
+
+        let mut keys = BTreeMap::new();
+        let mut named = vec![];
+        for key in signer_keys {
+            let public_key = PublicKey::decode_base64(&key.public_key)
+                .map_err(|e| anyhow!("Invalid public key from external signer {endpoint}: {e}"))?;
+            let address = SuiAddress::from(&public_key);
+            named.push((address, public_key.encode_base64(), key.alias));
+            keys.insert(address, public_key);
+        }
+
+        let taken: HashSet<_> = named
+            .iter()
+            .filter_map(|(_, _, alias)| alias.clone())
+            .collect();
+        let mut unnamed = random_names(taken, named.iter().filter(|k| k.2.is_none()).count());
+        let aliases = named
+            .into_iter()
+            .map(|(address, public_key_base64, alias)| {
+                let alias = match alias {
+                    Some(alias) => validate_alias(&alias)?,
+                    None => unnamed.pop().expect("a name for every unnamed key"),
+                };
+                Ok((
+                    address,
+                    Alias {
+                        alias,
+                        public_key_base64,
+                    },
+                ))
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+
+        Ok(Self { keys, aliases })
+    }
+}
+
+/// Verifies that `signature` is a signature of `msg` itself, as produced by
+/// [`Signature::new_hashed`].
+fn verify_hashed(signature: &Signature, msg: &[u8]) -> Result<(), anyhow::Error> {
+    fn verify<S: SuiSignatureInner>(signature: &S, msg: &[u8]) -> Result<(), anyhow::Error> {
+        let (sig, pk) = signature.get_verification_inputs()?;
+        pk.verify(msg, &sig).map_err(|e| anyhow!("{e}"))
+    }
+
+    match signature {
+        Signature::Ed25519SuiSignature(signature) => verify(signature, msg),
+        Signature::Secp256k1SuiSignature(signature) => verify(signature, msg),
+        Signature::Secp256r1SuiSignature(signature) => verify(signature, msg),
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let signature = self.sign(
+            address,
+            SignerRequest::SignHashed {
+                address: *address,
+                message: Base64::encode(msg),
+            },
+        )?;
+        verify_hashed(&signature, msg).map_err(|e| self.invalid_signature(e))?;
+        Ok(signature)
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_message = IntentMessage::new(intent, msg);
+        let bytes = bcs::to_bytes(&intent_message).map_err(signature::Error::from_source)?;
+        let signature = self.sign(
+            address,
+            SignerRequest::SignSecure {
+                address: *address,
+                intent_message: Base64::encode(bytes),
+            },
+        )?;
+        signature
+            .verify_secure(&intent_message, *address, signature.scheme())
+            .map_err(|e| self.invalid_signature(e))?;
+        Ok(signature)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!(
+            "Keys cannot be added to an external signer keystore, they are managed by {}",
+            self.endpoint
+        )
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.signer_keys_or_none()
+            .map(|signer| signer.keys.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        if self.signer_keys()?.keys.contains_key(address) {
+            bail!(
+                "The private key for address [{address}] is held by external signer {}, and \
+                 cannot be exported",
+                self.endpoint
+            )
+        } else {
+            bail!("Cannot find key for address: [{address}]")
+        }
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.signer_keys_or_none()
+            .map(|signer| signer.aliases.values().collect())
+            .unwrap_or_default()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.signer_keys_or_none()
+            .map(|signer| signer.aliases.iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        let _ = self.signer_keys();
+        match self.keys.get_mut() {
+            Some(Ok(signer)) => signer.aliases.values_mut().collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Get alias of address
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.signer_keys()?.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.signer_keys()?
+            .aliases
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    fn create_alias(&self, _alias: Option<String>) -> Result<String, anyhow::Error> {
+        bail!(
+            "Aliases of external signer keys are managed by {}",
+            self.endpoint
+        )
+    }
+
+    fn update_alias(
+        &mut self,
+        _old_alias: &str,
+        _new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        bail!(
+            "Aliases of external signer keys are managed by {}",
+            self.endpoint
+        )
+    }
+}
+
+/// Replaces the contents of the file at `path` with `contents`, by writing them to a temporary file
+/// next to it first, so that a failed write cannot leave a truncated keystore behind.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut file = File::create(&tmp_path)
+        .with_context(|| format!("Cannot create temporary file: {}", tmp_path.display()))?;
+    if let Ok(metadata) = fs::metadata(path) {
+        file.set_permissions(metadata.permissions())?;
+    }
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Cannot replace keystore file: {}", path.display()))?;
+    Ok(())
+}
+
+fn key_map(keys: Vec<SuiKeyPair>) -> BTreeMap<SuiAddress, SuiKeyPair> {
+    keys.into_iter()
+        .map(|k| (SuiAddress::from(&k.public()), k))
+        .collect()
+}
+
 fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
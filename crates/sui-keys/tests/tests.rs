@@ -3,14 +3,23 @@
 
 use std::fs;
 use std::str::FromStr;
+use std::sync::Mutex;
 
+use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::EncodeDecodeBase64;
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use sui_keys::external_signer::{SignerEndpoint, SignerKey, SignerRequest, SignerResponse};
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use sui_keys::keystore::{
+    AccountKeystore, ExternalKeystore, FileBasedKeystore, InMemKeystore, Keystore,
+};
+use sui_types::crypto::{
+    get_key_pair, DefaultHash, Signature, SignatureScheme, SuiKeyPair, SuiSignature,
+    SuiSignatureInner,
+};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -266,3 +275,303 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+/// How a mock external signer deviates from the protocol.
+#[cfg(unix)]
+enum Misbehavior {
+    None,
+    /// Signs with this key instead of the requested address's.
+    WrongKey(SuiKeyPair),
+    /// Signs something other than the requested message.
+    WrongMessage,
+}
+
+/// Starts a signing daemon that serves the keys in `keystore` over a Unix socket in `dir`, and
+/// deviates from the protocol as described by `misbehavior`.
+#[cfg(unix)]
+fn mock_signer(dir: &TempDir, keystore: InMemKeystore, misbehavior: Misbehavior) -> SignerEndpoint {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    let path = dir.path().join("signer.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+    std::thread::spawn(move || {
+        let sign = |address: &SuiAddress, msg: &[u8]| match keystore.get_key(address) {
+            Ok(kp) => SignerResponse::Signature {
+                signature: match &misbehavior {
+                    Misbehavior::None => Signature::new_hashed(msg, kp),
+                    Misbehavior::WrongKey(impostor) => Signature::new_hashed(msg, impostor),
+                    Misbehavior::WrongMessage => Signature::new_hashed(b"something else", kp),
+                },
+            },
+            Err(e) => SignerResponse::Error {
+                error: e.to_string(),
+            },
+        };
+
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let response = match serde_json::from_str(&line).unwrap() {
+                SignerRequest::Keys => SignerResponse::Keys {
+                    keys: keystore
+                        .keys()
+                        .iter()
+                        .map(|pk| SignerKey {
+                            public_key: pk.encode_base64(),
+                            alias: None,
+                        })
+                        .collect(),
+                },
+                SignerRequest::SignSecure {
+                    address,
+                    intent_message,
+                } => {
+                    let mut hasher = DefaultHash::default();
+                    hasher.update(Base64::decode(&intent_message).unwrap());
+                    sign(&address, &hasher.finalize().digest)
+                }
+                SignerRequest::SignHashed { address, message } => {
+                    sign(&address, &Base64::decode(&message).unwrap())
+                }
+            };
+            let mut response = serde_json::to_vec(&response).unwrap();
+            response.push(b'\n');
+            stream.write_all(&response).unwrap();
+        }
+    });
+
+    SignerEndpoint::UnixSocket(path)
+}
+
+#[cfg(unix)]
+#[test]
+fn external_signer_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let local = InMemKeystore::new_insecure_for_tests(2);
+    let addresses = local.addresses();
+    let endpoint = mock_signer(
+        &temp_dir,
+        InMemKeystore::new_insecure_for_tests(2),
+        Misbehavior::None,
+    );
+    let mut keystore = Keystore::from(ExternalKeystore::new(endpoint));
+    assert_eq!(addresses, keystore.addresses());
+    assert_eq!(2, keystore.alias_names().len());
+    assert!(keystore.to_string().contains("External"));
+
+    // Signatures from the signer are the same as the ones produced locally.
+    let msg = PersonalMessage {
+        message: "Hello".as_bytes().to_vec(),
+    };
+    let intent = Intent::personal_message();
+    let signature = keystore
+        .sign_secure(&addresses[0], &msg, intent.clone())
+        .unwrap();
+    assert_eq!(
+        local
+            .sign_secure(&addresses[0], &msg, intent.clone())
+            .unwrap(),
+        signature
+    );
+    signature
+        .verify_secure(
+            &IntentMessage::new(intent, msg),
+            addresses[0],
+            SignatureScheme::ED25519,
+        )
+        .unwrap();
+    assert_eq!(
+        local.sign_hashed(&addresses[1], b"digest").unwrap(),
+        keystore.sign_hashed(&addresses[1], b"digest").unwrap()
+    );
+
+    // Keys are managed by the signer, and never leave it.
+    let unknown = SuiAddress::ZERO;
+    assert!(keystore.sign_hashed(&unknown, b"digest").is_err());
+    assert!(keystore.get_key(&addresses[0]).is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_signer_wrong_key_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (_, impostor) = get_key_pair();
+    let endpoint = mock_signer(
+        &temp_dir,
+        InMemKeystore::new_insecure_for_tests(1),
+        Misbehavior::WrongKey(SuiKeyPair::Ed25519(impostor)),
+    );
+    let keystore = Keystore::from(ExternalKeystore::new(endpoint));
+    let address = keystore.addresses()[0];
+    let msg = PersonalMessage {
+        message: "Hello".as_bytes().to_vec(),
+    };
+    assert!(keystore
+        .sign_secure(&address, &msg, Intent::personal_message())
+        .is_err());
+    assert!(keystore.sign_hashed(&address, b"digest").is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_signer_wrong_message_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let endpoint = mock_signer(
+        &temp_dir,
+        InMemKeystore::new_insecure_for_tests(1),
+        Misbehavior::WrongMessage,
+    );
+    let keystore = Keystore::from(ExternalKeystore::new(endpoint));
+    let address = keystore.addresses()[0];
+    let msg = PersonalMessage {
+        message: "Hello".as_bytes().to_vec(),
+    };
+    assert!(keystore
+        .sign_secure(&address, &msg, Intent::personal_message())
+        .is_err());
+    assert!(keystore.sign_hashed(&address, b"digest").is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_signer_lazy_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("signer.sock");
+
+    // Loading the config does not need the signer to be up.
+    let config = serde_json::to_string(&Keystore::from(ExternalKeystore::new(
+        SignerEndpoint::UnixSocket(path.clone()),
+    )))
+    .unwrap();
+    let keystore: Keystore = serde_json::from_str(&config).unwrap();
+    assert!(keystore.to_string().contains(&path.display().to_string()));
+
+    // Until it is used.
+    assert!(keystore.addresses().is_empty());
+    assert!(keystore.get_alias_by_address(&SuiAddress::ZERO).is_err());
+    assert!(keystore.sign_hashed(&SuiAddress::ZERO, b"digest").is_err());
+
+    // A signer that could not be reached is asked again once it is up.
+    let signer = InMemKeystore::new_insecure_for_tests(1);
+    let addresses = signer.addresses();
+    mock_signer(&temp_dir, signer, Misbehavior::None);
+    assert_eq!(addresses, keystore.addresses());
+}
+
+#[test]
+fn encrypted_keystore_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let msg = PersonalMessage {
+        message: "Hello".as_bytes().to_vec(),
+    };
+    let signature = keystore
+        .sign_secure(&address, &msg, Intent::personal_message())
+        .unwrap();
+
+    // Migrate the plaintext keystore. The private key no longer appears in the file.
+    let exported = keystore.get_key(&address).unwrap().encode_base64();
+    keystore.set_passphrase("correct horse").unwrap();
+    assert!(!fs::read_to_string(&keystore_path)
+        .unwrap()
+        .contains(&exported));
+
+    // Without the passphrase, only public information is available.
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+    assert_eq!(vec![address], keystore.addresses());
+    assert!(keystore.get_alias_by_address(&address).is_ok());
+    assert!(keystore
+        .sign_secure(&address, &msg, Intent::personal_message())
+        .is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+
+    assert!(keystore.unlock("battery staple").is_err());
+    assert!(keystore.is_locked());
+    keystore.unlock("correct horse").unwrap();
+    assert_eq!(
+        signature,
+        keystore
+            .sign_secure(&address, &msg, Intent::personal_message())
+            .unwrap()
+    );
+
+    // Keys added to an unlocked keystore are encrypted too, and survive a passphrase change.
+    let (second, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    keystore.set_passphrase("battery staple").unwrap();
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.unlock("correct horse").is_err());
+    keystore.unlock("battery staple").unwrap();
+    assert!(keystore.get_key(&second).is_ok());
+
+    // Back to plaintext.
+    keystore.remove_passphrase().unwrap();
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(!keystore.is_encrypted());
+    assert_eq!(
+        exported,
+        keystore.get_key(&address).unwrap().encode_base64()
+    );
+}
+
+#[test]
+fn encrypted_keystore_unlock_on_use_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    keystore.set_passphrase("correct horse").unwrap();
+
+    // The keystore is replaced as a whole, without leaving its temporary copy behind.
+    let files: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert!(
+        files.iter().all(|name| !name.ends_with(".tmp")),
+        "{files:?}"
+    );
+
+    // Public information does not need the passphrase, which is only read once a key is used.
+    // A wrong passphrase is not remembered, and the passphrase is read again on the next use.
+    let passphrases = Mutex::new(vec!["correct horse", "battery staple"]);
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .unlock_on_use_with(move |_| Ok(passphrases.lock().unwrap().pop().unwrap().to_string()));
+    assert!(keystore.is_locked());
+    assert_eq!(vec![address], keystore.addresses());
+    let msg = PersonalMessage {
+        message: "Hello".as_bytes().to_vec(),
+    };
+    assert!(keystore
+        .sign_secure(&address, &msg, Intent::personal_message())
+        .is_err());
+    assert!(keystore.is_locked());
+    keystore
+        .sign_secure(&address, &msg, Intent::personal_message())
+        .unwrap();
+    assert!(!keystore.is_locked());
+
+    // Modifying the keystore keeps it encrypted with the same passphrase.
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore.unlock("correct horse").unwrap();
+    assert_eq!(2, keystore.key_pairs().len());
+}
@@ -0,0 +1,177 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use sui_rest_api::Client;
+use sui_types::{
+    committee::{Committee, EpochId},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData},
+};
+
+/// The chain of committees of a Sui network, starting from a trusted (genesis) committee and
+/// extended one epoch at a time by end-of-epoch checkpoints certified by the previous committee.
+#[derive(Clone, Debug)]
+pub struct CommitteeChain {
+    /// Committees indexed by `epoch - committees[0].epoch`.
+    committees: Vec<Committee>,
+    /// The verified end-of-epoch checkpoints, one per committee except the latest.
+    end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+impl CommitteeChain {
+    /// Starts a chain from a committee that is trusted without verification, usually the genesis
+    /// committee.
+    pub fn new(trusted_committee: Committee) -> Self {
+        Self {
+            committees: vec![trusted_committee],
+            end_of_epoch_checkpoints: vec![],
+        }
+    }
+
+    /// Builds a chain from a trusted committee and the end-of-epoch checkpoints that follow it,
+    /// verifying each of them.
+    pub fn from_end_of_epoch_checkpoints(
+        trusted_committee: Committee,
+        checkpoints: impl IntoIterator<Item = CertifiedCheckpointSummary>,
+    ) -> anyhow::Result<Self> {
+        let mut chain = Self::new(trusted_committee);
+        for checkpoint in checkpoints {
+            chain.append(checkpoint)?;
+        }
+        Ok(chain)
+    }
+
+    /// The committee of the latest epoch known to the chain.
+    pub fn latest_committee(&self) -> &Committee {
+        self.committees
+            .last()
+            .expect("committee chain always has a committee")
+    }
+
+    /// The committee of `epoch`, if the chain has been synced up to it.
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        let first_epoch = self.committees[0].epoch;
+        let index = epoch.checked_sub(first_epoch)?;
+        self.committees.get(usize::try_from(index).ok()?)
+    }
+
+    /// The verified end-of-epoch checkpoints, in epoch order.
+    pub fn end_of_epoch_checkpoints(&self) -> &[CertifiedCheckpointSummary] {
+        &self.end_of_epoch_checkpoints
+    }
+
+    /// Verifies `checkpoint` as the end-of-epoch checkpoint of the latest epoch of the chain and
+    /// extends the chain with the committee of the next epoch.
+    pub fn append(&mut self, checkpoint: CertifiedCheckpointSummary) -> anyhow::Result<()> {
+        let committee = self.latest_committee();
+        anyhow::ensure!(
+            checkpoint.epoch() == committee.epoch,
+            "Expected an end-of-epoch checkpoint for epoch {}, got checkpoint {} of epoch {}",
+            committee.epoch,
+            checkpoint.sequence_number,
+            checkpoint.epoch()
+        );
+        checkpoint.verify_authority_signatures(committee)?;
+        let next_committee = next_epoch_committee(&checkpoint)?;
+        self.committees.push(next_committee);
+        self.end_of_epoch_checkpoints.push(checkpoint);
+        Ok(())
+    }
+
+    /// Verifies the signatures of a checkpoint of any epoch the chain has been synced up to.
+    pub fn verify_checkpoint_summary(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+    ) -> anyhow::Result<()> {
+        checkpoint.verify_authority_signatures(self.committee_for(checkpoint)?)?;
+        Ok(())
+    }
+
+    /// The committee that must have certified `checkpoint`.
+    pub fn committee_for(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+    ) -> anyhow::Result<&Committee> {
+        self.committee(checkpoint.epoch()).ok_or_else(|| {
+            anyhow!(
+                "No committee known for epoch {}, the committee chain needs to be synced",
+                checkpoint.epoch()
+            )
+        })
+    }
+
+    /// Downloads and appends the end-of-epoch checkpoints of all the epochs between the latest
+    /// one of the chain and the current epoch of the full node behind `client`. Returns the
+    /// checkpoints that were appended.
+    pub async fn sync(
+        &mut self,
+        client: &Client,
+    ) -> anyhow::Result<Vec<CertifiedCheckpointSummary>> {
+        let latest = client.get_latest_checkpoint().await?;
+        let mut start = self
+            .end_of_epoch_checkpoints
+            .last()
+            .map_or(0, |checkpoint| checkpoint.sequence_number + 1);
+        let mut appended = vec![];
+        while self.latest_committee().epoch < latest.epoch() {
+            let checkpoint = find_end_of_epoch_checkpoint(
+                client,
+                self.latest_committee().epoch,
+                start,
+                latest.sequence_number,
+            )
+            .await?;
+            start = checkpoint.sequence_number + 1;
+            self.append(checkpoint.clone())?;
+            appended.push(checkpoint);
+        }
+        Ok(appended)
+    }
+}
+
+/// The committee an end-of-epoch checkpoint hands over to.
+pub fn next_epoch_committee(checkpoint: &CertifiedCheckpointSummary) -> anyhow::Result<Committee> {
+    let Some(EndOfEpochData {
+        next_epoch_committee,
+        ..
+    }) = &checkpoint.end_of_epoch_data
+    else {
+        return Err(anyhow!(
+            "Checkpoint {} is not an end-of-epoch checkpoint",
+            checkpoint.sequence_number
+        ));
+    };
+    let next_epoch = checkpoint
+        .epoch()
+        .checked_add(1)
+        .ok_or_else(|| anyhow!("Epoch overflow"))?;
+    Ok(Committee::new(
+        next_epoch,
+        next_epoch_committee.iter().cloned().collect(),
+    ))
+}
+
+/// Binary searches the checkpoints in `[start, end)` for the end-of-epoch checkpoint of `epoch`.
+/// The checkpoint is not verified.
+pub async fn find_end_of_epoch_checkpoint(
+    client: &Client,
+    epoch: EpochId,
+    mut start: CheckpointSequenceNumber,
+    mut end: CheckpointSequenceNumber,
+) -> anyhow::Result<CertifiedCheckpointSummary> {
+    while start < end {
+        let mid = start + (end - start) / 2;
+        let summary = client.get_checkpoint_summary(mid).await?;
+        if summary.epoch() == epoch && summary.end_of_epoch_data.is_some() {
+            return Ok(summary);
+        }
+        if summary.epoch() <= epoch {
+            start = mid + 1;
+        } else {
+            end = mid;
+        }
+    }
+    Err(anyhow!(
+        "End-of-epoch checkpoint for epoch {epoch} not found"
+    ))
+}
@@ -0,0 +1,14 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of Sui data served by untrusted full nodes.
+//!
+//! A [`CommitteeChain`] is built from a trusted genesis committee and extended with verified
+//! end-of-epoch checkpoints, giving the committee of every synced epoch. With it, the functions in
+//! [`verifier`] check that a checkpoint is certified and that transaction effects, events and
+//! object states are included in its certified contents.
+
+pub mod committee_chain;
+pub mod verifier;
+
+pub use committee_chain::CommitteeChain;
@@ -3,19 +3,30 @@
 
 use anyhow::anyhow;
 use async_trait::async_trait;
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
 
+use sui_light_client::{
+    committee_chain::next_epoch_committee,
+    verifier::{
+        extract_verified_dynamic_field, extract_verified_effects_and_events,
+        extract_verified_event, extract_verified_events, verify_dynamic_field, verify_object,
+    },
+    CommitteeChain,
+};
 use sui_rest_api::{CheckpointData, Client};
 use sui_types::{
     base_types::ObjectID,
     committee::Committee,
     crypto::AuthorityQuorumSignInfo,
     digests::TransactionDigest,
-    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    dynamic_field::derive_dynamic_field_id,
+    effects::{TransactionEffects, TransactionEvents},
+    event::{Event, EventID},
     message_envelope::Envelope,
-    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSummary, EndOfEpochData},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSummary},
     object::{Data, Object},
+    parse_sui_type_tag,
 };
 
 use sui_config::genesis::Genesis;
@@ -80,6 +91,36 @@ enum SCommands {
         #[arg(short, long, value_name = "OID")]
         oid: String,
     },
+
+    /// Checks the events emitted by a transaction using the light client
+    Events {
+        /// Transaction hash
+        #[arg(short, long, value_name = "TID")]
+        tid: String,
+
+        /// Only check the event with this sequence number within the transaction
+        #[arg(short, long, value_name = "SEQ")]
+        event_seq: Option<u64>,
+    },
+
+    /// Checks the value of a dynamic field using the light client
+    DynamicField {
+        /// Parent object id
+        #[arg(short, long, value_name = "PARENT")]
+        parent: String,
+
+        /// Type of the field name, e.g. u64 or 0x1::string::String
+        #[arg(long, value_name = "TYPE")]
+        name_type: String,
+
+        /// Field name, as JSON
+        #[arg(short, long, value_name = "NAME")]
+        name: String,
+
+        /// Check the value written in this checkpoint instead of the latest one
+        #[arg(long, value_name = "SEQ")]
+        checkpoint: Option<u64>,
+    },
 }
 
 // The config file for the light client including the root of trust genesis digest
@@ -189,81 +230,9 @@ async fn download_checkpoint_summary(
     client.get_checkpoint_summary(seq).await
 }
 
-/// Run binary search to for each end of epoch checkpoint that is missing
-/// between the latest on the list and the latest checkpoint.
-async fn sync_checkpoint_list_to_latest(config: &Config) -> anyhow::Result<()> {
-    // Get the local checkpoint list
-    let mut checkpoints_list: CheckpointsList = read_checkpoint_list(config)?;
-    let latest_in_list = checkpoints_list
-        .checkpoints
-        .last()
-        .ok_or(anyhow!("Empty checkpoint list"))?;
-
-    // Download the latest in list checkpoint
-    let summary = download_checkpoint_summary(config, *latest_in_list).await?;
-    let mut last_epoch = summary.epoch();
-    let mut last_checkpoint_seq = summary.sequence_number;
-
-    // Download the very latest checkpoint
-    let client = Client::new(config.rest_url());
-    let latest = client.get_latest_checkpoint().await?;
-
-    // Binary search to find missing checkpoints
-    while last_epoch + 1 < latest.epoch() {
-        let mut start = last_checkpoint_seq;
-        let mut end = latest.sequence_number;
-
-        let target_epoch = last_epoch + 1;
-        // Print target
-        println!("Target Epoch: {}", target_epoch);
-        let mut found_summary = None;
-
-        while start < end {
-            let mid = (start + end) / 2;
-            let summary = download_checkpoint_summary(config, mid).await?;
-
-            // print summary epoch and seq
-            println!(
-                "Epoch: {} Seq: {}: {}",
-                summary.epoch(),
-                summary.sequence_number,
-                summary.end_of_epoch_data.is_some()
-            );
-
-            if summary.epoch() == target_epoch && summary.end_of_epoch_data.is_some() {
-                found_summary = Some(summary);
-                break;
-            }
-
-            if summary.epoch() <= target_epoch {
-                start = mid + 1;
-            } else {
-                end = mid;
-            }
-        }
-
-        if let Some(summary) = found_summary {
-            // Note: Do not write summary to file, since we must only persist
-            //       checkpoints that have been verified by the previous committee
-
-            // Add to the list
-            checkpoints_list.checkpoints.push(summary.sequence_number);
-            write_checkpoint_list(config, &checkpoints_list)?;
-
-            // Update
-            last_epoch = summary.epoch();
-            last_checkpoint_seq = summary.sequence_number;
-        }
-    }
-
-    Ok(())
-}
-
 async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
-    sync_checkpoint_list_to_latest(config).await?;
-
     // Get the local checkpoint list
-    let checkpoints_list: CheckpointsList = read_checkpoint_list(config)?;
+    let mut checkpoints_list: CheckpointsList = read_checkpoint_list(config)?;
 
     // Load the genesis committee
     let mut genesis_path = config.checkpoint_summary_dir.clone();
@@ -272,24 +241,24 @@ async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
 
     // Check the signatures of all checkpoints
     // And download any missing ones
-
-    let mut prev_committee = genesis_committee;
+    let mut committee_chain = CommitteeChain::new(genesis_committee);
     for ckp_id in &checkpoints_list.checkpoints {
         // check if there is a file with this name ckp_id.yaml in the checkpoint_summary_dir
         let mut checkpoint_path = config.checkpoint_summary_dir.clone();
         checkpoint_path.push(format!("{}.yaml", ckp_id));
 
         // If file exists read the file otherwise download it from the server
-        let summary = if checkpoint_path.exists() {
+        let stored = checkpoint_path.exists();
+        let summary = if stored {
             read_checkpoint(config, *ckp_id)?
         } else {
-            // Download the checkpoint from the server
-            let summary = download_checkpoint_summary(config, *ckp_id).await?;
-            summary.clone().try_into_verified(&prev_committee)?;
-            // Write the checkpoint summary to a file
-            write_checkpoint(config, &summary)?;
-            summary
+            download_checkpoint_summary(config, *ckp_id).await?
         };
+        committee_chain.append(summary.clone())?;
+        if !stored {
+            // Write the checkpoint summary to a file, now that it has been verified
+            write_checkpoint(config, &summary)?;
+        }
 
         // Print the id of the checkpoint and the epoch number
         println!(
@@ -297,21 +266,20 @@ async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
             summary.epoch(),
             summary.digest()
         );
+    }
 
-        // Extract the new committee information
-        if let Some(EndOfEpochData {
-            next_epoch_committee,
-            ..
-        }) = &summary.end_of_epoch_data
-        {
-            let next_committee = next_epoch_committee.iter().cloned().collect();
-            prev_committee =
-                Committee::new(summary.epoch().checked_add(1).unwrap(), next_committee);
-        } else {
-            return Err(anyhow!(
-                "Expected all checkpoints to be end-of-epoch checkpoints"
-            ));
-        }
+    // Find and verify the end-of-epoch checkpoints of the epochs after the last one in the list
+    let client = Client::new(config.rest_url());
+    for summary in committee_chain.sync(&client).await? {
+        write_checkpoint(config, &summary)?;
+        checkpoints_list.checkpoints.push(summary.sequence_number);
+        write_checkpoint_list(config, &checkpoints_list)?;
+
+        println!(
+            "Epoch: {} Checkpoint ID: {}",
+            summary.epoch(),
+            summary.digest()
+        );
     }
 
     Ok(())
@@ -325,44 +293,11 @@ async fn get_full_checkpoint(config: &Config, seq: u64) -> anyhow::Result<Checkp
     Ok(full_checkpoint)
 }
 
-fn extract_verified_effects_and_events(
-    checkpoint: &CheckpointData,
-    committee: &Committee,
-    tid: TransactionDigest,
-) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
-    let summary = &checkpoint.checkpoint_summary;
-
-    // Verify the checkpoint summary using the committee
-    summary.verify_with_contents(committee, Some(&checkpoint.checkpoint_contents))?;
-
-    // Check the validity of the transaction
-    let contents = &checkpoint.checkpoint_contents;
-    let (matching_tx, _) = checkpoint
-        .transactions
-        .iter()
-        .zip(contents.iter())
-        // Note that we get the digest of the effects to ensure this is
-        // indeed the correct effects that are authenticated in the contents.
-        .find(|(tx, digest)| {
-            tx.effects.execution_digests() == **digest && digest.transaction == tid
-        })
-        .ok_or(anyhow!("Transaction not found in checkpoint contents"))?;
-
-    // Check the events are all correct.
-    let events_digest = matching_tx.events.as_ref().map(|events| events.digest());
-    anyhow::ensure!(
-        events_digest.as_ref() == matching_tx.effects.events_digest(),
-        "Events digest does not match"
-    );
-
-    // Since we do not check objects we do not return them
-    Ok((matching_tx.effects.clone(), matching_tx.events.clone()))
-}
-
-async fn get_verified_effects_and_events(
+/// Looks up the sequence number of the checkpoint that includes transaction `tid`
+async fn get_transaction_checkpoint(
     config: &Config,
     tid: TransactionDigest,
-) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
+) -> anyhow::Result<u64> {
     let sui_mainnet: Arc<sui_sdk::SuiClient> = Arc::new(
         SuiClientBuilder::default()
             .build(config.full_node_url.as_str())
@@ -373,12 +308,19 @@ async fn get_verified_effects_and_events(
 
     // Lookup the transaction id and get the checkpoint sequence number
     let options = SuiTransactionBlockResponseOptions::new();
-    let seq = read_api
+    read_api
         .get_transaction_with_options(tid, options)
         .await?
         .checkpoint
-        .ok_or(anyhow!("Transaction not found"))?;
+        .ok_or(anyhow!("Transaction not found"))
+}
 
+/// Downloads the full checkpoint `seq` together with the committee of its epoch, taken from the
+/// synced end-of-epoch checkpoints
+async fn get_checkpoint_and_committee(
+    config: &Config,
+    seq: u64,
+) -> anyhow::Result<(CheckpointData, Committee)> {
     // Download the full checkpoint for this sequence number
     let full_check_point = get_full_checkpoint(config, seq).await?;
 
@@ -403,19 +345,7 @@ async fn get_verified_effects_and_events(
         );
 
         // Get the committee from the previous checkpoint
-        let current_committee = prev_ckp
-            .end_of_epoch_data
-            .as_ref()
-            .ok_or(anyhow!(
-                "Expected all checkpoints to be end-of-epoch checkpoints"
-            ))?
-            .next_epoch_committee
-            .iter()
-            .cloned()
-            .collect();
-
-        // Make a committee object using this
-        Committee::new(prev_ckp.epoch().checked_add(1).unwrap(), current_committee)
+        next_epoch_committee(&prev_ckp)?
     } else {
         // Since we did not find a small committee checkpoint we use the genesis
         let mut genesis_path = config.checkpoint_summary_dir.clone();
@@ -423,9 +353,67 @@ async fn get_verified_effects_and_events(
         Genesis::load(&genesis_path)?.committee()?
     };
 
+    Ok((full_check_point, committee))
+}
+
+async fn get_verified_effects_and_events(
+    config: &Config,
+    tid: TransactionDigest,
+) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
+    let seq = get_transaction_checkpoint(config, tid).await?;
+    let (full_check_point, committee) = get_checkpoint_and_committee(config, seq).await?;
     extract_verified_effects_and_events(&full_check_point, &committee, tid)
 }
 
+async fn get_verified_events(
+    config: &Config,
+    tid: TransactionDigest,
+    event_seq: Option<u64>,
+) -> anyhow::Result<Vec<Event>> {
+    let seq = get_transaction_checkpoint(config, tid).await?;
+    let (full_check_point, committee) = get_checkpoint_and_committee(config, seq).await?;
+    match event_seq {
+        Some(event_seq) => {
+            let id = EventID {
+                tx_digest: tid,
+                event_seq,
+            };
+            Ok(vec![extract_verified_event(
+                &full_check_point,
+                &committee,
+                &id,
+            )?])
+        }
+        None => extract_verified_events(&full_check_point, &committee, tid),
+    }
+}
+
+async fn get_verified_dynamic_field(
+    config: &Config,
+    parent: ObjectID,
+    name_type: &TypeTag,
+    name_bcs: &[u8],
+    checkpoint: Option<u64>,
+) -> anyhow::Result<Object> {
+    if let Some(seq) = checkpoint {
+        // The value written by a transaction of this checkpoint
+        let (full_check_point, committee) = get_checkpoint_and_committee(config, seq).await?;
+        return extract_verified_dynamic_field(
+            &full_check_point,
+            &committee,
+            parent,
+            name_type,
+            name_bcs,
+        );
+    }
+
+    // The latest value, authenticated by the checkpoint of the transaction that wrote it
+    let field_id = derive_dynamic_field_id(parent, name_type, name_bcs)?;
+    let object = get_verified_object(config, field_id).await?;
+    verify_dynamic_field(&object, parent, name_type, name_bcs)?;
+    Ok(object)
+}
+
 async fn get_verified_object(config: &Config, id: ObjectID) -> anyhow::Result<Object> {
     let client: Client = Client::new(config.rest_url());
     let object = client.get_object(id).await?;
@@ -434,15 +422,54 @@ async fn get_verified_object(config: &Config, id: ObjectID) -> anyhow::Result<Ob
     let (effects, _) = get_verified_effects_and_events(config, object.previous_transaction).await?;
 
     // check that this object ID, version and hash is in the effects
-    effects
-        .all_changed_objects()
-        .iter()
-        .find(|object_ref| object_ref.0 == object.compute_object_reference())
-        .ok_or(anyhow!("Object not found"))?;
+    verify_object(&effects, &object)?;
 
     Ok(object)
 }
 
+async fn print_event(resolver: &Resolver<RemotePackageStore>, event: &Event) {
+    let type_layout = resolver
+        .type_layout(event.type_.clone().into())
+        .await
+        .unwrap();
+
+    let json_val = SuiJsonValue::from_bcs_bytes(Some(&type_layout), &event.contents).unwrap();
+
+    println!(
+        "Event:\n - Package: {}\n - Module: {}\n - Sender: {}\n - Type: {}\n{}",
+        event.package_id,
+        event.transaction_module,
+        event.sender,
+        event.type_,
+        serde_json::to_string_pretty(&json_val.to_json_value()).unwrap()
+    );
+}
+
+async fn print_object(resolver: &Resolver<RemotePackageStore>, object: &Object) {
+    if let Data::Move(move_object) = &object.data {
+        let object_type = move_object.type_().clone();
+
+        let type_layout = resolver
+            .type_layout(object_type.clone().into())
+            .await
+            .unwrap();
+
+        let json_val =
+            SuiJsonValue::from_bcs_bytes(Some(&type_layout), move_object.contents()).unwrap();
+
+        let (oid, version, hash) = object.compute_object_reference();
+        println!(
+            "OID: {}\n - Version: {}\n - Hash: {}\n - Owner: {}\n - Type: {}\n{}",
+            oid,
+            version,
+            hash,
+            object.owner,
+            object_type,
+            serde_json::to_string_pretty(&json_val.to_json_value()).unwrap()
+        );
+    }
+}
+
 #[tokio::main]
 pub async fn main() {
     // Command line arguments and config loading
@@ -479,52 +506,47 @@ pub async fn main() {
                 exec_digests.transaction, exec_digests.effects
             );
 
-            for event in events.as_ref().unwrap().data.iter() {
-                let type_layout = resolver
-                    .type_layout(event.type_.clone().into())
-                    .await
-                    .unwrap();
-
-                let json_val =
-                    SuiJsonValue::from_bcs_bytes(Some(&type_layout), &event.contents).unwrap();
-
-                println!(
-                    "Event:\n - Package: {}\n - Module: {}\n - Sender: {}\n - Type: {}\n{}",
-                    event.package_id,
-                    event.transaction_module,
-                    event.sender,
-                    event.type_,
-                    serde_json::to_string_pretty(&json_val.to_json_value()).unwrap()
-                );
+            for event in events.iter().flat_map(|events| &events.data) {
+                print_event(&resolver, event).await;
             }
         }
         Some(SCommands::Object { oid }) => {
             let oid = ObjectID::from_str(&oid).unwrap();
             let object = get_verified_object(&config, oid).await.unwrap();
+            print_object(&resolver, &object).await;
+        }
+        Some(SCommands::Events { tid, event_seq }) => {
+            let events = get_verified_events(
+                &config,
+                TransactionDigest::from_str(&tid).unwrap(),
+                event_seq,
+            )
+            .await
+            .unwrap();
 
-            if let Data::Move(move_object) = &object.data {
-                let object_type = move_object.type_().clone();
-
-                let type_layout = resolver
-                    .type_layout(object_type.clone().into())
+            for event in &events {
+                print_event(&resolver, event).await;
+            }
+        }
+        Some(SCommands::DynamicField {
+            parent,
+            name_type,
+            name,
+            checkpoint,
+        }) => {
+            let parent = ObjectID::from_str(&parent).unwrap();
+            let name_type = parse_sui_type_tag(&name_type).unwrap();
+
+            // Encode the name with the layout of its type
+            let name_layout = resolver.type_layout(name_type.clone()).await.unwrap();
+            let name_json = SuiJsonValue::new(serde_json::from_str(&name).unwrap()).unwrap();
+            let name_bcs = name_json.to_bcs_bytes(&name_layout).unwrap();
+
+            let object =
+                get_verified_dynamic_field(&config, parent, &name_type, &name_bcs, checkpoint)
                     .await
                     .unwrap();
-
-                let json_val =
-                    SuiJsonValue::from_bcs_bytes(Some(&type_layout), move_object.contents())
-                        .unwrap();
-
-                let (oid, version, hash) = object.compute_object_reference();
-                println!(
-                    "OID: {}\n - Version: {}\n - Hash: {}\n - Owner: {}\n - Type: {}\n{}",
-                    oid,
-                    version,
-                    hash,
-                    object.owner,
-                    object_type,
-                    serde_json::to_string_pretty(&json_val.to_json_value()).unwrap()
-                );
-            }
+            print_object(&resolver, &object).await;
         }
 
         Some(SCommands::Sync {}) => {
@@ -535,149 +557,3 @@ pub async fn main() {
         _ => {}
     }
 }
-
-// Make a test namespace
-#[cfg(test)]
-mod tests {
-    use sui_types::messages_checkpoint::FullCheckpointContents;
-
-    use super::*;
-    use std::path::{Path, PathBuf};
-
-    async fn read_full_checkpoint(checkpoint_path: &PathBuf) -> anyhow::Result<CheckpointData> {
-        let mut reader = fs::File::open(checkpoint_path.clone())?;
-        let metadata = fs::metadata(checkpoint_path)?;
-        let mut buffer = vec![0; metadata.len() as usize];
-        reader.read_exact(&mut buffer)?;
-        bcs::from_bytes(&buffer).map_err(|_| anyhow!("Unable to parse checkpoint file"))
-    }
-
-    // clippy ignore dead-code
-    #[allow(dead_code)]
-    async fn write_full_checkpoint(
-        checkpoint_path: &Path,
-        checkpoint: &CheckpointData,
-    ) -> anyhow::Result<()> {
-        let mut writer = fs::File::create(checkpoint_path)?;
-        let bytes = bcs::to_bytes(&checkpoint)
-            .map_err(|_| anyhow!("Unable to serialize checkpoint summary"))?;
-        writer.write_all(&bytes)?;
-        Ok(())
-    }
-
-    async fn read_data() -> (Committee, CheckpointData) {
-        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        d.push("example_config/20873329.yaml");
-
-        let mut reader = fs::File::open(d.clone()).unwrap();
-        let metadata = fs::metadata(&d).unwrap();
-        let mut buffer = vec![0; metadata.len() as usize];
-        reader.read_exact(&mut buffer).unwrap();
-        let checkpoint: Envelope<CheckpointSummary, AuthorityQuorumSignInfo<true>> =
-            bcs::from_bytes(&buffer)
-                .map_err(|_| anyhow!("Unable to parse checkpoint file"))
-                .unwrap();
-
-        let prev_committee = checkpoint
-            .end_of_epoch_data
-            .as_ref()
-            .ok_or(anyhow!(
-                "Expected all checkpoints to be end-of-epoch checkpoints"
-            ))
-            .unwrap()
-            .next_epoch_committee
-            .iter()
-            .cloned()
-            .collect();
-
-        // Make a committee object using this
-        let committee = Committee::new(checkpoint.epoch().checked_add(1).unwrap(), prev_committee);
-
-        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        d.push("example_config/20958462.bcs");
-
-        let full_checkpoint = read_full_checkpoint(&d).await.unwrap();
-
-        (committee, full_checkpoint)
-    }
-
-    #[tokio::test]
-    async fn test_checkpoint_all_good() {
-        let (committee, full_checkpoint) = read_data().await;
-
-        extract_verified_effects_and_events(
-            &full_checkpoint,
-            &committee,
-            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap(),
-        )
-        .unwrap();
-    }
-
-    #[tokio::test]
-    async fn test_checkpoint_bad_committee() {
-        let (mut committee, full_checkpoint) = read_data().await;
-
-        // Change committee
-        committee.epoch += 10;
-
-        assert!(extract_verified_effects_and_events(
-            &full_checkpoint,
-            &committee,
-            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap(),
-        )
-        .is_err());
-    }
-
-    #[tokio::test]
-    async fn test_checkpoint_no_transaction() {
-        let (committee, full_checkpoint) = read_data().await;
-
-        assert!(extract_verified_effects_and_events(
-            &full_checkpoint,
-            &committee,
-            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap(),
-        )
-        .is_err());
-    }
-
-    #[tokio::test]
-    async fn test_checkpoint_bad_contents() {
-        let (committee, mut full_checkpoint) = read_data().await;
-
-        // Change contents
-        let random_contents = FullCheckpointContents::random_for_testing();
-        full_checkpoint.checkpoint_contents = random_contents.checkpoint_contents();
-
-        assert!(extract_verified_effects_and_events(
-            &full_checkpoint,
-            &committee,
-            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap(),
-        )
-        .is_err());
-    }
-
-    #[tokio::test]
-    async fn test_checkpoint_bad_events() {
-        let (committee, mut full_checkpoint) = read_data().await;
-
-        let event = full_checkpoint.transactions[4]
-            .events
-            .as_ref()
-            .unwrap()
-            .data[0]
-            .clone();
-
-        for t in &mut full_checkpoint.transactions {
-            if let Some(events) = &mut t.events {
-                events.data.push(event.clone());
-            }
-        }
-
-        assert!(extract_verified_effects_and_events(
-            &full_checkpoint,
-            &committee,
-            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap(),
-        )
-        .is_err());
-    }
-}
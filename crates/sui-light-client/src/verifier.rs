@@ -0,0 +1,392 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use move_core_types::language_storage::TypeTag;
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::{
+    base_types::ObjectID,
+    committee::Committee,
+    digests::TransactionDigest,
+    dynamic_field::derive_dynamic_field_id,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    event::{Event, EventID},
+    object::{Object, Owner},
+};
+
+/// Checks the signatures on the checkpoint summary and that the checkpoint contents are the ones
+/// it certifies.
+pub fn verify_checkpoint(checkpoint: &CheckpointData, committee: &Committee) -> anyhow::Result<()> {
+    checkpoint
+        .checkpoint_summary
+        .verify_with_contents(committee, Some(&checkpoint.checkpoint_contents))?;
+    Ok(())
+}
+
+/// Checks that `events` are the events committed to by `effects`.
+pub fn verify_events(
+    effects: &TransactionEffects,
+    events: Option<&TransactionEvents>,
+) -> anyhow::Result<()> {
+    let events_digest = events.map(|events| events.digest());
+    anyhow::ensure!(
+        events_digest.as_ref() == effects.events_digest(),
+        "Events digest does not match"
+    );
+    Ok(())
+}
+
+/// Checks that `object`, at its current version and contents, is one of the objects written by
+/// the transaction with `effects`.
+pub fn verify_object(effects: &TransactionEffects, object: &Object) -> anyhow::Result<()> {
+    let object_ref = object.compute_object_reference();
+    effects
+        .all_changed_objects()
+        .iter()
+        .find(|(changed_ref, _, _)| *changed_ref == object_ref)
+        .ok_or(anyhow!("Object not found"))?;
+    Ok(())
+}
+
+/// Checks that `object` is the dynamic field of `parent` with the BCS encoded name `name_bcs` of
+/// type `name_type`, and that it is still attached to `parent`.
+pub fn verify_dynamic_field(
+    object: &Object,
+    parent: ObjectID,
+    name_type: &TypeTag,
+    name_bcs: &[u8],
+) -> anyhow::Result<()> {
+    let field_id = derive_dynamic_field_id(parent, name_type, name_bcs)?;
+    anyhow::ensure!(
+        object.id() == field_id,
+        "Object {} is not the dynamic field {field_id} of {parent}",
+        object.id()
+    );
+    anyhow::ensure!(
+        object.type_().is_some_and(|type_| type_.is_dynamic_field()),
+        "Object {field_id} is not a dynamic field"
+    );
+    anyhow::ensure!(
+        object.owner == Owner::ObjectOwner(parent.into()),
+        "Dynamic field {field_id} is not owned by {parent}"
+    );
+    Ok(())
+}
+
+/// Verifies the checkpoint and returns the transaction `tid` in it, after checking that its
+/// effects are included in the certified checkpoint contents and that its events match them.
+pub fn extract_verified_transaction<'a>(
+    checkpoint: &'a CheckpointData,
+    committee: &Committee,
+    tid: TransactionDigest,
+) -> anyhow::Result<&'a CheckpointTransaction> {
+    verify_checkpoint(checkpoint, committee)?;
+
+    // Check the validity of the transaction
+    let (matching_tx, _) = checkpoint
+        .transactions
+        .iter()
+        .zip(checkpoint.checkpoint_contents.iter())
+        // Note that we get the digest of the effects to ensure this is
+        // indeed the correct effects that are authenticated in the contents.
+        .find(|(tx, digest)| {
+            tx.effects.execution_digests() == **digest && digest.transaction == tid
+        })
+        .ok_or(anyhow!("Transaction not found in checkpoint contents"))?;
+
+    // Check the events are all correct.
+    verify_events(&matching_tx.effects, matching_tx.events.as_ref())?;
+
+    Ok(matching_tx)
+}
+
+/// Same as `extract_verified_transaction`, returning the verified effects and events.
+pub fn extract_verified_effects_and_events(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    tid: TransactionDigest,
+) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
+    let transaction = extract_verified_transaction(checkpoint, committee, tid)?;
+    Ok((transaction.effects.clone(), transaction.events.clone()))
+}
+
+/// Verifies the checkpoint and returns the events emitted by the transaction `tid`, after checking
+/// that they are the ones committed to by its certified effects.
+pub fn extract_verified_events(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    tid: TransactionDigest,
+) -> anyhow::Result<Vec<Event>> {
+    let transaction = extract_verified_transaction(checkpoint, committee, tid)?;
+    Ok(transaction
+        .events
+        .as_ref()
+        .map(|events| events.data.clone())
+        .unwrap_or_default())
+}
+
+/// Verifies the checkpoint and returns the event `id`, proving that it was emitted.
+pub fn extract_verified_event(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    id: &EventID,
+) -> anyhow::Result<Event> {
+    let mut events = extract_verified_events(checkpoint, committee, id.tx_digest)?;
+    let index = usize::try_from(id.event_seq)?;
+    anyhow::ensure!(
+        index < events.len(),
+        "Transaction {} emitted {} events, event {} not found",
+        id.tx_digest,
+        events.len(),
+        id.event_seq
+    );
+    Ok(events.swap_remove(index))
+}
+
+/// Verifies the checkpoint and returns the state of object `id` written by one of its
+/// transactions, after checking that the object is included in the certified effects.
+pub fn extract_verified_object(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    id: ObjectID,
+) -> anyhow::Result<Object> {
+    // The last write of the object in the checkpoint
+    let object = checkpoint
+        .transactions
+        .iter()
+        .rev()
+        .flat_map(|tx| &tx.output_objects)
+        .find(|object| object.id() == id)
+        .ok_or(anyhow!("Object not found in checkpoint"))?;
+    let transaction =
+        extract_verified_transaction(checkpoint, committee, object.previous_transaction)?;
+    verify_object(&transaction.effects, object)?;
+    Ok(object.clone())
+}
+
+/// Verifies the checkpoint and returns the value of the dynamic field of `parent` with the BCS
+/// encoded name `name_bcs` of type `name_type`, as written by one of its transactions.
+pub fn extract_verified_dynamic_field(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    parent: ObjectID,
+    name_type: &TypeTag,
+    name_bcs: &[u8],
+) -> anyhow::Result<Object> {
+    let field_id = derive_dynamic_field_id(parent, name_type, name_bcs)?;
+    let object = extract_verified_object(checkpoint, committee, field_id)?;
+    verify_dynamic_field(&object, parent, name_type, name_bcs)?;
+    Ok(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Read, path::PathBuf, str::FromStr};
+
+    use sui_types::{
+        crypto::AuthorityQuorumSignInfo,
+        message_envelope::Envelope,
+        messages_checkpoint::{CheckpointSummary, FullCheckpointContents},
+    };
+
+    use super::*;
+    use crate::committee_chain::{next_epoch_committee, CommitteeChain};
+
+    const TID: &str = "8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk";
+    const MISSING_TID: &str = "8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk";
+
+    fn read_bcs<T: serde::de::DeserializeOwned>(file: &str) -> T {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("example_config");
+        d.push(file);
+
+        let mut reader = fs::File::open(d.clone()).unwrap();
+        let metadata = fs::metadata(&d).unwrap();
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer).unwrap();
+        bcs::from_bytes(&buffer).unwrap()
+    }
+
+    fn read_data() -> (Committee, CheckpointData) {
+        let checkpoint: Envelope<CheckpointSummary, AuthorityQuorumSignInfo<true>> =
+            read_bcs("20873329.yaml");
+        let committee = next_epoch_committee(&checkpoint).unwrap();
+        let full_checkpoint = read_bcs("20958462.bcs");
+        (committee, full_checkpoint)
+    }
+
+    #[test]
+    fn test_checkpoint_all_good() {
+        let (committee, full_checkpoint) = read_data();
+
+        extract_verified_effects_and_events(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str(TID).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_bad_committee() {
+        let (mut committee, full_checkpoint) = read_data();
+
+        // Change committee
+        committee.epoch += 10;
+
+        assert!(extract_verified_effects_and_events(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str(TID).unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_no_transaction() {
+        let (committee, full_checkpoint) = read_data();
+
+        assert!(extract_verified_effects_and_events(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str(MISSING_TID).unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_bad_contents() {
+        let (committee, mut full_checkpoint) = read_data();
+
+        // Change contents
+        let random_contents = FullCheckpointContents::random_for_testing();
+        full_checkpoint.checkpoint_contents = random_contents.checkpoint_contents();
+
+        assert!(extract_verified_effects_and_events(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str(TID).unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_bad_events() {
+        let (committee, mut full_checkpoint) = read_data();
+
+        let event = full_checkpoint.transactions[4]
+            .events
+            .as_ref()
+            .unwrap()
+            .data[0]
+            .clone();
+
+        for t in &mut full_checkpoint.transactions {
+            if let Some(events) = &mut t.events {
+                events.data.push(event.clone());
+            }
+        }
+
+        assert!(extract_verified_effects_and_events(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str(TID).unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verified_events() {
+        let (committee, full_checkpoint) = read_data();
+        let tid = TransactionDigest::from_str(TID).unwrap();
+
+        let events = extract_verified_events(&full_checkpoint, &committee, tid).unwrap();
+        let expected = full_checkpoint
+            .transactions
+            .iter()
+            .find(|tx| tx.effects.transaction_digest() == &tid)
+            .and_then(|tx| tx.events.as_ref())
+            .map(|events| events.data.clone())
+            .unwrap_or_default();
+        assert_eq!(events, expected);
+
+        for (event_seq, event) in events.iter().enumerate() {
+            let id = EventID {
+                tx_digest: tid,
+                event_seq: event_seq as u64,
+            };
+            let verified = extract_verified_event(&full_checkpoint, &committee, &id).unwrap();
+            assert_eq!(&verified, event);
+        }
+
+        let missing = EventID {
+            tx_digest: tid,
+            event_seq: events.len() as u64,
+        };
+        assert!(extract_verified_event(&full_checkpoint, &committee, &missing).is_err());
+    }
+
+    #[test]
+    fn test_verified_object() {
+        let (committee, full_checkpoint) = read_data();
+        let expected = full_checkpoint.output_objects()[0].clone();
+
+        let object = extract_verified_object(&full_checkpoint, &committee, expected.id()).unwrap();
+        assert_eq!(
+            object.compute_object_reference().0,
+            expected.compute_object_reference().0
+        );
+
+        // The object is not written by any other transaction of the checkpoint
+        let other_effects = full_checkpoint
+            .transactions
+            .iter()
+            .map(|tx| &tx.effects)
+            .find(|effects| effects.transaction_digest() != &object.previous_transaction)
+            .unwrap();
+        assert!(verify_object(other_effects, &object).is_err());
+    }
+
+    #[test]
+    fn test_not_a_dynamic_field() {
+        let (committee, full_checkpoint) = read_data();
+        let object = full_checkpoint.output_objects()[0].clone();
+        let name_type = TypeTag::U64;
+        let name_bcs = bcs::to_bytes(&0u64).unwrap();
+
+        // The object is not a dynamic field of an arbitrary parent
+        assert!(verify_dynamic_field(&object, ObjectID::ZERO, &name_type, &name_bcs).is_err());
+        assert!(extract_verified_dynamic_field(
+            &full_checkpoint,
+            &committee,
+            ObjectID::ZERO,
+            &name_type,
+            &name_bcs
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_committee_chain() {
+        let (committee, full_checkpoint) = read_data();
+        let epoch = committee.epoch;
+        let mut chain = CommitteeChain::new(committee);
+
+        chain
+            .verify_checkpoint_summary(&full_checkpoint.checkpoint_summary)
+            .unwrap();
+        assert!(chain.committee(epoch + 1).is_none());
+        assert!(chain.committee(epoch - 1).is_none());
+
+        // A checkpoint that is not an end-of-epoch checkpoint cannot extend the chain
+        assert!(full_checkpoint
+            .checkpoint_summary
+            .end_of_epoch_data
+            .is_none());
+        assert!(chain
+            .append(full_checkpoint.checkpoint_summary.clone())
+            .is_err());
+        assert_eq!(chain.latest_committee().epoch, epoch);
+        assert!(chain.end_of_epoch_checkpoints().is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::net::IpAddr;
+
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use tracing::{debug, warn};
+
+/// Name of the nftables table, and of the iptables chain, holding the rules of the daemon.
+/// Everything the daemon installs lives there so that it can be removed in one go.
+pub const RULESET_NAME: &str = "sui_nodefw";
+
+/// A blocked source address and destination port pair.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BlockTarget {
+    pub source_address: IpAddr,
+    pub destination_port: u16,
+}
+
+/// A single invocation of a firewall tool. Arguments are passed to the tool directly rather than
+/// through a shell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FirewallCommand {
+    pub program: &'static str,
+    pub args: Vec<String>,
+    /// Failures are expected for some commands, e.g. when removing rules that do not exist yet.
+    pub ignore_failure: bool,
+}
+
+impl FirewallCommand {
+    /// Splits `args` on whitespace, none of the generated arguments contain any.
+    fn new(program: &'static str, args: &str) -> Self {
+        Self {
+            program,
+            args: args.split_whitespace().map(str::to_string).collect(),
+            ignore_failure: false,
+        }
+    }
+
+    fn ignore_failure(mut self) -> Self {
+        self.ignore_failure = true;
+        self
+    }
+}
+
+impl fmt::Display for FirewallCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            // Quote the characters a shell would interpret, so that dry-run output can be pasted
+            if arg.contains([';', '{', '}']) {
+                write!(f, " '{}'", arg)?;
+            } else {
+                write!(f, " {}", arg)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generates the commands that enforce blocks with one of the Linux packet filters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// Rules in a dedicated `inet` table, using sets of address and port pairs.
+    Nftables,
+    /// Rules in a dedicated chain jumped to from `INPUT`, in both `iptables` and `ip6tables`.
+    Iptables,
+}
+
+impl Backend {
+    /// Commands creating the (empty) ruleset. Any state left over by a previous run is removed
+    /// first.
+    pub fn setup(&self) -> Vec<FirewallCommand> {
+        let mut commands = self.teardown();
+        match self {
+            Backend::Nftables => {
+                commands.push(nft(&format!("add table inet {RULESET_NAME}")));
+                commands.push(nft(&format!(
+                    "add chain inet {RULESET_NAME} input \
+                     {{ type filter hook input priority 0 ; policy accept ; }}"
+                )));
+                for (set, address_type, family) in [
+                    ("blocked_v4", "ipv4_addr", "ip"),
+                    ("blocked_v6", "ipv6_addr", "ip6"),
+                ] {
+                    commands.push(nft(&format!(
+                        "add set inet {RULESET_NAME} {set} {{ type {address_type} . inet_service ; }}"
+                    )));
+                    commands.push(nft(&format!(
+                        "add rule inet {RULESET_NAME} input {family} saddr . tcp dport @{set} drop"
+                    )));
+                }
+            }
+            Backend::Iptables => {
+                for program in IPTABLES_PROGRAMS {
+                    commands.push(FirewallCommand::new(program, &format!("-N {RULESET_NAME}")));
+                    commands.push(FirewallCommand::new(
+                        program,
+                        &format!("-I INPUT -j {RULESET_NAME}"),
+                    ));
+                }
+            }
+        }
+        commands
+    }
+
+    /// Commands removing the ruleset, and with it every block.
+    pub fn teardown(&self) -> Vec<FirewallCommand> {
+        match self {
+            Backend::Nftables => {
+                vec![nft(&format!("delete table inet {RULESET_NAME}")).ignore_failure()]
+            }
+            Backend::Iptables => IPTABLES_PROGRAMS
+                .iter()
+                .flat_map(|program| {
+                    [
+                        format!("-D INPUT -j {RULESET_NAME}"),
+                        format!("-F {RULESET_NAME}"),
+                        format!("-X {RULESET_NAME}"),
+                    ]
+                    .map(|args| FirewallCommand::new(program, &args).ignore_failure())
+                })
+                .collect(),
+        }
+    }
+
+    pub fn block(&self, target: &BlockTarget) -> Vec<FirewallCommand> {
+        vec![self.rule_command(target, true)]
+    }
+
+    pub fn unblock(&self, target: &BlockTarget) -> Vec<FirewallCommand> {
+        vec![self.rule_command(target, false)]
+    }
+
+    fn rule_command(&self, target: &BlockTarget, add: bool) -> FirewallCommand {
+        let BlockTarget {
+            source_address,
+            destination_port,
+        } = target;
+        match self {
+            Backend::Nftables => {
+                let set = match source_address {
+                    IpAddr::V4(_) => "blocked_v4",
+                    IpAddr::V6(_) => "blocked_v6",
+                };
+                let action = if add { "add" } else { "delete" };
+                nft(&format!(
+                    "{action} element inet {RULESET_NAME} {set} \
+                     {{ {source_address} . {destination_port} }}"
+                ))
+            }
+            Backend::Iptables => {
+                let program = match source_address {
+                    IpAddr::V4(_) => "iptables",
+                    IpAddr::V6(_) => "ip6tables",
+                };
+                let action = if add { "-A" } else { "-D" };
+                FirewallCommand::new(
+                    program,
+                    &format!(
+                        "{action} {RULESET_NAME} -s {source_address} \
+                         -p tcp --dport {destination_port} -j DROP"
+                    ),
+                )
+            }
+        }
+    }
+}
+
+const IPTABLES_PROGRAMS: [&str; 2] = ["iptables", "ip6tables"];
+
+fn nft(args: &str) -> FirewallCommand {
+    FirewallCommand::new("nft", args)
+}
+
+/// Runs firewall commands, or only prints them in dry-run mode.
+#[derive(Clone, Copy, Debug)]
+pub struct Executor {
+    dry_run: bool,
+}
+
+impl Executor {
+    pub fn new(dry_run: bool) -> Self {
+        Self { dry_run }
+    }
+
+    pub async fn run(&self, commands: &[FirewallCommand]) -> anyhow::Result<()> {
+        for command in commands {
+            if self.dry_run {
+                println!("{command}");
+                continue;
+            }
+            debug!("Running `{command}`");
+            let output = tokio::process::Command::new(command.program)
+                .args(&command.args)
+                .output()
+                .await
+                .with_context(|| format!("failed to run `{command}`"))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stderr = stderr.trim();
+                if command.ignore_failure {
+                    debug!("Ignoring failure of `{command}`: {stderr}");
+                } else {
+                    warn!("`{command}` failed: {stderr}");
+                    bail!("`{command}` exited with {}: {stderr}", output.status);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_generation() {
+        let v4 = BlockTarget {
+            source_address: "10.1.2.3".parse().unwrap(),
+            destination_port: 9000,
+        };
+        let v6 = BlockTarget {
+            source_address: "2001:db8::1".parse().unwrap(),
+            destination_port: 8080,
+        };
+
+        let rules = |commands: Vec<FirewallCommand>| {
+            commands.iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            rules(Backend::Nftables.block(&v4)),
+            vec!["nft add element inet sui_nodefw blocked_v4 '{' 10.1.2.3 . 9000 '}'"]
+        );
+        assert_eq!(
+            rules(Backend::Nftables.unblock(&v6)),
+            vec!["nft delete element inet sui_nodefw blocked_v6 '{' 2001:db8::1 . 8080 '}'"]
+        );
+        assert_eq!(
+            rules(Backend::Iptables.block(&v4)),
+            vec!["iptables -A sui_nodefw -s 10.1.2.3 -p tcp --dport 9000 -j DROP"]
+        );
+        assert_eq!(
+            rules(Backend::Iptables.unblock(&v6)),
+            vec!["ip6tables -D sui_nodefw -s 2001:db8::1 -p tcp --dport 8080 -j DROP"]
+        );
+
+        // Setup starts from a clean slate
+        for backend in [Backend::Nftables, Backend::Iptables] {
+            let teardown = backend.teardown();
+            let setup = backend.setup();
+            assert_eq!(setup[..teardown.len()], teardown[..]);
+            assert!(teardown.iter().all(|command| command.ignore_failure));
+            assert!(setup[teardown.len()..]
+                .iter()
+                .all(|command| !command.ignore_failure));
+        }
+        assert_eq!(
+            rules(Backend::Nftables.setup())[1..3],
+            [
+                "nft add table inet sui_nodefw",
+                "nft add chain inet sui_nodefw input '{' type filter hook input priority 0 ';' policy accept ';' '}'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A firewall daemon implementing the remote firewall protocol used by the traffic controller of
+//! a node (see `RemoteFirewallConfig`), so that blocking can be delegated to the host firewall
+//! without a custom service. Blocks are enforced with nftables or iptables rules.
+
+pub mod backend;
+pub mod server;
+
+pub use backend::{Backend, Executor};
+pub use server::NodeFirewall;
@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Parser;
+use sui_node_firewall::{Backend, Executor, NodeFirewall};
+use telemetry_subscribers::TelemetryConfig;
+use tracing::info;
+
+// Define the `GIT_REVISION` and `VERSION` consts
+bin_version::bin_version!();
+
+/// Enforces the blocks delegated by a node's traffic controller with host firewall rules.
+///
+/// Point `remote-fw-url` in the node's `firewall-config` at the listen address of this daemon.
+#[derive(Parser, Debug)]
+#[clap(name = env!("CARGO_BIN_NAME"), version = VERSION)]
+struct Args {
+    /// Address to serve the firewall api on.
+    #[clap(long, default_value = "127.0.0.1:9110")]
+    listen_address: SocketAddr,
+
+    /// Packet filter used to enforce blocks.
+    #[clap(long, value_enum, default_value = "nftables")]
+    backend: Backend,
+
+    /// Print the firewall commands instead of running them.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// The `drain-path` of the node's `firewall-config`. While this file exists all blocks are
+    /// lifted and new ones are refused.
+    #[clap(long)]
+    drain_path: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let _guard = TelemetryConfig::new().with_env().init();
+    info!("Starting node firewall with {:?}", args);
+
+    let firewall = Arc::new(NodeFirewall::new(
+        args.backend,
+        Executor::new(args.dry_run),
+        args.drain_path,
+    ));
+    firewall.setup().await?;
+
+    firewall
+        .clone()
+        .serve(args.listen_address, shutdown_signal())
+        .await?;
+
+    // Do not leave blocks behind that nothing will lift
+    info!("Shutting down, removing firewall rules");
+    firewall.teardown().await
+}
+
+/// Resolves on Ctrl-C or, e.g. when stopped by systemd, on SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use sui_core::traffic_controller::nodefw_client::{BlockAddress, BlockAddresses};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+use crate::backend::{Backend, BlockTarget, Executor};
+
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Enforces the blocks requested by the traffic controller of a node, speaking the protocol of
+/// `NodeFWClient`. Each block is installed as a firewall rule and removed when its ttl expires.
+pub struct NodeFirewall {
+    backend: Backend,
+    executor: Executor,
+    /// The node touches this file when its dead man's switch trips, after which every block is
+    /// lifted and new ones are refused until the file is removed.
+    drain_path: Option<PathBuf>,
+    /// Block target -> expiry time
+    blocklist: Mutex<BTreeMap<BlockTarget, Instant>>,
+}
+
+impl NodeFirewall {
+    pub fn new(backend: Backend, executor: Executor, drain_path: Option<PathBuf>) -> Self {
+        Self {
+            backend,
+            executor,
+            drain_path,
+            blocklist: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Installs the empty ruleset, removing anything left by a previous run.
+    pub async fn setup(&self) -> anyhow::Result<()> {
+        self.executor.run(&self.backend.setup()).await
+    }
+
+    /// Removes the ruleset and with it every block.
+    pub async fn teardown(&self) -> anyhow::Result<()> {
+        let mut blocklist = self.blocklist.lock().await;
+        blocklist.clear();
+        self.executor.run(&self.backend.teardown()).await
+    }
+
+    fn is_draining(&self) -> bool {
+        self.drain_path.as_ref().is_some_and(|path| path.exists())
+    }
+
+    pub async fn block_addresses(&self, addresses: &[BlockAddress]) -> Result<(), BlockError> {
+        // Validate the whole request before touching the firewall
+        let targets = addresses
+            .iter()
+            .map(|address| {
+                let source_address = address
+                    .source_address
+                    .parse::<IpAddr>()
+                    .map_err(|_| BlockError::InvalidAddress(address.source_address.clone()))?;
+                Ok((
+                    BlockTarget {
+                        source_address,
+                        destination_port: address.destination_port,
+                    },
+                    Duration::from_secs(address.ttl),
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if self.is_draining() {
+            return Err(BlockError::Draining);
+        }
+
+        let mut blocklist = self.blocklist.lock().await;
+        let now = Instant::now();
+        for (target, ttl) in targets {
+            let expiry = now + ttl;
+            match blocklist.get_mut(&target) {
+                // Already enforced, only extend the block
+                Some(current) => *current = (*current).max(expiry),
+                None => {
+                    self.executor
+                        .run(&self.backend.block(&target))
+                        .await
+                        .map_err(BlockError::Firewall)?;
+                    info!(
+                        "Blocked {} on port {} for {:?}",
+                        target.source_address, target.destination_port, ttl
+                    );
+                    blocklist.insert(target, expiry);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The current blocks, with their remaining ttl in seconds.
+    pub async fn list_addresses(&self) -> Vec<BlockAddress> {
+        let blocklist = self.blocklist.lock().await;
+        let now = Instant::now();
+        blocklist
+            .iter()
+            .map(|(target, expiry)| BlockAddress {
+                source_address: target.source_address.to_string(),
+                destination_port: target.destination_port,
+                ttl: expiry.saturating_duration_since(now).as_secs(),
+            })
+            .collect()
+    }
+
+    /// Lifts the blocks that expired, or all of them if the node asked to drain.
+    pub async fn remove_expired(&self) {
+        let draining = self.is_draining();
+        let mut blocklist = self.blocklist.lock().await;
+        if draining && !blocklist.is_empty() {
+            warn!("Drain file present, lifting all {} blocks", blocklist.len());
+        }
+        let now = Instant::now();
+        let expired: Vec<_> = blocklist
+            .iter()
+            .filter(|(_, expiry)| draining || **expiry <= now)
+            .map(|(target, _)| *target)
+            .collect();
+        for target in expired {
+            match self.executor.run(&self.backend.unblock(&target)).await {
+                Ok(()) => {
+                    info!(
+                        "Unblocked {} on port {}",
+                        target.source_address, target.destination_port
+                    );
+                    blocklist.remove(&target);
+                }
+                // Keep the entry so that removal is retried on the next check
+                Err(e) => error!("Failed to unblock {target:?}: {e:?}"),
+            }
+        }
+    }
+
+    pub fn into_router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/list_addresses", get(list_addresses))
+            .route("/block_addresses", post(block_addresses))
+            .with_state(self)
+    }
+
+    /// Serves the firewall api on `address` and lifts expired blocks until `shutdown` resolves.
+    pub async fn serve(
+        self: Arc<Self>,
+        address: SocketAddr,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> anyhow::Result<()> {
+        let server =
+            axum::Server::try_bind(&address)?.serve(self.clone().into_router().into_make_service());
+        info!("Node firewall listening on {}", server.local_addr());
+
+        let firewall = self.clone();
+        let expiry_task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EXPIRY_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                firewall.remove_expired().await;
+            }
+        });
+
+        let result = server.with_graceful_shutdown(shutdown).await;
+        expiry_task.abort();
+        Ok(result?)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BlockError {
+    #[error("invalid source address {0:?}")]
+    InvalidAddress(String),
+    #[error("firewall is draining")]
+    Draining,
+    #[error("failed to update firewall: {0:?}")]
+    Firewall(anyhow::Error),
+}
+
+async fn list_addresses(State(firewall): State<Arc<NodeFirewall>>) -> Json<BlockAddresses> {
+    Json(BlockAddresses {
+        addresses: firewall.list_addresses().await,
+    })
+}
+
+async fn block_addresses(
+    State(firewall): State<Arc<NodeFirewall>>,
+    Json(addresses): Json<BlockAddresses>,
+) -> (StatusCode, String) {
+    match firewall.block_addresses(&addresses.addresses).await {
+        Ok(()) => (StatusCode::CREATED, "created".to_string()),
+        Err(e) => {
+            let status = match e {
+                BlockError::InvalidAddress(_) => StatusCode::BAD_REQUEST,
+                BlockError::Draining => StatusCode::SERVICE_UNAVAILABLE,
+                BlockError::Firewall(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_core::traffic_controller::nodefw_client::NodeFWClient;
+
+    fn block(source_address: &str, ttl: u64) -> BlockAddress {
+        BlockAddress {
+            source_address: source_address.to_string(),
+            destination_port: 9000,
+            ttl,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_block_and_expire() {
+        let firewall = NodeFirewall::new(Backend::Nftables, Executor::new(true), None);
+        firewall
+            .block_addresses(&[block("10.0.0.1", 10), block("2001:db8::1", 20)])
+            .await
+            .unwrap();
+        // Blocking again extends, but never shortens, a block
+        firewall
+            .block_addresses(&[block("10.0.0.1", 30), block("2001:db8::1", 5)])
+            .await
+            .unwrap();
+        assert!(matches!(
+            firewall.block_addresses(&[block("10.0.0.1:80", 10)]).await,
+            Err(BlockError::InvalidAddress(_))
+        ));
+
+        tokio::time::advance(Duration::from_secs(25)).await;
+        firewall.remove_expired().await;
+        let remaining = firewall.list_addresses().await;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].source_address, "10.0.0.1");
+        assert_eq!(remaining[0].ttl, 5);
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        firewall.remove_expired().await;
+        assert!(firewall.list_addresses().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_drain() {
+        let dir = tempfile::tempdir().unwrap();
+        let drain_path = dir.path().join("drain");
+        let firewall = NodeFirewall::new(
+            Backend::Iptables,
+            Executor::new(true),
+            Some(drain_path.clone()),
+        );
+        firewall
+            .block_addresses(&[block("10.0.0.1", 60)])
+            .await
+            .unwrap();
+
+        std::fs::File::create(&drain_path).unwrap();
+        firewall.remove_expired().await;
+        assert!(firewall.list_addresses().await.is_empty());
+        assert!(matches!(
+            firewall.block_addresses(&[block("10.0.0.1", 60)]).await,
+            Err(BlockError::Draining)
+        ));
+
+        std::fs::remove_file(&drain_path).unwrap();
+        firewall
+            .block_addresses(&[block("10.0.0.1", 60)])
+            .await
+            .unwrap();
+        assert_eq!(firewall.list_addresses().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_node_fw_client() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let firewall = Arc::new(NodeFirewall::new(
+            Backend::Nftables,
+            Executor::new(true),
+            None,
+        ));
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(firewall.serve(address, async {
+            shutdown_rx.await.ok();
+        }));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let client = NodeFWClient::new(format!("http://{address}"));
+        client
+            .block_addresses(BlockAddresses {
+                addresses: vec![block("10.0.0.1", 60)],
+            })
+            .await
+            .unwrap();
+        let listed = client.list_addresses().await.unwrap().addresses;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].source_address, "10.0.0.1");
+        assert!(client
+            .block_addresses(BlockAddresses {
+                addresses: vec![block("not-an-ip", 60)],
+            })
+            .await
+            .is_err());
+
+        shutdown_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+}
@@ -6,13 +6,17 @@ use axum::{
     extract::{Query, State},
     http::StatusCode,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use humantime::parse_duration;
 use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use sui_config::certificate_deny_config::CertificateDenyConfig;
+use sui_config::transaction_deny_config::TransactionDenyConfig;
+use sui_core::transaction_signing_filter::DenyConfigFile;
 use sui_types::error::SuiError;
+use sui_types::traffic_control::PolicyConfig;
 use telemetry_subscribers::TracingHandle;
 use tracing::info;
 
@@ -47,6 +51,32 @@ use tracing::info;
 // Reset tracing to the TRACE_FILTER env var.
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/reset-tracing'
+//
+// View the traffic control policy config currently in effect:
+//
+//   $ curl 'http://127.0.0.1:1337/traffic-control'
+//
+// Replace the traffic control policy config without restarting the node. Fields
+// omitted from the body are reset to their defaults, so start from the output of
+// the command above:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/traffic-control' -H 'Content-Type: application/json' \
+//       -d '{"dry-run": false, "denylist": ["10.0.0.0/8"], "method-policies": {"suix_getOwnedObjects": {"rate-limit": {"capacity": 100, "refill-per-sec": 10}}}}'
+//
+// View the transaction and certificate deny configs currently in effect:
+//
+//   $ curl 'http://127.0.0.1:1337/deny-config'
+//
+// Replace the transaction deny config without restarting the node. The change is not
+// persisted, use `deny-config-path` in the node config for that:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/transaction-deny-config' -H 'Content-Type: application/json' \
+//       -d '{"package-deny-list": ["0x1234"], "shared-object-disabled": true}'
+//
+// Replace the certificate deny config without restarting the node:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/certificate-deny-config' -H 'Content-Type: application/json' \
+//       -d '{"certificate-deny-list": ["<transaction digest>"]}'
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -56,6 +86,10 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const TRAFFIC_CONTROL: &str = "/traffic-control";
+const DENY_CONFIG: &str = "/deny-config";
+const TRANSACTION_DENY_CONFIG: &str = "/transaction-deny-config";
+const CERTIFICATE_DENY_CONFIG: &str = "/certificate-deny-config";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -74,6 +108,8 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(TRAFFIC_CONTROL, get(get_traffic_control_policy))
+        .route(DENY_CONFIG, get(get_deny_config))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -86,6 +122,9 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
         .route(TRACING_ROUTE, post(enable_tracing))
         .route(TRACING_RESET_ROUTE, post(reset_tracing))
+        .route(TRAFFIC_CONTROL, post(set_traffic_control_policy))
+        .route(TRANSACTION_DENY_CONFIG, post(set_transaction_deny_config))
+        .route(CERTIFICATE_DENY_CONFIG, post(set_certificate_deny_config))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -216,6 +255,70 @@ async fn node_config(State(state): State<Arc<AppState>>) -> (StatusCode, String)
     (StatusCode::OK, format!("{:#?}\n", node_config))
 }
 
+async fn get_traffic_control_policy(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<PolicyConfig>, (StatusCode, String)> {
+    match state.node.traffic_controller() {
+        Some(traffic_controller) => Ok(Json(traffic_controller.policy_config())),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            "traffic control is not enabled on this node\n".to_string(),
+        )),
+    }
+}
+
+async fn set_traffic_control_policy(
+    State(state): State<Arc<AppState>>,
+    Json(policy_config): Json<PolicyConfig>,
+) -> (StatusCode, String) {
+    // Traffic control can only be reconfigured, not enabled, at runtime as
+    // the servers decide whether to consult a controller at startup
+    let Some(traffic_controller) = state.node.traffic_controller() else {
+        return (
+            StatusCode::NOT_FOUND,
+            "traffic control is not enabled on this node\n".to_string(),
+        );
+    };
+    traffic_controller.update_policy_config(policy_config);
+    info!("Traffic control policy updated via admin interface");
+    (
+        StatusCode::OK,
+        "traffic control policy updated\n".to_string(),
+    )
+}
+
+async fn get_deny_config(State(state): State<Arc<AppState>>) -> Json<DenyConfigFile> {
+    let authority_state = state.node.state();
+    Json(DenyConfigFile {
+        transaction_deny_config: Some(authority_state.transaction_deny_config().as_ref().clone()),
+        certificate_deny_config: Some(authority_state.certificate_deny_config().as_ref().clone()),
+    })
+}
+
+async fn set_transaction_deny_config(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<TransactionDenyConfig>,
+) -> (StatusCode, String) {
+    state.node.update_transaction_deny_config(config);
+    info!("Transaction deny config updated via admin interface");
+    (
+        StatusCode::OK,
+        "transaction deny config updated\n".to_string(),
+    )
+}
+
+async fn set_certificate_deny_config(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<CertificateDenyConfig>,
+) -> (StatusCode, String) {
+    state.node.update_certificate_deny_config(config);
+    info!("Certificate deny config updated via admin interface");
+    (
+        StatusCode::OK,
+        "certificate deny config updated\n".to_string(),
+    )
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
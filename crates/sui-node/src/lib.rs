@@ -32,6 +32,7 @@ use sui_core::execution_cache::build_execution_cache;
 use sui_core::state_accumulator::StateAccumulatorMetrics;
 use sui_core::storage::RestReadStore;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
+use sui_core::traffic_controller::TrafficController;
 use sui_json_rpc::bridge_api::BridgeReadApi;
 use sui_json_rpc_api::JsonRpcMetrics;
 use sui_network::randomness;
@@ -39,6 +40,7 @@ use sui_rest_api::RestMetrics;
 use sui_types::base_types::ConciseableName;
 use sui_types::crypto::RandomnessRound;
 use sui_types::digests::ChainIdentifier;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_system_state::SuiSystemState;
 use tap::tap::TapFallible;
 use tokio::runtime::Handle;
@@ -58,9 +60,11 @@ use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
 use sui_archival::reader::ArchiveReaderBalancer;
 use sui_archival::writer::ArchiveWriter;
+use sui_config::certificate_deny_config::CertificateDenyConfig;
 use sui_config::node::{DBCheckpointConfig, RunWithRange};
 use sui_config::node_config_metrics::NodeConfigMetrics;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_config::{ConsensusConfig, NodeConfig};
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
@@ -100,6 +104,7 @@ use sui_core::{
 };
 use sui_json_rpc::coin_api::CoinReadApi;
 use sui_json_rpc::governance_api::GovernanceReadApi;
+use sui_json_rpc::grpc_api::GrpcReadApi;
 use sui_json_rpc::indexer_api::IndexerApi;
 use sui_json_rpc::move_utils::MoveUtils;
 use sui_json_rpc::read_api::ReadApi;
@@ -222,6 +227,10 @@ pub struct SuiNode {
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
     metrics: Arc<SuiNodeMetrics>,
+    /// Shared by the JSON-RPC server on fullnodes and the gRPC validator
+    /// service on validators, so that its policy can be reloaded at runtime.
+    traffic_controller: Option<Arc<TrafficController>>,
+    node_config_metrics: Arc<NodeConfigMetrics>,
 
     _discovery: discovery::Handle,
     state_sync_handle: state_sync::Handle,
@@ -236,6 +245,10 @@ pub struct SuiNode {
     /// Broadcast channel to notify state-sync for new validator peers.
     trusted_peer_change_tx: watch::Sender<TrustedPeerChangeEvent>,
 
+    /// Broadcast channel of the sequence numbers of executed checkpoints, e.g. to push them to the
+    /// checkpoint subscriptions of the REST API.
+    executed_checkpoint_channel: broadcast::Sender<CheckpointSequenceNumber>,
+
     _db_checkpoint_handle: Option<tokio::sync::broadcast::Sender<()>>,
 
     #[cfg(msim)]
@@ -408,7 +421,8 @@ impl SuiNode {
         custom_rpc_runtime: Option<Handle>,
         software_version: &'static str,
     ) -> Result<Arc<SuiNode>> {
-        NodeConfigMetrics::new(&registry_service.default_registry()).record_metrics(&config);
+        let node_config_metrics = NodeConfigMetrics::new(&registry_service.default_registry());
+        node_config_metrics.record_metrics(&config);
         let mut config = config.clone();
         if config.supported_protocol_versions.is_none() {
             info!(
@@ -702,6 +716,18 @@ impl SuiNode {
             None
         };
 
+        // Subscribers that fall behind catch up by reading the checkpoint store, so the channel only
+        // needs to buffer a few notifications.
+        let (executed_checkpoint_channel, _) = broadcast::channel(16);
+
+        let traffic_controller = config.policy_config.clone().map(|policy| {
+            Arc::new(TrafficController::spawn(
+                policy,
+                TrafficControllerMetrics::new(&prometheus_registry),
+                config.firewall_config.clone(),
+            ))
+        });
+
         let http_server = build_http_server(
             state.clone(),
             state_sync_store,
@@ -710,6 +736,8 @@ impl SuiNode {
             &prometheus_registry,
             custom_rpc_runtime,
             software_version,
+            traffic_controller.clone(),
+            &executed_checkpoint_channel,
         )
         .await?;
 
@@ -757,6 +785,7 @@ impl SuiNode {
                 connection_monitor_status.clone(),
                 &registry_service,
                 sui_node_metrics.clone(),
+                traffic_controller.clone(),
             )
             .await?;
             // This is only needed during cold start.
@@ -778,6 +807,8 @@ impl SuiNode {
             transaction_orchestrator,
             registry_service,
             metrics: sui_node_metrics,
+            traffic_controller,
+            node_config_metrics,
 
             _discovery: discovery_handle,
             state_sync_handle,
@@ -787,6 +818,7 @@ impl SuiNode {
             end_of_epoch_channel,
             connection_monitor_status,
             trusted_peer_change_tx,
+            executed_checkpoint_channel,
 
             _db_checkpoint_handle: db_checkpoint_handle,
 
@@ -798,6 +830,17 @@ impl SuiNode {
             shutdown_channel_tx: shutdown_channel,
         };
 
+        if let Some(deny_config_path) = node.config.deny_config_path.clone() {
+            let node_config_metrics = node.node_config_metrics.clone();
+            spawn_monitored_task!(node.state.transaction_signing_filter().clone().watch_file(
+                deny_config_path,
+                move |filter| {
+                    node_config_metrics
+                        .record_transaction_deny_config(&filter.transaction_deny_config());
+                }
+            ));
+        }
+
         info!("SuiNode started!");
         let node = Arc::new(node);
         let node_copy = node.clone();
@@ -899,6 +942,7 @@ impl SuiNode {
                 &config.snapshot_path(),
                 remote_store_config.clone(),
                 60,
+                config.state_snapshot_write_config.full_snapshot_interval,
                 prometheus_registry,
                 checkpoint_store,
             )?;
@@ -1119,6 +1163,7 @@ impl SuiNode {
         connection_monitor_status: Arc<ConnectionMonitorStatus>,
         registry_service: &RegistryService,
         sui_node_metrics: Arc<SuiNodeMetrics>,
+        traffic_controller: Option<Arc<TrafficController>>,
     ) -> Result<ValidatorComponents> {
         let mut config_clone = config.clone();
         let consensus_config = config_clone
@@ -1154,6 +1199,7 @@ impl SuiNode {
             state.clone(),
             consensus_adapter.clone(),
             &registry_service.default_registry(),
+            traffic_controller,
         )
         .await?;
 
@@ -1380,14 +1426,13 @@ impl SuiNode {
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
         prometheus_registry: &Registry,
+        traffic_controller: Option<Arc<TrafficController>>,
     ) -> Result<tokio::task::JoinHandle<Result<()>>> {
-        let validator_service = ValidatorService::new(
+        let validator_service = ValidatorService::new_with_traffic_controller(
             state.clone(),
             consensus_adapter,
             Arc::new(ValidatorServiceMetrics::new(prometheus_registry)),
-            TrafficControllerMetrics::new(prometheus_registry),
-            config.policy_config.clone(),
-            config.firewall_config.clone(),
+            traffic_controller,
         );
 
         let mut server_conf = mysten_network::config::Config::new();
@@ -1413,6 +1458,28 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn traffic_controller(&self) -> Option<Arc<TrafficController>> {
+        self.traffic_controller.clone()
+    }
+
+    /// Replaces the transaction deny config used when signing transactions, without
+    /// restarting the node. Does not persist across restarts.
+    pub fn update_transaction_deny_config(&self, config: TransactionDenyConfig) {
+        self.node_config_metrics
+            .record_transaction_deny_config(&config);
+        self.state
+            .transaction_signing_filter()
+            .set_transaction_deny_config(config);
+    }
+
+    /// Replaces the certificate deny config used when executing certificates, without
+    /// restarting the node. Does not persist across restarts.
+    pub fn update_certificate_deny_config(&self, config: CertificateDenyConfig) {
+        self.state
+            .transaction_signing_filter()
+            .set_certificate_deny_config(config);
+    }
+
     // Only used for testing because of how epoch store is loaded.
     pub fn reference_gas_price_for_testing(&self) -> Result<u64, anyhow::Error> {
         self.state.reference_gas_price_for_testing()
@@ -1472,6 +1539,7 @@ impl SuiNode {
                 accumulator.clone(),
                 self.config.checkpoint_executor_config.clone(),
                 checkpoint_executor_metrics.clone(),
+                Some(self.executed_checkpoint_channel.clone()),
             );
 
             let run_with_range = self.config.run_with_range;
@@ -1686,6 +1754,7 @@ impl SuiNode {
                             self.connection_monitor_status.clone(),
                             &self.registry_service,
                             self.metrics.clone(),
+                            self.traffic_controller.clone(),
                         )
                         .await?,
                     )
@@ -1880,6 +1949,8 @@ pub async fn build_http_server(
     prometheus_registry: &Registry,
     _custom_runtime: Option<Handle>,
     software_version: &'static str,
+    traffic_controller: Option<Arc<TrafficController>>,
+    executed_checkpoint_channel: &broadcast::Sender<CheckpointSequenceNumber>,
 ) -> Result<Option<tokio::task::JoinHandle<()>>> {
     // Validators do not expose these APIs
     if config.consensus_config().is_some() {
@@ -1887,6 +1958,7 @@ pub async fn build_http_server(
     }
 
     let mut router = axum::Router::new();
+    let mut grpc_router = None;
 
     let json_rpc_router = {
         let mut server = JsonRpcServerBuilder::new(
@@ -1895,6 +1967,9 @@ pub async fn build_http_server(
             config.policy_config.clone(),
             config.firewall_config.clone(),
         );
+        if let Some(traffic_controller) = &traffic_controller {
+            server.set_traffic_controller(traffic_controller.clone());
+        }
 
         let kv_store = build_kv_store(&state, config, prometheus_registry)?;
 
@@ -1910,6 +1985,17 @@ pub async fn build_http_server(
             metrics.clone(),
         ))?;
 
+        if config.enable_grpc_read_api {
+            let mut grpc_read_api = GrpcReadApi::new(
+                ReadApi::new(state.clone(), kv_store.clone(), metrics.clone()),
+                CoinReadApi::new(state.clone(), kv_store.clone(), metrics.clone()),
+            );
+            if let Some(traffic_controller) = traffic_controller {
+                grpc_read_api = grpc_read_api.with_traffic_controller(traffic_controller);
+            }
+            grpc_router = Some(grpc_read_api.into_router());
+        }
+
         // if run_with_range is enabled we want to prevent any transactions
         // run_with_range = None is normal operating conditions
         if config.run_with_range.is_none() {
@@ -1958,6 +2044,10 @@ pub async fn build_http_server(
 
     router = router.merge(json_rpc_router);
 
+    if let Some(grpc_router) = grpc_router {
+        router = router.merge(grpc_router);
+    }
+
     if config.enable_experimental_rest_api {
         let mut rest_service = sui_rest_api::RestService::new(
             Arc::new(RestReadStore::new(state, store)),
@@ -1965,6 +2055,7 @@ pub async fn build_http_server(
         );
 
         rest_service.with_metrics(RestMetrics::new(prometheus_registry));
+        rest_service.with_executed_checkpoints(executed_checkpoint_channel.clone());
 
         if let Some(transaction_orchestrator) = transaction_orchestrator {
             rest_service.with_executor(transaction_orchestrator.clone())
@@ -153,6 +153,7 @@ const MAX_PROTOCOL_VERSION: u64 = 52;
 //             Enable soft bundle in devnet and testnet.
 //             Core macro visibility in sui core framework.
 //             Enable Mysticeti on mainnet.
+//             Enable consensus DAG garbage collection in devnet.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -1178,6 +1179,11 @@ pub struct ProtocolConfig {
 
     /// The max number of transactions that can be included in a single Soft Bundle.
     max_soft_bundle_size: Option<u64>,
+
+    /// The number of rounds below the last committed leader round after which consensus blocks
+    /// are garbage collected: they are no longer committed and no longer needed to accept new
+    /// blocks. Garbage collection is disabled when not set.
+    consensus_gc_depth: Option<u32>,
 }
 
 // feature flags
@@ -1474,6 +1480,11 @@ impl ProtocolConfig {
     pub fn soft_bundle(&self) -> bool {
         self.feature_flags.soft_bundle
     }
+
+    /// Depth of the consensus DAG garbage collection, 0 when it is disabled.
+    pub fn gc_depth(&self) -> u32 {
+        self.consensus_gc_depth.unwrap_or(0)
+    }
 }
 
 #[cfg(not(msim))]
@@ -1939,6 +1950,8 @@ impl ProtocolConfig {
             checkpoint_summary_version_specific_data: None,
 
             max_soft_bundle_size: None,
+
+            consensus_gc_depth: None,
             // When adding a new constant, set it to None in the earliest version, like this:
             // new_constant: None,
         };
@@ -2466,6 +2479,11 @@ impl ProtocolConfig {
                     }
 
                     cfg.feature_flags.consensus_choice = ConsensusChoice::Mysticeti;
+
+                    // Garbage collect the consensus DAG in devnet.
+                    if chain != Chain::Testnet && chain != Chain::Mainnet {
+                        cfg.consensus_gc_depth = Some(60);
+                    }
                 }
                 // Use this template when making changes:
                 //
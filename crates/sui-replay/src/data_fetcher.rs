@@ -3,15 +3,26 @@
 
 use crate::types::ReplayEngineError;
 use crate::types::EPOCH_CHANGE_STRUCT_TAG;
+use crate::types::{MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD};
 use async_trait::async_trait;
 use futures::future::join_all;
 use lru::LruCache;
+use move_core_types::language_storage::StructTag;
 use move_core_types::parser::parse_struct_tag;
 use parking_lot::RwLock;
 use rand::Rng;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
 use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use sui_archival::reader::{ArchiveReader, ArchiveReaderMetrics};
+use sui_archival::{FileType, MANIFEST_FILENAME};
+use sui_config::node::ArchiveReaderConfig;
+use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::NodeStateDump;
 use sui_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
 use sui_json_rpc_types::EventFilter;
@@ -21,15 +32,26 @@ use sui_json_rpc_types::SuiObjectData;
 use sui_json_rpc_types::SuiObjectDataOptions;
 use sui_json_rpc_types::SuiObjectResponse;
 use sui_json_rpc_types::SuiPastObjectResponse;
+use sui_json_rpc_types::SuiTransactionBlock;
+use sui_json_rpc_types::SuiTransactionBlockEffects;
 use sui_json_rpc_types::SuiTransactionBlockResponse;
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
-use sui_sdk::SuiClient;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_storage::blob::Blob;
 use sui_types::base_types::{ObjectID, SequenceNumber, VersionNumber};
-use sui_types::digests::TransactionDigest;
+use sui_types::digests::{ChainIdentifier, CheckpointDigest, TransactionDigest};
+use sui_types::effects::{TransactionEffects, TransactionEvents};
+use sui_types::event::{EventID, SystemEpochInfoEvent};
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::in_memory_storage::InMemoryStorage;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
+use sui_types::storage::{ReadStore, SharedInMemoryStore};
 use sui_types::transaction::SenderSignedData;
 use sui_types::transaction::TransactionDataAPI;
-use sui_types::transaction::{EndOfEpochTransactionKind, TransactionKind};
+use sui_types::transaction::{EndOfEpochTransactionKind, Transaction, TransactionKind};
+use tokio::sync::Mutex;
+use tracing::info;
 
 /// This trait defines the interfaces for fetching data from some local or remote store
 #[async_trait]
@@ -96,13 +118,16 @@ pub(crate) trait DataFetcher {
 pub enum Fetchers {
     Remote(RemoteFetcher),
     NodeStateDump(NodeStateDumpFetcher),
+    LocalArchive(LocalArchiveFetcher),
 }
 
 impl Fetchers {
     pub fn as_remote(&self) -> &RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::LocalArchive(_) => {
+                panic!("not a remote fetcher")
+            }
         }
     }
 
@@ -114,13 +139,17 @@ impl Fetchers {
                 q.clear_cache_for_new_task();
                 q
             }
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::LocalArchive(_) => {
+                panic!("not a remote fetcher")
+            }
         }
     }
 
     pub fn as_node_state_dump(&self) -> &NodeStateDumpFetcher {
         match self {
-            Fetchers::Remote(_) => panic!("not a node state dump fetcher"),
+            Fetchers::Remote(_) | Fetchers::LocalArchive(_) => {
+                panic!("not a node state dump fetcher")
+            }
             Fetchers::NodeStateDump(q) => q,
         }
     }
@@ -136,6 +165,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_versioned(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_versioned(objects).await,
+            Fetchers::LocalArchive(q) => q.multi_get_versioned(objects).await,
         }
     }
 
@@ -146,6 +176,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_latest(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_latest(objects).await,
+            Fetchers::LocalArchive(q) => q.multi_get_latest(objects).await,
         }
     }
 
@@ -156,6 +187,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_checkpoint_txs(id).await,
             Fetchers::NodeStateDump(q) => q.get_checkpoint_txs(id).await,
+            Fetchers::LocalArchive(q) => q.get_checkpoint_txs(id).await,
         }
     }
 
@@ -166,6 +198,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_transaction(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_transaction(tx_digest).await,
+            Fetchers::LocalArchive(q) => q.get_transaction(tx_digest).await,
         }
     }
 
@@ -176,6 +209,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_loaded_child_objects(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_loaded_child_objects(tx_digest).await,
+            Fetchers::LocalArchive(q) => q.get_loaded_child_objects(tx_digest).await,
         }
     }
 
@@ -183,6 +217,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_latest_checkpoint_sequence_number().await,
             Fetchers::NodeStateDump(q) => q.get_latest_checkpoint_sequence_number().await,
+            Fetchers::LocalArchive(q) => q.get_latest_checkpoint_sequence_number().await,
         }
     }
 
@@ -200,6 +235,10 @@ impl DataFetcher for Fetchers {
                 q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
                     .await
             }
+            Fetchers::LocalArchive(q) => {
+                q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
+                    .await
+            }
         }
     }
 
@@ -210,6 +249,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
+            Fetchers::LocalArchive(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
         }
     }
 
@@ -220,12 +260,14 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_change_events(reverse).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_change_events(reverse).await,
+            Fetchers::LocalArchive(q) => q.get_epoch_change_events(reverse).await,
         }
     }
     async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
         match self {
             Fetchers::Remote(q) => q.get_chain_id().await,
             Fetchers::NodeStateDump(q) => q.get_chain_id().await,
+            Fetchers::LocalArchive(q) => q.get_chain_id().await,
         }
     }
     async fn get_child_object(
@@ -236,6 +278,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_child_object(object_id, version_upper_bound).await,
             Fetchers::NodeStateDump(q) => q.get_child_object(object_id, version_upper_bound).await,
+            Fetchers::LocalArchive(q) => q.get_child_object(object_id, version_upper_bound).await,
         }
     }
 }
@@ -824,3 +867,760 @@ impl DataFetcher for NodeStateDumpFetcher {
         unimplemented!("get child object is not implemented for state dump");
     }
 }
+
+const ARCHIVE_CHECKPOINT_CACHE_CAPACITY: Option<NonZeroUsize> = NonZeroUsize::new(100);
+
+/// Fetches data from a local directory of checkpoints, in either of two formats:
+/// - written by `sui-data-ingestion`: one Blob encoded `CheckpointData` per checkpoint, in a file
+///   named `<sequence number>.chk`. These carry the events and the input and output objects of
+///   every transaction, so replay can run from them alone.
+/// - written by `sui-archival`: a manifest listing summary and content files that each cover a
+///   range of checkpoints. These only carry transactions and effects, so objects and epoch
+///   information are fetched from the backup RPC endpoint, which is required for this format.
+///
+/// Only the sequence numbers of the checkpoints are listed up front. Checkpoints are read when
+/// they are first needed, highest first when searching, and the transactions and objects they
+/// hold are indexed as they are read, keeping only the most recently read checkpoints in memory.
+/// Anything the archive does not hold, such as history before its first checkpoint, is fetched
+/// from the backup RPC endpoint, so without one the archive has to start from genesis.
+#[derive(Clone)]
+pub struct LocalArchiveFetcher {
+    archive: Arc<LocalArchive>,
+    /// Used to fetch anything the archive does not hold
+    pub backup_remote_fetcher: Option<RemoteFetcher>,
+}
+
+struct LocalArchive {
+    path: PathBuf,
+    source: CheckpointSource,
+    /// Sequence number of every checkpoint in the archive
+    sequence_numbers: BTreeSet<CheckpointSequenceNumber>,
+    epoch_change_tag: StructTag,
+    index: Mutex<ArchiveIndex>,
+}
+
+enum CheckpointSource {
+    /// Checkpoint files written by `sui-data-ingestion`, by sequence number
+    Blobs(BTreeMap<CheckpointSequenceNumber, PathBuf>),
+    /// Archive written by `sui-archival`, with the range of checkpoints in each of its files, by
+    /// the first sequence number in the range
+    Archival {
+        reader: ArchiveReader,
+        files: BTreeMap<CheckpointSequenceNumber, Range<CheckpointSequenceNumber>>,
+    },
+}
+
+struct ArchiveIndex {
+    /// Checkpoints that have not been read yet
+    unread: BTreeSet<CheckpointSequenceNumber>,
+    /// The most recently read checkpoints
+    checkpoints: LruCache<CheckpointSequenceNumber, Arc<ArchivedCheckpoint>>,
+    /// Checkpoint that every transaction read so far is in
+    transactions: HashMap<TransactionDigest, CheckpointSequenceNumber>,
+    /// Checkpoint that every object version read so far was found in
+    objects: BTreeMap<(ObjectID, SequenceNumber), CheckpointSequenceNumber>,
+    /// Epoch change events read so far, by the checkpoint they were emitted in
+    epoch_change_events: BTreeMap<CheckpointSequenceNumber, Vec<SuiEvent>>,
+    /// Epoch start timestamp and reference gas price, keyed by the epoch in the epoch change event
+    epoch_info: BTreeMap<u64, (u64, u64)>,
+    /// Latest version of every package read so far, used to resolve types when rendering
+    /// transactions
+    packages: InMemoryStorage,
+}
+
+struct ArchivedCheckpoint {
+    digest: CheckpointDigest,
+    timestamp_ms: u64,
+    transactions: Vec<ArchivedTransaction>,
+}
+
+struct ArchivedTransaction {
+    transaction: Transaction,
+    effects: TransactionEffects,
+    /// Only stored in `sui-data-ingestion` checkpoints
+    events: Option<TransactionEvents>,
+    /// Input and output objects, only stored in `sui-data-ingestion` checkpoints
+    objects: Vec<Object>,
+}
+
+impl LocalArchiveFetcher {
+    /// Open the archive at `path`, without reading any of its checkpoints yet. If
+    /// `backup_rpc_url` is given, anything the archive does not hold is fetched from it.
+    pub async fn new(
+        path: &Path,
+        backup_rpc_url: Option<String>,
+    ) -> Result<Self, ReplayEngineError> {
+        let backup_remote_fetcher = match backup_rpc_url {
+            Some(url) => Some(RemoteFetcher::new(
+                SuiClientBuilder::default()
+                    .request_timeout(RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD)
+                    .max_concurrent_requests(MAX_CONCURRENT_REQUESTS)
+                    .build(url)
+                    .await?,
+            )),
+            None => None,
+        };
+
+        let source = if path.join(MANIFEST_FILENAME).exists() {
+            if backup_remote_fetcher.is_none() {
+                return Err(archive_error(
+                    path,
+                    "sui-archival archives do not store objects, an RPC endpoint is needed to \
+                     replay from them",
+                ));
+            }
+            CheckpointSource::archival(path).await?
+        } else {
+            CheckpointSource::blobs(path)?
+        };
+        let sequence_numbers = source.sequence_numbers();
+        if sequence_numbers.is_empty() {
+            return Err(archive_error(path, "no checkpoint files found"));
+        }
+        info!(
+            "Found {} checkpoints in {}",
+            sequence_numbers.len(),
+            path.display()
+        );
+
+        let index = ArchiveIndex {
+            unread: sequence_numbers.clone(),
+            checkpoints: LruCache::new(
+                ARCHIVE_CHECKPOINT_CACHE_CAPACITY.expect("Cache size must be non zero"),
+            ),
+            transactions: HashMap::new(),
+            objects: BTreeMap::new(),
+            epoch_change_events: BTreeMap::new(),
+            epoch_info: BTreeMap::new(),
+            packages: InMemoryStorage::default(),
+        };
+        Ok(Self {
+            archive: Arc::new(LocalArchive {
+                path: path.to_path_buf(),
+                source,
+                sequence_numbers,
+                epoch_change_tag: parse_struct_tag(EPOCH_CHANGE_STRUCT_TAG)?,
+                index: Mutex::new(index),
+            }),
+            backup_remote_fetcher,
+        })
+    }
+}
+
+impl CheckpointSource {
+    fn blobs(path: &Path) -> Result<Self, ReplayEngineError> {
+        let mut files = BTreeMap::new();
+        for entry in fs::read_dir(path).map_err(|e| archive_error(path, e))? {
+            let file = entry.map_err(|e| archive_error(path, e))?.path();
+            if file.extension().and_then(|ext| ext.to_str()) != Some("chk") {
+                continue;
+            }
+            if let Some(sequence_number) = file
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<CheckpointSequenceNumber>().ok())
+            {
+                files.insert(sequence_number, file);
+            }
+        }
+        Ok(Self::Blobs(files))
+    }
+
+    async fn archival(path: &Path) -> Result<Self, ReplayEngineError> {
+        let config = ArchiveReaderConfig {
+            remote_store_config: ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(path.to_path_buf()),
+                ..Default::default()
+            },
+            download_concurrency: NonZeroUsize::new(1).unwrap(),
+            use_for_pruning_watermark: false,
+        };
+        let metrics = ArchiveReaderMetrics::new(&prometheus::Registry::new());
+        let reader = ArchiveReader::new(config, &metrics).map_err(|e| archive_error(path, e))?;
+        reader
+            .sync_manifest_once()
+            .await
+            .map_err(|e| archive_error(path, e))?;
+        let files = reader
+            .get_manifest()
+            .await
+            .map_err(|e| archive_error(path, e))?
+            .files()
+            .into_iter()
+            .filter(|file| file.file_type == FileType::CheckpointSummary)
+            .map(|file| (file.checkpoint_seq_range.start, file.checkpoint_seq_range))
+            .collect();
+        Ok(Self::Archival { reader, files })
+    }
+
+    fn sequence_numbers(&self) -> BTreeSet<CheckpointSequenceNumber> {
+        match self {
+            Self::Blobs(files) => files.keys().copied().collect(),
+            Self::Archival { files, .. } => files.values().flat_map(Range::clone).collect(),
+        }
+    }
+
+    /// Whether checkpoints from this source carry objects and events
+    fn is_complete(&self) -> bool {
+        matches!(self, Self::Blobs(_))
+    }
+
+    /// Read checkpoint `sequence_number`, along with the other checkpoints stored in the same file
+    async fn read(
+        &self,
+        path: &Path,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<(CheckpointSequenceNumber, ArchivedCheckpoint)>, ReplayEngineError> {
+        match self {
+            Self::Blobs(files) => {
+                let Some(file) = files.get(&sequence_number) else {
+                    return Ok(vec![]);
+                };
+                let bytes = fs::read(file).map_err(|e| archive_error(file, e))?;
+                let checkpoint = Blob::from_bytes::<CheckpointData>(&bytes)
+                    .map_err(|e| archive_error(file, e))?;
+                Ok(vec![(sequence_number, checkpoint.into())])
+            }
+            Self::Archival { reader, files } => {
+                let Some((_, range)) = files.range(..=sequence_number).next_back() else {
+                    return Ok(vec![]);
+                };
+                if !range.contains(&sequence_number) {
+                    return Ok(vec![]);
+                }
+                let store = SharedInMemoryStore::default();
+                reader
+                    .read(
+                        store.clone(),
+                        range.clone(),
+                        Arc::new(AtomicU64::new(0)),
+                        Arc::new(AtomicU64::new(0)),
+                        false,
+                    )
+                    .await
+                    .map_err(|e| archive_error(path, e))?;
+
+                let mut checkpoints = vec![];
+                for sequence_number in range.clone() {
+                    let missing =
+                        || archive_error(path, format!("checkpoint {sequence_number} is missing"));
+                    let summary = store
+                        .get_checkpoint_by_sequence_number(sequence_number)
+                        .map_err(|e| archive_error(path, e))?
+                        .ok_or_else(missing)?;
+                    let contents = store
+                        .get_full_checkpoint_contents_by_sequence_number(sequence_number)
+                        .map_err(|e| archive_error(path, e))?
+                        .ok_or_else(missing)?;
+                    checkpoints.push((
+                        sequence_number,
+                        ArchivedCheckpoint {
+                            digest: *summary.digest(),
+                            timestamp_ms: summary.timestamp_ms,
+                            transactions: contents
+                                .iter()
+                                .map(|data| ArchivedTransaction {
+                                    transaction: data.transaction.clone(),
+                                    effects: data.effects.clone(),
+                                    events: None,
+                                    objects: vec![],
+                                })
+                                .collect(),
+                        },
+                    ));
+                }
+                Ok(checkpoints)
+            }
+        }
+    }
+}
+
+impl From<CheckpointData> for ArchivedCheckpoint {
+    fn from(checkpoint: CheckpointData) -> Self {
+        Self {
+            digest: *checkpoint.checkpoint_summary.digest(),
+            timestamp_ms: checkpoint.checkpoint_summary.timestamp_ms,
+            transactions: checkpoint
+                .transactions
+                .into_iter()
+                .map(|tx| ArchivedTransaction {
+                    transaction: tx.transaction,
+                    effects: tx.effects,
+                    events: tx.events,
+                    objects: tx
+                        .input_objects
+                        .into_iter()
+                        .chain(tx.output_objects)
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ArchivedCheckpoint {
+    fn transaction(&self, digest: &TransactionDigest) -> Option<&ArchivedTransaction> {
+        self.transactions
+            .iter()
+            .find(|tx| tx.transaction.digest() == digest)
+    }
+
+    fn object(&self, id: &ObjectID, version: SequenceNumber) -> Option<&Object> {
+        self.transactions
+            .iter()
+            .flat_map(|tx| &tx.objects)
+            .find(|object| object.id() == *id && object.version() == version)
+    }
+}
+
+impl ArchiveIndex {
+    fn insert(
+        &mut self,
+        sequence_number: CheckpointSequenceNumber,
+        checkpoint: ArchivedCheckpoint,
+        epoch_change_tag: &StructTag,
+    ) -> Result<Arc<ArchivedCheckpoint>, ReplayEngineError> {
+        self.unread.remove(&sequence_number);
+
+        let mut epoch_change_events = vec![];
+        for tx in &checkpoint.transactions {
+            let digest = *tx.transaction.digest();
+            self.transactions.insert(digest, sequence_number);
+
+            for object in &tx.objects {
+                self.objects
+                    .insert((object.id(), object.version()), sequence_number);
+                if object.is_package()
+                    && self
+                        .packages
+                        .get_object(&object.id())
+                        .map_or(true, |package| package.version() < object.version())
+                {
+                    self.packages.insert_object(object.clone());
+                }
+            }
+
+            let events = tx.events.iter().flat_map(|events| &events.data);
+            for (event_seq, event) in events.enumerate() {
+                if &event.type_ != epoch_change_tag {
+                    continue;
+                }
+                let info: SystemEpochInfoEvent = bcs::from_bytes(&event.contents)
+                    .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?;
+                if let Some(epoch_start_timestamp_ms) =
+                    epoch_start_timestamp_ms(tx.transaction.transaction_data().kind())
+                {
+                    self.epoch_info.insert(
+                        info.epoch,
+                        (epoch_start_timestamp_ms, info.reference_gas_price),
+                    );
+                }
+                epoch_change_events.push(SuiEvent {
+                    id: EventID {
+                        tx_digest: digest,
+                        event_seq: event_seq as u64,
+                    },
+                    package_id: event.package_id,
+                    transaction_module: event.transaction_module.clone(),
+                    sender: event.sender,
+                    type_: event.type_.clone(),
+                    // Only the fields read by `extract_epoch_and_version` and
+                    // `get_epoch_start_timestamp_and_rgp`, rendered the way the RPC renders them.
+                    parsed_json: serde_json::json!({
+                        "epoch": info.epoch.to_string(),
+                        "protocol_version": info.protocol_version.to_string(),
+                        "reference_gas_price": info.reference_gas_price.to_string(),
+                    }),
+                    bcs: event.contents.clone(),
+                    timestamp_ms: Some(checkpoint.timestamp_ms),
+                });
+            }
+        }
+        if !epoch_change_events.is_empty() {
+            self.epoch_change_events
+                .insert(sequence_number, epoch_change_events);
+        }
+
+        let checkpoint = Arc::new(checkpoint);
+        self.checkpoints.put(sequence_number, checkpoint.clone());
+        Ok(checkpoint)
+    }
+}
+
+impl LocalArchive {
+    /// Checkpoint `sequence_number`, read from the archive unless it is still cached
+    async fn checkpoint(
+        &self,
+        index: &mut ArchiveIndex,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<Arc<ArchivedCheckpoint>>, ReplayEngineError> {
+        if let Some(checkpoint) = index.checkpoints.get(&sequence_number) {
+            return Ok(Some(checkpoint.clone()));
+        }
+        let mut found = None;
+        for (read, checkpoint) in self.source.read(&self.path, sequence_number).await? {
+            let checkpoint = index.insert(read, checkpoint, &self.epoch_change_tag)?;
+            if read == sequence_number {
+                found = Some(checkpoint);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Read the highest checkpoint that has not been read yet. Returns false once every
+    /// checkpoint in the archive has been read.
+    async fn read_next(&self, index: &mut ArchiveIndex) -> Result<bool, ReplayEngineError> {
+        let Some(sequence_number) = index.unread.pop_last() else {
+            return Ok(false);
+        };
+        self.checkpoint(index, sequence_number).await?;
+        Ok(true)
+    }
+
+    async fn read_all(&self, index: &mut ArchiveIndex) -> Result<(), ReplayEngineError> {
+        while self.read_next(index).await? {}
+        Ok(())
+    }
+
+    async fn transaction(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<(CheckpointSequenceNumber, Arc<ArchivedCheckpoint>)>, ReplayEngineError>
+    {
+        let mut index = self.index.lock().await;
+        loop {
+            if let Some(sequence_number) = index.transactions.get(digest).copied() {
+                return Ok(self
+                    .checkpoint(&mut index, sequence_number)
+                    .await?
+                    .map(|checkpoint| (sequence_number, checkpoint)));
+            }
+            if !self.read_next(&mut index).await? {
+                return Ok(None);
+            }
+        }
+    }
+
+    async fn object(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        let mut index = self.index.lock().await;
+        loop {
+            if let Some(sequence_number) = index.objects.get(&(*id, version)).copied() {
+                return self
+                    .object_in(&mut index, sequence_number, id, version)
+                    .await;
+            }
+            if !self.read_next(&mut index).await? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// The highest version of `id` that is at most `version`
+    async fn object_at_or_before(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        let mut index = self.index.lock().await;
+        loop {
+            let candidate = index
+                .objects
+                .range((*id, SequenceNumber::MIN)..=(*id, version))
+                .next_back()
+                .map(|((_, version), sequence_number)| (*version, *sequence_number));
+            // Later versions of the object can only be in the checkpoint the candidate was found
+            // in or after it, so once those have all been read, the candidate is the answer.
+            if let Some((version, sequence_number)) = candidate {
+                if index.unread.range(sequence_number..).next().is_none() {
+                    return self
+                        .object_in(&mut index, sequence_number, id, version)
+                        .await;
+                }
+            }
+            if !self.read_next(&mut index).await? {
+                return Ok(None);
+            }
+        }
+    }
+
+    async fn object_in(
+        &self,
+        index: &mut ArchiveIndex,
+        sequence_number: CheckpointSequenceNumber,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        Ok(self
+            .checkpoint(index, sequence_number)
+            .await?
+            .and_then(|checkpoint| checkpoint.object(id, version).cloned()))
+    }
+
+    fn starts_at_genesis(&self) -> bool {
+        self.sequence_numbers.first() == Some(&0)
+    }
+}
+
+#[async_trait]
+impl DataFetcher for LocalArchiveFetcher {
+    async fn multi_get_versioned(
+        &self,
+        objects: &[(ObjectID, SequenceNumber)],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        let mut found = vec![];
+        let mut missing = vec![];
+        for (id, version) in objects {
+            let object = if self.archive.source.is_complete() {
+                self.archive.object(id, *version).await?
+            } else {
+                None
+            };
+            match object {
+                Some(object) => found.push(object),
+                None => missing.push((*id, *version)),
+            }
+        }
+
+        if let Some((id, version)) = missing.first() {
+            let Some(backup_remote_fetcher) = &self.backup_remote_fetcher else {
+                return Err(ReplayEngineError::ObjectVersionNotFound {
+                    id: *id,
+                    version: *version,
+                });
+            };
+            found.extend(backup_remote_fetcher.multi_get_versioned(&missing).await?);
+        }
+        Ok(found)
+    }
+
+    async fn multi_get_latest(
+        &self,
+        objects: &[ObjectID],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        // The archive can end before the latest checkpoint, so prefer the backup if there is one
+        if let Some(backup_remote_fetcher) = &self.backup_remote_fetcher {
+            return backup_remote_fetcher.multi_get_latest(objects).await;
+        }
+
+        let mut found = vec![];
+        for id in objects {
+            found.push(
+                self.archive
+                    .object_at_or_before(id, SequenceNumber::MAX)
+                    .await?
+                    .ok_or(ReplayEngineError::ObjectNotExist { id: *id })?,
+            );
+        }
+        Ok(found)
+    }
+
+    async fn get_checkpoint_txs(
+        &self,
+        id: u64,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        let checkpoint = if self.archive.sequence_numbers.contains(&id) {
+            let mut index = self.archive.index.lock().await;
+            self.archive.checkpoint(&mut index, id).await?
+        } else {
+            None
+        };
+
+        match (checkpoint, &self.backup_remote_fetcher) {
+            (Some(checkpoint), _) => Ok(checkpoint
+                .transactions
+                .iter()
+                .map(|tx| *tx.transaction.digest())
+                .collect()),
+            (None, Some(backup_remote_fetcher)) => {
+                backup_remote_fetcher.get_checkpoint_txs(id).await
+            }
+            (None, None) => Err(ReplayEngineError::CheckpointNotFound {
+                sequence_number: id,
+            }),
+        }
+    }
+
+    async fn get_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, ReplayEngineError> {
+        let Some((sequence_number, checkpoint)) = self.archive.transaction(tx_digest).await? else {
+            return match &self.backup_remote_fetcher {
+                Some(backup_remote_fetcher) => {
+                    backup_remote_fetcher.get_transaction(tx_digest).await
+                }
+                None => Err(ReplayEngineError::TransactionNotFound { digest: *tx_digest }),
+            };
+        };
+        let tx = checkpoint
+            .transaction(tx_digest)
+            .ok_or(ReplayEngineError::TransactionNotFound { digest: *tx_digest })?;
+        let sender_signed_data = tx.transaction.data();
+
+        let transaction = {
+            let index = self.archive.index.lock().await;
+            SuiTransactionBlock::try_from(sender_signed_data.clone(), &index.packages)
+                .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?
+        };
+        Ok(SuiTransactionBlockResponse {
+            transaction: Some(transaction),
+            raw_transaction: bcs::to_bytes(sender_signed_data)
+                .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?,
+            effects: Some(SuiTransactionBlockEffects::try_from(tx.effects.clone())?),
+            timestamp_ms: Some(checkpoint.timestamp_ms),
+            checkpoint: Some(sequence_number),
+            ..SuiTransactionBlockResponse::new(*tx_digest)
+        })
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        _: &TransactionDigest,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+        // Child objects are fetched on demand through `get_child_object`
+        Ok(vec![])
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, ReplayEngineError> {
+        Ok(*self
+            .archive
+            .sequence_numbers
+            .last()
+            .expect("Archive must contain at least one checkpoint"))
+    }
+
+    async fn fetch_random_transaction(
+        &self,
+        checkpoint_id_start: Option<u64>,
+        checkpoint_id_end: Option<u64>,
+    ) -> Result<TransactionDigest, ReplayEngineError> {
+        let start = checkpoint_id_start.unwrap_or(1);
+        let end = checkpoint_id_end.unwrap_or(u64::MAX);
+        let sequence_numbers: Vec<_> = self.archive.sequence_numbers.range(start..=end).collect();
+        if sequence_numbers.is_empty() {
+            return Err(ReplayEngineError::CheckpointNotFound {
+                sequence_number: start,
+            });
+        }
+
+        let mut rng = rand::thread_rng();
+        let sequence_number = *sequence_numbers[rng.gen_range(0..sequence_numbers.len())];
+        let txs = self.get_checkpoint_txs(sequence_number).await?;
+        txs.get(rng.gen_range(0..txs.len().max(1)))
+            .copied()
+            .ok_or(ReplayEngineError::CheckpointNotFound { sequence_number })
+    }
+
+    async fn get_epoch_start_timestamp_and_rgp(
+        &self,
+        epoch_id: u64,
+    ) -> Result<(u64, u64), ReplayEngineError> {
+        let mut index = self.archive.index.lock().await;
+        if let Some(info) = index.epoch_info.get(&epoch_id) {
+            return Ok(*info);
+        }
+        if let Some(backup_remote_fetcher) = &self.backup_remote_fetcher {
+            return backup_remote_fetcher
+                .get_epoch_start_timestamp_and_rgp(epoch_id)
+                .await;
+        }
+        self.archive.read_all(&mut index).await?;
+        index
+            .epoch_info
+            .get(&epoch_id)
+            .copied()
+            .ok_or(ReplayEngineError::EventNotFound { epoch: epoch_id })
+    }
+
+    async fn get_epoch_change_events(
+        &self,
+        reverse: bool,
+    ) -> Result<Vec<SuiEvent>, ReplayEngineError> {
+        // Without history from genesis, the archive only holds some of the epoch change events
+        if let Some(backup_remote_fetcher) = &self.backup_remote_fetcher {
+            return backup_remote_fetcher.get_epoch_change_events(reverse).await;
+        }
+        if !self.archive.starts_at_genesis() {
+            return Err(archive_error(
+                &self.archive.path,
+                "the archive does not start from genesis, an RPC endpoint is needed for the \
+                 epoch history",
+            ));
+        }
+
+        let mut index = self.archive.index.lock().await;
+        self.archive.read_all(&mut index).await?;
+        let mut events: Vec<_> = index
+            .epoch_change_events
+            .values()
+            .flatten()
+            .cloned()
+            .collect();
+        if reverse {
+            events.reverse();
+        }
+        Ok(events)
+    }
+
+    async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
+        if self.archive.starts_at_genesis() {
+            let mut index = self.archive.index.lock().await;
+            if let Some(genesis) = self.archive.checkpoint(&mut index, 0).await? {
+                return Ok(ChainIdentifier::from(genesis.digest).to_string());
+            }
+        }
+        match &self.backup_remote_fetcher {
+            Some(backup_remote_fetcher) => backup_remote_fetcher.get_chain_id().await,
+            None => Err(ReplayEngineError::UnableToGetChainId {
+                err: "genesis checkpoint is not in the archive".to_string(),
+            }),
+        }
+    }
+
+    async fn get_child_object(
+        &self,
+        object_id: &ObjectID,
+        version_upper_bound: VersionNumber,
+    ) -> Result<Object, ReplayEngineError> {
+        let object = if self.archive.source.is_complete() {
+            self.archive
+                .object_at_or_before(object_id, version_upper_bound)
+                .await?
+        } else {
+            None
+        };
+
+        match (object, &self.backup_remote_fetcher) {
+            (Some(object), _) => Ok(object),
+            (None, Some(backup_remote_fetcher)) => {
+                backup_remote_fetcher
+                    .get_child_object(object_id, version_upper_bound)
+                    .await
+            }
+            (None, None) => Err(ReplayEngineError::ObjectNotExist { id: *object_id }),
+        }
+    }
+}
+
+fn epoch_start_timestamp_ms(kind: &TransactionKind) -> Option<u64> {
+    match kind {
+        TransactionKind::ChangeEpoch(change) => Some(change.epoch_start_timestamp_ms),
+        TransactionKind::EndOfEpochTransaction(kinds) => kinds.iter().find_map(|kind| match kind {
+            EndOfEpochTransactionKind::ChangeEpoch(change) => Some(change.epoch_start_timestamp_ms),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn archive_error(path: &Path, err: impl std::fmt::Display) -> ReplayEngineError {
+    ReplayEngineError::UnableToReadCheckpointArchive {
+        path: path.display().to_string(),
+        err: err.to_string(),
+    }
+}
@@ -15,6 +15,7 @@ use tracing::warn;
 use transaction_provider::{FuzzStartPoint, TransactionSource};
 
 use crate::config::get_rpc_url;
+use crate::data_fetcher::LocalArchiveFetcher;
 use crate::replay::ExecutionSandboxState;
 use crate::replay::LocalExec;
 use crate::replay::ProtocolVersionSummary;
@@ -96,6 +97,10 @@ pub enum ReplayToolCommand {
         /// Optional protocol version to use, if not specified defaults to the one originally used for the transaction.
         #[arg(long, short, allow_hyphen_values = true)]
         protocol_version: Option<i64>,
+        /// Optional directory of checkpoints, written by sui-data-ingestion or sui-archival, to
+        /// replay from. The RPC endpoint, if given, is only used for what the directory lacks.
+        #[arg(long)]
+        archive_path: Option<PathBuf>,
     },
 
     /// Replay transactions listed in a file
@@ -157,6 +162,10 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[arg(long, short, default_value = "16")]
         max_tasks: u64,
+        /// Optional directory of checkpoints, written by sui-data-ingestion or sui-archival, to
+        /// replay from. The RPC endpoint, if given, is only used for what the directory lacks.
+        #[arg(long)]
+        archive_path: Option<PathBuf>,
     },
 
     /// Replay all transactions in an epoch
@@ -168,6 +177,10 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[arg(long, short, default_value = "16")]
         max_tasks: u64,
+        /// Optional directory of checkpoints, written by sui-data-ingestion or sui-archival, to
+        /// replay from. The RPC endpoint, if given, is only used for what the directory lacks.
+        #[arg(long)]
+        archive_path: Option<PathBuf>,
     },
 
     /// Run the replay based fuzzer
@@ -367,19 +380,37 @@ pub async fn execute_replay_command(
             show_effects,
             executor_version,
             protocol_version,
+            archive_path,
         } => {
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
-            let sandbox_state = LocalExec::replay_with_network_config(
-                get_rpc_url(rpc_url, cfg_path, chain)?,
-                tx_digest,
-                safety,
-                use_authority,
-                executor_version,
-                protocol_version,
-                None,
-            )
-            .await?;
+            let sandbox_state = match archive_path {
+                Some(archive_path) => {
+                    LocalExec::replay_with_local_archive(
+                        &archive_path,
+                        rpc_url,
+                        tx_digest,
+                        safety,
+                        use_authority,
+                        executor_version,
+                        protocol_version,
+                        None,
+                    )
+                    .await?
+                }
+                None => {
+                    LocalExec::replay_with_network_config(
+                        get_rpc_url(rpc_url, cfg_path, chain)?,
+                        tx_digest,
+                        safety,
+                        use_authority,
+                        executor_version,
+                        protocol_version,
+                        None,
+                    )
+                    .await?
+                }
+            };
 
             if show_effects {
                 println!("{}", sandbox_state.local_exec_effects);
@@ -440,6 +471,7 @@ pub async fn execute_replay_command(
             end,
             terminate_early,
             max_tasks,
+            archive_path,
         } => {
             assert!(start <= end, "Start checkpoint must be <= end checkpoint");
             assert!(max_tasks > 0, "Max tasks must be > 0");
@@ -450,16 +482,26 @@ pub async fn execute_replay_command(
                 start, end, max_tasks, checkpoints_per_task
             );
 
+            // Open the archive once, and share it between all tasks
+            let archive = match archive_path {
+                Some(path) => Some(LocalArchiveFetcher::new(&path, rpc_url.clone()).await?),
+                None => None,
+            };
+
             let range: Vec<_> = (start..=end).collect();
             for (task_count, checkpoints) in range.chunks(checkpoints_per_task).enumerate() {
                 let checkpoints = checkpoints.to_vec();
                 let rpc_url = rpc_url.clone();
+                let archive = archive.clone();
                 let safety = safety.clone();
                 handles.push(tokio::spawn(async move {
                     info!("Spawning task {task_count} for checkpoints {checkpoints:?}");
                     let time = std::time::Instant::now();
-                    let (succeeded, total) = LocalExec::new_from_fn_url(&rpc_url.expect("Url must be provided"))
-                        .await
+                    let lx = match archive {
+                        Some(archive) => LocalExec::new_for_local_archive(archive).await,
+                        None => LocalExec::new_from_fn_url(&rpc_url.expect("Url must be provided")).await,
+                    };
+                    let (succeeded, total) = lx
                         .unwrap()
                         .init_for_execution()
                         .await
@@ -506,9 +548,20 @@ pub async fn execute_replay_command(
             epoch,
             terminate_early,
             max_tasks,
+            archive_path,
         } => {
-            let lx =
-                LocalExec::new_from_fn_url(&rpc_url.clone().expect("Url must be provided")).await?;
+            let lx = match &archive_path {
+                Some(path) => {
+                    LocalExec::new_for_local_archive(
+                        LocalArchiveFetcher::new(path, rpc_url.clone()).await?,
+                    )
+                    .await?
+                }
+                None => {
+                    LocalExec::new_from_fn_url(&rpc_url.clone().expect("Url must be provided"))
+                        .await?
+                }
+            };
 
             let (start, end) = lx.checkpoints_for_epoch(epoch).await?;
 
@@ -527,6 +580,7 @@ pub async fn execute_replay_command(
                     end,
                     terminate_early,
                     max_tasks,
+                    archive_path,
                 },
             )
             .await;
@@ -4,7 +4,8 @@
 use crate::chain_from_chain_id;
 use crate::{
     data_fetcher::{
-        extract_epoch_and_version, DataFetcher, Fetchers, NodeStateDumpFetcher, RemoteFetcher,
+        extract_epoch_and_version, DataFetcher, Fetchers, LocalArchiveFetcher,
+        NodeStateDumpFetcher, RemoteFetcher,
     },
     displays::{
         transaction_displays::{transform_command_results_to_annotated, FullPTB},
@@ -25,7 +26,7 @@ use serde::{Deserialize, Serialize};
 use similar::{ChangeTag, TextDiff};
 use std::{
     collections::{BTreeMap, HashSet},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     sync::Mutex,
 };
@@ -339,6 +340,34 @@ impl LocalExec {
             .await
     }
 
+    pub async fn replay_with_local_archive(
+        archive_path: &Path,
+        backup_rpc_url: Option<String>,
+        tx_digest: TransactionDigest,
+        expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+        use_authority: bool,
+        executor_version: Option<i64>,
+        protocol_version: Option<i64>,
+        enable_profiler: Option<PathBuf>,
+    ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        info!("Using local checkpoint archive: {}", archive_path.display());
+        LocalExec::new_for_local_archive(
+            LocalArchiveFetcher::new(archive_path, backup_rpc_url).await?,
+        )
+        .await?
+        .init_for_execution()
+        .await?
+        .execute_transaction(
+            &tx_digest,
+            expensive_safety_check_config,
+            use_authority,
+            executor_version,
+            protocol_version,
+            enable_profiler,
+        )
+        .await
+    }
+
     /// This captures the state of the network at a given point in time and populates
     /// prptocol version tables including which system packages to fetch
     /// If this function is called across epoch boundaries, the info might be stale.
@@ -387,6 +416,34 @@ impl LocalExec {
         })
     }
 
+    /// Execute against the checkpoints read by `fetcher`, without network access unless it has a
+    /// backup RPC endpoint. The fetcher is cheap to clone, so that one archive can be shared
+    /// between several executors.
+    pub async fn new_for_local_archive(
+        fetcher: LocalArchiveFetcher,
+    ) -> Result<Self, ReplayEngineError> {
+        // Use a throwaway metrics registry for local execution.
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(LimitsMetrics::new(&registry));
+
+        Ok(Self {
+            client: None,
+            protocol_version_epoch_table: BTreeMap::new(),
+            protocol_version_system_package_table: BTreeMap::new(),
+            current_protocol_version: 0,
+            exec_store_events: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            storage: Storage::default(),
+            fetcher: Fetchers::LocalArchive(fetcher),
+            // TODO: make these configurable
+            num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
+            sleep_period_for_timeout: RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+            executor_version: None,
+            protocol_version: None,
+            enable_profiler: None,
+        })
+    }
+
     pub async fn new_for_state_dump(
         path: &str,
         backup_rpc_url: Option<String>,
@@ -1073,8 +1130,13 @@ impl LocalExec {
         Ok(Some(o))
     }
 
+    /// Whether transactions are resolved through the fetcher, from an RPC endpoint or a local
+    /// checkpoint archive, rather than taken from a node state dump
     pub fn is_remote_replay(&self) -> bool {
-        matches!(self.fetcher, Fetchers::Remote(_))
+        matches!(
+            self.fetcher,
+            Fetchers::Remote(_) | Fetchers::LocalArchive(_)
+        )
     }
 
     /// Must be called after `populate_protocol_version_tables`
@@ -1083,7 +1145,7 @@ impl LocalExec {
         protocol_version: u64,
     ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
         match &self.fetcher {
-            Fetchers::Remote(_) => Ok(self
+            Fetchers::Remote(_) | Fetchers::LocalArchive(_) => Ok(self
                 .protocol_version_system_package_table
                 .get(&protocol_version)
                 .ok_or(ReplayEngineError::FrameworkObjectVersionTableNotPopulated {
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::ReplayableNetworkConfigSet;
+use crate::data_fetcher::{DataFetcher, LocalArchiveFetcher};
 use crate::types::ReplayEngineError;
 use crate::types::{MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD};
 use crate::LocalExec;
+use simulacrum::Simulacrum;
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
@@ -170,3 +172,67 @@ async fn execute_replay(url: &str, tx: &TransactionDigest) -> Result<(), ReplayE
 
     Ok(())
 }
+
+#[tokio::test]
+async fn local_archive_requires_checkpoints() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("README"), "not a checkpoint").unwrap();
+    assert!(matches!(
+        LocalArchiveFetcher::new(dir.path(), None).await,
+        Err(ReplayEngineError::UnableToReadCheckpointArchive { .. })
+    ));
+}
+
+#[tokio::test]
+async fn replay_from_local_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut sim = Simulacrum::new();
+    sim.set_data_ingestion_path(dir.path().to_path_buf());
+    sim.create_checkpoint();
+    // Transactions from epoch 0 can't be replayed
+    sim.advance_epoch(false);
+
+    let (transaction, _) = sim.transfer_txn(SuiAddress::random_for_testing_only());
+    let digest = *transaction.digest();
+    sim.execute_transaction(transaction).unwrap();
+    sim.create_checkpoint();
+
+    // Only the sequence numbers are listed up front, checkpoints are read on demand
+    let fetcher = LocalArchiveFetcher::new(dir.path(), None).await.unwrap();
+    assert_eq!(
+        fetcher
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .unwrap(),
+        3
+    );
+    assert!(fetcher
+        .get_checkpoint_txs(3)
+        .await
+        .unwrap()
+        .contains(&digest));
+
+    LocalExec::replay_with_local_archive(
+        dir.path(),
+        None,
+        digest,
+        ExpensiveSafetyCheckConfig::default(),
+        false,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+    .check_effects()
+    .unwrap();
+
+    // Without history from genesis, the epoch history has to come from an RPC endpoint
+    std::fs::remove_file(dir.path().join("0.chk")).unwrap();
+    let fetcher = LocalArchiveFetcher::new(dir.path(), None).await.unwrap();
+    assert!(fetcher.get_transaction(&digest).await.is_ok());
+    assert!(matches!(
+        fetcher.get_epoch_change_events(false).await,
+        Err(ReplayEngineError::UnableToReadCheckpointArchive { .. })
+    ));
+}
@@ -197,6 +197,15 @@ pub enum ReplayEngineError {
 
     #[error("Unable to get chain id: {}", err)]
     UnableToGetChainId { err: String },
+
+    #[error("Unable to read checkpoint archive at {}: {}", path, err)]
+    UnableToReadCheckpointArchive { path: String, err: String },
+
+    #[error("Transaction {digest} not found in checkpoint archive")]
+    TransactionNotFound { digest: TransactionDigest },
+
+    #[error("Checkpoint {sequence_number} not found in checkpoint archive")]
+    CheckpointNotFound { sequence_number: u64 },
 }
 
 impl From<SuiObjectResponseError> for ReplayEngineError {
@@ -7,7 +7,7 @@ use crate::{accept::AcceptFormat, response::ResponseContent, Result};
 use axum::extract::Query;
 use axum::extract::{Path, State};
 use itertools::Itertools;
-use sui_sdk2::types::{Address, Object, ObjectId};
+use sui_sdk2::types::{Address, Object, ObjectId, TypeTag};
 use sui_types::storage::ObjectKey;
 use tap::Pipe;
 
@@ -53,7 +53,7 @@ pub async fn list_account_owned_objects(
     .pipe(Ok)
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct ListAccountOwnedObjectsQueryParameters {
     pub limit: Option<u32>,
     pub start: Option<ObjectId>,
@@ -70,3 +70,115 @@ impl ListAccountOwnedObjectsQueryParameters {
         self.start.map(Into::into)
     }
 }
+
+pub const LIST_ACCOUNT_BALANCES_PATH: &str = "/accounts/:account/balances";
+
+pub async fn list_account_balances(
+    Path(address): Path<Address>,
+    Query(parameters): Query<ListAccountBalancesQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<StateReader>,
+) -> Result<Page<Balance, CoinTypeCursor>> {
+    let limit = parameters.limit();
+    let start = parameters.start.map(|cursor| cursor.0);
+
+    let mut balances = state
+        .inner()
+        .account_balances_iter(address.into(), start)?
+        .take(limit + 1)
+        .collect::<Vec<_>>();
+
+    let cursor = if balances.len() > limit {
+        // SAFETY: We've already verified that balances is greater than limit, which is
+        // gaurenteed to be >= 1.
+        balances
+            .pop()
+            .unwrap()
+            .coin_type
+            .pipe(CoinTypeCursor)
+            .pipe(Some)
+    } else {
+        None
+    };
+
+    let balances = balances
+        .into_iter()
+        .map(|info| Balance {
+            coin_type: info.coin_type.into(),
+            balance: info.balance,
+            coin_object_count: info.coin_object_count,
+        })
+        .collect_vec();
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(balances),
+        AcceptFormat::Bcs => ResponseContent::Bcs(balances),
+    }
+    .pipe(|entries| Page { entries, cursor })
+    .pipe(Ok)
+}
+
+/// The total balance of the coins of one type owned by an account
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Balance {
+    /// Type of the coin, e.g. `0x2::sui::SUI`
+    pub coin_type: TypeTag,
+    /// Sum of the values of all the coins of this type owned by the account
+    pub balance: u64,
+    /// Number of coin objects of this type owned by the account
+    pub coin_object_count: u64,
+}
+
+/// A Cursor that points at the balance of a coin type.
+///
+/// Has the format of a canonical type tag, e.g. `0x0000..0002::sui::SUI`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinTypeCursor(pub sui_types::TypeTag);
+
+impl std::fmt::Display for CoinTypeCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_canonical_display(true))
+    }
+}
+
+impl std::str::FromStr for CoinTypeCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        sui_types::parse_sui_type_tag(s).map(Self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CoinTypeCursor {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde_with::DeserializeAs;
+        serde_with::DisplayFromStr::deserialize_as(deserializer)
+    }
+}
+
+impl serde::Serialize for CoinTypeCursor {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde_with::SerializeAs;
+        serde_with::DisplayFromStr::serialize_as(self, serializer)
+    }
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ListAccountBalancesQueryParameters {
+    pub limit: Option<u32>,
+    pub start: Option<CoinTypeCursor>,
+}
+
+impl ListAccountBalancesQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::MAX_PAGE_SIZE))
+            .unwrap_or(crate::DEFAULT_PAGE_SIZE)
+    }
+}
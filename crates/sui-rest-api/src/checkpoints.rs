@@ -3,11 +3,15 @@
 
 use axum::extract::Query;
 use axum::extract::{Path, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures::{Stream, StreamExt};
 use sui_sdk2::types::{
     CheckpointData, CheckpointDigest, CheckpointSequenceNumber, SignedCheckpointSummary,
 };
 use sui_types::storage::ReadStore;
 use tap::Pipe;
+use tokio::sync::broadcast;
 
 use crate::reader::StateReader;
 use crate::Direction;
@@ -17,6 +21,7 @@ use crate::{accept::AcceptFormat, response::ResponseContent, Result};
 pub const LIST_CHECKPOINT_PATH: &str = "/checkpoints";
 pub const GET_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint";
 pub const GET_FULL_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint/full";
+pub const SUBSCRIBE_CHECKPOINTS_PATH: &str = "/checkpoints/subscribe";
 
 pub async fn get_full_checkpoint(
     Path(checkpoint_id): Path<CheckpointId>,
@@ -166,7 +171,7 @@ pub async fn list_checkpoints(
     .pipe(Ok)
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct ListCheckpointsQueryParameters {
     pub limit: Option<u32>,
     /// The checkpoint to start listing from.
@@ -191,3 +196,152 @@ impl ListCheckpointsQueryParameters {
         self.direction.unwrap_or(Direction::Descending)
     }
 }
+
+/// Streams full checkpoints, starting from `start`, as they are executed by the node.
+///
+/// With a JSON `accept` header checkpoints are sent as Server-Sent Events, one `data` field holding
+/// a JSON encoded `CheckpointData` per event. Otherwise the response is a chunked stream of BCS
+/// frames: each frame is the length of a BCS encoded `CheckpointData` as a little-endian `u32`,
+/// followed by the checkpoint itself.
+pub async fn subscribe_checkpoints(
+    Query(parameters): Query<SubscribeCheckpointsQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<StateReader>,
+    State(executed_checkpoints): State<broadcast::Sender<CheckpointSequenceNumber>>,
+) -> Result<Response> {
+    // Subscribe before reading the latest checkpoint so that no checkpoint executed in between is
+    // missed
+    let executed_checkpoints = executed_checkpoints.subscribe();
+    let latest_checkpoint = state.inner().get_latest_checkpoint()?.sequence_number;
+    let oldest_checkpoint = state.inner().get_lowest_available_checkpoint_objects()?;
+    let start = parameters.start.unwrap_or(latest_checkpoint);
+
+    if start < oldest_checkpoint {
+        return Err(crate::RestError::new(
+            axum::http::StatusCode::GONE,
+            "Old checkpoints have been pruned",
+        ));
+    }
+
+    let checkpoints = full_checkpoint_stream(state, executed_checkpoints, start, latest_checkpoint);
+
+    let response = match accept {
+        AcceptFormat::Json => checkpoints
+            .map(|checkpoint| {
+                checkpoint.and_then(|checkpoint| {
+                    Event::default()
+                        .json_data(CheckpointData::from(checkpoint))
+                        .map_err(Into::into)
+                })
+            })
+            .pipe(Sse::new)
+            .keep_alive(KeepAlive::default())
+            .into_response(),
+        AcceptFormat::Bcs => checkpoints
+            .map(|checkpoint| checkpoint.and_then(|checkpoint| encode_frame(&checkpoint)))
+            .pipe(axum::body::StreamBody::new)
+            .pipe(|body| {
+                (
+                    [(
+                        axum::http::header::CONTENT_TYPE,
+                        crate::APPLICATION_BCS_STREAM,
+                    )],
+                    body,
+                )
+            })
+            .into_response(),
+    };
+
+    Ok(response)
+}
+
+/// Encodes a value, e.g. a checkpoint, as a frame of a BCS stream.
+pub(crate) fn encode_frame<T: serde::Serialize>(value: &T) -> anyhow::Result<Vec<u8>> {
+    let bytes = bcs::to_bytes(value)?;
+    let len = u32::try_from(bytes.len())?;
+
+    let mut frame = Vec::with_capacity(4 + bytes.len());
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(&bytes);
+    Ok(frame)
+}
+
+/// A stream of the full checkpoints executed by the node, starting from `start`, which waits for
+/// `executed_checkpoints` to announce the checkpoints past `latest_checkpoint`. The stream ends
+/// after the first error or once the node stops executing checkpoints.
+fn full_checkpoint_stream(
+    state: StateReader,
+    executed_checkpoints: broadcast::Receiver<CheckpointSequenceNumber>,
+    start: CheckpointSequenceNumber,
+    latest_checkpoint: CheckpointSequenceNumber,
+) -> impl Stream<Item = anyhow::Result<sui_types::full_checkpoint_content::CheckpointData>> {
+    let subscription = Some((state, executed_checkpoints, start, latest_checkpoint));
+
+    futures::stream::unfold(subscription, |subscription| async move {
+        let (state, mut executed_checkpoints, next, mut latest_checkpoint) = subscription?;
+
+        while latest_checkpoint < next {
+            latest_checkpoint = match executed_checkpoints.recv().await {
+                Ok(executed) => latest_checkpoint.max(executed),
+                // Missed some notifications, so look up how far execution got instead
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    match state.inner().get_latest_checkpoint() {
+                        Ok(checkpoint) => checkpoint.sequence_number,
+                        Err(e) => return Some((Err(e.into()), None)),
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            };
+        }
+
+        match read_full_checkpoint(&state, next) {
+            Ok(checkpoint) => Some((
+                Ok(checkpoint),
+                Some((state, executed_checkpoints, next + 1, latest_checkpoint)),
+            )),
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Reads checkpoint `sequence_number`, which must have been executed.
+fn read_full_checkpoint(
+    state: &StateReader,
+    sequence_number: CheckpointSequenceNumber,
+) -> anyhow::Result<sui_types::full_checkpoint_content::CheckpointData> {
+    let checkpoint_id = CheckpointId::SequenceNumber(sequence_number);
+    let summary = state
+        .inner()
+        .get_checkpoint_by_sequence_number(sequence_number)?
+        .ok_or(CheckpointNotFoundError(checkpoint_id))?;
+    let contents = state
+        .inner()
+        .get_checkpoint_contents_by_digest(&summary.content_digest)?
+        .ok_or(CheckpointNotFoundError(checkpoint_id))?;
+
+    state.inner().get_checkpoint_data(summary, contents)
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SubscribeCheckpointsQueryParameters {
+    /// The checkpoint to start streaming from.
+    ///
+    /// Defaults to the latest checkpoint if not provided.
+    pub start: Option<CheckpointSequenceNumber>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_is_prefixed_with_length() {
+        let value = vec![1u64, 2, 3];
+        let bytes = bcs::to_bytes(&value).unwrap();
+
+        let frame = encode_frame(&value).unwrap();
+
+        assert_eq!(frame[..4], (bytes.len() as u32).to_le_bytes());
+        assert_eq!(frame[4..], bytes);
+    }
+}
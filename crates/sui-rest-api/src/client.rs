@@ -1,53 +1,365 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::checkpoints::ListCheckpointsQueryParameters;
-use crate::transactions::ExecuteTransactionQueryParameters;
+use crate::accounts::{
+    Balance, CoinTypeCursor, ListAccountBalancesQueryParameters,
+    ListAccountOwnedObjectsQueryParameters,
+};
+use crate::checkpoints::{ListCheckpointsQueryParameters, SubscribeCheckpointsQueryParameters};
+use crate::events::{EventCursor, EventResponse, ListEventsQueryParameters};
+use crate::health::Threshold;
+use crate::info::NodeInfo;
+use crate::objects::{DynamicFieldInfo, ListDynamicFieldsQueryParameters};
+use crate::system::{GasInfo, ProtocolConfigResponse, SystemStateSummary};
+use crate::transactions::{
+    ExecuteTransactionQueryParameters, ListTransactionsQueryParameters,
+    SimulateTransactionQueryParameters, TransactionCursor, TransactionResponse,
+    TransactionSimulationResponse,
+};
 use anyhow::{anyhow, Result};
-use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use futures::{Stream, StreamExt, TryStreamExt};
+use std::future::Future;
+use std::time::Duration;
+use sui_sdk2::types::{ObjectId, ValidatorCommittee};
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::crypto::AuthorityStrongQuorumSignInfo;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
-use sui_types::transaction::Transaction;
+use sui_types::transaction::{Transaction, TransactionData};
 use sui_types::TypeTag;
 
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// A typed client for the REST api served by a Sui node.
+///
+/// Responses are requested and decoded as BCS, except for the endpoints which only support JSON.
+/// Requests which fail with a connection error, a timeout, a `429 Too Many Requests` or a `5xx`
+/// status are retried with an exponential backoff.
 #[derive(Clone)]
 pub struct Client {
     inner: reqwest::Client,
     base_url: String,
+    max_retries: u32,
+    retry_backoff: Duration,
+    request_timeout: Option<Duration>,
 }
 
 impl Client {
     pub fn new<S: Into<String>>(base_url: S) -> Self {
+        Self::new_with_client(base_url, reqwest::Client::new())
+    }
+
+    /// Creates a client which sends its requests using the provided `reqwest::Client`, e.g. one
+    /// configured with a timeout or default headers.
+    pub fn new_with_client<S: Into<String>>(base_url: S, inner: reqwest::Client) -> Self {
         Self {
-            inner: reqwest::Client::new(),
+            inner,
             base_url: base_url.into(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            request_timeout: None,
         }
     }
 
-    pub async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
-        let url = format!("{}/checkpoints", self.base_url);
+    /// Sets the number of times a failed request is retried. `0` disables retries.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a request, doubled after every attempt.
+    pub fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Sets a timeout for every request, from sending it until its response has been read.
+    /// Subscriptions are exempt, as their responses only end when the subscription does.
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    pub async fn get_node_info(&self) -> Result<NodeInfo> {
+        let url = format!("{}/", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
+
+        self.bcs(response).await
+    }
+
+    /// Checks that the node is up and, if `threshold_seconds` is provided, that its latest
+    /// checkpoint is no older than `threshold_seconds`.
+    pub async fn health(&self, threshold_seconds: Option<u32>) -> Result<()> {
+        let url = format!("{}/health", self.base_url);
+
+        let response = self
+            .send(self.inner.get(url).query(&Threshold { threshold_seconds }))
+            .await?;
+
+        self.check_response(response).map(drop)
+    }
+
+    pub async fn list_account_owned_objects(
+        &self,
+        address: SuiAddress,
+        parameters: &ListAccountOwnedObjectsQueryParameters,
+    ) -> Result<Page<Object, ObjectId>> {
+        let url = format!("{}/accounts/{address}/objects", self.base_url);
+
+        let response = self
+            .send(self.inner.get(url).query(parameters).accept_bcs())
+            .await?;
+
+        self.page(response).await
+    }
+
+    /// Returns a stream of all the objects owned by `address`, fetching a page at a time.
+    pub fn list_account_owned_objects_stream(
+        &self,
+        address: SuiAddress,
+        parameters: ListAccountOwnedObjectsQueryParameters,
+    ) -> impl Stream<Item = Result<Object>> + '_ {
+        let ListAccountOwnedObjectsQueryParameters { limit, start } = parameters;
+
+        paginate(start, move |start| async move {
+            self.list_account_owned_objects(
+                address,
+                &ListAccountOwnedObjectsQueryParameters { limit, start },
+            )
+            .await
+        })
+    }
+
+    pub async fn list_account_balances(
+        &self,
+        address: SuiAddress,
+        parameters: &ListAccountBalancesQueryParameters,
+    ) -> Result<Page<Balance, CoinTypeCursor>> {
+        let url = format!("{}/accounts/{address}/balances", self.base_url);
+
+        let response = self
+            .send(self.inner.get(url).query(parameters).accept_bcs())
+            .await?;
+
+        self.page(response).await
+    }
+
+    pub async fn list_events(
+        &self,
+        parameters: &ListEventsQueryParameters,
+    ) -> Result<Page<EventResponse, EventCursor>> {
+        let url = format!("{}/events", self.base_url);
+
+        let response = self
+            .send(self.inner.get(url).query(parameters).accept_bcs())
+            .await?;
+
+        self.page(response).await
+    }
+
+    pub async fn get_transaction(
+        &self,
+        transaction_digest: TransactionDigest,
+    ) -> Result<TransactionResponse> {
+        let url = format!("{}/transactions/{transaction_digest}", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
+
+        self.bcs(response).await
+    }
+
+    pub async fn list_transactions(
+        &self,
+        parameters: &ListTransactionsQueryParameters,
+    ) -> Result<Page<TransactionResponse, TransactionCursor>> {
+        let url = format!("{}/transactions", self.base_url);
+
+        let response = self
+            .send(self.inner.get(url).query(parameters).accept_bcs())
+            .await?;
+
+        self.page(response).await
+    }
+
+    /// Returns a stream of transactions in checkpoint order, fetching a page at a time.
+    pub fn list_transactions_stream(
+        &self,
+        parameters: ListTransactionsQueryParameters,
+    ) -> impl Stream<Item = Result<TransactionResponse>> + '_ {
+        let ListTransactionsQueryParameters {
+            limit,
+            start,
+            direction,
+        } = parameters;
+
+        paginate(start, move |start| async move {
+            self.list_transactions(&ListTransactionsQueryParameters {
+                limit,
+                start,
+                direction,
+            })
+            .await
+        })
+    }
+
+    /// Executes a signed transaction.
+    ///
+    /// Executing a transaction is idempotent, so a request which failed in flight is retried like
+    /// any other.
+    pub async fn execute_transaction(
+        &self,
+        parameters: &ExecuteTransactionQueryParameters,
+        transaction: &Transaction,
+    ) -> Result<TransactionExecutionResponse> {
+        #[derive(serde::Serialize)]
+        struct SignedTransaction<'a> {
+            transaction: &'a sui_types::transaction::TransactionData,
+            signatures: &'a [sui_types::signature::GenericSignature],
+        }
+
+        let url = format!("{}/transactions", self.base_url);
+        let body = bcs::to_bytes(&SignedTransaction {
+            transaction: &transaction.inner().intent_message.value,
+            signatures: &transaction.inner().tx_signatures,
+        })?;
+
+        let response = self
+            .send(
+                self.inner
+                    .post(url)
+                    .query(parameters)
+                    .accept_bcs()
+                    .header(reqwest::header::CONTENT_TYPE, crate::APPLICATION_BCS)
+                    .body(body),
+            )
+            .await?;
+
+        self.bcs(response).await
+    }
+
+    /// Simulates the execution of a transaction, which doesn't need to be signed, without
+    /// committing it.
+    pub async fn simulate_transaction(
+        &self,
+        parameters: &SimulateTransactionQueryParameters,
+        transaction: &TransactionData,
+    ) -> Result<TransactionSimulationResponse> {
+        let url = format!("{}/transactions/simulate", self.base_url);
+        let body = bcs::to_bytes(transaction)?;
+
+        let response = self
+            .send(
+                self.inner
+                    .post(url)
+                    .query(parameters)
+                    .accept_bcs()
+                    .header(reqwest::header::CONTENT_TYPE, crate::APPLICATION_BCS)
+                    .body(body),
+            )
+            .await?;
+
+        self.bcs(response).await
+    }
+
+    pub async fn get_latest_committee(&self) -> Result<ValidatorCommittee> {
+        let url = format!("{}/committee", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
+
+        self.bcs(response).await
+    }
+
+    pub async fn get_committee(&self, epoch: EpochId) -> Result<ValidatorCommittee> {
+        let url = format!("{}/committee/{epoch}", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
+
+        self.bcs(response).await
+    }
+
+    pub async fn get_system_state_summary(&self) -> Result<SystemStateSummary> {
+        let url = format!("{}/system", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_json()).await?;
+
+        self.json(response).await
+    }
+
+    pub async fn get_current_protocol_config(&self) -> Result<ProtocolConfigResponse> {
+        let url = format!("{}/system/protocol", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_json()).await?;
+
+        self.json(response).await
+    }
+
+    pub async fn get_protocol_config(&self, version: u64) -> Result<ProtocolConfigResponse> {
+        let url = format!("{}/system/protocol/{version}", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_json()).await?;
+
+        self.json(response).await
+    }
+
+    pub async fn get_gas_info(&self) -> Result<GasInfo> {
+        let url = format!("{}/system/gas", self.base_url);
+
+        let response = self.send(self.inner.get(url).accept_json()).await?;
+
+        self.json(response).await
+    }
 
-        let query = ListCheckpointsQueryParameters {
+    pub async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
+        let parameters = ListCheckpointsQueryParameters {
             limit: Some(1),
             start: None,
             direction: None,
         };
 
+        let mut page = self.list_checkpoints(&parameters).await?;
+
+        page.entries
+            .pop()
+            .ok_or_else(|| anyhow!("server returned empty checkpoint list"))
+    }
+
+    pub async fn list_checkpoints(
+        &self,
+        parameters: &ListCheckpointsQueryParameters,
+    ) -> Result<Page<CertifiedCheckpointSummary, CheckpointSequenceNumber>> {
+        let url = format!("{}/checkpoints", self.base_url);
+
         let response = self
-            .inner
-            .get(url)
-            .query(&query)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
-            .send()
+            .send(self.inner.get(url).query(parameters).accept_bcs())
             .await?;
 
-        let mut page: Vec<CertifiedCheckpointSummary> = self.bcs(response).await?;
+        self.page(response).await
+    }
 
-        page.pop()
-            .ok_or_else(|| anyhow!("server returned empty checkpoint list"))
+    /// Returns a stream of checkpoint summaries, fetching a page at a time.
+    pub fn list_checkpoints_stream(
+        &self,
+        parameters: ListCheckpointsQueryParameters,
+    ) -> impl Stream<Item = Result<CertifiedCheckpointSummary>> + '_ {
+        let ListCheckpointsQueryParameters {
+            limit,
+            start,
+            direction,
+        } = parameters;
+
+        paginate(start, move |start| async move {
+            self.list_checkpoints(&ListCheckpointsQueryParameters {
+                limit,
+                start,
+                direction,
+            })
+            .await
+        })
     }
 
     pub async fn get_full_checkpoint(
@@ -59,14 +371,30 @@ impl Client {
             self.base_url
         );
 
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
+
+        self.bcs(response).await
+    }
+
+    /// Subscribes to the full checkpoints executed by the node, starting from
+    /// `checkpoint_sequence_number`. The stream ends with an error if the connection is lost.
+    pub async fn subscribe_checkpoints(
+        &self,
+        checkpoint_sequence_number: CheckpointSequenceNumber,
+    ) -> Result<impl Stream<Item = Result<CheckpointData>>> {
+        let url = format!("{}/checkpoints/subscribe", self.base_url);
+
+        let query = SubscribeCheckpointsQueryParameters {
+            start: Some(checkpoint_sequence_number),
+        };
+
+        // Sent without the request timeout, which would cut the subscription off
         let response = self
-            .inner
-            .get(url)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
-            .send()
+            .send_with_retries(self.inner.get(url).query(&query).accept_bcs())
             .await?;
+        let response = self.check_response(response)?;
 
-        self.bcs(response).await
+        Ok(decode_bcs_stream(response.bytes_stream().boxed()))
     }
 
     pub async fn get_checkpoint_summary(
@@ -75,12 +403,7 @@ impl Client {
     ) -> Result<CertifiedCheckpointSummary> {
         let url = format!("{}/checkpoints/{checkpoint_sequence_number}", self.base_url);
 
-        let response = self
-            .inner
-            .get(url)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
-            .send()
-            .await?;
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
 
         self.bcs(response).await
     }
@@ -88,12 +411,7 @@ impl Client {
     pub async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
         let url = format!("{}/objects/{object_id}", self.base_url);
 
-        let response = self
-            .inner
-            .get(url)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
-            .send()
-            .await?;
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
 
         self.bcs(response).await
     }
@@ -105,44 +423,67 @@ impl Client {
     ) -> Result<Object> {
         let url = format!("{}/objects/{object_id}/version/{version}", self.base_url);
 
-        let response = self
-            .inner
-            .get(url)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
-            .send()
-            .await?;
+        let response = self.send(self.inner.get(url).accept_bcs()).await?;
 
         self.bcs(response).await
     }
 
-    pub async fn execute_transaction(
+    pub async fn list_dynamic_fields(
         &self,
-        parameters: &ExecuteTransactionQueryParameters,
-        transaction: &Transaction,
-    ) -> Result<TransactionExecutionResponse> {
-        #[derive(serde::Serialize)]
-        struct SignedTransaction<'a> {
-            transaction: &'a sui_types::transaction::TransactionData,
-            signatures: &'a [sui_types::signature::GenericSignature],
-        }
-
-        let url = format!("{}/transactions", self.base_url);
-        let body = bcs::to_bytes(&SignedTransaction {
-            transaction: &transaction.inner().intent_message.value,
-            signatures: &transaction.inner().tx_signatures,
-        })?;
+        object_id: ObjectID,
+        parameters: &ListDynamicFieldsQueryParameters,
+    ) -> Result<Page<DynamicFieldInfo, ObjectId>> {
+        let url = format!("{}/objects/{object_id}/dynamic-fields", self.base_url);
 
         let response = self
-            .inner
-            .post(url)
-            .query(parameters)
-            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
-            .header(reqwest::header::CONTENT_TYPE, crate::APPLICATION_BCS)
-            .body(body)
-            .send()
+            .send(self.inner.get(url).query(parameters).accept_bcs())
             .await?;
 
-        self.bcs(response).await
+        self.page(response).await
+    }
+
+    /// Sends a request with the request timeout of the client, retrying it when it fails with an
+    /// error that may be transient.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let request = match self.request_timeout {
+            Some(request_timeout) => request.timeout(request_timeout),
+            None => request,
+        };
+
+        self.send_with_retries(request).await
+    }
+
+    /// Sends a request, retrying it when it fails with an error that may be transient.
+    async fn send_with_retries(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let response = request
+                .try_clone()
+                .ok_or_else(|| anyhow!("request body cannot be retried"))?
+                .send()
+                .await;
+
+            let retryable = match &response {
+                Ok(response) => {
+                    response.status().is_server_error()
+                        || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+
+            if !retryable || attempt >= self.max_retries {
+                return response.map_err(Into::into);
+            }
+
+            let backoff = self
+                .retry_backoff
+                .saturating_mul(2u32.saturating_pow(attempt));
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
     }
 
     fn check_response(&self, response: reqwest::Response) -> Result<reqwest::Response> {
@@ -154,7 +495,6 @@ impl Client {
         Ok(response)
     }
 
-    #[allow(unused)]
     async fn json<T: serde::de::DeserializeOwned>(&self, response: reqwest::Response) -> Result<T> {
         let response = self.check_response(response)?;
 
@@ -169,6 +509,108 @@ impl Client {
         let bcs = bcs::from_bytes(&bytes)?;
         Ok(bcs)
     }
+
+    async fn page<T, C>(&self, response: reqwest::Response) -> Result<Page<T, C>>
+    where
+        T: serde::de::DeserializeOwned,
+        C: std::str::FromStr,
+        C::Err: std::fmt::Display,
+    {
+        let response = self.check_response(response)?;
+
+        let next_cursor = response
+            .headers()
+            .get(crate::types::X_SUI_CURSOR)
+            .map(|cursor| {
+                cursor
+                    .to_str()
+                    .map_err(Into::into)
+                    .and_then(|cursor| cursor.parse().map_err(|e| anyhow!("invalid cursor: {e}")))
+            })
+            .transpose()?;
+
+        let entries = self.bcs(response).await?;
+        Ok(Page {
+            entries,
+            next_cursor,
+        })
+    }
+}
+
+/// A page of results from an endpoint which supports cursor-based pagination.
+#[derive(Debug)]
+pub struct Page<T, C> {
+    pub entries: Vec<T>,
+    /// Cursor to pass as the `start` of the next request to get the next page, or `None` if this
+    /// is the last page.
+    pub next_cursor: Option<C>,
+}
+
+trait RequestBuilderExt {
+    fn accept_bcs(self) -> Self;
+    fn accept_json(self) -> Self;
+}
+
+impl RequestBuilderExt for reqwest::RequestBuilder {
+    fn accept_bcs(self) -> Self {
+        self.header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+    }
+
+    fn accept_json(self) -> Self {
+        self.header(reqwest::header::ACCEPT, crate::APPLICATION_JSON)
+    }
+}
+
+/// Flattens the pages returned by `fetch` into a stream of entries, requesting the next page with
+/// the cursor of the previous one until a page without a cursor is returned.
+fn paginate<T, C, F, Fut>(start: Option<C>, fetch: F) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(Option<C>) -> Fut,
+    Fut: Future<Output = Result<Page<T, C>>>,
+{
+    futures::stream::try_unfold(Some((start, fetch)), |state| async move {
+        let Some((cursor, mut fetch)) = state else {
+            return Ok(None);
+        };
+
+        let page = fetch(cursor).await?;
+        let entries = futures::stream::iter(page.entries.into_iter().map(Ok::<_, anyhow::Error>));
+        let next = page.next_cursor.map(|cursor| (Some(cursor), fetch));
+
+        Ok(Some((entries, next)))
+    })
+    .try_flatten()
+}
+
+/// Decodes a stream of BCS values, each prefixed with its length as a little-endian `u32`.
+fn decode_bcs_stream<T, S, B>(bytes: S) -> impl Stream<Item = Result<T>>
+where
+    T: serde::de::DeserializeOwned,
+    S: Stream<Item = reqwest::Result<B>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    futures::stream::unfold(Some((bytes, Vec::new())), |state| async move {
+        let (mut bytes, mut buffer) = state?;
+
+        loop {
+            if buffer.len() >= 4 {
+                // SAFETY: the slice is exactly 4 bytes long
+                let len = u32::from_le_bytes(buffer[..4].try_into().unwrap()) as usize;
+                if buffer.len() >= 4 + len {
+                    let value = bcs::from_bytes(&buffer[4..4 + len]).map_err(Into::into);
+                    buffer.drain(..4 + len);
+                    return Some((value, Some((bytes, buffer))));
+                }
+            }
+
+            match bytes.next().await {
+                Some(Ok(chunk)) => buffer.extend_from_slice(chunk.as_ref()),
+                Some(Err(e)) => return Some((Err(e.into()), None)),
+                None if buffer.is_empty() => return None,
+                None => return Some((Err(anyhow!("stream ended in the middle of a value")), None)),
+            }
+        }
+    })
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -202,3 +644,214 @@ pub struct BalanceChange {
     /// negative amount means spending coin value and positive means receiving coin value.
     pub amount: i128,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoints::encode_frame;
+    use axum::http::StatusCode;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Serves `/health`, answering the n-th request with `statuses[n]`, or with the last status once
+    /// they run out. Returns the url of the server and the number of requests it received.
+    fn serve_statuses(statuses: Vec<StatusCode>) -> (String, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let router = axum::Router::new().route(
+            crate::health::HEALTH_PATH,
+            axum::routing::get(move || {
+                let request = counter.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[request.min(statuses.len() - 1)];
+                async move { status }
+            }),
+        );
+
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(router.into_make_service());
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        (url, requests)
+    }
+
+    fn client(url: String) -> Client {
+        Client::new(url).with_retry_backoff(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn retry_server_errors() {
+        let (url, requests) = serve_statuses(vec![
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::OK,
+        ]);
+
+        client(url).health(None).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_too_many_requests() {
+        let (url, requests) = serve_statuses(vec![StatusCode::TOO_MANY_REQUESTS, StatusCode::OK]);
+
+        client(url).health(None).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn do_not_retry_client_errors() {
+        let (url, requests) = serve_statuses(vec![StatusCode::NOT_FOUND, StatusCode::OK]);
+
+        client(url).health(None).await.unwrap_err();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_retries() {
+        let (url, requests) = serve_statuses(vec![StatusCode::SERVICE_UNAVAILABLE]);
+
+        client(url)
+            .with_max_retries(2)
+            .health(None)
+            .await
+            .unwrap_err();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn paginate_until_page_without_cursor() {
+        let requests = AtomicUsize::new(0);
+
+        let entries: Vec<u64> = paginate(Some(0), |cursor: Option<u64>| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let start = cursor.unwrap();
+                Ok(Page {
+                    entries: vec![start, start + 1],
+                    next_cursor: (start < 4).then_some(start + 2),
+                })
+            }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(entries, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn paginate_empty_page() {
+        let requests = AtomicUsize::new(0);
+
+        let entries: Vec<u64> = paginate(None, |_: Option<u64>| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Ok(Page {
+                    entries: vec![],
+                    next_cursor: None,
+                })
+            }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert!(entries.is_empty());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn paginate_stops_after_error() {
+        let requests = AtomicUsize::new(0);
+
+        let entries: Vec<Result<u64>> = paginate(Some(0), |cursor: Option<u64>| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                match cursor.unwrap() {
+                    0 => Ok(Page {
+                        entries: vec![0, 1],
+                        next_cursor: Some(2),
+                    }),
+                    _ => Err(anyhow!("page unavailable")),
+                }
+            }
+        })
+        .collect()
+        .await;
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].as_ref().unwrap(), &0);
+        assert_eq!(entries[1].as_ref().unwrap(), &1);
+        assert!(entries[2].is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    fn values() -> Vec<(u64, String)> {
+        vec![
+            (1, "one".to_owned()),
+            (2, String::new()),
+            (3, "three".repeat(100)),
+        ]
+    }
+
+    fn frames() -> Vec<u8> {
+        values()
+            .iter()
+            .flat_map(|value| encode_frame(value).unwrap())
+            .collect()
+    }
+
+    async fn decode(chunks: Vec<Vec<u8>>) -> Vec<Result<(u64, String)>> {
+        let chunks = futures::stream::iter(chunks.into_iter().map(Ok::<_, reqwest::Error>));
+        decode_bcs_stream(chunks).collect().await
+    }
+
+    #[tokio::test]
+    async fn decode_frames_split_across_chunks() {
+        let bytes = frames();
+
+        for chunk_size in [1, 3, 4, 7, bytes.len()] {
+            let chunks = bytes.chunks(chunk_size).map(<[u8]>::to_vec).collect();
+            let decoded = decode(chunks)
+                .await
+                .into_iter()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(decoded, values(), "chunk size {chunk_size}");
+        }
+    }
+
+    #[tokio::test]
+    async fn decode_empty_stream() {
+        assert!(decode(vec![]).await.is_empty());
+        assert!(decode(vec![vec![]]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn decode_truncated_frame() {
+        let mut bytes = frames();
+        bytes.pop();
+
+        let decoded = decode(vec![bytes]).await;
+
+        // The complete frames are decoded before the stream ends with an error
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].as_ref().unwrap(), &values()[0]);
+        assert_eq!(decoded[1].as_ref().unwrap(), &values()[1]);
+        assert!(decoded[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn decode_truncated_length() {
+        let mut bytes = frames();
+        bytes.extend_from_slice(&[1, 0]);
+
+        let decoded = decode(vec![bytes]).await;
+
+        assert_eq!(decoded.len(), 4);
+        assert!(decoded[..3].iter().all(Result::is_ok));
+        assert!(decoded[3].is_err());
+    }
+}
@@ -47,6 +47,21 @@ impl From<anyhow::Error> for RestError {
     }
 }
 
+impl From<sui_types::error::SuiError> for RestError {
+    fn from(error: sui_types::error::SuiError) -> Self {
+        use sui_types::error::SuiError;
+
+        let status = match &error {
+            SuiError::UserInputError { .. } | SuiError::UnsupportedFeatureError { .. } => {
+                StatusCode::BAD_REQUEST
+            }
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        RestError::new(status, error.to_string())
+    }
+}
+
 impl From<sui_types::quorum_driver_types::QuorumDriverError> for RestError {
     fn from(error: sui_types::quorum_driver_types::QuorumDriverError) -> Self {
         use itertools::Itertools;
@@ -0,0 +1,446 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use move_core_types::language_storage::StructTag;
+use sui_sdk2::types::{Address, CheckpointSequenceNumber, Event, TransactionDigest};
+use sui_types::base_types::SuiAddress;
+use sui_types::storage::{EventPosition, RestEventInfo};
+use sui_types::TypeTag;
+use tap::Pipe;
+
+use crate::reader::StateReader;
+use crate::Direction;
+use crate::Page;
+use crate::RestError;
+use crate::Result;
+use crate::{accept::AcceptFormat, response::ResponseContent};
+
+pub const LIST_EVENTS_PATH: &str = "/events";
+
+/// Maximum number of transactions whose events are read to serve a single page when listing
+/// events without a filter.
+///
+/// Transactions can emit no events, so a page is cut short once this many transactions have been
+/// scanned and the returned cursor can be used to resume the scan. Filtered listings are served
+/// from the event indexes instead.
+const MAX_TRANSACTIONS_SCANNED: usize = 1000;
+
+pub async fn list_events(
+    Query(parameters): Query<ListEventsQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<StateReader>,
+) -> Result<Page<EventResponse, EventCursor>> {
+    let latest_checkpoint = state.inner().get_latest_checkpoint()?.sequence_number;
+    let oldest_checkpoint = state.inner().get_lowest_available_checkpoint()?;
+    let limit = parameters.limit();
+    let start = parameters.start(latest_checkpoint);
+    let direction = parameters.direction();
+    let filter = parameters.filter()?;
+
+    if start.checkpoint < oldest_checkpoint {
+        return Err(RestError::new(
+            StatusCode::GONE,
+            "Old events have been pruned",
+        ));
+    }
+
+    let descending = matches!(direction, Direction::Descending);
+    let (events, next_cursor) = match (&filter.event_type, filter.sender) {
+        // An address sends fewer transactions than a type has events in most cases, so the
+        // sender index is preferred when filtering on both
+        (_, Some(sender)) => {
+            let indexed = state.inner().event_by_sender_iter(
+                sender,
+                start.position(direction),
+                descending,
+            )?;
+            list_indexed_events(&state, indexed, &parameters, &filter, limit)?
+        }
+        (Some(event_type), None) => {
+            let indexed = state.inner().event_by_type_iter(
+                event_type,
+                start.position(direction),
+                descending,
+            )?;
+            list_indexed_events(&state, indexed, &parameters, &filter, limit)?
+        }
+        (None, None) => scan_events(&state, start, &parameters, limit)?,
+    };
+
+    let entries = match accept {
+        AcceptFormat::Json => ResponseContent::Json(events),
+        AcceptFormat::Bcs => ResponseContent::Bcs(events),
+    };
+
+    let cursor = next_cursor.filter(|cursor| cursor.checkpoint >= oldest_checkpoint);
+
+    Ok(Page { entries, cursor })
+}
+
+/// Reads a page of events, and the cursor to the next one, from an event index.
+fn list_indexed_events(
+    state: &StateReader,
+    indexed: impl Iterator<Item = RestEventInfo>,
+    parameters: &ListEventsQueryParameters,
+    filter: &EventFilter,
+    limit: usize,
+) -> Result<(Vec<EventResponse>, Option<EventCursor>)> {
+    let mut events = Vec::new();
+    // The events of the transaction that emitted the previous event, which likely emitted the
+    // next one as well
+    let mut transaction_events: Option<(
+        sui_types::digests::TransactionDigest,
+        Vec<sui_types::event::Event>,
+    )> = None;
+
+    for info in indexed {
+        if parameters.is_past_end(info.position.checkpoint, parameters.direction()) {
+            return Ok((events, None));
+        }
+
+        if !filter.matches(&info.type_, info.sender) {
+            continue;
+        }
+
+        if events.len() == limit {
+            return Ok((events, Some(info.position.into())));
+        }
+
+        let digest = info.transaction_digest;
+        if transaction_events
+            .as_ref()
+            .map_or(true, |(transaction_digest, _)| {
+                *transaction_digest != digest
+            })
+        {
+            let data = state
+                .get_transaction_events(&info.transaction_digest)?
+                .map(|events| events.data)
+                .unwrap_or_default();
+            transaction_events = Some((digest, data));
+        }
+        let event = transaction_events
+            .as_ref()
+            .and_then(|(_, data)| data.get(info.position.event as usize))
+            .ok_or_else(|| {
+                RestError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!(
+                        "missing event {} of transaction {}",
+                        info.position.event, info.transaction_digest
+                    ),
+                )
+            })?;
+
+        events.push(EventResponse {
+            transaction_digest: digest.into(),
+            event_seq: info.position.event,
+            checkpoint: info.position.checkpoint,
+            timestamp_ms: info.timestamp_ms,
+            event: event.clone().into(),
+        });
+    }
+
+    Ok((events, None))
+}
+
+/// Reads a page of events, and the cursor to the next one, by reading the events of every
+/// transaction from `start`.
+fn scan_events(
+    state: &StateReader,
+    start: EventCursor,
+    parameters: &ListEventsQueryParameters,
+    limit: usize,
+) -> Result<(Vec<EventResponse>, Option<EventCursor>)> {
+    let direction = parameters.direction();
+    let mut events = Vec::new();
+    let mut next_cursor = Some(start);
+    let mut transactions = state.transaction_iter(direction, (start.checkpoint, start.index));
+    let mut scanned = 0;
+
+    while events.len() < limit && scanned < MAX_TRANSACTIONS_SCANNED {
+        let Some(entry) = transactions.next() else {
+            next_cursor = None;
+            break;
+        };
+        let (cursor_info, digest) = entry?;
+
+        if parameters.is_past_end(cursor_info.checkpoint, direction) {
+            next_cursor = None;
+            break;
+        }
+
+        // The first transaction may have had some of its events returned by the previous page
+        let first_event = if scanned == 0 { start.event } else { None };
+        scanned += 1;
+
+        next_cursor = cursor_info
+            .next_cursor
+            .map(|(checkpoint, index)| EventCursor {
+                checkpoint,
+                index,
+                event: None,
+            });
+
+        let mut transaction_events: Vec<_> = state
+            .get_transaction_events(&digest)?
+            .map(|events| events.data)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .collect();
+        if matches!(direction, Direction::Descending) {
+            transaction_events.reverse();
+        }
+        let transaction_events =
+            transaction_events.into_iter().skip_while(|(event_seq, _)| {
+                match (first_event, direction) {
+                    (None, _) => false,
+                    (Some(first), Direction::Ascending) => (*event_seq as u64) < first,
+                    (Some(first), Direction::Descending) => (*event_seq as u64) > first,
+                }
+            });
+
+        for (event_seq, event) in transaction_events {
+            if events.len() == limit {
+                next_cursor = Some(EventCursor {
+                    checkpoint: cursor_info.checkpoint,
+                    index: Some(cursor_info.index as usize),
+                    event: Some(event_seq as u64),
+                });
+                break;
+            }
+
+            events.push(EventResponse {
+                transaction_digest: digest.into(),
+                event_seq: event_seq as u64,
+                checkpoint: cursor_info.checkpoint,
+                timestamp_ms: cursor_info.timestamp_ms,
+                event: event.into(),
+            });
+        }
+    }
+
+    Ok((events, next_cursor))
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct EventResponse {
+    pub transaction_digest: TransactionDigest,
+    /// Index of the event in the events emitted by the transaction
+    pub event_seq: u64,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub event: Event,
+}
+
+/// A Cursor that points at a specific event in history.
+///
+/// Has the format of: `<checkpoint>[.<index>[.<event>]]`
+/// where `<checkpoint>` is the sequence number of a checkpoint, `<index>` is the index of a
+/// transaction in the particular checkpoint and `<event>` is the index of an event emitted by that
+/// transaction.
+///
+/// `index` is optional and if omitted iteration will start at the first or last transaction in a
+/// checkpoint based on the provided `Direction`, like for a `TransactionCursor`. `event` is
+/// optional and if omitted iteration will likewise start at the first or last event of the
+/// transaction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EventCursor {
+    checkpoint: CheckpointSequenceNumber,
+    index: Option<usize>,
+    event: Option<u64>,
+}
+
+impl EventCursor {
+    /// The position of the first event to return when iterating in `direction`.
+    fn position(&self, direction: Direction) -> EventPosition {
+        let (transaction, event) = match (self.index, direction) {
+            (Some(index), Direction::Ascending) => (index as u64, self.event.unwrap_or(0)),
+            (Some(index), Direction::Descending) => (index as u64, self.event.unwrap_or(u64::MAX)),
+            (None, Direction::Ascending) => (0, 0),
+            (None, Direction::Descending) => (u64::MAX, u64::MAX),
+        };
+        EventPosition {
+            checkpoint: self.checkpoint,
+            transaction,
+            event,
+        }
+    }
+}
+
+impl From<EventPosition> for EventCursor {
+    fn from(position: EventPosition) -> Self {
+        Self {
+            checkpoint: position.checkpoint,
+            index: Some(position.transaction as usize),
+            event: Some(position.event),
+        }
+    }
+}
+
+impl std::fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.checkpoint)?;
+        if let Some(index) = self.index {
+            write!(f, ".{index}")?;
+            if let Some(event) = self.event {
+                write!(f, ".{event}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for EventCursor {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '.');
+        // SAFETY: splitn always returns at least one item
+        let checkpoint = parts.next().unwrap().parse()?;
+        let index = parts.next().map(str::parse).transpose()?;
+        let event = parts.next().map(str::parse).transpose()?;
+
+        Ok(Self {
+            checkpoint,
+            index,
+            event,
+        })
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EventCursor {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde_with::DeserializeAs;
+        serde_with::DisplayFromStr::deserialize_as(deserializer)
+    }
+}
+
+impl serde::Serialize for EventCursor {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde_with::SerializeAs;
+        serde_with::DisplayFromStr::serialize_as(self, serializer)
+    }
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ListEventsQueryParameters {
+    pub limit: Option<u32>,
+    pub start: Option<EventCursor>,
+    /// Last checkpoint to return events from, in the direction of iteration
+    pub end_checkpoint: Option<CheckpointSequenceNumber>,
+    pub direction: Option<Direction>,
+    /// Only return events of this type, e.g. `0x2::coin::CoinEvent`. If the type has no type
+    /// parameters, events of every instantiation of the type are returned.
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    /// Only return events emitted by transactions sent by this address
+    pub sender: Option<Address>,
+}
+
+impl ListEventsQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::MAX_PAGE_SIZE))
+            .unwrap_or(crate::DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn start(&self, default: CheckpointSequenceNumber) -> EventCursor {
+        self.start.unwrap_or(EventCursor {
+            checkpoint: default,
+            index: None,
+            event: None,
+        })
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or(Direction::Descending)
+    }
+
+    fn is_past_end(&self, checkpoint: CheckpointSequenceNumber, direction: Direction) -> bool {
+        match (self.end_checkpoint, direction) {
+            (None, _) => false,
+            (Some(end), Direction::Ascending) => checkpoint > end,
+            (Some(end), Direction::Descending) => checkpoint < end,
+        }
+    }
+
+    fn filter(&self) -> Result<EventFilter> {
+        let event_type = self
+            .event_type
+            .as_deref()
+            .map(
+                |event_type| match sui_types::parse_sui_type_tag(event_type) {
+                    Ok(TypeTag::Struct(tag)) => Ok(*tag),
+                    _ => Err(RestError::new(
+                        StatusCode::BAD_REQUEST,
+                        format!("invalid event type {event_type}"),
+                    )),
+                },
+            )
+            .transpose()?;
+
+        EventFilter {
+            event_type,
+            sender: self.sender.map(Into::into),
+        }
+        .pipe(Ok)
+    }
+}
+
+struct EventFilter {
+    event_type: Option<StructTag>,
+    sender: Option<SuiAddress>,
+}
+
+impl EventFilter {
+    fn matches(&self, type_: &StructTag, sender: SuiAddress) -> bool {
+        let type_matches = self.event_type.as_ref().map_or(true, |event_type| {
+            if event_type.type_params.is_empty() {
+                event_type.address == type_.address
+                    && event_type.module == type_.module
+                    && event_type.name == type_.name
+            } else {
+                event_type == type_
+            }
+        });
+        let sender_matches = self.sender.map_or(true, |filter| filter == sender);
+
+        type_matches && sender_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_cursor_round_trip() {
+        for s in ["7", "7.3", "7.3.12"] {
+            let cursor: EventCursor = s.parse().unwrap();
+            assert_eq!(cursor.to_string(), s);
+        }
+
+        let cursor: EventCursor = "7.3.12".parse().unwrap();
+        assert_eq!(
+            cursor,
+            EventCursor {
+                checkpoint: 7,
+                index: Some(3),
+                event: Some(12),
+            }
+        );
+
+        assert!("7.3.12.1".parse::<EventCursor>().is_err());
+        assert!("seven".parse::<EventCursor>().is_err());
+    }
+}
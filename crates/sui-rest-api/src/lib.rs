@@ -13,6 +13,7 @@ pub mod client;
 mod committee;
 pub mod content_type;
 mod error;
+mod events;
 mod health;
 mod info;
 mod metrics;
@@ -23,20 +24,37 @@ mod system;
 pub mod transactions;
 pub mod types;
 
+pub use accounts::{
+    Balance, CoinTypeCursor, ListAccountBalancesQueryParameters,
+    ListAccountOwnedObjectsQueryParameters,
+};
+pub use checkpoints::{ListCheckpointsQueryParameters, SubscribeCheckpointsQueryParameters};
 pub use client::Client;
 pub use error::{RestError, Result};
+pub use events::{EventCursor, EventResponse, ListEventsQueryParameters};
+pub use info::NodeInfo;
 pub use metrics::RestMetrics;
 use mysten_network::callback::CallbackLayer;
+pub use objects::{DynamicFieldInfo, DynamicFieldType, ListDynamicFieldsQueryParameters};
 use reader::StateReader;
 use std::sync::Arc;
 pub use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::storage::RestStateReader;
+pub use system::{GasInfo, ProtocolConfigResponse, SystemStateSummary, ValidatorSummary};
 use tap::Pipe;
-pub use transactions::{ExecuteTransactionQueryParameters, TransactionExecutor};
+use tokio::sync::broadcast;
+pub use transactions::{
+    ExecuteTransactionQueryParameters, ListTransactionsQueryParameters,
+    SimulateTransactionQueryParameters, SimulateTransactionResult, TransactionCursor,
+    TransactionExecutor, TransactionResponse, TransactionSimulationResponse,
+};
 
 pub const TEXT_PLAIN_UTF_8: &str = "text/plain; charset=utf-8";
 pub const APPLICATION_BCS: &str = "application/bcs";
 pub const APPLICATION_JSON: &str = "application/json";
+/// A stream of BCS values, each prefixed with its length as a little-endian `u32`
+pub const APPLICATION_BCS_STREAM: &str = "application/x.sui.bcs-stream";
 
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -67,6 +85,7 @@ impl<T: serde::Serialize, C: std::fmt::Display> axum::response::IntoResponse for
 pub struct RestService {
     reader: StateReader,
     executor: Option<Arc<dyn TransactionExecutor>>,
+    executed_checkpoints: Option<broadcast::Sender<CheckpointSequenceNumber>>,
     chain_id: sui_types::digests::ChainIdentifier,
     software_version: &'static str,
     metrics: Option<Arc<RestMetrics>>,
@@ -84,6 +103,7 @@ impl RestService {
         Self {
             reader: StateReader::new(reader),
             executor: None,
+            executed_checkpoints: None,
             chain_id,
             software_version,
             metrics: None,
@@ -98,6 +118,15 @@ impl RestService {
         self.executor = Some(executor);
     }
 
+    /// Enables checkpoint subscriptions, which are notified through `executed_checkpoints` of the
+    /// sequence number of every checkpoint executed by the node.
+    pub fn with_executed_checkpoints(
+        &mut self,
+        executed_checkpoints: broadcast::Sender<CheckpointSequenceNumber>,
+    ) {
+        self.executed_checkpoints = Some(executed_checkpoints);
+    }
+
     pub fn with_metrics(&mut self, metrics: RestMetrics) {
         self.metrics = Some(Arc::new(metrics));
     }
@@ -112,6 +141,7 @@ impl RestService {
 
     pub fn into_router(self) -> Router {
         let executor = self.executor.clone();
+        let executed_checkpoints = self.executed_checkpoints.clone();
         let metrics = self.metrics.clone();
 
         Router::new()
@@ -121,6 +151,11 @@ impl RestService {
                 accounts::LIST_ACCOUNT_OWNED_OBJECTS_PATH,
                 get(accounts::list_account_owned_objects),
             )
+            .route(
+                accounts::LIST_ACCOUNT_BALANCES_PATH,
+                get(accounts::list_account_balances),
+            )
+            .route(events::LIST_EVENTS_PATH, get(events::list_events))
             .route(
                 transactions::GET_TRANSACTION_PATH,
                 get(transactions::get_transaction),
@@ -164,10 +199,24 @@ impl RestService {
                 objects::GET_OBJECT_WITH_VERSION_PATH,
                 get(objects::get_object_with_version),
             )
+            .route(
+                objects::LIST_DYNAMIC_FIELDS_PATH,
+                get(objects::list_dynamic_fields),
+            )
             .with_state(self.clone())
             .pipe(|router| {
                 if let Some(executor) = executor {
-                    router.merge(execution_router(executor))
+                    router.merge(execution_router(executor, self.reader.clone()))
+                } else {
+                    router
+                }
+            })
+            .pipe(|router| {
+                if let Some(executed_checkpoints) = executed_checkpoints {
+                    router.merge(subscription_router(
+                        executed_checkpoints,
+                        self.reader.clone(),
+                    ))
                 } else {
                     router
                 }
@@ -201,11 +250,66 @@ impl RestService {
     }
 }
 
-fn execution_router(executor: Arc<dyn TransactionExecutor>) -> Router {
+fn execution_router(executor: Arc<dyn TransactionExecutor>, reader: StateReader) -> Router {
     Router::new()
         .route(
             transactions::POST_EXECUTE_TRANSACTION_PATH,
             post(transactions::execute_transaction),
         )
-        .with_state(executor)
+        .route(
+            transactions::POST_SIMULATE_TRANSACTION_PATH,
+            post(transactions::simulate_transaction),
+        )
+        .with_state(ExecutionState { executor, reader })
+}
+
+#[derive(Clone)]
+struct ExecutionState {
+    executor: Arc<dyn TransactionExecutor>,
+    reader: StateReader,
+}
+
+impl axum::extract::FromRef<ExecutionState> for Arc<dyn TransactionExecutor> {
+    fn from_ref(input: &ExecutionState) -> Self {
+        input.executor.clone()
+    }
+}
+
+impl axum::extract::FromRef<ExecutionState> for StateReader {
+    fn from_ref(input: &ExecutionState) -> Self {
+        input.reader.clone()
+    }
+}
+
+fn subscription_router(
+    executed_checkpoints: broadcast::Sender<CheckpointSequenceNumber>,
+    reader: StateReader,
+) -> Router {
+    Router::new()
+        .route(
+            checkpoints::SUBSCRIBE_CHECKPOINTS_PATH,
+            get(checkpoints::subscribe_checkpoints),
+        )
+        .with_state(SubscriptionState {
+            executed_checkpoints,
+            reader,
+        })
+}
+
+#[derive(Clone)]
+struct SubscriptionState {
+    executed_checkpoints: broadcast::Sender<CheckpointSequenceNumber>,
+    reader: StateReader,
+}
+
+impl axum::extract::FromRef<SubscriptionState> for broadcast::Sender<CheckpointSequenceNumber> {
+    fn from_ref(input: &SubscriptionState) -> Self {
+        input.executed_checkpoints.clone()
+    }
+}
+
+impl axum::extract::FromRef<SubscriptionState> for StateReader {
+    fn from_ref(input: &SubscriptionState) -> Self {
+        input.reader.clone()
+    }
 }
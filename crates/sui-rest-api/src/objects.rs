@@ -1,9 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{accept::AcceptFormat, reader::StateReader, response::ResponseContent, Result};
-use axum::extract::{Path, State};
-use sui_sdk2::types::{Object, ObjectId, Version};
+use crate::{accept::AcceptFormat, reader::StateReader, response::ResponseContent, Page, Result};
+use axum::extract::{Path, Query, State};
+use sui_sdk2::types::{Object, ObjectId, TypeTag, Version};
 use tap::Pipe;
 
 pub const GET_OBJECT_PATH: &str = "/objects/:object_id";
@@ -42,6 +42,95 @@ pub async fn get_object_with_version(
     .pipe(Ok)
 }
 
+pub const LIST_DYNAMIC_FIELDS_PATH: &str = "/objects/:object_id/dynamic-fields";
+
+pub async fn list_dynamic_fields(
+    Path(parent): Path<ObjectId>,
+    Query(parameters): Query<ListDynamicFieldsQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<StateReader>,
+) -> Result<Page<DynamicFieldInfo, ObjectId>> {
+    let limit = parameters.limit();
+    let start = parameters.start.map(Into::into);
+
+    let mut dynamic_fields = state
+        .inner()
+        .dynamic_field_iter(parent.into(), start)?
+        .take(limit + 1)
+        .map(DynamicFieldInfo::from)
+        .collect::<Vec<_>>();
+
+    let cursor = if dynamic_fields.len() > limit {
+        // SAFETY: We've already verified that dynamic_fields is greater than limit, which is
+        // gaurenteed to be >= 1.
+        dynamic_fields.pop().unwrap().field_id.pipe(Some)
+    } else {
+        None
+    };
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(dynamic_fields),
+        AcceptFormat::Bcs => ResponseContent::Bcs(dynamic_fields),
+    }
+    .pipe(|entries| Page { entries, cursor })
+    .pipe(Ok)
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ListDynamicFieldsQueryParameters {
+    pub limit: Option<u32>,
+    pub start: Option<ObjectId>,
+}
+
+impl ListDynamicFieldsQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|l| (l as usize).clamp(1, crate::MAX_PAGE_SIZE))
+            .unwrap_or(crate::DEFAULT_PAGE_SIZE)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DynamicFieldInfo {
+    pub parent: ObjectId,
+    pub field_id: ObjectId,
+    pub dynamic_field_type: DynamicFieldType,
+    pub name_type: TypeTag,
+    /// BCS bytes of the name of the field
+    pub name_value: Vec<u8>,
+    /// ObjectId of the child object when `dynamic_field_type == DynamicFieldType::Object`
+    pub dynamic_object_id: Option<ObjectId>,
+}
+
+impl From<sui_types::storage::RestDynamicFieldInfo> for DynamicFieldInfo {
+    fn from(value: sui_types::storage::RestDynamicFieldInfo) -> Self {
+        Self {
+            parent: value.parent.into(),
+            field_id: value.field_id.into(),
+            dynamic_field_type: value.dynamic_field_type.into(),
+            name_type: value.name_type.into(),
+            name_value: value.name_value,
+            dynamic_object_id: value.dynamic_object_id.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DynamicFieldType {
+    Field,
+    Object,
+}
+
+impl From<sui_types::dynamic_field::DynamicFieldType> for DynamicFieldType {
+    fn from(value: sui_types::dynamic_field::DynamicFieldType) -> Self {
+        match value {
+            sui_types::dynamic_field::DynamicFieldType::DynamicField => Self::Field,
+            sui_types::dynamic_field::DynamicFieldType::DynamicObject => Self::Object,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ObjectNotFoundError {
     object_id: ObjectId,
@@ -93,6 +93,28 @@ impl StateReader {
         Ok((transaction.into(), effects.into(), events.map(Into::into)))
     }
 
+    pub fn get_transaction_events(
+        &self,
+        digest: &sui_types::digests::TransactionDigest,
+    ) -> crate::Result<Option<sui_types::effects::TransactionEvents>> {
+        use super::transactions::TransactionNotFoundError;
+        use sui_types::effects::TransactionEffectsAPI;
+
+        let effects = self
+            .inner()
+            .get_transaction_effects(digest)?
+            .ok_or(TransactionNotFoundError((*digest).into()))?;
+        if let Some(event_digest) = effects.events_digest() {
+            self.inner()
+                .get_events(event_digest)?
+                .ok_or(TransactionNotFoundError((*digest).into()))?
+                .pipe(Some)
+                .pipe(Ok)
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn get_transaction_response(
         &self,
         digest: sui_sdk2::types::TransactionDigest,
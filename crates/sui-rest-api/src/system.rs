@@ -595,8 +595,8 @@ pub async fn get_gas_info(
     }))
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct GasInfo {
     #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
-    reference_gas_price: u64,
+    pub reference_gas_price: u64,
 }
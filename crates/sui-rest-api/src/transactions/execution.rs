@@ -1,20 +1,30 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::extract::{Query, State};
+use sui_protocol_config::ProtocolConfig;
 use sui_sdk2::types::framework::Coin;
 use sui_sdk2::types::{
-    Address, BalanceChange, CheckpointSequenceNumber, Object, Owner, SignedTransaction,
-    TransactionEffects, TransactionEvents, ValidatorAggregatedSignature,
+    Address, BalanceChange, CheckpointSequenceNumber, Object, ObjectId, Owner, SignedTransaction,
+    Transaction, TransactionEffects, TransactionEvents, ValidatorAggregatedSignature, Version,
 };
+use sui_types::base_types::ObjectID;
+use sui_types::effects::{IDOperation, TransactionEffectsAPI};
+use sui_types::gas::GasCostSummary;
+use sui_types::storage::ObjectStore;
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
 use tap::Pipe;
 
+use crate::objects::ObjectNotFoundError;
+use crate::reader::StateReader;
 use crate::response::Bcs;
-use crate::Result;
+use crate::system::ProtocolNotFoundError;
 use crate::{accept::AcceptFormat, response::ResponseContent};
+use crate::{RestError, Result};
 
 pub const POST_EXECUTE_TRANSACTION_PATH: &str = "/transactions";
 
@@ -31,7 +41,21 @@ pub trait TransactionExecutor: Send + Sync {
         sui_types::quorum_driver_types::QuorumDriverError,
     >;
 
-    //TODO include Simulate functionality
+    /// Executes a transaction against the latest state of the node without committing its results.
+    async fn simulate_transaction(
+        &self,
+        transaction: TransactionData,
+    ) -> Result<SimulateTransactionResult, sui_types::error::SuiError>;
+}
+
+/// The outcome of simulating a transaction with a `TransactionExecutor`.
+pub struct SimulateTransactionResult {
+    pub effects: sui_types::effects::TransactionEffects,
+    pub events: Option<sui_types::effects::TransactionEvents>,
+    pub input_objects: BTreeMap<ObjectID, sui_types::object::Object>,
+    pub output_objects: BTreeMap<ObjectID, sui_types::object::Object>,
+    /// Id of the mock gas coin used to pay for a transaction without a gas payment
+    pub mock_gas_id: Option<ObjectID>,
 }
 
 /// Execute Transaction REST endpoint.
@@ -135,7 +159,7 @@ pub async fn execute_transaction(
 }
 
 /// Query parameters for the execute transaction endpoint
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct ExecuteTransactionQueryParameters {
     // TODO once transaction finality support is more fully implemented up and down the stack, add
     // back in this parameter, which will be mutally-exclusive with the other parameters. When
@@ -167,6 +191,239 @@ pub struct TransactionExecutionResponse {
     output_objects: Option<Vec<Object>>,
 }
 
+pub const POST_SIMULATE_TRANSACTION_PATH: &str = "/transactions/simulate";
+
+/// Simulate Transaction REST endpoint.
+///
+/// Executes the provided BCS `TransactionData` against the latest state of the node without
+/// committing it. No signatures are required and, if the transaction has no gas payment, a mock gas
+/// coin owned by the sender is used to pay for it.
+///
+/// When a gas budget estimate is requested the transaction is simulated with its budget raised to
+/// the largest one its gas payment and the protocol allow, so the returned effects are those of the
+/// transaction with that budget.
+///
+/// A client can signal, using the `Accept` header, the response format as either JSON or BCS.
+pub async fn simulate_transaction(
+    State(executor): State<Arc<dyn TransactionExecutor>>,
+    State(reader): State<StateReader>,
+    Query(parameters): Query<SimulateTransactionQueryParameters>,
+    accept: AcceptFormat,
+    Bcs(transaction): Bcs<Transaction>,
+) -> Result<ResponseContent<TransactionSimulationResponse>> {
+    let mut transaction = TransactionData::from(transaction);
+    let gas_price = transaction.gas_price();
+
+    if parameters.estimate_gas_budget {
+        transaction.gas_data_mut().budget = max_gas_budget(&reader, &transaction)?;
+    }
+
+    let SimulateTransactionResult {
+        effects,
+        events,
+        mut input_objects,
+        mut output_objects,
+        mock_gas_id,
+    } = executor.simulate_transaction(transaction).await?;
+
+    // The mock gas coin isn't part of the chain's state so it is left out of the response
+    if let Some(mock_gas_id) = &mock_gas_id {
+        input_objects.remove(mock_gas_id);
+        output_objects.remove(mock_gas_id);
+    }
+
+    let estimated_gas_budget = parameters
+        .estimate_gas_budget
+        .then(|| estimate_gas_budget(effects.gas_cost_summary(), gas_price));
+
+    let object_changes = parameters
+        .object_changes
+        .then(|| derive_object_changes(&effects, &output_objects, mock_gas_id));
+
+    let effects = TransactionEffects::from(effects);
+    let events = if parameters.events {
+        events.map(Into::into)
+    } else {
+        None
+    };
+
+    let input_objects = input_objects
+        .into_values()
+        .map(Into::into)
+        .collect::<Vec<_>>();
+    let output_objects = output_objects
+        .into_values()
+        .map(Into::into)
+        .collect::<Vec<_>>();
+
+    let balance_changes = parameters
+        .balance_changes
+        .then(|| derive_balance_changes(&effects, &input_objects, &output_objects));
+
+    let response = TransactionSimulationResponse {
+        effects,
+        events,
+        balance_changes,
+        object_changes,
+        input_objects: parameters.input_objects.then_some(input_objects),
+        output_objects: parameters.output_objects.then_some(output_objects),
+        estimated_gas_budget,
+    };
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(response),
+        AcceptFormat::Bcs => ResponseContent::Bcs(response),
+    }
+    .pipe(Ok)
+}
+
+/// Query parameters for the simulate transaction endpoint
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SimulateTransactionQueryParameters {
+    /// Request `TransactionEvents` be included in the Response.
+    #[serde(default)]
+    pub events: bool,
+    /// Request `BalanceChanges` be included in the Response.
+    #[serde(default)]
+    pub balance_changes: bool,
+    /// Request `ObjectChanges` be included in the Response.
+    #[serde(default)]
+    pub object_changes: bool,
+    /// Request input `Object`s be included in the Response.
+    #[serde(default)]
+    pub input_objects: bool,
+    /// Request output `Object`s be included in the Response.
+    #[serde(default)]
+    pub output_objects: bool,
+    /// Request an estimate of the gas budget needed by the transaction be included in the
+    /// Response.
+    #[serde(default)]
+    pub estimate_gas_budget: bool,
+}
+
+/// Response type for the simulate transaction endpoint
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct TransactionSimulationResponse {
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+    pub balance_changes: Option<Vec<BalanceChange>>,
+    pub object_changes: Option<Vec<ObjectChange>>,
+    pub input_objects: Option<Vec<Object>>,
+    pub output_objects: Option<Vec<Object>>,
+    pub estimated_gas_budget: Option<u64>,
+}
+
+/// A change made by a transaction to an object
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ObjectChange {
+    pub object_id: ObjectId,
+    pub change_type: ObjectChangeType,
+    /// Version of the object before the transaction, if it was live
+    pub input_version: Option<Version>,
+    /// Version of the object after the transaction, if it is live
+    pub output_version: Option<Version>,
+    /// Owner of the object after the transaction, if it is live
+    pub owner: Option<Owner>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectChangeType {
+    Created,
+    Published,
+    Mutated,
+    Deleted,
+    Wrapped,
+    Unwrapped,
+}
+
+/// Returns the largest gas budget a transaction can be simulated with: the maximum budget allowed
+/// by the protocol, capped by the balance of the transaction's gas payment if it has one.
+fn max_gas_budget(reader: &StateReader, transaction: &TransactionData) -> Result<u64> {
+    let protocol_version = reader.get_system_state_summary()?.protocol_version;
+    let max_tx_gas = ProtocolConfig::get_for_version_if_supported(
+        protocol_version.into(),
+        reader.inner().get_chain_identifier()?.chain(),
+    )
+    .ok_or_else(|| ProtocolNotFoundError::new(protocol_version))?
+    .max_tx_gas();
+
+    if transaction.gas().is_empty() {
+        return Ok(max_tx_gas);
+    }
+
+    let mut balance = 0u64;
+    for (object_id, _, _) in transaction.gas() {
+        let object = reader
+            .inner()
+            .get_object(object_id)?
+            .ok_or_else(|| ObjectNotFoundError::new((*object_id).into()))?;
+        let coin = sui_types::gas_coin::GasCoin::try_from(&object)
+            .map_err(|e| RestError::new(axum::http::StatusCode::BAD_REQUEST, e.to_string()))?;
+        balance = balance.saturating_add(coin.value());
+    }
+
+    Ok(max_tx_gas.min(balance))
+}
+
+/// Estimates the gas budget of a transaction from the gas costs of its simulation, with a margin
+/// of `GAS_SAFE_OVERHEAD` gas units for variations between the simulation and the execution.
+fn estimate_gas_budget(gas_cost_summary: &GasCostSummary, gas_price: u64) -> u64 {
+    const GAS_SAFE_OVERHEAD: u64 = 1000;
+
+    let safe_overhead = GAS_SAFE_OVERHEAD.saturating_mul(gas_price);
+    let computation_cost = gas_cost_summary
+        .computation_cost
+        .saturating_add(safe_overhead);
+    let net_gas_usage = gas_cost_summary
+        .net_gas_usage()
+        .saturating_add(safe_overhead as i64);
+
+    computation_cost.max(net_gas_usage.max(0) as u64)
+}
+
+fn derive_object_changes(
+    effects: &sui_types::effects::TransactionEffects,
+    output_objects: &BTreeMap<ObjectID, sui_types::object::Object>,
+    mock_gas_id: Option<ObjectID>,
+) -> Vec<ObjectChange> {
+    effects
+        .object_changes()
+        .into_iter()
+        .filter(|change| Some(change.id) != mock_gas_id)
+        .filter_map(|change| {
+            let output_object = output_objects.get(&change.id);
+            let change_type = match (
+                change.id_operation,
+                change.input_version,
+                change.output_version,
+            ) {
+                // Created and wrapped by the same transaction, so it was never observable
+                (IDOperation::Created, _, None) => return None,
+                (IDOperation::Created, _, Some(_))
+                    if output_object.is_some_and(|object| object.is_package()) =>
+                {
+                    ObjectChangeType::Published
+                }
+                (IDOperation::Created, _, Some(_)) => ObjectChangeType::Created,
+                (IDOperation::Deleted, _, _) => ObjectChangeType::Deleted,
+                (IDOperation::None, None, Some(_)) => ObjectChangeType::Unwrapped,
+                (IDOperation::None, Some(_), None) => ObjectChangeType::Wrapped,
+                (IDOperation::None, Some(_), Some(_)) => ObjectChangeType::Mutated,
+                (IDOperation::None, None, None) => return None,
+            };
+
+            Some(ObjectChange {
+                object_id: change.id.into(),
+                change_type,
+                input_version: change.input_version.map(Into::into),
+                output_version: change.output_version.map(Into::into),
+                owner: output_object.map(|object| object.owner.into()),
+            })
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub enum EffectsFinality {
     Certified {
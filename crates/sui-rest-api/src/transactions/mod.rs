@@ -3,9 +3,16 @@
 
 mod execution;
 pub use execution::execute_transaction;
+pub use execution::simulate_transaction;
 pub use execution::ExecuteTransactionQueryParameters;
+pub use execution::ObjectChange;
+pub use execution::ObjectChangeType;
+pub use execution::SimulateTransactionQueryParameters;
+pub use execution::SimulateTransactionResult;
 pub use execution::TransactionExecutor;
+pub use execution::TransactionSimulationResponse;
 pub use execution::POST_EXECUTE_TRANSACTION_PATH;
+pub use execution::POST_SIMULATE_TRANSACTION_PATH;
 
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
@@ -184,7 +191,7 @@ impl serde::Serialize for TransactionCursor {
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct ListTransactionsQueryParameters {
     pub limit: Option<u32>,
     pub start: Option<TransactionCursor>,
@@ -253,6 +253,42 @@ pub async fn metadata(
             let amount = amounts.iter().sum::<u64>();
             (Some(amount), vec![])
         }
+        InternalOperation::PayCoin {
+            amounts, currency, ..
+        } => {
+            let coin_type = currency.coin_type()?;
+            let known_currency = context.coin_metadata.get_currency(&coin_type).await?;
+            match known_currency {
+                Some(known) if !known.is_sui() => {
+                    if known.symbol != currency.symbol || known.decimals != currency.decimals {
+                        return Err(Error::InvalidInput(format!(
+                            "Currency {currency:?} does not match the metadata of its coin type, expected {known:?}"
+                        )));
+                    }
+                }
+                _ => {
+                    return Err(Error::InvalidInput(format!(
+                        "Cannot pay currency {currency:?} with PayCoin"
+                    )))
+                }
+            }
+
+            let amount = amounts.iter().sum::<u64>();
+            let coins = context
+                .client
+                .coin_read_api()
+                .select_coins(
+                    sender,
+                    Some(coin_type.to_canonical_string(true)),
+                    amount.into(),
+                    vec![],
+                )
+                .await?;
+            let coin_refs = coins.into_iter().map(|c| c.object_ref()).collect();
+
+            // The coins paid are passed as objects, SUI is only needed for gas.
+            (Some(0), coin_refs)
+        }
         InternalOperation::Stake { amount, .. } => (*amount, vec![]),
         InternalOperation::WithdrawStake { sender, stake_ids } => {
             let stake_ids = if stake_ids.is_empty() {
@@ -14,7 +14,7 @@ use mysten_metrics::spawn_monitored_task;
 use sui_sdk::SuiClient;
 
 use crate::errors::Error;
-use crate::state::{CheckpointBlockProvider, OnlineServerContext};
+use crate::state::{CheckpointBlockProvider, CoinMetadataCache, OnlineServerContext};
 use crate::types::{Currency, SuiEnv};
 
 /// This lib implements the Rosetta online and offline server defined by the [Rosetta API Spec](https://www.rosetta-api.org/docs/Reference.html)
@@ -30,6 +30,7 @@ pub mod types;
 pub static SUI: Lazy<Currency> = Lazy::new(|| Currency {
     symbol: "SUI".to_string(),
     decimals: 9,
+    metadata: None,
 });
 
 pub struct RosettaOnlineServer {
@@ -39,10 +40,14 @@ pub struct RosettaOnlineServer {
 
 impl RosettaOnlineServer {
     pub fn new(env: SuiEnv, client: SuiClient) -> Self {
-        let blocks = Arc::new(CheckpointBlockProvider::new(client.clone()));
+        let coin_metadata = CoinMetadataCache::new(client.clone());
+        let blocks = Arc::new(CheckpointBlockProvider::new(
+            client.clone(),
+            coin_metadata.clone(),
+        ));
         Self {
             env,
-            context: OnlineServerContext::new(client, blocks),
+            context: OnlineServerContext::new(client, coin_metadata, blocks),
         }
     }
 
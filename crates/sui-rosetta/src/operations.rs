@@ -8,7 +8,7 @@ use std::vec;
 
 use anyhow::anyhow;
 use move_core_types::ident_str;
-use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
 use move_core_types::resolver::ModuleResolver;
 use serde::Deserialize;
 use serde::Serialize;
@@ -29,11 +29,12 @@ use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::TransactionData;
 use sui_types::{SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID};
 
+use crate::state::CoinMetadataCache;
 use crate::types::{
-    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, InternalOperation,
-    OperationIdentifier, OperationStatus, OperationType,
+    AccountIdentifier, Amount, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
+    InternalOperation, OperationIdentifier, OperationStatus, OperationType,
 };
-use crate::Error;
+use crate::{Error, SUI};
 
 #[cfg(test)]
 #[path = "unit_tests/operations_tests.rs"]
@@ -101,6 +102,7 @@ impl Operations {
             .ok_or_else(|| Error::MissingInput("Operation type".into()))?;
         match type_ {
             OperationType::PaySui => self.pay_sui_ops_to_internal(),
+            OperationType::PayCoin => self.pay_coin_ops_to_internal(),
             OperationType::Stake => self.stake_ops_to_internal(),
             OperationType::WithdrawStake => self.withdraw_stake_ops_to_internal(),
             op => Err(Error::UnsupportedOperation(op)),
@@ -108,11 +110,49 @@ impl Operations {
     }
 
     fn pay_sui_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let (sender, recipients, amounts, _) = self.pay_ops_to_internal()?;
+        Ok(InternalOperation::PaySui {
+            sender,
+            recipients,
+            amounts,
+        })
+    }
+
+    fn pay_coin_ops_to_internal(self) -> Result<InternalOperation, Error> {
+        let (sender, recipients, amounts, currencies) = self.pay_ops_to_internal()?;
+        let mut currencies = currencies.into_iter();
+        let currency = currencies
+            .next()
+            .ok_or_else(|| Error::MissingInput("Currency".to_string()))?;
+        if currencies.any(|c| c != currency) {
+            return Err(Error::MalformedOperationError(
+                "PayCoin operations should all be in the same currency.".into(),
+            ));
+        }
+        if currency.is_sui() {
+            return Err(Error::MalformedOperationError(
+                "PayCoin operations cannot pay SUI, use PaySui instead.".into(),
+            ));
+        }
+        Ok(InternalOperation::PayCoin {
+            sender,
+            recipients,
+            amounts,
+            currency,
+        })
+    }
+
+    /// Sender, recipients, amounts and currencies of pay operations.
+    fn pay_ops_to_internal(
+        self,
+    ) -> Result<(SuiAddress, Vec<SuiAddress>, Vec<u64>, Vec<Currency>), Error> {
         let mut recipients = vec![];
         let mut amounts = vec![];
+        let mut currencies = vec![];
         let mut sender = None;
         for op in self {
             if let (Some(amount), Some(account)) = (op.amount.clone(), op.account.clone()) {
+                currencies.push(amount.currency);
                 if amount.value.is_negative() {
                     sender = Some(account.address)
                 } else {
@@ -128,11 +168,7 @@ impl Operations {
             }
         }
         let sender = sender.ok_or_else(|| Error::MissingInput("Sender address".to_string()))?;
-        Ok(InternalOperation::PaySui {
-            sender,
-            recipients,
-            amounts,
-        })
+        Ok((sender, recipients, amounts, currencies))
     }
 
     fn stake_ops_to_internal(self) -> Result<InternalOperation, Error> {
@@ -213,14 +249,17 @@ impl Operations {
         Ok(InternalOperation::WithdrawStake { sender, stake_ids })
     }
 
+    /// `pay_currency` is the currency of the non-SUI coins passed to the transaction as inputs, if
+    /// known, which allows payments from them to be parsed into PayCoin operations.
     fn from_transaction(
         tx: SuiTransactionBlockKind,
         sender: SuiAddress,
         status: Option<OperationStatus>,
+        pay_currency: Option<&Currency>,
     ) -> Result<Vec<Operation>, Error> {
         Ok(match tx {
             SuiTransactionBlockKind::ProgrammableTransaction(pt) => {
-                Self::parse_programmable_transaction(sender, status, pt)?
+                Self::parse_programmable_transaction(sender, status, pt, pay_currency)?
             }
             _ => vec![Operation::generic_op(status, sender, tx)],
         })
@@ -230,10 +269,21 @@ impl Operations {
         sender: SuiAddress,
         status: Option<OperationStatus>,
         pt: SuiProgrammableTransactionBlock,
+        pay_currency: Option<&Currency>,
     ) -> Result<Vec<Operation>, Error> {
         #[derive(Debug)]
         enum KnownValue {
             GasCoin(u64),
+            // Split from a coin of `pay_currency`
+            Coin(u64),
+        }
+        impl KnownValue {
+            fn split(&self) -> fn(u64) -> KnownValue {
+                match self {
+                    KnownValue::GasCoin(_) => KnownValue::GasCoin,
+                    KnownValue::Coin(_) => KnownValue::Coin,
+                }
+            }
         }
         fn resolve_result(
             known_results: &[Vec<KnownValue>],
@@ -249,16 +299,14 @@ impl Operations {
             known_results: &[Vec<KnownValue>],
             coin: SuiArgument,
             amounts: &[SuiArgument],
+            pays_coin: bool,
         ) -> Option<Vec<KnownValue>> {
-            match coin {
-                SuiArgument::Result(i) => {
-                    let KnownValue::GasCoin(_) = resolve_result(known_results, i, 0)?;
-                }
-                SuiArgument::NestedResult(i, j) => {
-                    let KnownValue::GasCoin(_) = resolve_result(known_results, i, j)?;
-                }
-                SuiArgument::GasCoin => (),
-                // Might not be a SUI coin
+            let known_value = match coin {
+                SuiArgument::Result(i) => resolve_result(known_results, i, 0)?.split(),
+                SuiArgument::NestedResult(i, j) => resolve_result(known_results, i, j)?.split(),
+                SuiArgument::GasCoin => KnownValue::GasCoin,
+                // Input coins are only known to be of `pay_currency`
+                SuiArgument::Input(_) if pays_coin => KnownValue::Coin,
                 SuiArgument::Input(_) => return None,
             };
             let amounts = amounts
@@ -273,13 +321,23 @@ impl Operations {
                         | SuiArgument::Result(_)
                         | SuiArgument::NestedResult(_, _) => return None,
                     };
-                    Some(KnownValue::GasCoin(value))
+                    Some(known_value(value))
                 })
                 .collect::<Option<_>>()?;
             Some(amounts)
         }
+        fn merge_coins(
+            primary: SuiArgument,
+            coins: &[SuiArgument],
+            pays_coin: bool,
+        ) -> Option<Vec<KnownValue>> {
+            // Only merging input coins into the coin that is paid from is supported.
+            let is_input = |coin: &SuiArgument| matches!(coin, SuiArgument::Input(_));
+            (pays_coin && is_input(&primary) && coins.iter().all(is_input)).then_some(vec![])
+        }
         fn transfer_object(
             aggregated_recipients: &mut HashMap<SuiAddress, u64>,
+            coin_recipients: &mut HashMap<SuiAddress, u64>,
             inputs: &[SuiCallArg],
             known_results: &[Vec<KnownValue>],
             objs: &[SuiArgument],
@@ -293,17 +351,15 @@ impl Operations {
             };
             for obj in objs {
                 let value = match *obj {
-                    SuiArgument::Result(i) => {
-                        let KnownValue::GasCoin(value) = resolve_result(known_results, i, 0)?;
-                        value
-                    }
-                    SuiArgument::NestedResult(i, j) => {
-                        let KnownValue::GasCoin(value) = resolve_result(known_results, i, j)?;
-                        value
-                    }
+                    SuiArgument::Result(i) => resolve_result(known_results, i, 0)?,
+                    SuiArgument::NestedResult(i, j) => resolve_result(known_results, i, j)?,
                     SuiArgument::GasCoin | SuiArgument::Input(_) => return None,
                 };
-                let aggregate = aggregated_recipients.entry(addr).or_default();
+                let (recipients, value) = match *value {
+                    KnownValue::GasCoin(value) => (&mut *aggregated_recipients, value),
+                    KnownValue::Coin(value) => (&mut *coin_recipients, value),
+                };
+                let aggregate = recipients.entry(addr).or_default();
                 *aggregate += value;
             }
             Some(vec![])
@@ -318,7 +374,9 @@ impl Operations {
                 [_, coin, validator] => {
                     let amount = match coin {
                         SuiArgument::Result(i) =>{
-                            let KnownValue::GasCoin(value) = resolve_result(known_results, *i, 0).ok_or_else(||anyhow!("Cannot resolve Gas coin value at Result({i})"))?;
+                            let KnownValue::GasCoin(value) = resolve_result(known_results, *i, 0).ok_or_else(||anyhow!("Cannot resolve Gas coin value at Result({i})"))? else {
+                                return Ok(None);
+                            };
                             value
                         },
                         _ => return Ok(None),
@@ -363,16 +421,20 @@ impl Operations {
         let SuiProgrammableTransactionBlock { inputs, commands } = &pt;
         let mut known_results: Vec<Vec<KnownValue>> = vec![];
         let mut aggregated_recipients: HashMap<SuiAddress, u64> = HashMap::new();
+        let mut coin_recipients: HashMap<SuiAddress, u64> = HashMap::new();
+        let pays_coin = pay_currency.is_some();
         let mut needs_generic = false;
         let mut operations = vec![];
         let mut stake_ids = vec![];
         for command in commands {
             let result = match command {
                 SuiCommand::SplitCoins(coin, amounts) => {
-                    split_coins(inputs, &known_results, *coin, amounts)
+                    split_coins(inputs, &known_results, *coin, amounts, pays_coin)
                 }
+                SuiCommand::MergeCoins(primary, coins) => merge_coins(*primary, coins, pays_coin),
                 SuiCommand::TransferObjects(objs, addr) => transfer_object(
                     &mut aggregated_recipients,
+                    &mut coin_recipients,
                     inputs,
                     &known_results,
                     objs,
@@ -408,7 +470,10 @@ impl Operations {
             }
         }
 
-        if !needs_generic && !aggregated_recipients.is_empty() {
+        // Transactions paying both SUI and another currency are not parsed into pay operations.
+        let pays_sui_only = aggregated_recipients.is_empty().not() && coin_recipients.is_empty();
+        let pays_coin_only = aggregated_recipients.is_empty() && coin_recipients.is_empty().not();
+        if !needs_generic && pays_sui_only {
             let total_paid: u64 = aggregated_recipients.values().copied().sum();
             operations.extend(
                 aggregated_recipients
@@ -418,6 +483,17 @@ impl Operations {
                     }),
             );
             operations.push(Operation::pay_sui(status, sender, -(total_paid as i128)));
+        } else if let Some(currency) = pay_currency.filter(|_| !needs_generic && pays_coin_only) {
+            let total_paid: u64 = coin_recipients.values().copied().sum();
+            operations.extend(coin_recipients.into_iter().map(|(recipient, amount)| {
+                Operation::pay_coin(status, recipient, amount.into(), currency.clone())
+            }));
+            operations.push(Operation::pay_coin(
+                status,
+                sender,
+                -(total_paid as i128),
+                currency.clone(),
+            ));
         } else if !stake_ids.is_empty() {
             let stake_ids = stake_ids.into_iter().flatten().collect::<Vec<_>>();
             let metadata = stake_ids
@@ -460,26 +536,38 @@ impl Operations {
         gas_used: i128,
         balance_changes: &[BalanceChange],
         status: Option<OperationStatus>,
-        balances: HashMap<SuiAddress, i128>,
+        balances: HashMap<(SuiAddress, Currency), i128>,
+        currencies: &HashMap<TypeTag, Currency>,
     ) -> impl Iterator<Item = Operation> {
         let mut balances = balance_changes
             .iter()
             .fold(balances, |mut balances, balance_change| {
                 // Rosetta only care about address owner
                 if let Owner::AddressOwner(owner) = balance_change.owner {
-                    if balance_change.coin_type == GAS::type_tag() {
-                        *balances.entry(owner).or_default() += balance_change.amount;
+                    let currency = if balance_change.coin_type == GAS::type_tag() {
+                        Some(&*SUI)
+                    } else {
+                        currencies.get(&balance_change.coin_type)
+                    };
+                    if let Some(currency) = currency {
+                        *balances.entry((owner, currency.clone())).or_default() +=
+                            balance_change.amount;
                     }
                 }
                 balances
             });
         // separate gas from balances
-        *balances.entry(gas_owner).or_default() -= gas_used;
+        *balances.entry((gas_owner, SUI.clone())).or_default() -= gas_used;
 
-        let balance_change = balances
-            .into_iter()
-            .filter(|(_, amount)| *amount != 0)
-            .map(move |(addr, amount)| Operation::balance_change(status, addr, amount));
+        let balance_change = balances.into_iter().filter(|(_, amount)| *amount != 0).map(
+            move |((addr, currency), amount)| {
+                if currency.is_sui() {
+                    Operation::balance_change(status, addr, amount)
+                } else {
+                    Operation::coin_balance_change(status, addr, amount, currency)
+                }
+            },
+        );
 
         let gas = if gas_used != 0 {
             vec![Operation::gas(gas_owner, gas_used)]
@@ -495,17 +583,55 @@ impl TryFrom<SuiTransactionBlockData> for Operations {
     type Error = Error;
     fn try_from(data: SuiTransactionBlockData) -> Result<Self, Self::Error> {
         let sender = *data.sender();
+        let pay_currency = match data.transaction() {
+            SuiTransactionBlockKind::ProgrammableTransaction(pt) => pay_coin_currency(&pt.inputs),
+            _ => None,
+        };
         Ok(Self::new(Self::from_transaction(
             data.transaction().clone(),
             sender,
             None,
+            pay_currency.as_ref(),
         )?))
     }
 }
 
-impl TryFrom<SuiTransactionBlockResponse> for Operations {
-    type Error = Error;
-    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+/// The currency that `InternalOperation::PayCoin` passes as the last input of the transactions
+/// it builds, if any.
+fn pay_coin_currency(inputs: &[SuiCallArg]) -> Option<Currency> {
+    // The input is unused by the transaction, so its type is unknown and it is left as bytes.
+    let bytes = inputs
+        .last()?
+        .pure()?
+        .to_json_value()
+        .as_array()?
+        .iter()
+        .map(|b| u8::try_from(b.as_u64()?).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let currency: Currency = serde_json::from_str(&bcs::from_bytes::<String>(&bytes).ok()?).ok()?;
+    (!currency.is_sui()).then_some(currency)
+}
+
+impl Operations {
+    /// Parse the operations of an executed transaction, including the changes to the balances of
+    /// non-SUI coins that have `CoinMetadata`.
+    pub async fn try_from_response(
+        response: SuiTransactionBlockResponse,
+        coin_metadata: &CoinMetadataCache,
+    ) -> Result<Self, Error> {
+        let currencies = match &response.balance_changes {
+            Some(balance_changes) => coin_metadata.get_currencies(balance_changes).await?,
+            None => HashMap::new(),
+        };
+        Self::from_response(response, &currencies)
+    }
+
+    /// `currencies` are the currencies of the non-SUI coin types whose balance changes are
+    /// reported, changes to other coin types are ignored.
+    fn from_response(
+        response: SuiTransactionBlockResponse,
+        currencies: &HashMap<TypeTag, Currency>,
+    ) -> Result<Self, Error> {
         let tx = response
             .transaction
             .ok_or_else(|| anyhow!("Response input should not be empty"))?;
@@ -520,7 +646,17 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
             - gas_summary.computation_cost as i128;
 
         let status = Some(effect.into_status().into());
-        let ops: Operations = tx.data.try_into()?;
+        // Coins passed as inputs can only be attributed to a currency if a single non-SUI coin
+        // type had its balance changed.
+        let pay_currency = (currencies.len() == 1)
+            .then(|| currencies.values().next())
+            .flatten();
+        let ops = Operations::new(Self::from_transaction(
+            tx.data.transaction().clone(),
+            sender,
+            None,
+            pay_currency,
+        )?);
         let ops = ops.set_status(status).into_iter();
 
         // We will need to subtract the operation amounts from the actual balance
@@ -532,7 +668,9 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
                     if let (Some(acc), Some(amount), Some(OperationStatus::Success)) =
                         (&op.account, &op.amount, &op.status)
                     {
-                        *balances.entry(acc.address).or_default() -= amount.value;
+                        *balances
+                            .entry((acc.address, amount.currency.clone()))
+                            .or_default() -= amount.value;
                     }
                     balances
                 });
@@ -564,8 +702,8 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
             }
         }
         let staking_balance = if principal_amounts != 0 {
-            *accounted_balances.entry(sender).or_default() -= principal_amounts;
-            *accounted_balances.entry(sender).or_default() -= reward_amounts;
+            *accounted_balances.entry((sender, SUI.clone())).or_default() -= principal_amounts;
+            *accounted_balances.entry((sender, SUI.clone())).or_default() -= reward_amounts;
             vec![
                 Operation::stake_principle(status, sender, principal_amounts),
                 Operation::stake_reward(status, sender, reward_amounts),
@@ -583,6 +721,7 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
                 .ok_or_else(|| anyhow!("Response balance changes should not be empty."))?,
             status,
             accounted_balances,
+            currencies,
         );
 
         Ok(ops
@@ -593,6 +732,14 @@ impl TryFrom<SuiTransactionBlockResponse> for Operations {
     }
 }
 
+/// Only reports changes to SUI balances, see [`Operations::try_from_response`].
+impl TryFrom<SuiTransactionBlockResponse> for Operations {
+    type Error = Error;
+    fn try_from(response: SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        Self::from_response(response, &HashMap::new())
+    }
+}
+
 fn is_unstake_event(tag: &StructTag) -> bool {
     tag.address == SUI_SYSTEM_ADDRESS
         && tag.module.as_ident_str() == ident_str!("validator")
@@ -698,6 +845,23 @@ impl Operation {
         }
     }
 
+    fn pay_coin(
+        status: Option<OperationStatus>,
+        address: SuiAddress,
+        amount: i128,
+        currency: Currency,
+    ) -> Self {
+        Operation {
+            operation_identifier: Default::default(),
+            type_: OperationType::PayCoin,
+            status,
+            account: Some(address.into()),
+            amount: Some(Amount::new_with_currency(amount, currency)),
+            coin_change: None,
+            metadata: None,
+        }
+    }
+
     fn balance_change(status: Option<OperationStatus>, addr: SuiAddress, amount: i128) -> Self {
         Self {
             operation_identifier: Default::default(),
@@ -709,6 +873,22 @@ impl Operation {
             metadata: None,
         }
     }
+    fn coin_balance_change(
+        status: Option<OperationStatus>,
+        addr: SuiAddress,
+        amount: i128,
+        currency: Currency,
+    ) -> Self {
+        Self {
+            operation_identifier: Default::default(),
+            type_: OperationType::CoinBalanceChange,
+            status,
+            account: Some(addr.into()),
+            amount: Some(Amount::new_with_currency(amount, currency)),
+            coin_change: None,
+            metadata: None,
+        }
+    }
     fn gas(addr: SuiAddress, amount: i128) -> Self {
         Self {
             operation_identifier: Default::default(),
@@ -3,14 +3,17 @@
 
 use crate::operations::Operations;
 use crate::types::{
-    Block, BlockHash, BlockIdentifier, BlockResponse, Transaction, TransactionIdentifier,
+    Block, BlockHash, BlockIdentifier, BlockResponse, Currency, Transaction, TransactionIdentifier,
 };
-use crate::Error;
+use crate::{Error, SUI};
 use async_trait::async_trait;
-use std::sync::Arc;
-use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use move_core_types::language_storage::TypeTag;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use sui_json_rpc_types::{BalanceChange, SuiTransactionBlockResponseOptions};
 use sui_sdk::rpc_types::Checkpoint;
 use sui_sdk::SuiClient;
+use sui_types::gas_coin::GAS;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 #[cfg(test)]
@@ -20,13 +23,19 @@ mod balance_changing_tx_tests;
 #[derive(Clone)]
 pub struct OnlineServerContext {
     pub client: SuiClient,
+    pub coin_metadata: CoinMetadataCache,
     block_provider: Arc<dyn BlockProvider + Send + Sync>,
 }
 
 impl OnlineServerContext {
-    pub fn new(client: SuiClient, block_provider: Arc<dyn BlockProvider + Send + Sync>) -> Self {
+    pub fn new(
+        client: SuiClient,
+        coin_metadata: CoinMetadataCache,
+        block_provider: Arc<dyn BlockProvider + Send + Sync>,
+    ) -> Self {
         Self {
             client,
+            coin_metadata,
             block_provider,
         }
     }
@@ -53,6 +62,7 @@ pub trait BlockProvider {
 #[derive(Clone)]
 pub struct CheckpointBlockProvider {
     client: SuiClient,
+    coin_metadata: CoinMetadataCache,
 }
 
 #[async_trait]
@@ -103,8 +113,11 @@ impl BlockProvider for CheckpointBlockProvider {
 }
 
 impl CheckpointBlockProvider {
-    pub fn new(client: SuiClient) -> Self {
-        Self { client }
+    pub fn new(client: SuiClient, coin_metadata: CoinMetadataCache) -> Self {
+        Self {
+            client,
+            coin_metadata,
+        }
     }
 
     async fn create_block_response(&self, checkpoint: Checkpoint) -> Result<BlockResponse, Error> {
@@ -127,7 +140,7 @@ impl CheckpointBlockProvider {
             for tx in transaction_responses.into_iter() {
                 transactions.push(Transaction {
                     transaction_identifier: TransactionIdentifier { hash: tx.digest },
-                    operations: Operations::try_from(tx)?,
+                    operations: Operations::try_from_response(tx, &self.coin_metadata).await?,
                     related_transactions: vec![],
                     metadata: None,
                 })
@@ -176,3 +189,60 @@ impl CheckpointBlockProvider {
         })
     }
 }
+
+/// Resolves the currencies of coin types from their `CoinMetadata`. Results are cached, as the
+/// metadata of a coin type is created along with it, and its symbol and decimals rarely change.
+#[derive(Clone)]
+pub struct CoinMetadataCache {
+    client: SuiClient,
+    currencies: Arc<Mutex<HashMap<TypeTag, Option<Currency>>>>,
+}
+
+impl CoinMetadataCache {
+    pub fn new(client: SuiClient) -> Self {
+        Self {
+            client,
+            currencies: Default::default(),
+        }
+    }
+
+    /// The currency of `Coin<coin_type>`, or `None` if the coin type has no `CoinMetadata`.
+    pub async fn get_currency(&self, coin_type: &TypeTag) -> Result<Option<Currency>, Error> {
+        if *coin_type == GAS::type_tag() {
+            return Ok(Some(SUI.clone()));
+        }
+        if let Some(currency) = self.currencies.lock().unwrap().get(coin_type) {
+            return Ok(currency.clone());
+        }
+
+        let currency = self
+            .client
+            .coin_read_api()
+            .get_coin_metadata(coin_type.to_canonical_string(true))
+            .await?
+            .map(|metadata| Currency::new(coin_type, metadata));
+        self.currencies
+            .lock()
+            .unwrap()
+            .insert(coin_type.clone(), currency.clone());
+        Ok(currency)
+    }
+
+    /// The currencies of the non-SUI coin types in `balance_changes`, leaving out coin types that
+    /// have no `CoinMetadata`.
+    pub async fn get_currencies(
+        &self,
+        balance_changes: &[BalanceChange],
+    ) -> Result<HashMap<TypeTag, Currency>, Error> {
+        let mut currencies = HashMap::new();
+        for BalanceChange { coin_type, .. } in balance_changes {
+            if *coin_type == GAS::type_tag() || currencies.contains_key(coin_type) {
+                continue;
+            }
+            if let Some(currency) = self.get_currency(coin_type).await? {
+                currencies.insert(coin_type.clone(), currency);
+            }
+        }
+        Ok(currencies)
+    }
+}
@@ -14,16 +14,18 @@ use serde_json::Value;
 use strum_macros::EnumIter;
 use strum_macros::EnumString;
 
-use sui_sdk::rpc_types::{SuiExecutionStatus, SuiTransactionBlockKind};
+use move_core_types::language_storage::TypeTag;
+use sui_sdk::rpc_types::{SuiCoinMetadata, SuiExecutionStatus, SuiTransactionBlockKind};
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::crypto::PublicKey as SuiPublicKey;
 use sui_types::crypto::SignatureScheme;
+use sui_types::gas_coin::GAS;
 use sui_types::governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages_checkpoint::CheckpointDigest;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{Argument, CallArg, Command, ObjectArg, TransactionData};
-use sui_types::SUI_SYSTEM_PACKAGE_ID;
+use sui_types::{parse_sui_type_tag, SUI_SYSTEM_PACKAGE_ID};
 
 use crate::errors::{Error, ErrorType};
 use crate::operations::Operations;
@@ -95,10 +97,44 @@ impl From<SuiAddress> for AccountIdentifier {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Currency {
     pub symbol: String,
     pub decimals: u64,
+    /// Identifies the coin type of non-SUI currencies, `None` for SUI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CurrencyMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CurrencyMetadata {
+    pub coin_type: String,
+}
+
+impl Currency {
+    /// Currency of `Coin<coin_type>`, as described by its `CoinMetadata`.
+    pub fn new(coin_type: &TypeTag, metadata: SuiCoinMetadata) -> Self {
+        Self {
+            symbol: metadata.symbol,
+            decimals: metadata.decimals.into(),
+            metadata: Some(CurrencyMetadata {
+                coin_type: coin_type.to_canonical_string(true),
+            }),
+        }
+    }
+
+    pub fn is_sui(&self) -> bool {
+        self.metadata.is_none()
+    }
+
+    /// The `T` in `Coin<T>` for this currency.
+    pub fn coin_type(&self) -> Result<TypeTag, Error> {
+        match &self.metadata {
+            Some(CurrencyMetadata { coin_type }) => parse_sui_type_tag(coin_type)
+                .map_err(|e| Error::InvalidInput(format!("Invalid coin type {coin_type}: {e}"))),
+            None => Ok(GAS::type_tag()),
+        }
+    }
 }
 #[derive(Serialize, Deserialize)]
 pub struct AccountBalanceRequest {
@@ -159,6 +195,13 @@ impl Amount {
             metadata: None,
         }
     }
+    pub fn new_with_currency(value: i128, currency: Currency) -> Self {
+        Self {
+            value,
+            currency,
+            metadata: None,
+        }
+    }
     pub fn new_from_sub_balances(sub_balances: Vec<SubBalance>) -> Self {
         let value = sub_balances.iter().map(|b| b.value).sum();
 
@@ -197,6 +240,8 @@ pub struct AccountCoinsRequest {
     pub network_identifier: NetworkIdentifier,
     pub account_identifier: AccountIdentifier,
     pub include_mempool: bool,
+    #[serde(default)]
+    pub currencies: Vec<Currency>,
 }
 #[derive(Serialize)]
 pub struct AccountCoinsResponse {
@@ -216,6 +261,12 @@ pub struct Coin {
 
 impl From<sui_sdk::rpc_types::Coin> for Coin {
     fn from(coin: sui_sdk::rpc_types::Coin) -> Self {
+        Self::new(coin, SUI.clone())
+    }
+}
+
+impl Coin {
+    pub fn new(coin: sui_sdk::rpc_types::Coin, currency: Currency) -> Self {
         Self {
             coin_identifier: CoinIdentifier {
                 identifier: CoinID {
@@ -223,11 +274,7 @@ impl From<sui_sdk::rpc_types::Coin> for Coin {
                     version: coin.version,
                 },
             },
-            amount: Amount {
-                value: coin.balance as i128,
-                currency: SUI.clone(),
-                metadata: None,
-            },
+            amount: Amount::new_with_currency(coin.balance as i128, currency),
         }
     }
 }
@@ -401,10 +448,12 @@ pub enum OperationType {
     // Balance changing operations from TransactionEffect
     Gas,
     SuiBalanceChange,
+    CoinBalanceChange,
     StakeReward,
     StakePrinciple,
     // sui-rosetta supported operation type
     PaySui,
+    PayCoin,
     Stake,
     WithdrawStake,
     // All other Sui transaction types, readonly
@@ -863,6 +912,12 @@ pub enum InternalOperation {
         recipients: Vec<SuiAddress>,
         amounts: Vec<u64>,
     },
+    PayCoin {
+        sender: SuiAddress,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        currency: Currency,
+    },
     Stake {
         sender: SuiAddress,
         validator: SuiAddress,
@@ -879,6 +934,7 @@ impl InternalOperation {
     pub fn sender(&self) -> SuiAddress {
         match self {
             InternalOperation::PaySui { sender, .. }
+            | InternalOperation::PayCoin { sender, .. }
             | InternalOperation::Stake { sender, .. }
             | InternalOperation::WithdrawStake { sender, .. } => *sender,
         }
@@ -895,6 +951,21 @@ impl InternalOperation {
                 builder.pay_sui(recipients, amounts)?;
                 builder.finish()
             }
+            // The coins to pay with are selected by /construction/metadata, and are passed in
+            // as objects, while gas is paid from the SUI coins.
+            Self::PayCoin {
+                recipients,
+                amounts,
+                currency,
+                ..
+            } => {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                builder.pay(metadata.objects, recipients, amounts)?;
+                // [WORKAROUND] - the currency is passed as an unused last input, so that
+                // /construction/parse, which is offline, can tell what the coins paid are.
+                builder.pure(serde_json::to_string(&currency).map_err(anyhow::Error::from)?)?;
+                builder.finish()
+            }
             InternalOperation::Stake {
                 validator, amount, ..
             } => {
@@ -7,8 +7,9 @@ use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::transaction::{CallArg, TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
 
-use crate::operations::Operations;
-use crate::types::ConstructionMetadata;
+use crate::operations::{Operation, Operations};
+use crate::types::{ConstructionMetadata, Currency, CurrencyMetadata};
+use crate::SUI;
 
 #[tokio::test]
 async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
@@ -50,6 +51,94 @@ async fn test_operation_data_parsing() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_pay_coin_operation_data_parsing() -> Result<(), anyhow::Error> {
+    let object_ref = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::random(),
+        )
+    };
+    let gas = object_ref();
+    let coins = vec![object_ref(), object_ref()];
+
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = SuiAddress::random_for_testing_only();
+    let currency = Currency {
+        symbol: "TEST".to_string(),
+        decimals: 6,
+        metadata: Some(CurrencyMetadata {
+            coin_type: "0x1234::test::TEST".to_string(),
+        }),
+    };
+
+    let ops = Operations::new(vec![
+        Operation::pay_coin(None, recipient, 5000, currency.clone()),
+        Operation::pay_coin(None, sender, -5000, currency.clone()),
+    ]);
+    let gas_price = 10;
+    let metadata = ConstructionMetadata {
+        sender,
+        coins: vec![gas],
+        objects: coins,
+        total_coin_value: 0,
+        gas_price,
+        budget: TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+    };
+    let data = ops.clone().into_internal()?.try_into_data(metadata)?;
+
+    let parsed: Operations = data.try_into()?;
+    assert_eq!(ops, parsed);
+
+    Ok(())
+}
+
+#[test]
+fn test_pay_coin_ops_need_single_currency() {
+    let sender = SuiAddress::random_for_testing_only();
+    let currency = Currency {
+        symbol: "TEST".to_string(),
+        decimals: 6,
+        metadata: Some(CurrencyMetadata {
+            coin_type: "0x1234::test::TEST".to_string(),
+        }),
+    };
+
+    let ops = Operations::new(vec![
+        Operation::pay_coin(
+            None,
+            SuiAddress::random_for_testing_only(),
+            5000,
+            SUI.clone(),
+        ),
+        Operation::pay_coin(None, sender, -5000, currency.clone()),
+    ]);
+    assert!(ops.into_internal().is_err());
+
+    let ops = Operations::new(vec![
+        Operation::pay_coin(
+            None,
+            SuiAddress::random_for_testing_only(),
+            5000,
+            SUI.clone(),
+        ),
+        Operation::pay_coin(None, sender, -5000, SUI.clone()),
+    ]);
+    assert!(ops.into_internal().is_err());
+}
+
+#[test]
+fn test_sui_currency_serialization() {
+    // SUI amounts are serialized without currency metadata, as they were before other currencies
+    // were supported.
+    assert_eq!(
+        serde_json::json!({"symbol": "SUI", "decimals": 9}),
+        serde_json::to_value(&*SUI).unwrap()
+    );
+}
+
 #[tokio::test]
 async fn test_sui_json() {
     let arg1 = CallArg::Pure(bcs::to_bytes(&1000000u64).unwrap());
@@ -15,6 +15,8 @@ pub enum Error {
     #[error(transparent)]
     JsonRpcError(JsonRpcError),
     #[error(transparent)]
+    HttpError(#[from] reqwest::Error),
+    #[error(transparent)]
     BcsSerialisationError(#[from] bcs::Error),
     #[error(transparent)]
     UserInputError(#[from] UserInputError),
@@ -31,4 +33,10 @@ pub enum Error {
     },
     #[error("Insufficient fund for address [{address}], requested amount: {amount}")]
     InsufficientFund { address: SuiAddress, amount: u128 },
+    #[cfg(feature = "grpc")]
+    #[error(transparent)]
+    GrpcError(#[from] tonic::Status),
+    #[cfg(feature = "grpc")]
+    #[error(transparent)]
+    GrpcTransportError(#[from] tonic::transport::Error),
 }
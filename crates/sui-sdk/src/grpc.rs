@@ -0,0 +1,137 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client for the BCS encoded gRPC read api of a fullnode, a faster alternative to the read
+//! methods of [crate::apis::ReadApi] and [crate::apis::CoinReadApi] that returns native Sui types.
+//! Use [crate::SuiClientBuilder::build_grpc] to create one.
+
+use tonic::metadata::MetadataMap;
+use tonic::transport::Channel;
+use tonic::Request;
+
+pub use sui_json_rpc_api::grpc::{
+    Balance, Coin, GetCoinsResponse, ObjectResponse, TransactionBlockOptions,
+    TransactionBlockResponse,
+};
+use sui_json_rpc_api::grpc::{
+    GetAllBalancesRequest, GetBalanceRequest, GetCoinsRequest, GetObjectRequest,
+    GetTransactionBlockRequest, MultiGetObjectsRequest, MultiGetTransactionBlocksRequest,
+    ReadServiceClient,
+};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+
+use crate::error::SuiRpcResult;
+
+#[derive(Clone, Debug)]
+pub struct GrpcReadClient {
+    inner: ReadServiceClient<Channel>,
+    metadata: MetadataMap,
+}
+
+impl GrpcReadClient {
+    pub(crate) fn new(channel: Channel, metadata: MetadataMap) -> Self {
+        Self {
+            inner: ReadServiceClient::new(channel),
+            metadata,
+        }
+    }
+
+    fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        *request.metadata_mut() = self.metadata.clone();
+        request
+    }
+
+    /// Return the latest version of an object, or why it is not available.
+    pub async fn get_object(&self, object_id: ObjectID) -> SuiRpcResult<ObjectResponse> {
+        let request = self.request(GetObjectRequest { object_id });
+        Ok(self.inner.clone().get_object(request).await?.into_inner())
+    }
+
+    /// Return the latest versions of a list of objects, in the order of `object_ids`.
+    pub async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> SuiRpcResult<Vec<ObjectResponse>> {
+        let request = self.request(MultiGetObjectsRequest { object_ids });
+        Ok(self
+            .inner
+            .clone()
+            .multi_get_objects(request)
+            .await?
+            .into_inner()
+            .objects)
+    }
+
+    /// Return a transaction block, failing with a `NotFound` status if the node does not know it.
+    pub async fn get_transaction_block(
+        &self,
+        digest: TransactionDigest,
+        options: TransactionBlockOptions,
+    ) -> SuiRpcResult<TransactionBlockResponse> {
+        let request = self.request(GetTransactionBlockRequest { digest, options });
+        Ok(self
+            .inner
+            .clone()
+            .get_transaction_block(request)
+            .await?
+            .into_inner())
+    }
+
+    /// Return a list of transaction blocks in the order of `digests`, with `None` for the ones
+    /// the node does not know.
+    pub async fn multi_get_transaction_blocks(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: TransactionBlockOptions,
+    ) -> SuiRpcResult<Vec<Option<TransactionBlockResponse>>> {
+        let request = self.request(MultiGetTransactionBlocksRequest { digests, options });
+        Ok(self
+            .inner
+            .clone()
+            .multi_get_transaction_blocks(request)
+            .await?
+            .into_inner()
+            .transactions)
+    }
+
+    /// Return the balance of the given coin type owned by `owner`, SUI if `coin_type` is `None`.
+    pub async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> SuiRpcResult<Balance> {
+        let request = self.request(GetBalanceRequest { owner, coin_type });
+        Ok(self.inner.clone().get_balance(request).await?.into_inner())
+    }
+
+    /// Return the balances of all coin types owned by `owner`.
+    pub async fn get_all_balances(&self, owner: SuiAddress) -> SuiRpcResult<Vec<Balance>> {
+        let request = self.request(GetAllBalancesRequest { owner });
+        Ok(self
+            .inner
+            .clone()
+            .get_all_balances(request)
+            .await?
+            .into_inner()
+            .balances)
+    }
+
+    /// Return a page of the coins of the given type owned by `owner`, SUI if `coin_type` is
+    /// `None`. Pass the `next_cursor` of a page as `cursor` to fetch the next one.
+    pub async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<u32>,
+    ) -> SuiRpcResult<GetCoinsResponse> {
+        let request = self.request(GetCoinsRequest {
+            owner,
+            coin_type,
+            cursor,
+            limit,
+        });
+        Ok(self.inner.clone().get_coins(request).await?.into_inner())
+    }
+}
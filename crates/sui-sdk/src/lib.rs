@@ -93,6 +93,8 @@ use sui_json_rpc_types::{
     ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
     SuiObjectResponseQuery,
 };
+#[cfg(feature = "rest-api")]
+pub use sui_rest_api as rest_api;
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
@@ -102,6 +104,8 @@ use crate::error::{Error, SuiRpcResult};
 
 pub mod apis;
 pub mod error;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod json_rpc_error;
 pub mod sui_client_config;
 pub mod wallet_context;
@@ -343,6 +347,112 @@ impl SuiClientBuilder {
         self.build(SUI_TESTNET_URL).await
     }
 
+    /// Returns a client for the REST api of a fullnode, an alternative to the JSON RPC
+    /// [SuiClient]. `url` is the root of the REST api, e.g. `http://127.0.0.1:9000/rest`.
+    ///
+    /// The request timeout and basic auth credentials of the builder apply to the requests of the
+    /// returned client. Checkpoint subscriptions stay open for as long as the node pushes
+    /// checkpoints, so only the time to connect is limited for them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let rest = SuiClientBuilder::default().build_rest("http://127.0.0.1:9000/rest")?;
+    ///
+    ///     let checkpoint = rest.get_latest_checkpoint().await?;
+    ///     println!("{:?}", checkpoint);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "rest-api")]
+    pub fn build_rest(self, url: impl AsRef<str>) -> SuiRpcResult<sui_rest_api::Client> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            CLIENT_SDK_VERSION_HEADER,
+            reqwest::header::HeaderValue::from_static(env!("CARGO_PKG_VERSION")),
+        );
+        headers.insert(
+            CLIENT_SDK_TYPE_HEADER,
+            reqwest::header::HeaderValue::from_static("rust"),
+        );
+
+        if let Some((username, password)) = self.basic_auth {
+            let auth = base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", username, password));
+            headers.insert(
+                reqwest::header::AUTHORIZATION,
+                reqwest::header::HeaderValue::from_str(&format!("Basic {}", auth)).unwrap(),
+            );
+        }
+
+        let inner = reqwest::Client::builder()
+            .default_headers(headers)
+            .connect_timeout(self.request_timeout)
+            .build()?;
+
+        Ok(sui_rest_api::Client::new_with_client(url.as_ref(), inner)
+            .with_request_timeout(self.request_timeout))
+    }
+
+    /// Returns a client for the BCS encoded gRPC read api of a fullnode, a faster alternative to
+    /// the read methods of the JSON RPC [SuiClient]. The api is served on the JSON RPC address, so
+    /// `url` is the same as the one passed to [Self::build], e.g. `http://127.0.0.1:9000`.
+    ///
+    /// The request timeout and basic auth credentials of the builder apply to the requests of the
+    /// returned client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::SuiClientBuilder;
+    /// use sui_sdk::types::base_types::ObjectID;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let grpc = SuiClientBuilder::default()
+    ///         .build_grpc("http://127.0.0.1:9000")
+    ///         .await?;
+    ///
+    ///     let object = grpc.get_object(ObjectID::from_hex_literal("0x5")?).await?;
+    ///     println!("{:?}", object);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "grpc")]
+    pub async fn build_grpc(self, url: impl AsRef<str>) -> SuiRpcResult<grpc::GrpcReadClient> {
+        use tonic::metadata::{AsciiMetadataValue, MetadataMap};
+
+        let mut metadata = MetadataMap::new();
+        metadata.insert(
+            CLIENT_SDK_VERSION_HEADER,
+            AsciiMetadataValue::from_static(env!("CARGO_PKG_VERSION")),
+        );
+        metadata.insert(
+            CLIENT_SDK_TYPE_HEADER,
+            AsciiMetadataValue::from_static("rust"),
+        );
+
+        if let Some((username, password)) = self.basic_auth {
+            let auth = base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", username, password));
+            metadata.insert(
+                "authorization",
+                AsciiMetadataValue::try_from(format!("Basic {}", auth)).unwrap(),
+            );
+        }
+
+        let channel = tonic::transport::Endpoint::from_shared(url.as_ref().to_string())?
+            .timeout(self.request_timeout)
+            .connect()
+            .await?;
+
+        Ok(grpc::GrpcReadClient::new(channel, metadata))
+    }
+
     /// Return the server information as a `ServerInfo` structure.
     ///
     /// Fails with an error if it cannot call the RPC discover.
@@ -14,7 +14,7 @@ use sui_json_rpc_types::{
     SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
     SuiObjectResponseQuery, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_types::crypto::SuiKeyPair;
 use sui_types::gas_coin::GasCoin;
@@ -35,13 +35,19 @@ impl WalletContext {
         request_timeout: Option<std::time::Duration>,
         max_concurrent_requests: Option<u64>,
     ) -> Result<Self, anyhow::Error> {
-        let config: SuiClientConfig = PersistedConfig::read(config_path).map_err(|err| {
+        let mut config: SuiClientConfig = PersistedConfig::read(config_path).map_err(|err| {
             anyhow!(
                 "Cannot open wallet config file at {:?}. Err: {err}",
                 config_path
             )
         })?;
 
+        // Keys encrypted at rest need to be unlocked before they can be used to sign, which is
+        // only done once they are needed, so that read-only commands don't ask for a passphrase.
+        if let Keystore::File(keystore) = &mut config.keystore {
+            keystore.unlock_on_use();
+        }
+
         let config = config.persisted(config_path);
         let context = Self {
             config,
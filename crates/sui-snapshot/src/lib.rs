@@ -5,6 +5,7 @@
 #[cfg(test)]
 mod tests;
 
+pub mod filter;
 pub mod reader;
 pub mod uploader;
 mod writer;
@@ -72,6 +73,19 @@ use tokio::time::Instant;
 ///     - epoch_1/
 ///       - 1_1.obj
 ///       - ...
+///     - epoch_2/ (incremental)
+///       - 1_1.obj
+///       - 1_1.ref
+///       - 1_1.del
+///       - MANIFEST
+/// An incremental snapshot only holds the difference between the live object set at the end of
+/// its epoch and the one at the end of the base epoch its MANIFEST refers to. Its *.obj and *.ref
+/// files contain the objects added to the live object set, i.e. created, mutated or unwrapped
+/// objects. Its *.del files contain the references of the objects removed from it, i.e. deleted
+/// or wrapped objects and the previous versions of mutated ones. The base is itself either a full
+/// or an incremental snapshot, and restoring an incremental snapshot means restoring the full
+/// snapshot at the root of the chain and applying every incremental snapshot on top of it, in
+/// order.
 /// Object File Disk Format
 ///┌──────────────────────────────┐
 ///│  magic(0x00B7EC75) <4 byte>  │
@@ -106,6 +120,9 @@ use tokio::time::Instant;
 ///│         data (<(address_len + 8 + 32) bytes>)    │
 ///└───────────────┴───────────────────┴──────────────┘
 ///
+/// DELETED File Disk Format
+/// Same as the REFERENCE file format, including the magic.
+///
 /// MANIFEST File Disk Format
 ///┌──────────────────────────────┐
 ///│  magic(0x00C0FFEE) <4 byte>  │
@@ -142,6 +159,7 @@ const FILE_METADATA_BYTES: usize =
 pub enum FileType {
     Object = 0,
     Reference,
+    Deleted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -162,6 +180,9 @@ impl FileMetadata {
             FileType::Reference => {
                 dir_path.child(&*format!("{}_{}.ref", self.bucket_num, self.part_num))
             }
+            FileType::Deleted => {
+                dir_path.child(&*format!("{}_{}.del", self.bucket_num, self.part_num))
+            }
         }
     }
     pub fn local_file_path(&self, root_path: &std::path::Path, dir_path: &Path) -> Result<PathBuf> {
@@ -177,30 +198,53 @@ pub struct ManifestV1 {
     pub epoch: u64,
 }
 
+/// Manifest of an incremental snapshot, whose files describe the changes to the live object set
+/// since the end of `base_epoch`.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct IncrementalManifestV1 {
+    pub snapshot_version: u8,
+    pub address_length: u64,
+    pub file_metadata: Vec<FileMetadata>,
+    pub epoch: u64,
+    pub base_epoch: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum Manifest {
     V1(ManifestV1),
+    IncrementalV1(IncrementalManifestV1),
 }
 
 impl Manifest {
     pub fn snapshot_version(&self) -> u8 {
         match self {
             Self::V1(manifest) => manifest.snapshot_version,
+            Self::IncrementalV1(manifest) => manifest.snapshot_version,
         }
     }
     pub fn address_length(&self) -> u64 {
         match self {
             Self::V1(manifest) => manifest.address_length,
+            Self::IncrementalV1(manifest) => manifest.address_length,
         }
     }
     pub fn file_metadata(&self) -> &Vec<FileMetadata> {
         match self {
             Self::V1(manifest) => &manifest.file_metadata,
+            Self::IncrementalV1(manifest) => &manifest.file_metadata,
         }
     }
     pub fn epoch(&self) -> u64 {
         match self {
             Self::V1(manifest) => manifest.epoch,
+            Self::IncrementalV1(manifest) => manifest.epoch,
+        }
+    }
+    /// The epoch of the snapshot an incremental snapshot applies to, `None` for a full snapshot.
+    pub fn base_epoch(&self) -> Option<u64> {
+        match self {
+            Self::V1(_) => None,
+            Self::IncrementalV1(manifest) => Some(manifest.base_epoch),
         }
     }
 }
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::filter::ObjectFilter;
 use crate::{
     FileMetadata, FileType, Manifest, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
     OBJECT_ID_BYTES, OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES, SHA3_BYTES,
@@ -17,8 +18,7 @@ use integer_encoding::VarIntReader;
 use object_store::path::Path;
 use std::collections::BTreeMap;
 use std::fs;
-use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::Read;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
@@ -32,6 +32,7 @@ use sui_storage::object_store::util::{copy_file, copy_files, path_to_filesystem}
 use sui_storage::object_store::{ObjectStoreGetExt, ObjectStorePutExt};
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
+use sui_types::in_memory_storage::InMemoryStorage;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
@@ -47,6 +48,8 @@ pub struct StateSnapshotReaderV1 {
     local_object_store: Arc<dyn ObjectStorePutExt>,
     ref_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
     object_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    deleted_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    base_epoch: Option<u64>,
     indirect_objects_threshold: usize,
     m: MultiProgress,
     concurrency: usize,
@@ -107,6 +110,7 @@ impl StateSnapshotReaderV1 {
         }
         let mut object_files = BTreeMap::new();
         let mut ref_files = BTreeMap::new();
+        let mut deleted_files = BTreeMap::new();
         for file_metadata in manifest.file_metadata() {
             match file_metadata.file_type {
                 FileType::Object => {
@@ -121,11 +125,18 @@ impl StateSnapshotReaderV1 {
                         .or_insert_with(BTreeMap::new);
                     entry.insert(file_metadata.part_num, file_metadata.clone());
                 }
+                FileType::Deleted => {
+                    let entry = deleted_files
+                        .entry(file_metadata.bucket_num)
+                        .or_insert_with(BTreeMap::new);
+                    entry.insert(file_metadata.part_num, file_metadata.clone());
+                }
             }
         }
         let epoch_dir_path = Path::from(epoch_dir);
         let files: Vec<Path> = ref_files
             .values()
+            .chain(deleted_files.values())
             .flat_map(|entry| {
                 let files: Vec<_> = entry
                     .values()
@@ -138,7 +149,7 @@ impl StateSnapshotReaderV1 {
         let progress_bar = m.add(
             ProgressBar::new(files.len() as u64).with_style(
                 ProgressStyle::with_template(
-                    "[{elapsed_precise}] {wide_bar} {pos} out of {len} .ref files done ({msg})",
+                    "[{elapsed_precise}] {wide_bar} {pos} out of {len} .ref and .del files done ({msg})",
                 )
                 .unwrap(),
             ),
@@ -160,6 +171,8 @@ impl StateSnapshotReaderV1 {
             local_object_store,
             ref_files,
             object_files,
+            deleted_files,
+            base_epoch: manifest.base_epoch(),
             indirect_objects_threshold,
             m,
             concurrency: download_concurrency.get(),
@@ -178,8 +191,100 @@ impl StateSnapshotReaderV1 {
         // references and start building state accumulator and fail early if the state root hash
         // doesn't match but we still need to ensure that objects match references exactly.
         let sha3_digests: Arc<Mutex<DigestByBucketAndPartition>> =
-            Arc::new(Mutex::new(BTreeMap::new()));
+            Arc::new(Mutex::new(self.compute_checksums()?));
+
+        let num_part_files = self
+            .ref_files
+            .values()
+            .map(|part_files| part_files.len())
+            .sum::<usize>();
+
+        let accum_handle =
+            sender.map(|sender| self.spawn_accumulation_tasks(sender, num_part_files));
+
+        self.remove_deleted_objects(perpetual_db)?;
+        self.sync_live_objects(perpetual_db, abort_registration, sha3_digests)
+            .await?;
+
+        if let Some(handle) = accum_handle {
+            handle.await?;
+        }
+        Ok(())
+    }
+
+    /// Restores only the objects selected by `filter` into `storage`, on top of the objects
+    /// restored there from the base snapshot if this one is incremental. Wrapped objects are never
+    /// selected. Since the restored set is partial it can't be checked against the root state
+    /// hash, but every *.obj file is still checked against its REFERENCE file.
+    pub async fn read_filtered(
+        &self,
+        filter: &ObjectFilter,
+        storage: &mut InMemoryStorage,
+    ) -> Result<()> {
+        for (bucket, part_files) in self.deleted_files.iter() {
+            for part in part_files.keys() {
+                for (object_id, version, _) in self.deleted_ref_iter(*bucket, *part)? {
+                    if storage
+                        .get_object(&object_id)
+                        .is_some_and(|object| object.version() == version)
+                    {
+                        storage.remove_object(object_id);
+                    }
+                }
+            }
+        }
 
+        let sha3_digests = self.compute_checksums()?;
+        let epoch_dir = self.epoch_dir();
+        let remote_object_store = self.remote_object_store.clone();
+        let input_files: Vec<_> = self
+            .object_files
+            .iter()
+            .flat_map(|(bucket, parts)| {
+                parts.iter().map(move |(part, file)| (*bucket, *part, file))
+            })
+            .collect();
+        let obj_progress_bar = self.m.add(
+            ProgressBar::new(input_files.len() as u64).with_style(
+                ProgressStyle::with_template(
+                    "[{elapsed_precise}] {wide_bar} {pos} out of {len} .obj files filtered ({msg})",
+                )
+                .unwrap(),
+            ),
+        );
+        let mut num_matched = 0;
+        futures::stream::iter(input_files)
+            .map(|(bucket, part, file_metadata)| {
+                let file_path = file_metadata.file_path(&epoch_dir);
+                let remote_object_store = remote_object_store.clone();
+                async move {
+                    let bytes = remote_object_store.get_bytes(&file_path).await?;
+                    Ok::<_, anyhow::Error>((bucket, part, file_metadata, bytes))
+                }
+            })
+            .boxed()
+            .buffer_unordered(self.concurrency)
+            .try_for_each(|(bucket, part, file_metadata, bytes)| {
+                let result = filter_live_objects(
+                    LiveObjectIter::new(file_metadata, bytes),
+                    sha3_digests.get(&bucket).and_then(|parts| parts.get(&part)),
+                    filter,
+                    storage,
+                )
+                .map(|num_file_matched| num_matched += num_file_matched)
+                .with_context(|| format!("Failed to filter bucket: {bucket}, part: {part}"));
+                obj_progress_bar.inc(1);
+                obj_progress_bar.set_message(format!("Objects matched: {num_matched}"));
+                futures::future::ready(result)
+            })
+            .await?;
+        obj_progress_bar.finish_with_message("Objects filtering complete");
+        Ok(())
+    }
+
+    /// Computes the sha3 digest of the object references in each REFERENCE file, which the
+    /// objects downloaded from the matching *.obj file are checked against.
+    fn compute_checksums(&self) -> Result<DigestByBucketAndPartition> {
         let num_part_files = self
             .ref_files
             .values()
@@ -197,9 +302,9 @@ impl StateSnapshotReaderV1 {
             ),
         );
 
-        for (bucket, part_files) in self.ref_files.clone().iter() {
+        let mut sha3_digests = BTreeMap::new();
+        for (bucket, part_files) in self.ref_files.iter() {
             for (part, _part_file) in part_files.iter() {
-                let mut sha3_digests = sha3_digests.lock().await;
                 let ref_iter = self.ref_iter(*bucket, *part)?;
                 let mut hasher = Sha3_256::default();
                 let mut empty = true;
@@ -224,17 +329,7 @@ impl StateSnapshotReaderV1 {
             }
         }
         checksum_progress_bar.finish_with_message("Checksumming complete");
-
-        let accum_handle =
-            sender.map(|sender| self.spawn_accumulation_tasks(sender, num_part_files));
-
-        self.sync_live_objects(perpetual_db, abort_registration, sha3_digests)
-            .await?;
-
-        if let Some(handle) = accum_handle {
-            handle.await?;
-        }
-        Ok(())
+        Ok(sha3_digests)
     }
 
     fn spawn_accumulation_tasks(
@@ -327,6 +422,44 @@ impl StateSnapshotReaderV1 {
         })
     }
 
+    /// Removes the objects an incremental snapshot deletes from the live object set of its base,
+    /// which must already be restored in `perpetual_db`.
+    fn remove_deleted_objects(&self, perpetual_db: &AuthorityPerpetualTables) -> Result<()> {
+        for (bucket, part_files) in self.deleted_files.iter() {
+            for part in part_files.keys() {
+                AuthorityStore::bulk_remove_live_objects(
+                    perpetual_db,
+                    self.deleted_ref_iter(*bucket, *part)?,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an accumulator removing the objects deleted by an incremental snapshot from the
+    /// live object set of its base, along with the number of deleted objects. Both are empty
+    /// for a full snapshot.
+    pub fn deleted_objects_accumulator(&self) -> Result<(Accumulator, u64)> {
+        let mut acc = Accumulator::default();
+        let mut num_objects = 0;
+        for (bucket, part_files) in self.deleted_files.iter() {
+            for part in part_files.keys() {
+                let obj_digests = self
+                    .deleted_ref_iter(*bucket, *part)?
+                    .map(|obj_ref| obj_ref.2)
+                    .collect::<Vec<ObjectDigest>>();
+                num_objects += obj_digests.len() as u64;
+                acc.remove_all(obj_digests);
+            }
+        }
+        Ok((acc, num_objects))
+    }
+
+    /// The epoch of the snapshot this one applies to if it is incremental.
+    pub fn base_epoch(&self) -> Option<u64> {
+        self.base_epoch
+    }
+
     async fn sync_live_objects(
         &self,
         perpetual_db: &AuthorityPerpetualTables,
@@ -466,6 +599,22 @@ impl StateSnapshotReaderV1 {
         )
     }
 
+    pub fn deleted_ref_iter(&self, bucket_num: u32, part_num: u32) -> Result<ObjectRefIter> {
+        let file_metadata = self
+            .deleted_files
+            .get(&bucket_num)
+            .context(format!("No deleted files found for bucket: {bucket_num}"))?
+            .get(&part_num)
+            .context(format!(
+                "No deleted files found for bucket: {bucket_num}, part: {part_num}"
+            ))?;
+        ObjectRefIter::new(
+            file_metadata,
+            self.local_staging_dir_root.clone(),
+            self.epoch_dir(),
+        )
+    }
+
     fn buckets(&self) -> Result<Vec<u32>> {
         Ok(self.ref_files.keys().copied().collect())
     }
@@ -475,22 +624,20 @@ impl StateSnapshotReaderV1 {
     }
 
     fn read_manifest(path: PathBuf) -> anyhow::Result<Manifest> {
-        let manifest_file = File::open(path)?;
-        let manifest_file_size = manifest_file.metadata()?.len() as usize;
-        let mut manifest_reader = BufReader::new(manifest_file);
-        manifest_reader.rewind()?;
-        let magic = manifest_reader.read_u32::<BigEndian>()?;
+        Self::parse_manifest(&fs::read(path)?)
+    }
+
+    fn parse_manifest(bytes: &[u8]) -> anyhow::Result<Manifest> {
+        if bytes.len() < MAGIC_BYTES + SHA3_BYTES {
+            return Err(anyhow!("Unexpected manifest size: {}", bytes.len()));
+        }
+        let magic = bytes[..MAGIC_BYTES].reader().read_u32::<BigEndian>()?;
         if magic != MANIFEST_FILE_MAGIC {
             return Err(anyhow!("Unexpected magic byte: {}", magic));
         }
-        manifest_reader.seek(SeekFrom::End(-(SHA3_BYTES as i64)))?;
-        let mut sha3_digest = [0u8; SHA3_BYTES];
-        manifest_reader.read_exact(&mut sha3_digest)?;
-        manifest_reader.rewind()?;
-        let mut content_buf = vec![0u8; manifest_file_size - SHA3_BYTES];
-        manifest_reader.read_exact(&mut content_buf)?;
+        let (content_buf, sha3_digest) = bytes.split_at(bytes.len() - SHA3_BYTES);
         let mut hasher = Sha3_256::default();
-        hasher.update(&content_buf);
+        hasher.update(content_buf);
         let computed_digest = hasher.finalize().digest;
         if computed_digest != sha3_digest {
             return Err(anyhow!(
@@ -499,13 +646,54 @@ impl StateSnapshotReaderV1 {
                 sha3_digest
             ));
         }
-        manifest_reader.rewind()?;
-        manifest_reader.seek(SeekFrom::Start(MAGIC_BYTES as u64))?;
         let manifest = bcs::from_bytes(&content_buf[MAGIC_BYTES..])?;
         Ok(manifest)
     }
 }
 
+/// Returns the epochs of the snapshots to restore, in order, to restore the snapshot of `epoch`:
+/// the full snapshot at the root of its chain followed by every incremental snapshot up to and
+/// including `epoch`.
+pub async fn snapshot_chain(
+    epoch: u64,
+    remote_store_config: &ObjectStoreConfig,
+) -> Result<Vec<u64>> {
+    let remote_object_store = if remote_store_config.no_sign_request {
+        remote_store_config.make_http()?
+    } else {
+        remote_store_config.make().map(Arc::new)?
+    };
+    let mut chain = vec![epoch];
+    let mut current_epoch = epoch;
+    loop {
+        let manifest_file_path = Path::from(format!("epoch_{}", current_epoch)).child("MANIFEST");
+        let bytes = remote_object_store.get_bytes(&manifest_file_path).await?;
+        let manifest = StateSnapshotReaderV1::parse_manifest(&bytes)?;
+        if manifest.epoch() != current_epoch {
+            return Err(anyhow!(
+                "Download manifest is not for epoch: {}",
+                current_epoch
+            ));
+        }
+        match manifest.base_epoch() {
+            None => break,
+            Some(base_epoch) if base_epoch < current_epoch => {
+                chain.push(base_epoch);
+                current_epoch = base_epoch;
+            }
+            Some(base_epoch) => {
+                return Err(anyhow!(
+                    "Snapshot of epoch {} has invalid base epoch: {}",
+                    current_epoch,
+                    base_epoch
+                ));
+            }
+        }
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
 /// An iterator over all object refs in a .ref file.
 pub struct ObjectRefIter {
     reader: Box<dyn Read>,
@@ -550,6 +738,39 @@ impl Iterator for ObjectRefIter {
     }
 }
 
+/// Inserts the objects of a *.obj file selected by `filter` into `storage` after checking the
+/// file against the sha3 digest of its REFERENCE file, returning the number of objects inserted.
+fn filter_live_objects(
+    obj_iter: Result<LiveObjectIter>,
+    expected_sha3_digest: Option<&[u8; 32]>,
+    filter: &ObjectFilter,
+    storage: &mut InMemoryStorage,
+) -> Result<usize> {
+    let mut hasher = Sha3_256::default();
+    let mut empty = true;
+    let mut matched = vec![];
+    for object in obj_iter? {
+        hasher.update(object.object_reference().2.inner());
+        empty = false;
+        if let LiveObject::Normal(object) = object {
+            if filter.matches(&object) {
+                matched.push(object);
+            }
+        }
+    }
+    if !empty {
+        let expected_sha3_digest = expected_sha3_digest.context("Missing sha3 digest")?;
+        if hasher.finalize().digest != *expected_sha3_digest {
+            return Err(anyhow!("Sha3 digest mismatch"));
+        }
+    }
+    let num_matched = matched.len();
+    for object in matched {
+        storage.insert_object(object);
+    }
+    Ok(num_matched)
+}
+
 /// An iterator over all objects in a *.obj file.
 pub struct LiveObjectIter {
     reader: Box<dyn Read>,
@@ -1,12 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::reader::StateSnapshotReaderV1;
+use crate::filter::{restore_filtered, ObjectFilter};
+use crate::reader::{snapshot_chain, StateSnapshotReaderV1};
 use crate::writer::StateSnapshotWriterV1;
 use crate::FileCompression;
 use fastcrypto::hash::MultisetHash;
 use futures::future::AbortHandle;
 use indicatif::MultiProgress;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
@@ -15,9 +17,12 @@ use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::state_accumulator::StateAccumulator;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::accumulator::Accumulator;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use sui_types::digests::TransactionDigest;
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
-use sui_types::object::Object;
+use sui_types::move_package::MovePackage;
+use sui_types::object::{Data, MoveObject, Object, Owner, OBJECT_START_VERSION};
+use sui_types::parse_sui_struct_tag;
 use tempfile::tempdir;
 
 fn temp_dir() -> std::path::PathBuf {
@@ -181,3 +186,283 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
     )?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_snapshot_incremental() -> Result<(), anyhow::Error> {
+    let base_db_path = temp_dir();
+    let db_path = temp_dir();
+    let restored_db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote),
+        ..Default::default()
+    };
+
+    // Between the two epochs, the first 100 objects are deleted, the next 100 are mutated and
+    // 100 new objects are created
+    let base_perpetual_db = Arc::new(AuthorityPerpetualTables::open(&base_db_path, None));
+    insert_keys(&base_perpetual_db, 1000)?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None));
+    let ids = ObjectID::in_range(ObjectID::ZERO, 1100)?;
+    for (i, id) in ids.into_iter().enumerate().skip(100) {
+        let object = if i < 200 {
+            Object::with_id_owner_version_for_testing(
+                id,
+                SequenceNumber::from_u64(2),
+                SuiAddress::ZERO,
+            )
+        } else {
+            Object::immutable_with_id_for_testing(id)
+        };
+        perpetual_db.insert_object_test_only(object)?;
+    }
+
+    for (epoch, base) in [(0, None), (1, Some((0, base_perpetual_db.clone())))] {
+        let snapshot_writer = StateSnapshotWriterV1::new(
+            &local_store_config,
+            &remote_store_config,
+            FileCompression::Zstd,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await?;
+        match base {
+            None => {
+                let root_accumulator = ECMHLiveObjectSetDigest::from(
+                    accumulate_live_object_set(&base_perpetual_db, true).digest(),
+                );
+                snapshot_writer
+                    .write_internal(epoch, true, base_perpetual_db.clone(), root_accumulator)
+                    .await?;
+            }
+            Some((base_epoch, base_perpetual_db)) => {
+                let root_accumulator = ECMHLiveObjectSetDigest::from(
+                    accumulate_live_object_set(&perpetual_db, true).digest(),
+                );
+                snapshot_writer
+                    .write_incremental_internal(
+                        epoch,
+                        base_epoch,
+                        true,
+                        perpetual_db.clone(),
+                        base_perpetual_db,
+                        root_accumulator,
+                    )
+                    .await?;
+            }
+        }
+    }
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let chain = snapshot_chain(1, &remote_store_config).await?;
+    assert_eq!(chain, vec![0, 1]);
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None);
+    let mut root_accumulator = Accumulator::default();
+    for epoch in chain {
+        let mut snapshot_reader = StateSnapshotReaderV1::new(
+            epoch,
+            &remote_store_config,
+            &local_store_restore_config,
+            usize::MAX,
+            NonZeroUsize::new(1).unwrap(),
+            MultiProgress::new(),
+        )
+        .await?;
+        let (deleted_accumulator, num_deleted_objects) =
+            snapshot_reader.deleted_objects_accumulator()?;
+        match snapshot_reader.base_epoch() {
+            None => assert_eq!(num_deleted_objects, 0),
+            Some(base_epoch) => {
+                assert_eq!(base_epoch, 0);
+                // Mutated objects are deleted at their previous version
+                assert_eq!(num_deleted_objects, 200);
+            }
+        }
+        root_accumulator.union(&deleted_accumulator);
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(100);
+        let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+        snapshot_reader
+            .read(&restored_perpetual_db, abort_registration, Some(sender))
+            .await?;
+        while let Some((partial_acc, _num_objects)) = receiver.recv().await {
+            root_accumulator.union(&partial_acc);
+        }
+        let expected_db = if epoch == 0 {
+            &base_perpetual_db
+        } else {
+            &perpetual_db
+        };
+        compare_live_objects(expected_db, &restored_perpetual_db, true)?;
+        assert_eq!(
+            ECMHLiveObjectSetDigest::from(root_accumulator.digest()),
+            ECMHLiveObjectSetDigest::from(accumulate_live_object_set(expected_db, true).digest()),
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_filtered_restore() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote),
+        ..Default::default()
+    };
+
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let owner = SuiAddress::random_for_testing_only();
+    let owned_objects: Vec<_> = ObjectID::in_range(ObjectID::ZERO, 1100)?
+        .into_iter()
+        .skip(1000)
+        .map(|id| Object::with_id_owner_version_for_testing(id, SequenceNumber::from_u64(1), owner))
+        .collect();
+    for object in owned_objects.iter() {
+        perpetual_db.insert_object_test_only(object.clone())?;
+    }
+    let root_accumulator =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    snapshot_writer
+        .write_internal(0, true, perpetual_db.clone(), root_accumulator)
+        .await?;
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let filter = ObjectFilter {
+        owners: [owner].into_iter().collect(),
+        ..Default::default()
+    };
+    let storage = restore_filtered(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        &filter,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+    )
+    .await?;
+    let restored_objects: Vec<_> = storage.into_inner().into_values().collect();
+    assert_eq!(restored_objects, owned_objects);
+    Ok(())
+}
+
+fn object_of_type(type_: &str) -> Object {
+    let type_ = parse_sui_struct_tag(type_).unwrap();
+    Object::new_move(
+        MoveObject::new_coin(type_.into(), OBJECT_START_VERSION, ObjectID::random(), 0),
+        Owner::Immutable,
+        TransactionDigest::genesis_marker(),
+    )
+}
+
+fn package_with_id(id: ObjectID) -> Object {
+    let package = MovePackage::new(
+        id,
+        OBJECT_START_VERSION,
+        BTreeMap::new(),
+        u64::MAX,
+        vec![],
+        BTreeMap::new(),
+    )
+    .unwrap();
+    Object::new_package_from_data(Data::Package(package), TransactionDigest::genesis_marker())
+}
+
+fn filter_for_types(types: &[&str]) -> ObjectFilter {
+    ObjectFilter {
+        types: types
+            .iter()
+            .map(|type_| parse_sui_struct_tag(type_).unwrap())
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_filter_type_without_type_params() {
+    let filter = filter_for_types(&["0x2::coin::Coin"]);
+    assert!(filter.matches(&object_of_type("0x2::coin::Coin<0x2::sui::SUI>")));
+    assert!(filter.matches(&object_of_type("0x2::coin::Coin<0xabc::m::TOKEN>")));
+    assert!(!filter.matches(&object_of_type("0x2::balance::Balance<0x2::sui::SUI>")));
+    assert!(!filter.matches(&object_of_type("0xabc::coin::Coin<0x2::sui::SUI>")));
+}
+
+#[test]
+fn test_filter_type_with_type_params() {
+    let filter = filter_for_types(&["0x2::coin::Coin<0x2::sui::SUI>"]);
+    assert!(filter.matches(&object_of_type("0x2::coin::Coin<0x2::sui::SUI>")));
+    assert!(!filter.matches(&object_of_type("0x2::coin::Coin<0xabc::m::TOKEN>")));
+    assert!(!filter.matches(&object_of_type(
+        "0x2::coin::Coin<0xabc::m::Wrapper<0x2::sui::SUI>>"
+    )));
+}
+
+#[test]
+fn test_filter_dynamic_field_packages() {
+    let filter = ObjectFilter {
+        dynamic_field_packages: [ObjectID::from_hex_literal("0xabc").unwrap()]
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    };
+    assert!(filter.matches(&object_of_type(
+        "0x2::dynamic_field::Field<0xabc::m::Name, u64>"
+    )));
+    assert!(filter.matches(&object_of_type(
+        "0x2::dynamic_field::Field<u64, vector<0xabc::m::Value>>"
+    )));
+    assert!(filter.matches(&object_of_type(
+        "0x2::dynamic_field::Field<0x2::dynamic_object_field::Wrapper<0xabc::m::Name>, 0x2::object::ID>"
+    )));
+    assert!(!filter.matches(&object_of_type(
+        "0x2::dynamic_field::Field<0xdef::m::Name, u64>"
+    )));
+    // Only dynamic fields are selected by the packages their types are defined in
+    assert!(!filter.matches(&object_of_type("0xabc::m::Value")));
+}
+
+#[test]
+fn test_filter_packages() {
+    let package_id = |id: &str| ObjectID::from_hex_literal(id).unwrap();
+    let filter = ObjectFilter {
+        types: vec![parse_sui_struct_tag("0xabc::m::Value<0xdef::n::Param>").unwrap()],
+        dynamic_field_packages: [package_id("0x123")].into_iter().collect(),
+        packages: [package_id("0x456")].into_iter().collect(),
+        ..Default::default()
+    };
+    for selected in ["0xabc", "0xdef", "0x123", "0x456"] {
+        assert!(filter.matches(&package_with_id(package_id(selected))));
+    }
+    assert!(!filter.matches(&package_with_id(package_id("0x789"))));
+    assert!(!ObjectFilter::default().matches(&package_with_id(package_id("0x2"))));
+}
@@ -4,6 +4,7 @@
 use crate::writer::StateSnapshotWriterV1;
 use anyhow::Result;
 use bytes::Bytes;
+use object_store::path::Path;
 use object_store::DynObjectStore;
 use prometheus::{
     register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
@@ -65,6 +66,8 @@ pub struct StateSnapshotUploader {
     snapshot_store: Arc<DynObjectStore>,
     /// Time interval to check for presence of new db checkpoint
     interval: Duration,
+    /// Epoch interval between full snapshots, only full snapshots are written if unset
+    full_snapshot_interval: Option<u64>,
     metrics: Arc<StateSnapshotUploaderMetrics>,
 }
 
@@ -74,6 +77,7 @@ impl StateSnapshotUploader {
         staging_path: &std::path::Path,
         snapshot_store_config: ObjectStoreConfig,
         interval_s: u64,
+        full_snapshot_interval: Option<u64>,
         registry: &Registry,
         checkpoint_store: Arc<CheckpointStore>,
     ) -> Result<Arc<Self>> {
@@ -95,6 +99,7 @@ impl StateSnapshotUploader {
             staging_store: staging_store_config.make()?,
             snapshot_store: snapshot_store_config.make()?,
             interval: Duration::from_secs(interval_s),
+            full_snapshot_interval,
             metrics: StateSnapshotUploaderMetrics::new(registry),
         }))
     }
@@ -111,12 +116,14 @@ impl StateSnapshotUploader {
 
     async fn upload_state_snapshot_to_object_store(&self, missing_epochs: Vec<u64>) -> Result<()> {
         let last_missing_epoch = missing_epochs.last().cloned().unwrap_or(0);
+        let is_missing =
+            |epoch: u64| missing_epochs.contains(&epoch) || epoch >= last_missing_epoch;
         let local_checkpoints_by_epoch =
             find_all_dirs_with_epoch_prefix(&self.db_checkpoint_store, None).await?;
         let mut dirs: Vec<_> = local_checkpoints_by_epoch.iter().collect();
         dirs.sort_by_key(|(epoch_num, _path)| *epoch_num);
         for (epoch, db_path) in dirs {
-            if missing_epochs.contains(epoch) || *epoch >= last_missing_epoch {
+            if is_missing(*epoch) {
                 info!("Starting state snapshot creation for epoch: {}", *epoch);
                 let state_snapshot_writer = StateSnapshotWriterV1::new_from_store(
                     &self.staging_path,
@@ -139,40 +146,84 @@ impl StateSnapshotUploader {
                     .last()
                     .expect("Expected at least one commitment")
                     .clone();
-                state_snapshot_writer
-                    .write(*epoch, db, state_hash_commitment)
-                    .await?;
+                // The db checkpoint of the previous epoch is still around if it is needed as the
+                // base of an incremental snapshot, and its snapshot was written before this one.
+                // Fall back to a full snapshot otherwise.
+                let base = if self.is_incremental(*epoch) {
+                    local_checkpoints_by_epoch
+                        .get(&(*epoch - 1))
+                        .map(|base_db_path| (*epoch - 1, base_db_path))
+                } else {
+                    None
+                };
+                match base {
+                    Some((base_epoch, base_db_path)) => {
+                        let base_db = Arc::new(AuthorityPerpetualTables::open(
+                            &path_to_filesystem(
+                                self.db_checkpoint_path.clone(),
+                                &base_db_path.child("store"),
+                            )?,
+                            None,
+                        ));
+                        state_snapshot_writer
+                            .write_incremental(
+                                *epoch,
+                                base_epoch,
+                                db,
+                                base_db,
+                                state_hash_commitment,
+                            )
+                            .await?;
+                    }
+                    None => {
+                        state_snapshot_writer
+                            .write(*epoch, db, state_hash_commitment)
+                            .await?;
+                    }
+                }
                 info!("State snapshot creation successful for epoch: {}", *epoch);
                 // Drop marker in the output directory that upload completed successfully
                 let bytes = Bytes::from_static(b"success");
                 let success_marker = db_path.child(SUCCESS_MARKER);
                 put(&self.snapshot_store, &success_marker, bytes.clone()).await?;
-                let bytes = Bytes::from_static(b"success");
-                let state_snapshot_completed_marker =
-                    db_path.child(STATE_SNAPSHOT_COMPLETED_MARKER);
-                put(
-                    &self.db_checkpoint_store.clone(),
-                    &state_snapshot_completed_marker,
-                    bytes.clone(),
-                )
-                .await?;
+                if let Some((base_epoch, base_db_path)) = base {
+                    self.mark_state_snapshot_completed(base_db_path).await?;
+                    info!("State snapshot base released for epoch: {base_epoch}");
+                }
+                if !self.is_incremental(*epoch + 1) {
+                    self.mark_state_snapshot_completed(db_path).await?;
+                }
                 info!("State snapshot completed for epoch: {epoch}");
+            } else if self.is_incremental(*epoch + 1) && is_missing(*epoch + 1) {
+                info!("State snapshot skipped for epoch: {epoch}, kept as base of the next one");
             } else {
-                let bytes = Bytes::from_static(b"success");
-                let state_snapshot_completed_marker =
-                    db_path.child(STATE_SNAPSHOT_COMPLETED_MARKER);
-                put(
-                    &self.db_checkpoint_store.clone(),
-                    &state_snapshot_completed_marker,
-                    bytes.clone(),
-                )
-                .await?;
+                self.mark_state_snapshot_completed(db_path).await?;
                 info!("State snapshot skipped for epoch: {epoch}");
             }
         }
         Ok(())
     }
 
+    /// Whether the snapshot of `epoch` is written as an incremental snapshot over the one of the
+    /// previous epoch.
+    fn is_incremental(&self, epoch: u64) -> bool {
+        matches!(self.full_snapshot_interval, Some(interval) if interval > 1 && epoch % interval != 0)
+    }
+
+    /// Drops the marker allowing the db checkpoint at `db_path` to be uploaded and garbage
+    /// collected.
+    async fn mark_state_snapshot_completed(&self, db_path: &Path) -> Result<()> {
+        let bytes = Bytes::from_static(b"success");
+        let state_snapshot_completed_marker = db_path.child(STATE_SNAPSHOT_COMPLETED_MARKER);
+        put(
+            &self.db_checkpoint_store.clone(),
+            &state_snapshot_completed_marker,
+            bytes.clone(),
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn run_upload_loop(
         self: Arc<Self>,
         mut recv: tokio::sync::broadcast::Receiver<()>,
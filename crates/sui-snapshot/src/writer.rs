@@ -3,9 +3,9 @@
 #![allow(dead_code)]
 
 use crate::{
-    compute_sha3_checksum, create_file_metadata, FileCompression, FileMetadata, FileType, Manifest,
-    ManifestV1, FILE_MAX_BYTES, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
-    OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES,
+    compute_sha3_checksum, create_file_metadata, FileCompression, FileMetadata, FileType,
+    IncrementalManifestV1, Manifest, ManifestV1, FILE_MAX_BYTES, MAGIC_BYTES, MANIFEST_FILE_MAGIC,
+    OBJECT_FILE_MAGIC, OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES,
 };
 use anyhow::{anyhow, Context, Result};
 use byteorder::{BigEndian, ByteOrder};
@@ -14,7 +14,7 @@ use futures::StreamExt;
 use integer_encoding::VarInt;
 use object_store::path::Path;
 use object_store::DynObjectStore;
-use std::collections::hash_map::Entry::Vacant;
+use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
 use std::fs;
 use std::fs::{File, OpenOptions};
@@ -193,19 +193,118 @@ impl LiveObjectSetWriterV1 {
         Ok(())
     }
     fn write_object_ref(&mut self, object_ref: &ObjectRef) -> Result<()> {
-        let mut buf = [0u8; OBJECT_REF_BYTES];
-        buf[0..ObjectID::LENGTH].copy_from_slice(object_ref.0.as_ref());
-        BigEndian::write_u64(
-            &mut buf[ObjectID::LENGTH..OBJECT_REF_BYTES],
-            object_ref.1.value(),
-        );
-        buf[ObjectID::LENGTH + SEQUENCE_NUM_BYTES..OBJECT_REF_BYTES]
-            .copy_from_slice(object_ref.2.as_ref());
-        self.ref_wbuf.write_all(&buf)?;
+        write_object_ref(&mut self.ref_wbuf, object_ref)
+    }
+}
+
+/// DeletedObjectRefWriterV1 writes the references of the objects an incremental snapshot removes
+/// from the live object set. It creates multiple *.del files
+struct DeletedObjectRefWriterV1 {
+    dir_path: PathBuf,
+    bucket_num: u32,
+    current_part_num: u32,
+    wbuf: BufWriter<File>,
+    n: usize,
+    files: Vec<FileMetadata>,
+    sender: Option<Sender<FileMetadata>>,
+    file_compression: FileCompression,
+}
+
+impl DeletedObjectRefWriterV1 {
+    fn new(
+        dir_path: PathBuf,
+        bucket_num: u32,
+        file_compression: FileCompression,
+        sender: Sender<FileMetadata>,
+    ) -> Result<Self> {
+        let part_num = 1;
+        let (n, f) = Self::deleted_file(dir_path.clone(), bucket_num, part_num)?;
+        Ok(DeletedObjectRefWriterV1 {
+            dir_path,
+            bucket_num,
+            current_part_num: part_num,
+            wbuf: BufWriter::new(f),
+            n,
+            files: vec![],
+            sender: Some(sender),
+            file_compression,
+        })
+    }
+    pub fn write(&mut self, object_ref: &ObjectRef) -> Result<()> {
+        if (self.n + OBJECT_REF_BYTES) > FILE_MAX_BYTES {
+            self.cut()?;
+        }
+        write_object_ref(&mut self.wbuf, object_ref)?;
+        self.n += OBJECT_REF_BYTES;
+        Ok(())
+    }
+    pub fn done(mut self) -> Result<Vec<FileMetadata>> {
+        self.finalize()?;
+        self.sender = None;
+        Ok(self.files.clone())
+    }
+    fn deleted_file(dir_path: PathBuf, bucket_num: u32, part_num: u32) -> Result<(usize, File)> {
+        let del_path = dir_path.join(format!("{bucket_num}_{part_num}.del"));
+        let del_tmp_path = dir_path.join(format!("{bucket_num}_{part_num}.del.tmp"));
+        let mut f = File::create(del_tmp_path.clone())?;
+        f.rewind()?;
+        let mut metab = [0u8; MAGIC_BYTES];
+        BigEndian::write_u32(&mut metab, REFERENCE_FILE_MAGIC);
+        let n = f.write(&metab)?;
+        drop(f);
+        fs::rename(del_tmp_path, del_path.clone())?;
+        let mut f = OpenOptions::new().append(true).open(del_path)?;
+        f.seek(SeekFrom::Start(n as u64))?;
+        Ok((n, f))
+    }
+    fn finalize(&mut self) -> Result<()> {
+        self.wbuf.flush()?;
+        self.wbuf.get_ref().sync_data()?;
+        let off = self.wbuf.get_ref().stream_position()?;
+        self.wbuf.get_ref().set_len(off)?;
+        let file_path = self
+            .dir_path
+            .join(format!("{}_{}.del", self.bucket_num, self.current_part_num));
+        let file_metadata = create_file_metadata(
+            &file_path,
+            self.file_compression,
+            FileType::Deleted,
+            self.bucket_num,
+            self.current_part_num,
+        )?;
+        self.files.push(file_metadata.clone());
+        if let Some(sender) = &self.sender {
+            sender.blocking_send(file_metadata)?;
+        }
+        Ok(())
+    }
+    fn cut(&mut self) -> Result<()> {
+        self.finalize()?;
+        self.current_part_num += 1;
+        let (n, f) = Self::deleted_file(
+            self.dir_path.clone(),
+            self.bucket_num,
+            self.current_part_num,
+        )?;
+        self.n = n;
+        self.wbuf = BufWriter::new(f);
         Ok(())
     }
 }
 
+fn write_object_ref<W: Write>(wbuf: &mut W, object_ref: &ObjectRef) -> Result<()> {
+    let mut buf = [0u8; OBJECT_REF_BYTES];
+    buf[0..ObjectID::LENGTH].copy_from_slice(object_ref.0.as_ref());
+    BigEndian::write_u64(
+        &mut buf[ObjectID::LENGTH..OBJECT_REF_BYTES],
+        object_ref.1.value(),
+    );
+    buf[ObjectID::LENGTH + SEQUENCE_NUM_BYTES..OBJECT_REF_BYTES]
+        .copy_from_slice(object_ref.2.as_ref());
+    wbuf.write_all(&buf)?;
+    Ok(())
+}
+
 /// StateSnapshotWriterV1 writes snapshot files to a local staging dir and simultaneously uploads them
 /// to a remote object store
 pub struct StateSnapshotWriterV1 {
@@ -261,7 +360,41 @@ impl StateSnapshotWriterV1 {
         perpetual_db: Arc<AuthorityPerpetualTables>,
         root_state_hash: ECMHLiveObjectSetDigest,
     ) -> Result<()> {
-        let system_state_object = get_sui_system_state(&perpetual_db)?;
+        let include_wrapped_tombstone = Self::include_wrapped_tombstone(&perpetual_db)?;
+        self.write_internal(
+            epoch,
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    /// Writes an incremental snapshot of the live object set at the end of `epoch`, recording the
+    /// changes since the end of `base_epoch`, whose live object set is read from
+    /// `base_perpetual_db`. The snapshot of `base_epoch` must already exist in the remote store.
+    pub async fn write_incremental(
+        self,
+        epoch: u64,
+        base_epoch: u64,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        base_perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        let include_wrapped_tombstone = Self::include_wrapped_tombstone(&perpetual_db)?;
+        self.write_incremental_internal(
+            epoch,
+            base_epoch,
+            include_wrapped_tombstone,
+            perpetual_db,
+            base_perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    fn include_wrapped_tombstone(perpetual_db: &AuthorityPerpetualTables) -> Result<bool> {
+        let system_state_object = get_sui_system_state(perpetual_db)?;
 
         let protocol_version = system_state_object.protocol_version();
         let chain_identifier = CHAIN_IDENTIFIER
@@ -271,9 +404,19 @@ impl StateSnapshotWriterV1 {
             ProtocolVersion::new(protocol_version),
             chain_identifier.chain(),
         );
-        let include_wrapped_tombstone = !protocol_config.simplified_unwrap_then_delete();
-        self.write_internal(
+        Ok(!protocol_config.simplified_unwrap_then_delete())
+    }
+
+    pub(crate) async fn write_internal(
+        self,
+        epoch: u64,
+        include_wrapped_tombstone: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        self.write_snapshot(
             epoch,
+            None,
             include_wrapped_tombstone,
             perpetual_db,
             root_state_hash,
@@ -281,9 +424,29 @@ impl StateSnapshotWriterV1 {
         .await
     }
 
-    pub(crate) async fn write_internal(
+    pub(crate) async fn write_incremental_internal(
+        self,
+        epoch: u64,
+        base_epoch: u64,
+        include_wrapped_tombstone: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        base_perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        self.write_snapshot(
+            epoch,
+            Some((base_epoch, base_perpetual_db)),
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    async fn write_snapshot(
         mut self,
         epoch: u64,
+        base: Option<(u64, Arc<AuthorityPerpetualTables>)>,
         include_wrapped_tombstone: bool,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         root_state_hash: ECMHLiveObjectSetDigest,
@@ -297,15 +460,25 @@ impl StateSnapshotWriterV1 {
 
         let (sender, receiver) = mpsc::channel::<FileMetadata>(1000);
         let upload_handle = self.start_upload(epoch, receiver)?;
-        let write_handler = tokio::task::spawn_blocking(move || {
-            self.write_live_object_set(
+        let write_handler = tokio::task::spawn_blocking(move || match base {
+            None => self.write_live_object_set(
+                epoch,
+                perpetual_db,
+                sender,
+                Self::bucket_func,
+                include_wrapped_tombstone,
+                root_state_hash,
+            ),
+            Some((base_epoch, base_perpetual_db)) => self.write_live_object_set_changes(
                 epoch,
+                base_epoch,
                 perpetual_db,
+                base_perpetual_db,
                 sender,
                 Self::bucket_func,
                 include_wrapped_tombstone,
                 root_state_hash,
-            )
+            ),
         });
         write_handler.await?.context(format!(
             "Failed to write state snapshot for epoch: {}",
@@ -407,19 +580,121 @@ impl StateSnapshotWriterV1 {
         for (_, writer) in object_writers.into_iter() {
             files.extend(writer.done()?);
         }
-        self.write_manifest(epoch, files)?;
+        self.write_manifest(epoch, None, files)?;
+        Ok(())
+    }
+
+    /// Writes the objects added to the live object set since `base_epoch` to *.obj and *.ref
+    /// files, and the references of the objects removed from it to *.del files.
+    fn write_live_object_set_changes<F>(
+        &mut self,
+        epoch: u64,
+        base_epoch: u64,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        base_perpetual_db: Arc<AuthorityPerpetualTables>,
+        sender: Sender<FileMetadata>,
+        bucket_func: F,
+        include_wrapped_tombstone: bool,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()>
+    where
+        F: Fn(&LiveObject) -> u32,
+    {
+        let mut object_writers: HashMap<u32, LiveObjectSetWriterV1> = HashMap::new();
+        let mut deleted_writers: HashMap<u32, DeletedObjectRefWriterV1> = HashMap::new();
+        let local_staging_dir_path =
+            path_to_filesystem(self.local_staging_dir.clone(), &self.epoch_dir(epoch))?;
+        let mut write_deleted = |object: &LiveObject| -> Result<()> {
+            let bucket_num = bucket_func(object);
+            let writer = match deleted_writers.entry(bucket_num) {
+                Occupied(entry) => entry.into_mut(),
+                Vacant(entry) => entry.insert(DeletedObjectRefWriterV1::new(
+                    local_staging_dir_path.clone(),
+                    bucket_num,
+                    self.file_compression,
+                    sender.clone(),
+                )?),
+            };
+            writer.write(&object.object_reference())
+        };
+        let mut acc = Accumulator::default();
+        // Both live object sets are iterated in (object id, version) order, which allows diffing
+        // them in a single pass
+        let mut base_iter = base_perpetual_db
+            .iter_live_object_set(include_wrapped_tombstone)
+            .peekable();
+        for object in perpetual_db.iter_live_object_set(include_wrapped_tombstone) {
+            StateAccumulator::accumulate_live_object(&mut acc, &object);
+            let object_ref = object.object_reference();
+            let mut unchanged = false;
+            while let Some(base_object) = base_iter.next_if(|base_object| {
+                (base_object.object_id(), base_object.version()) <= (object_ref.0, object_ref.1)
+            }) {
+                if base_object.object_reference() == object_ref {
+                    unchanged = true;
+                } else {
+                    write_deleted(&base_object)?;
+                }
+            }
+            if unchanged {
+                continue;
+            }
+            let bucket_num = bucket_func(&object);
+            if let Vacant(entry) = object_writers.entry(bucket_num) {
+                entry.insert(LiveObjectSetWriterV1::new(
+                    local_staging_dir_path.clone(),
+                    bucket_num,
+                    self.file_compression,
+                    sender.clone(),
+                )?);
+            }
+            let writer = object_writers
+                .get_mut(&bucket_num)
+                .context("Unexpected missing bucket writer")?;
+            writer.write(&object)?;
+        }
+        for base_object in base_iter {
+            write_deleted(&base_object)?;
+        }
+        assert_eq!(
+            ECMHLiveObjectSetDigest::from(acc.digest()),
+            root_state_hash,
+            "Root state hash mismatch!"
+        );
+        let mut files = vec![];
+        for (_, writer) in object_writers.into_iter() {
+            files.extend(writer.done()?);
+        }
+        for (_, writer) in deleted_writers.into_iter() {
+            files.extend(writer.done()?);
+        }
+        self.write_manifest(epoch, Some(base_epoch), files)?;
         Ok(())
     }
 
-    fn write_manifest(&mut self, epoch: u64, file_metadata: Vec<FileMetadata>) -> Result<()> {
+    fn write_manifest(
+        &mut self,
+        epoch: u64,
+        base_epoch: Option<u64>,
+        file_metadata: Vec<FileMetadata>,
+    ) -> Result<()> {
         let (f, manifest_file_path) = self.manifest_file(epoch)?;
         let mut wbuf = BufWriter::new(f);
-        let manifest: Manifest = Manifest::V1(ManifestV1 {
-            snapshot_version: 1,
-            address_length: ObjectID::LENGTH as u64,
-            file_metadata,
-            epoch,
-        });
+        let manifest: Manifest = match base_epoch {
+            None => Manifest::V1(ManifestV1 {
+                snapshot_version: 1,
+                address_length: ObjectID::LENGTH as u64,
+                file_metadata,
+                epoch,
+            }),
+            Some(base_epoch) => Manifest::IncrementalV1(IncrementalManifestV1 {
+                snapshot_version: 1,
+                address_length: ObjectID::LENGTH as u64,
+                file_metadata,
+                epoch,
+                base_epoch,
+            }),
+        };
         let serialized_manifest = bcs::to_bytes(&manifest)?;
         wbuf.write_all(&serialized_manifest)?;
         wbuf.flush()?;
@@ -4,13 +4,15 @@
 use crate::{
     check_completed_snapshot,
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
-    get_latest_available_epoch, get_object, get_transaction_block, make_clients, pkg_dump,
-    restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum, ConciseObjectOutput,
-    GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
+    download_db_snapshot, download_formal_snapshot, download_partial_snapshot,
+    dump_checkpoints_from_archive, get_latest_available_epoch, get_object, get_transaction_block,
+    make_clients, pkg_dump, restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum,
+    ConciseObjectOutput, GroupedObjectOutput, PartialSnapshotFormat, SnapshotVerifyMode,
+    VerboseObjectOutput,
 };
 use anyhow::Result;
 use futures::{future::join_all, StreamExt};
+use move_core_types::language_storage::StructTag;
 use std::path::PathBuf;
 use std::{collections::BTreeMap, env, sync::Arc};
 use sui_config::genesis::Genesis;
@@ -18,11 +20,12 @@ use sui_core::authority_client::AuthorityAPI;
 use sui_protocol_config::Chain;
 use sui_replay::{execute_replay_command, ReplayToolCommand};
 use sui_sdk::{rpc_types::SuiTransactionBlockResponseOptions, SuiClient, SuiClientBuilder};
+use sui_snapshot::filter::ObjectFilter;
 use telemetry_subscribers::TracingHandle;
 
 use sui_types::{
     base_types::*, crypto::AuthorityPublicKeyBytes, messages_grpc::TransactionInfoRequest,
-    object::Owner,
+    object::Owner, parse_sui_struct_tag,
 };
 
 use clap::*;
@@ -374,6 +377,87 @@ pub enum ToolCommand {
         all_checkpoints: bool,
     },
 
+    /// Restores only the objects of a formal snapshot owned by the given addresses, of the given
+    /// Move types, or holding dynamic fields of the given packages, and dumps them to a file, e.g.
+    /// to seed a simulacrum or a local test. Unlike `download-formal-snapshot`, the restored state
+    /// is not checked against the end of epoch root state hash.
+    #[clap(
+        name = "download-partial-snapshot",
+        about = "Downloads the objects of a formal snapshot matching a filter, outputs to a JSON or BCS file"
+    )]
+    DownloadPartialSnapshot {
+        #[clap(long = "epoch", conflicts_with = "latest")]
+        epoch: Option<u64>,
+        /// Directory used to stage the snapshot files while restoring.
+        #[clap(long = "path")]
+        path: PathBuf,
+        /// File to write the restored objects to.
+        #[clap(long = "output")]
+        output: PathBuf,
+        /// Format of the output file.
+        #[clap(long = "format", default_value = "json")]
+        format: PartialSnapshotFormat,
+        /// Restore objects owned by this address. Can be repeated.
+        #[clap(long = "owner")]
+        owners: Vec<SuiAddress>,
+        /// Restore objects of this Move type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`. A type given
+        /// without type parameters matches all of its instantiations. Can be repeated.
+        #[clap(long = "type", value_parser = parse_sui_struct_tag)]
+        types: Vec<StructTag>,
+        /// Restore dynamic fields whose name or value type is defined in this package. Can be
+        /// repeated.
+        #[clap(long = "dynamic-field-package")]
+        dynamic_field_packages: Vec<ObjectID>,
+        /// Restore the package with this ID. The packages of the `--type` filters and the
+        /// `--dynamic-field-package`s are restored too, but not the packages of objects restored
+        /// for an `--owner`, nor upgraded versions of any package. Can be repeated.
+        #[clap(long = "package")]
+        packages: Vec<ObjectID>,
+        /// Number of parallel downloads to perform. Defaults to a reasonable
+        /// value based on number of available logical cores.
+        #[clap(long = "num-parallel-downloads")]
+        num_parallel_downloads: Option<usize>,
+        /// Network to download snapshot for. Defaults to "mainnet".
+        /// If `--snapshot-bucket` is not specified, the value of this
+        /// flag is used to construct the default bucket name.
+        #[clap(long = "network", default_value = "mainnet")]
+        network: Chain,
+        /// Snapshot bucket name. If not specified, defaults are
+        /// based on value of `--network` flag.
+        #[clap(long = "snapshot-bucket", conflicts_with = "no_sign_request")]
+        snapshot_bucket: Option<String>,
+        /// Snapshot bucket type
+        #[clap(
+            long = "snapshot-bucket-type",
+            conflicts_with = "no_sign_request",
+            help = "Required if --no-sign-request is not set"
+        )]
+        snapshot_bucket_type: Option<ObjectStoreType>,
+        /// Path to snapshot directory on local filesystem.
+        /// Only applicable if `--snapshot-bucket-type` is "file".
+        #[clap(long = "snapshot-path")]
+        snapshot_path: Option<PathBuf>,
+        /// If true, no authentication is needed for snapshot restores
+        #[clap(
+            long = "no-sign-request",
+            conflicts_with_all = &["snapshot_bucket", "snapshot_bucket_type"],
+            help = "if set, no authentication is needed for snapshot restore"
+        )]
+        no_sign_request: bool,
+        /// Download snapshot of the latest available epoch.
+        /// If `--epoch` is specified, then this flag gets ignored.
+        #[clap(
+            long = "latest",
+            conflicts_with = "epoch",
+            help = "defaults to latest available snapshot in chosen bucket"
+        )]
+        latest: bool,
+        /// If false (default), log level will be overridden to "off",
+        /// and output will be reduced to necessary status information.
+        #[clap(long = "verbose")]
+        verbose: bool,
+    },
+
     #[clap(name = "replay")]
     Replay {
         #[arg(long = "rpc")]
@@ -414,6 +498,96 @@ pub enum ToolCommand {
     },
 }
 
+/// Builds the config of the object store holding formal snapshots, defaulting the bucket and
+/// endpoint based on `network`.
+fn formal_snapshot_store_config(
+    network: Chain,
+    snapshot_bucket: Option<String>,
+    snapshot_bucket_type: Option<ObjectStoreType>,
+    snapshot_path: Option<PathBuf>,
+    no_sign_request: bool,
+) -> ObjectStoreConfig {
+    let snapshot_bucket = snapshot_bucket.or_else(|| match (network, no_sign_request) {
+        (Chain::Mainnet, false) => Some(
+            env::var("MAINNET_FORMAL_SIGNED_BUCKET").unwrap_or("mysten-mainnet-formal".to_string()),
+        ),
+        (Chain::Mainnet, true) => env::var("MAINNET_FORMAL_UNSIGNED_BUCKET").ok(),
+        (Chain::Testnet, true) => env::var("TESTNET_FORMAL_UNSIGNED_BUCKET").ok(),
+        (Chain::Testnet, _) => Some(
+            env::var("TESTNET_FORMAL_SIGNED_BUCKET").unwrap_or("mysten-testnet-formal".to_string()),
+        ),
+        (Chain::Unknown, _) => {
+            panic!("Cannot generate default snapshot bucket for unknown network");
+        }
+    });
+
+    let aws_endpoint = env::var("AWS_SNAPSHOT_ENDPOINT").ok().or_else(|| {
+        if no_sign_request {
+            if network == Chain::Mainnet {
+                Some("https://formal-snapshot.mainnet.sui.io".to_string())
+            } else if network == Chain::Testnet {
+                Some("https://formal-snapshot.testnet.sui.io".to_string())
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    });
+
+    let snapshot_bucket_type = if no_sign_request {
+        ObjectStoreType::S3
+    } else {
+        snapshot_bucket_type
+            .expect("You must set either --snapshot-bucket-type or --no-sign-request")
+    };
+    match snapshot_bucket_type {
+        ObjectStoreType::S3 => ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::S3),
+            bucket: snapshot_bucket.filter(|s| !s.is_empty()),
+            aws_access_key_id: env::var("AWS_SNAPSHOT_ACCESS_KEY_ID").ok(),
+            aws_secret_access_key: env::var("AWS_SNAPSHOT_SECRET_ACCESS_KEY").ok(),
+            aws_region: env::var("AWS_SNAPSHOT_REGION").ok(),
+            aws_endpoint: aws_endpoint.filter(|s| !s.is_empty()),
+            aws_virtual_hosted_style_request: env::var("AWS_SNAPSHOT_VIRTUAL_HOSTED_REQUESTS")
+                .ok()
+                .and_then(|b| b.parse().ok())
+                .unwrap_or(no_sign_request),
+            object_store_connection_limit: 200,
+            no_sign_request,
+            ..Default::default()
+        },
+        ObjectStoreType::GCS => ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::GCS),
+            bucket: snapshot_bucket,
+            google_service_account: env::var("GCS_SNAPSHOT_SERVICE_ACCOUNT_FILE_PATH").ok(),
+            object_store_connection_limit: 200,
+            no_sign_request,
+            ..Default::default()
+        },
+        ObjectStoreType::Azure => ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::Azure),
+            bucket: snapshot_bucket,
+            azure_storage_account: env::var("AZURE_SNAPSHOT_STORAGE_ACCOUNT").ok(),
+            azure_storage_access_key: env::var("AZURE_SNAPSHOT_STORAGE_ACCESS_KEY").ok(),
+            object_store_connection_limit: 200,
+            no_sign_request,
+            ..Default::default()
+        },
+        ObjectStoreType::File => {
+            if snapshot_path.is_some() {
+                ObjectStoreConfig {
+                    object_store: Some(ObjectStoreType::File),
+                    directory: snapshot_path,
+                    ..Default::default()
+                }
+            } else {
+                panic!("--snapshot-path must be specified for --snapshot-bucket-type=file");
+            }
+        }
+    }
+}
+
 trait OptionDebug<T> {
     fn opt_debug(&self, def_str: &str) -> String;
 }
@@ -728,94 +902,13 @@ impl ToolCommand {
                         .checked_sub(1)
                         .expect("Failed to get number of CPUs")
                 });
-                let snapshot_bucket =
-                    snapshot_bucket.or_else(|| match (network, no_sign_request) {
-                        (Chain::Mainnet, false) => Some(
-                            env::var("MAINNET_FORMAL_SIGNED_BUCKET")
-                                .unwrap_or("mysten-mainnet-formal".to_string()),
-                        ),
-                        (Chain::Mainnet, true) => env::var("MAINNET_FORMAL_UNSIGNED_BUCKET").ok(),
-                        (Chain::Testnet, true) => env::var("TESTNET_FORMAL_UNSIGNED_BUCKET").ok(),
-                        (Chain::Testnet, _) => Some(
-                            env::var("TESTNET_FORMAL_SIGNED_BUCKET")
-                                .unwrap_or("mysten-testnet-formal".to_string()),
-                        ),
-                        (Chain::Unknown, _) => {
-                            panic!("Cannot generate default snapshot bucket for unknown network");
-                        }
-                    });
-
-                let aws_endpoint = env::var("AWS_SNAPSHOT_ENDPOINT").ok().or_else(|| {
-                    if no_sign_request {
-                        if network == Chain::Mainnet {
-                            Some("https://formal-snapshot.mainnet.sui.io".to_string())
-                        } else if network == Chain::Testnet {
-                            Some("https://formal-snapshot.testnet.sui.io".to_string())
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                });
-
-                let snapshot_bucket_type = if no_sign_request {
-                    ObjectStoreType::S3
-                } else {
-                    snapshot_bucket_type
-                        .expect("You must set either --snapshot-bucket-type or --no-sign-request")
-                };
-                let snapshot_store_config = match snapshot_bucket_type {
-                    ObjectStoreType::S3 => ObjectStoreConfig {
-                        object_store: Some(ObjectStoreType::S3),
-                        bucket: snapshot_bucket.filter(|s| !s.is_empty()),
-                        aws_access_key_id: env::var("AWS_SNAPSHOT_ACCESS_KEY_ID").ok(),
-                        aws_secret_access_key: env::var("AWS_SNAPSHOT_SECRET_ACCESS_KEY").ok(),
-                        aws_region: env::var("AWS_SNAPSHOT_REGION").ok(),
-                        aws_endpoint: aws_endpoint.filter(|s| !s.is_empty()),
-                        aws_virtual_hosted_style_request: env::var(
-                            "AWS_SNAPSHOT_VIRTUAL_HOSTED_REQUESTS",
-                        )
-                        .ok()
-                        .and_then(|b| b.parse().ok())
-                        .unwrap_or(no_sign_request),
-                        object_store_connection_limit: 200,
-                        no_sign_request,
-                        ..Default::default()
-                    },
-                    ObjectStoreType::GCS => ObjectStoreConfig {
-                        object_store: Some(ObjectStoreType::GCS),
-                        bucket: snapshot_bucket,
-                        google_service_account: env::var("GCS_SNAPSHOT_SERVICE_ACCOUNT_FILE_PATH")
-                            .ok(),
-                        object_store_connection_limit: 200,
-                        no_sign_request,
-                        ..Default::default()
-                    },
-                    ObjectStoreType::Azure => ObjectStoreConfig {
-                        object_store: Some(ObjectStoreType::Azure),
-                        bucket: snapshot_bucket,
-                        azure_storage_account: env::var("AZURE_SNAPSHOT_STORAGE_ACCOUNT").ok(),
-                        azure_storage_access_key: env::var("AZURE_SNAPSHOT_STORAGE_ACCESS_KEY")
-                            .ok(),
-                        object_store_connection_limit: 200,
-                        no_sign_request,
-                        ..Default::default()
-                    },
-                    ObjectStoreType::File => {
-                        if snapshot_path.is_some() {
-                            ObjectStoreConfig {
-                                object_store: Some(ObjectStoreType::File),
-                                directory: snapshot_path,
-                                ..Default::default()
-                            }
-                        } else {
-                            panic!(
-                                "--snapshot-path must be specified for --snapshot-bucket-type=file"
-                            );
-                        }
-                    }
-                };
+                let snapshot_store_config = formal_snapshot_store_config(
+                    network,
+                    snapshot_bucket,
+                    snapshot_bucket_type,
+                    snapshot_path,
+                    no_sign_request,
+                );
 
                 let archive_bucket = Some(
                     env::var("FORMAL_SNAPSHOT_ARCHIVE_BUCKET").unwrap_or_else(|_| match network {
@@ -929,6 +1022,73 @@ impl ToolCommand {
                 )
                 .await?;
             }
+            ToolCommand::DownloadPartialSnapshot {
+                epoch,
+                path,
+                output,
+                format,
+                owners,
+                types,
+                dynamic_field_packages,
+                packages,
+                num_parallel_downloads,
+                network,
+                snapshot_bucket,
+                snapshot_bucket_type,
+                snapshot_path,
+                no_sign_request,
+                latest,
+                verbose,
+            } => {
+                if !verbose {
+                    tracing_handle
+                        .update_log("off")
+                        .expect("Failed to update log level");
+                }
+                let num_parallel_downloads = num_parallel_downloads.unwrap_or_else(|| {
+                    num_cpus::get()
+                        .checked_sub(1)
+                        .expect("Failed to get number of CPUs")
+                });
+                let snapshot_store_config = formal_snapshot_store_config(
+                    network,
+                    snapshot_bucket,
+                    snapshot_bucket_type,
+                    snapshot_path,
+                    no_sign_request,
+                );
+                let latest_available_epoch =
+                    latest.then_some(get_latest_available_epoch(&snapshot_store_config).await?);
+                let epoch_to_download = epoch.or(latest_available_epoch).expect(
+                    "Either pass epoch with --epoch <epoch_num> or use latest with --latest",
+                );
+
+                if let Err(e) =
+                    check_completed_snapshot(&snapshot_store_config, epoch_to_download).await
+                {
+                    panic!(
+                        "Aborting snapshot restore: {}, snapshot may not be uploaded yet",
+                        e
+                    );
+                }
+
+                let filter = ObjectFilter {
+                    owners: owners.into_iter().collect(),
+                    types,
+                    dynamic_field_packages: dynamic_field_packages.into_iter().collect(),
+                    packages: packages.into_iter().collect(),
+                };
+                download_partial_snapshot(
+                    &path,
+                    &output,
+                    epoch_to_download,
+                    snapshot_store_config,
+                    filter,
+                    num_parallel_downloads,
+                    format,
+                )
+                .await?;
+            }
             ToolCommand::DownloadDBSnapshot {
                 epoch,
                 path,
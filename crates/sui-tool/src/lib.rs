@@ -54,7 +54,8 @@ use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::storage::RocksDbStore;
-use sui_snapshot::reader::StateSnapshotReaderV1;
+use sui_snapshot::filter::{restore_filtered, ObjectFilter};
+use sui_snapshot::reader::{snapshot_chain, StateSnapshotReaderV1};
 use sui_snapshot::setup_db_state;
 use sui_storage::object_store::util::{copy_file, exists, get_path};
 use sui_storage::object_store::ObjectStoreGetExt;
@@ -91,6 +92,17 @@ pub enum SnapshotVerifyMode {
     Strict,
 }
 
+#[derive(
+    Clone, Serialize, Deserialize, Debug, PartialEq, Copy, PartialOrd, Ord, Eq, ValueEnum, Default,
+)]
+pub enum PartialSnapshotFormat {
+    /// A JSON array of the restored objects.
+    #[default]
+    Json,
+    /// The BCS encoding of the vector of restored objects.
+    Bcs,
+}
+
 // This functions requires at least one of genesis or fullnode_rpc to be `Some`.
 async fn make_clients(
     sui_client: &Arc<SuiClient>,
@@ -844,6 +856,16 @@ fn start_summary_sync(
             verify_progress_bar.finish_with_message("Checkpoint summary verification is complete");
         }
 
+        // Incremental snapshot restores verify the state at the end of every epoch of their chain
+        for (cp_epoch, epoch_last_cp_seq_num) in end_of_epoch_checkpoint_seq_nums.iter().enumerate()
+        {
+            let epoch_last_checkpoint = checkpoint_store
+                .get_checkpoint_by_sequence_number(*epoch_last_cp_seq_num)?
+                .ok_or(anyhow!("Failed to read checkpoint"))?;
+            checkpoint_store
+                .insert_epoch_last_checkpoint(cp_epoch as u64, &epoch_last_checkpoint)?;
+        }
+
         checkpoint_store.update_highest_verified_checkpoint(&checkpoint)?;
         checkpoint_store.update_highest_synced_checkpoint(&checkpoint)?;
         checkpoint_store.update_highest_executed_checkpoint(&checkpoint)?;
@@ -899,6 +921,56 @@ pub async fn check_completed_snapshot(
     }
 }
 
+/// Restores the objects of the formal snapshot of `epoch` selected by `filter` and writes them
+/// to `output`, e.g. to seed a simulacrum or a local test.
+pub async fn download_partial_snapshot(
+    path: &Path,
+    output: &Path,
+    epoch: EpochId,
+    snapshot_store_config: ObjectStoreConfig,
+    filter: ObjectFilter,
+    num_parallel_downloads: usize,
+    format: PartialSnapshotFormat,
+) -> Result<(), anyhow::Error> {
+    let m = MultiProgress::new();
+    m.println(format!(
+        "Beginning partial snapshot restore of end of epoch {}, format: {:?}",
+        epoch, format,
+    ))?;
+    let snapshot_dir = path.join("snapshot");
+    if snapshot_dir.exists() {
+        fs::remove_dir_all(snapshot_dir.clone())?;
+    }
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(snapshot_dir.clone()),
+        ..Default::default()
+    };
+    let storage = restore_filtered(
+        epoch,
+        &snapshot_store_config,
+        &local_store_config,
+        &filter,
+        NonZeroUsize::new(num_parallel_downloads).unwrap(),
+        m.clone(),
+    )
+    .await?;
+    fs::remove_dir_all(snapshot_dir)?;
+
+    let objects: Vec<_> = storage.into_inner().into_values().collect();
+    let bytes = match format {
+        PartialSnapshotFormat::Json => serde_json::to_vec_pretty(&objects)?,
+        PartialSnapshotFormat::Bcs => bcs::to_bytes(&objects)?,
+    };
+    fs::write(output, bytes)?;
+    m.println(format!(
+        "Wrote {} objects to {}",
+        objects.len(),
+        output.display()
+    ))?;
+    Ok(())
+}
+
 pub async fn download_formal_snapshot(
     path: &Path,
     epoch: EpochId,
@@ -946,99 +1018,105 @@ pub async fn download_formal_snapshot(
         verify != SnapshotVerifyMode::None,
         all_checkpoints,
     );
-    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
-    let perpetual_db_clone = perpetual_db.clone();
     let snapshot_dir = path.parent().unwrap().join("snapshot");
     if snapshot_dir.exists() {
         fs::remove_dir_all(snapshot_dir.clone())?;
     }
-    let snapshot_dir_clone = snapshot_dir.clone();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(snapshot_dir.to_path_buf()),
+        ..Default::default()
+    };
 
-    // TODO if verify is false, we should skip generating these and
-    // not pass in a channel to the reader
-    let (sender, mut receiver) = mpsc::channel(num_parallel_downloads);
-    let m_clone = m.clone();
+    // An incremental snapshot is restored by restoring the full snapshot at the root of its chain,
+    // then applying every incremental snapshot of the chain on top of it
+    let snapshot_chain = snapshot_chain(epoch, &snapshot_store_config).await?;
+    if snapshot_chain.len() > 1 {
+        m.println(format!(
+            "Snapshot of epoch {} is incremental, restoring the snapshots of epochs {:?}",
+            epoch, snapshot_chain,
+        ))?;
+    }
 
-    let snapshot_handle = tokio::spawn(async move {
-        let local_store_config = ObjectStoreConfig {
-            object_store: Some(ObjectStoreType::File),
-            directory: Some(snapshot_dir_clone.to_path_buf()),
-            ..Default::default()
-        };
+    let mut summaries_handle = Some(summaries_handle);
+    let mut root_accumulator = Accumulator::default();
+    let mut num_live_objects: u64 = 0;
+    for snapshot_epoch in snapshot_chain {
         let mut reader = StateSnapshotReaderV1::new(
-            epoch,
+            snapshot_epoch,
             &snapshot_store_config,
             &local_store_config,
             usize::MAX,
             NonZeroUsize::new(num_parallel_downloads).unwrap(),
-            m_clone,
+            m.clone(),
         )
         .await
         .unwrap_or_else(|err| panic!("Failed to create reader: {}", err));
-        reader
-            .read(&perpetual_db_clone, abort_registration, Some(sender))
-            .await
-            .unwrap_or_else(|err| panic!("Failed during read: {}", err));
-        Ok::<(), anyhow::Error>(())
-    });
-    let mut root_accumulator = Accumulator::default();
-    let mut num_live_objects = 0;
-    while let Some((partial_acc, num_objects)) = receiver.recv().await {
-        num_live_objects += num_objects;
-        root_accumulator.union(&partial_acc);
-    }
-    summaries_handle
-        .await
-        .expect("Task join failed")
-        .expect("Summaries task failed");
-
-    let last_checkpoint = checkpoint_store
-        .get_highest_verified_checkpoint()?
-        .expect("Expected nonempty checkpoint store");
-
-    // Perform snapshot state verification
-    if verify != SnapshotVerifyMode::None {
-        assert_eq!(
-            last_checkpoint.epoch(),
-            epoch,
-            "Expected highest verified checkpoint ({}) to be for epoch {} but was for epoch {}",
-            last_checkpoint.sequence_number,
-            epoch,
-            last_checkpoint.epoch()
-        );
-        let commitment = last_checkpoint
-            .end_of_epoch_data
-            .as_ref()
-            .expect("Expected highest verified checkpoint to have end of epoch data")
-            .epoch_commitments
-            .last()
-            .expect(
-                "End of epoch has no commitments. This likely means that the epoch \
-                you are attempting to restore from does not support end of epoch state \
-                digest commitment. If restoring from mainnet, `--epoch` must be > 20, \
-                and for testnet, `--epoch` must be > 12.",
-            );
-        match commitment {
-            CheckpointCommitment::ECMHLiveObjectSetDigest(consensus_digest) => {
-                let local_digest: ECMHLiveObjectSetDigest = root_accumulator.digest().into();
+        let (deleted_accumulator, num_deleted_objects) = reader.deleted_objects_accumulator()?;
+        root_accumulator.union(&deleted_accumulator);
+        num_live_objects = num_live_objects
+            .checked_sub(num_deleted_objects)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Snapshot of epoch {} deletes more objects than its base contains",
+                    snapshot_epoch
+                )
+            })?;
+
+        // TODO if verify is false, we should skip generating these and
+        // not pass in a channel to the reader
+        let (sender, mut receiver) = mpsc::channel(num_parallel_downloads);
+        let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+        let perpetual_db_clone = perpetual_db.clone();
+        let snapshot_handle = tokio::spawn(async move {
+            reader
+                .read(&perpetual_db_clone, abort_registration, Some(sender))
+                .await
+                .unwrap_or_else(|err| panic!("Failed during read: {}", err));
+            Ok::<(), anyhow::Error>(())
+        });
+        while let Some((partial_acc, num_objects)) = receiver.recv().await {
+            num_live_objects += num_objects;
+            root_accumulator.union(&partial_acc);
+        }
+
+        if let Some(summaries_handle) = summaries_handle.take() {
+            summaries_handle
+                .await
+                .expect("Task join failed")
+                .expect("Summaries task failed");
+            let last_checkpoint = checkpoint_store
+                .get_highest_verified_checkpoint()?
+                .expect("Expected nonempty checkpoint store");
+            if verify != SnapshotVerifyMode::None {
                 assert_eq!(
-                    *consensus_digest, local_digest,
-                    "End of epoch {} root state digest {} does not match \
-                    local root state hash {} computed from snapshot data",
-                    epoch, consensus_digest.digest, local_digest.digest,
-                );
-                let progress_bar = m.add(
-                    ProgressBar::new(1).with_style(
-                        ProgressStyle::with_template(
-                            "[{elapsed_precise}] {wide_bar} Verifying snapshot contents against root state hash ({msg})",
-                        )
-                        .unwrap(),
-                    ),
+                    last_checkpoint.epoch(),
+                    epoch,
+                    "Expected highest verified checkpoint ({}) to be for epoch {} but was for epoch {}",
+                    last_checkpoint.sequence_number,
+                    epoch,
+                    last_checkpoint.epoch()
                 );
-                progress_bar.finish_with_message("Verification complete");
             }
-        };
-    } else {
+        }
+
+        // Perform snapshot state verification
+        if verify != SnapshotVerifyMode::None {
+            verify_snapshot_root_state_hash(
+                &checkpoint_store,
+                snapshot_epoch,
+                &root_accumulator,
+                &m,
+            )?;
+        }
+
+        snapshot_handle
+            .await
+            .expect("Task join failed")
+            .expect("Snapshot restore task failed");
+    }
+
+    if verify == SnapshotVerifyMode::None {
         m.println(
             "WARNING: Skipping snapshot verification! \
             This is highly discouraged unless you fully trust the source of this snapshot and its contents.
@@ -1046,16 +1124,6 @@ pub async fn download_formal_snapshot(
         )?;
     }
 
-    snapshot_handle
-        .await
-        .expect("Task join failed")
-        .expect("Snapshot restore task failed");
-
-    // TODO we should ensure this map is being updated for all end of epoch
-    // checkpoints during summary sync. This happens in `insert_{verified|certified}_checkpoint`
-    // in checkpoint store, but not in the corresponding functions in ObjectStore trait
-    checkpoint_store.insert_epoch_last_checkpoint(epoch, &last_checkpoint)?;
-
     setup_db_state(
         epoch,
         root_accumulator.clone(),
@@ -1083,6 +1151,52 @@ pub async fn download_formal_snapshot(
     Ok(())
 }
 
+/// Checks the root state hash of the live object set restored up to the end of `epoch` against
+/// the commitment in the last checkpoint of the epoch.
+fn verify_snapshot_root_state_hash(
+    checkpoint_store: &CheckpointStore,
+    epoch: EpochId,
+    root_accumulator: &Accumulator,
+    m: &MultiProgress,
+) -> Result<(), anyhow::Error> {
+    let epoch_last_checkpoint = checkpoint_store
+        .get_epoch_last_checkpoint(epoch)?
+        .ok_or_else(|| anyhow!("Missing last checkpoint of epoch {}", epoch))?;
+    let commitment = epoch_last_checkpoint
+        .end_of_epoch_data
+        .as_ref()
+        .expect("Expected last checkpoint of epoch to have end of epoch data")
+        .epoch_commitments
+        .last()
+        .expect(
+            "End of epoch has no commitments. This likely means that the epoch \
+            you are attempting to restore from does not support end of epoch state \
+            digest commitment. If restoring from mainnet, `--epoch` must be > 20, \
+            and for testnet, `--epoch` must be > 12.",
+        );
+    match commitment {
+        CheckpointCommitment::ECMHLiveObjectSetDigest(consensus_digest) => {
+            let local_digest: ECMHLiveObjectSetDigest = root_accumulator.digest().into();
+            assert_eq!(
+                *consensus_digest, local_digest,
+                "End of epoch {} root state digest {} does not match \
+                local root state hash {} computed from snapshot data",
+                epoch, consensus_digest.digest, local_digest.digest,
+            );
+            let progress_bar = m.add(
+                ProgressBar::new(1).with_style(
+                    ProgressStyle::with_template(
+                        "[{elapsed_precise}] {wide_bar} Verifying snapshot contents against root state hash ({msg})",
+                    )
+                    .unwrap(),
+                ),
+            );
+            progress_bar.finish_with_message(format!("Verification of epoch {} complete", epoch));
+        }
+    };
+    Ok(())
+}
+
 pub async fn download_db_snapshot(
     path: &Path,
     epoch: u64,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
+use std::fmt;
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_types::{
     base_types::ObjectRef,
@@ -11,17 +12,90 @@ use sui_types::{
     transaction::{Command, InputObjectKind, TransactionData, TransactionDataAPI},
 };
 macro_rules! deny_if_true {
-    ($cond:expr, $msg:expr) => {
+    ($cond:expr, $rule:expr, $msg:expr) => {
         if ($cond) {
-            return Err(SuiError::UserInputError {
-                error: UserInputError::TransactionDenied {
-                    error: $msg.to_string(),
+            return Err(DenyError::Denied {
+                rule: $rule,
+                error: SuiError::UserInputError {
+                    error: UserInputError::TransactionDenied {
+                        error: $msg.to_string(),
+                    },
                 },
             });
         }
     };
 }
 
+/// The rule of a `TransactionDenyConfig` that rejected a transaction. Named after the
+/// config field that enables it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenyRule {
+    UserTransactionDisabled,
+    ZkloginSigDisabled,
+    ZkloginDisabledProviders,
+    PackagePublishDisabled,
+    PackageUpgradeDisabled,
+    AddressDenyList,
+    ObjectDenyList,
+    SharedObjectDisabled,
+    PackageDenyList,
+    ReceivingObjectsDisabled,
+}
+
+impl DenyRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DenyRule::UserTransactionDisabled => "user-transaction-disabled",
+            DenyRule::ZkloginSigDisabled => "zklogin-sig-disabled",
+            DenyRule::ZkloginDisabledProviders => "zklogin-disabled-providers",
+            DenyRule::PackagePublishDisabled => "package-publish-disabled",
+            DenyRule::PackageUpgradeDisabled => "package-upgrade-disabled",
+            DenyRule::AddressDenyList => "address-deny-list",
+            DenyRule::ObjectDenyList => "object-deny-list",
+            DenyRule::SharedObjectDisabled => "shared-object-disabled",
+            DenyRule::PackageDenyList => "package-deny-list",
+            DenyRule::ReceivingObjectsDisabled => "receiving-objects-disabled",
+        }
+    }
+}
+
+impl fmt::Display for DenyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub enum DenyError {
+    /// The transaction was rejected by `rule`.
+    Denied { rule: DenyRule, error: SuiError },
+    /// The deny config could not be evaluated, e.g. because a package failed to load.
+    Other(SuiError),
+}
+
+impl DenyError {
+    pub fn rule(&self) -> Option<DenyRule> {
+        match self {
+            DenyError::Denied { rule, .. } => Some(*rule),
+            DenyError::Other(_) => None,
+        }
+    }
+}
+
+impl From<SuiError> for DenyError {
+    fn from(error: SuiError) -> Self {
+        DenyError::Other(error)
+    }
+}
+
+impl From<DenyError> for SuiError {
+    fn from(error: DenyError) -> Self {
+        match error {
+            DenyError::Denied { error, .. } | DenyError::Other(error) => error,
+        }
+    }
+}
+
 /// Check that the provided transaction is allowed to be signed according to the
 /// deny config.
 pub fn check_transaction_for_signing(
@@ -32,6 +106,27 @@ pub fn check_transaction_for_signing(
     filter_config: &TransactionDenyConfig,
     package_store: &dyn BackingPackageStore,
 ) -> SuiResult {
+    check_transaction_deny_rules(
+        tx_data,
+        tx_signatures,
+        input_object_kinds,
+        receiving_objects,
+        filter_config,
+        package_store,
+    )
+    .map_err(Into::into)
+}
+
+/// Same as `check_transaction_for_signing`, but reports which rule of the deny config
+/// rejected the transaction.
+pub fn check_transaction_deny_rules(
+    tx_data: &TransactionData,
+    tx_signatures: &[GenericSignature],
+    input_object_kinds: &[InputObjectKind],
+    receiving_objects: &[ObjectRef],
+    filter_config: &TransactionDenyConfig,
+    package_store: &dyn BackingPackageStore,
+) -> Result<(), DenyError> {
     check_disabled_features(filter_config, tx_data, tx_signatures)?;
 
     check_signers(filter_config, tx_data)?;
@@ -48,14 +143,16 @@ pub fn check_transaction_for_signing(
 fn check_receiving_objects(
     filter_config: &TransactionDenyConfig,
     receiving_objects: &[ObjectRef],
-) -> SuiResult {
+) -> Result<(), DenyError> {
     deny_if_true!(
         filter_config.receiving_objects_disabled() && !receiving_objects.is_empty(),
+        DenyRule::ReceivingObjectsDisabled,
         "Receiving objects is temporarily disabled".to_string()
     );
     for (id, _, _) in receiving_objects {
         deny_if_true!(
             filter_config.get_object_deny_set().contains(id),
+            DenyRule::ObjectDenyList,
             format!("Access to object {:?} is temporarily disabled", id)
         );
     }
@@ -66,9 +163,10 @@ fn check_disabled_features(
     filter_config: &TransactionDenyConfig,
     tx_data: &TransactionData,
     tx_signatures: &[GenericSignature],
-) -> SuiResult {
+) -> Result<(), DenyError> {
     deny_if_true!(
         filter_config.user_transaction_disabled(),
+        DenyRule::UserTransactionDisabled,
         "Transaction signing is temporarily disabled"
     );
 
@@ -76,6 +174,7 @@ fn check_disabled_features(
         if let GenericSignature::ZkLoginAuthenticator(z) = s {
             deny_if_true!(
                 filter_config.zklogin_sig_disabled(),
+                DenyRule::ZkloginSigDisabled,
                 "zkLogin authenticator is temporarily disabled"
             );
             deny_if_true!(
@@ -84,6 +183,7 @@ fn check_disabled_features(
                         .map_err(|_| SuiError::UnexpectedMessage)?
                         .to_string()
                 ),
+                DenyRule::ZkloginDisabledProviders,
                 "zkLogin OAuth provider is temporarily disabled"
             )
         }
@@ -97,17 +197,22 @@ fn check_disabled_features(
     for command in tx_data.kind().iter_commands() {
         deny_if_true!(
             filter_config.package_publish_disabled() && matches!(command, Command::Publish(..)),
+            DenyRule::PackagePublishDisabled,
             "Package publish is temporarily disabled"
         );
         deny_if_true!(
             filter_config.package_upgrade_disabled() && matches!(command, Command::Upgrade(..)),
+            DenyRule::PackageUpgradeDisabled,
             "Package upgrade is temporarily disabled"
         );
     }
     Ok(())
 }
 
-fn check_signers(filter_config: &TransactionDenyConfig, tx_data: &TransactionData) -> SuiResult {
+fn check_signers(
+    filter_config: &TransactionDenyConfig,
+    tx_data: &TransactionData,
+) -> Result<(), DenyError> {
     let deny_map = filter_config.get_address_deny_set();
     if deny_map.is_empty() {
         return Ok(());
@@ -115,6 +220,7 @@ fn check_signers(filter_config: &TransactionDenyConfig, tx_data: &TransactionDat
     for signer in tx_data.signers() {
         deny_if_true!(
             deny_map.contains(&signer),
+            DenyRule::AddressDenyList,
             format!(
                 "Access to account address {:?} is temporarily disabled",
                 signer
@@ -127,7 +233,7 @@ fn check_signers(filter_config: &TransactionDenyConfig, tx_data: &TransactionDat
 fn check_input_objects(
     filter_config: &TransactionDenyConfig,
     input_object_kinds: &[InputObjectKind],
-) -> SuiResult {
+) -> Result<(), DenyError> {
     let deny_map = filter_config.get_object_deny_set();
     let shared_object_disabled = filter_config.shared_object_disabled();
     if deny_map.is_empty() && !shared_object_disabled {
@@ -138,10 +244,12 @@ fn check_input_objects(
         let id = input_object_kind.object_id();
         deny_if_true!(
             deny_map.contains(&id),
+            DenyRule::ObjectDenyList,
             format!("Access to input object {:?} is temporarily disabled", id)
         );
         deny_if_true!(
             shared_object_disabled && input_object_kind.is_shared_object(),
+            DenyRule::SharedObjectDisabled,
             "Usage of shared object in transactions is temporarily disabled"
         );
     }
@@ -152,7 +260,7 @@ fn check_package_dependencies(
     filter_config: &TransactionDenyConfig,
     tx_data: &TransactionData,
     package_store: &dyn BackingPackageStore,
-) -> SuiResult {
+) -> Result<(), DenyError> {
     let deny_map = filter_config.get_package_deny_set();
     if deny_map.is_empty() {
         return Ok(());
@@ -204,6 +312,7 @@ fn check_package_dependencies(
     for dep in dependencies {
         deny_if_true!(
             deny_map.contains(&dep),
+            DenyRule::PackageDenyList,
             format!("Access to package {:?} is temporarily disabled", dep)
         );
     }
@@ -5,7 +5,7 @@ use std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration};
 
 use move_binary_format::CompiledModule;
 use move_bytecode_utils::module_cache::GetModule;
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
 use simulacrum::Simulacrum;
 use std::num::NonZeroUsize;
@@ -757,6 +757,38 @@ impl RestStateReader for PersistedStoreInnerReadOnlyWrapper {
     ) -> sui_types::storage::error::Result<Option<sui_types::storage::CoinInfo>> {
         todo!()
     }
+
+    fn account_balances_iter(
+        &self,
+        _owner: SuiAddress,
+        _cursor: Option<TypeTag>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::AccountBalanceInfo> + '_>,
+    > {
+        todo!()
+    }
+
+    fn event_by_type_iter(
+        &self,
+        _event_type: &StructTag,
+        _start: sui_types::storage::EventPosition,
+        _descending: bool,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::RestEventInfo> + '_>,
+    > {
+        todo!()
+    }
+
+    fn event_by_sender_iter(
+        &self,
+        _sender: SuiAddress,
+        _start: sui_types::storage::EventPosition,
+        _descending: bool,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::RestEventInfo> + '_>,
+    > {
+        todo!()
+    }
 }
 
 impl PersistedStoreInnerReadOnlyWrapper {
@@ -33,9 +33,12 @@ use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 pub use write_store::WriteStore;
 
+pub use read_store::AccountBalanceInfo;
 pub use read_store::AccountOwnedObjectInfo;
 pub use read_store::CoinInfo;
+pub use read_store::EventPosition;
 pub use read_store::RestDynamicFieldInfo;
+pub use read_store::RestEventInfo;
 
 /// A potential input to a transaction.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
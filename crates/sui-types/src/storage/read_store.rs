@@ -673,6 +673,30 @@ pub trait RestStateReader: ObjectStore + ReadStore + Send + Sync {
     ) -> Result<Box<dyn Iterator<Item = RestDynamicFieldInfo> + '_>>;
 
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    fn account_balances_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<Box<dyn Iterator<Item = AccountBalanceInfo> + '_>>;
+
+    /// Iterates over the events of `event_type`, regardless of its type parameters, starting at
+    /// `start` in the order they were emitted in, or the reverse order if `descending`.
+    fn event_by_type_iter(
+        &self,
+        event_type: &StructTag,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = RestEventInfo> + '_>>;
+
+    /// Iterates over the events emitted by transactions sent by `sender`, starting at `start` in
+    /// the order they were emitted in, or the reverse order if `descending`.
+    fn event_by_sender_iter(
+        &self,
+        sender: SuiAddress,
+        start: EventPosition,
+        descending: bool,
+    ) -> Result<Box<dyn Iterator<Item = RestEventInfo> + '_>>;
 }
 
 pub struct AccountOwnedObjectInfo {
@@ -682,6 +706,47 @@ pub struct AccountOwnedObjectInfo {
     pub type_: MoveObjectType,
 }
 
+/// The total balance of the coins of type `coin_type` owned by `owner`.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct AccountBalanceInfo {
+    pub owner: SuiAddress,
+    pub coin_type: TypeTag,
+    pub balance: u64,
+    pub coin_object_count: u64,
+}
+
+/// The position of an event in history: the checkpoint it was emitted in, the index of its
+/// transaction in the checkpoint and its index in the events of the transaction.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct EventPosition {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub transaction: u64,
+    pub event: u64,
+}
+
+impl EventPosition {
+    pub const MIN: Self = Self {
+        checkpoint: 0,
+        transaction: 0,
+        event: 0,
+    };
+
+    pub const MAX: Self = Self {
+        checkpoint: CheckpointSequenceNumber::MAX,
+        transaction: u64::MAX,
+        event: u64::MAX,
+    };
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct RestEventInfo {
+    pub position: EventPosition,
+    pub transaction_digest: TransactionDigest,
+    pub timestamp_ms: u64,
+    pub sender: SuiAddress,
+    pub type_: StructTag,
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
 pub struct RestDynamicFieldInfo {
     pub parent: ObjectID,
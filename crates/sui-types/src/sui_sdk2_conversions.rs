@@ -50,6 +50,8 @@ bcs_convert_impl!(
 );
 bcs_convert_impl!(crate::signature::GenericSignature, UserSignature);
 bcs_convert_impl!(crate::effects::TransactionEvents, TransactionEvents);
+bcs_convert_impl!(crate::event::Event, Event);
+bcs_convert_impl!(move_core_types::language_storage::TypeTag, TypeTag);
 
 impl<const T: bool> From<crate::crypto::AuthorityQuorumSignInfo<T>>
     for ValidatorAggregatedSignature
@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use ipnetwork::IpNetwork;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 // These values set to loosely attempt to limit
@@ -184,6 +186,35 @@ pub enum PolicyType {
     TestPanicOnInvocation,
 }
 
+/// Token bucket rate limit applied per client. Each request withdraws
+/// `cost` tokens from the bucket of the client that issued it, and the
+/// request is blocked if the bucket does not hold enough tokens. Buckets
+/// start full and are refilled continuously at `refill_per_sec`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TokenBucketConfig {
+    pub capacity: u64,
+    pub refill_per_sec: u64,
+    #[serde(default = "default_token_cost")]
+    pub cost: u64,
+}
+
+/// Traffic control settings for a single JSON-RPC method (e.g.
+/// `suix_getOwnedObjects`) or validator gRPC endpoint (e.g.
+/// `handle_certificate_v2`).
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct MethodPolicyConfig {
+    /// Per-client rate limit for this method, checked before the request
+    /// is served.
+    #[serde(default)]
+    pub rate_limit: Option<TokenBucketConfig>,
+    /// Weight with which requests to this method are sampled into the
+    /// spam policy. When unset, the weight chosen by the server is used.
+    #[serde(default)]
+    pub spam_weight: Option<Weight>,
+}
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -209,6 +240,18 @@ pub struct PolicyConfig {
     pub spam_sample_rate: Weight,
     #[serde(default = "default_dry_run")]
     pub dry_run: bool,
+    /// Clients within any of these networks bypass all traffic control
+    /// checks, including the blocklists and per-method rate limits.
+    #[serde(default)]
+    pub allowlist: Vec<IpNetwork>,
+    /// Requests from clients within any of these networks are always
+    /// blocked, unless the client is also allowlisted.
+    #[serde(default)]
+    pub denylist: Vec<IpNetwork>,
+    /// Per-method rate limits and spam weights, keyed by JSON-RPC method
+    /// name or validator gRPC endpoint name.
+    #[serde(default)]
+    pub method_policies: BTreeMap<String, MethodPolicyConfig>,
 }
 
 impl Default for PolicyConfig {
@@ -222,6 +265,9 @@ impl Default for PolicyConfig {
             channel_capacity: 100,
             spam_sample_rate: default_spam_sample_rate(),
             dry_run: default_dry_run(),
+            allowlist: vec![],
+            denylist: vec![],
+            method_policies: BTreeMap::new(),
         }
     }
 }
@@ -241,6 +287,10 @@ pub fn default_dry_run() -> bool {
     true
 }
 
+pub fn default_token_cost() -> u64 {
+    1
+}
+
 pub fn default_spam_sample_rate() -> Weight {
     Weight::new(0.2).unwrap()
 }
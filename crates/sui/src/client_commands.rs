@@ -548,6 +548,12 @@ pub enum SuiClientCommands {
         /// Optional protocol version to use, if not specified defaults to the one originally used for the transaction.
         #[arg(long, short, allow_hyphen_values = true)]
         protocol_version: Option<i64>,
+
+        /// Replay from a local directory of checkpoint files, in the format written by
+        /// sui-data-ingestion. The active environment's RPC endpoint is still used for what the
+        /// directory lacks, such as checkpoint contents
+        #[arg(long)]
+        archive_path: Option<PathBuf>,
     },
 
     /// Replay transactions listed in a file.
@@ -576,6 +582,12 @@ pub enum SuiClientCommands {
         /// If an error is encountered during a transaction, this specifies whether to terminate or continue
         #[arg(long, short)]
         terminate_early: bool,
+
+        /// Replay from a local directory of checkpoint files, in the format written by
+        /// sui-data-ingestion. The active environment's RPC endpoint is still used for what the
+        /// directory lacks, such as checkpoint contents
+        #[arg(long)]
+        archive_path: Option<PathBuf>,
     },
 }
 
@@ -697,15 +709,17 @@ impl SuiClientCommands {
                 ptb_info: _,
                 executor_version,
                 protocol_version,
+                archive_path,
             } => {
+                let rpc = context.config.get_active_env()?.rpc.clone();
                 let cmd = ReplayToolCommand::ReplayTransaction {
                     tx_digest,
                     show_effects: true,
                     executor_version,
                     protocol_version,
+                    archive_path,
                 };
 
-                let rpc = context.config.get_active_env()?.rpc.clone();
                 let _command_result =
                     sui_replay::execute_replay_command(Some(rpc), false, false, None, None, cmd)
                         .await?;
@@ -733,14 +747,16 @@ impl SuiClientCommands {
                 start,
                 end,
                 terminate_early,
+                archive_path,
             } => {
+                let rpc = context.config.get_active_env()?.rpc.clone();
                 let cmd = ReplayToolCommand::ReplayCheckpoints {
                     start,
                     end,
                     terminate_early,
                     max_tasks: 16,
+                    archive_path,
                 };
-                let rpc = context.config.get_active_env()?.rpc.clone();
                 let _command_result =
                     sui_replay::execute_replay_command(Some(rpc), false, false, None, None, cmd)
                         .await?;
@@ -33,12 +33,13 @@ use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use sui_keys::encryption::read_new_passphrase;
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
@@ -73,6 +74,9 @@ pub enum KeyToolCommand {
         /// The alias must start with a letter and can contain only letters, digits, dots, hyphens (-), or underscores (_).
         new_alias: Option<String>,
     },
+    /// Change the passphrase of an encrypted Sui CLI Keystore. Prompts for the current
+    /// passphrase, then for the new one.
+    ChangePassphrase,
     /// Convert private key in Hex or Base64 to new format (Bech32
     /// encoded 33 byte flag || private key starting with "suiprivkey").
     /// Hex private key format import and export are both deprecated in
@@ -99,6 +103,13 @@ pub enum KeyToolCommand {
         #[clap(long, default_value = "0")]
         cur_epoch: u64,
     },
+    /// Remove the encryption from an encrypted Sui CLI Keystore, storing its private keys in
+    /// plaintext again.
+    Decrypt,
+    /// Encrypt the private keys in Sui CLI Keystore at rest with a passphrase, migrating it from
+    /// the plaintext format. Commands that use private keys prompt for the passphrase to unlock
+    /// the keystore, or read it from the SUI_KEYSTORE_PASSPHRASE environment variable if it is set.
+    Encrypt,
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -339,6 +350,12 @@ pub struct Key {
     peer_id: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreEncryption {
+    encrypted: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportedKey {
@@ -453,6 +470,7 @@ pub struct ZkLoginInsecureSignPersonalMessage {
 pub enum CommandOutput {
     Alias(AliasUpdate),
     Convert(ConvertOutput),
+    Encryption(KeystoreEncryption),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
     Error(String),
@@ -486,10 +504,38 @@ impl KeyToolCommand {
                     new_alias,
                 })
             }
+            KeyToolCommand::ChangePassphrase => {
+                let keystore = file_keystore(keystore)?;
+                if !keystore.is_encrypted() {
+                    return Err(anyhow!(
+                        "Keystore is not encrypted, use `sui keytool encrypt` to encrypt it"
+                    ));
+                }
+                keystore.unlock_interactive()?;
+                keystore.set_passphrase(&read_new_passphrase()?)?;
+                CommandOutput::Encryption(KeystoreEncryption { encrypted: true })
+            }
             KeyToolCommand::Convert { value } => {
                 let result = convert_private_key_to_bech32(value)?;
                 CommandOutput::Convert(result)
             }
+            KeyToolCommand::Decrypt => {
+                let keystore = file_keystore(keystore)?;
+                keystore.unlock_interactive()?;
+                keystore.remove_passphrase()?;
+                CommandOutput::Encryption(KeystoreEncryption { encrypted: false })
+            }
+            KeyToolCommand::Encrypt => {
+                let keystore = file_keystore(keystore)?;
+                if keystore.is_encrypted() {
+                    return Err(anyhow!(
+                        "Keystore is already encrypted, use `sui keytool change-passphrase` to \
+                         change its passphrase"
+                    ));
+                }
+                keystore.set_passphrase(&read_new_passphrase()?)?;
+                CommandOutput::Encryption(KeystoreEncryption { encrypted: true })
+            }
 
             KeyToolCommand::DecodeMultiSig {
                 multisig,
@@ -626,6 +672,7 @@ impl KeyToolCommand {
                     ));
                 }
 
+                unlock(keystore)?;
                 match SuiKeyPair::decode(&input_string) {
                     Ok(skp) => {
                         info!("Importing Bech32 encoded private key to keystore");
@@ -648,6 +695,7 @@ impl KeyToolCommand {
                 }
             }
             KeyToolCommand::Export { key_identity } => {
+                unlock(keystore)?;
                 let address = get_identity_address_from_keystore(key_identity, keystore)?;
                 let skp = keystore.get_key(&address)?;
                 let key = ExportedKey {
@@ -808,6 +856,7 @@ impl KeyToolCommand {
                 data,
                 intent,
             } => {
+                unlock(keystore)?;
                 let address = get_identity_address_from_keystore(address, keystore)?;
                 let intent = intent.unwrap_or_else(Intent::sui_transaction);
                 let intent_clone = intent.clone();
@@ -985,6 +1034,7 @@ impl KeyToolCommand {
                 test_multisig,
                 sign_with_sk,
             } => {
+                unlock(keystore)?;
                 let skp = if fixed {
                     SuiKeyPair::Ed25519(Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32])))
                 } else {
@@ -1279,6 +1329,13 @@ impl Display for CommandOutput {
                     update.old_alias, update.new_alias
                 )
             }
+            CommandOutput::Encryption(encryption) => {
+                if encryption.encrypted {
+                    write!(formatter, "Keystore private keys are encrypted at rest")
+                } else {
+                    write!(formatter, "Keystore private keys are stored in plaintext")
+                }
+            }
             // Sign needs to be manually built because we need to wrap the very long
             // rawTxData string and rawIntentMsg strings into multiple rows due to
             // their lengths, which we cannot do with a JsonTable
@@ -1349,6 +1406,23 @@ impl Debug for CommandOutput {
     }
 }
 
+/// Returns the file based keystore behind `keystore`, which is the only kind that can be encrypted.
+fn file_keystore(keystore: &mut Keystore) -> Result<&mut FileBasedKeystore, anyhow::Error> {
+    match keystore {
+        Keystore::File(keystore) => Ok(keystore),
+        _ => Err(anyhow!("Only file based keystores can be encrypted")),
+    }
+}
+
+/// Prompts for the passphrase of an encrypted keystore if it has not been unlocked yet, so that
+/// its private keys can be used.
+fn unlock(keystore: &mut Keystore) -> Result<(), anyhow::Error> {
+    if let Keystore::File(keystore) = keystore {
+        keystore.unlock_interactive()?;
+    }
+    Ok(())
+}
+
 /// Converts legacy formatted private key to 33 bytes bech32 encoded private key or vice versa.
 /// It can handle:
 /// 1) Hex encoded 32 byte private key (assumes scheme is Ed25519), this is the legacy wallet format
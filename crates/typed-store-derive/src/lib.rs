@@ -896,6 +896,26 @@ pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
                             )*
                         }
                     }
+                    typed_store::sally::SallyDBOptions::Redb(path) => {
+                        let db = typed_store::redb_store::open_redb(&path, &[
+                            #(
+                                stringify!(#field_names),
+                            )*
+                        ]).expect(&format!("Cannot open DB at {:?}", path));
+                        let (
+                            #(
+                                #field_names
+                            ),*
+                        ) = (#(
+                            SallyColumn::Redb((typed_store::redb_store::RedbMap::#inner_types::reopen(&db, stringify!(#field_names)).expect(&format!("Cannot open {} table.", stringify!(#field_names))[..]), typed_store::sally::SallyConfig::default()))
+                            ),*);
+
+                        Self {
+                            #(
+                                #field_names,
+                            )*
+                        }
+                    }
                 }
             }
         }
@@ -981,6 +1001,14 @@ pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
                             )*
                         }
                     }
+                    typed_store::sally::SallyReadOnlyDBOptions::Redb(path) => {
+                        let db = typed_store::redb_store::open_redb_read_only(&path).expect(&format!("Cannot open DB at {:?}", path));
+                        Self {
+                            #(
+                                #field_names: SallyColumn::Redb((typed_store::redb_store::RedbMap::#inner_types::reopen(&db, stringify!(#field_names)).expect(&format!("Cannot open {} table.", stringify!(#field_names))[..]), typed_store::sally::SallyConfig::default())),
+                            )*
+                        }
+                    }
                 }
             }
 
@@ -1000,6 +1028,13 @@ pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
                                         .map(|(k, v)| (format!("{:?}", k), format!("{:?}", v)))
                                         .collect::<std::collections::BTreeMap<_, _>>()
                                 }
+                                SallyColumn::Redb((redb_map, typed_store::sally::SallyConfig { mode: typed_store::sally::SallyRunMode::FallbackToDB })) => {
+                                    typed_store::traits::Map::safe_iter(redb_map)
+                                        .skip((page_number * (page_size) as usize))
+                                        .take(page_size as usize)
+                                        .map(|entry| entry.map(|(k, v)| (format!("{:?}", k), format!("{:?}", v))))
+                                        .collect::<Result<std::collections::BTreeMap<_, _>, _>>()?
+                                }
                                 _ => unimplemented!(),
                             }
                         }
@@ -1020,6 +1055,9 @@ pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
                                     typed_store::traits::Map::try_catch_up_with_primary(db_map)?;
                                     db_map.table_summary()
                                 }
+                                SallyColumn::Redb((redb_map, typed_store::sally::SallyConfig { mode: typed_store::sally::SallyRunMode::FallbackToDB })) => {
+                                    redb_map.table_summary()
+                                }
                                 _ => unimplemented!(),
                             }
                         }
@@ -1040,6 +1078,9 @@ pub fn derive_sallydb_general(input: TokenStream) -> TokenStream {
                                     typed_store::traits::Map::try_catch_up_with_primary(db_map)?;
                                     typed_store::traits::Map::unbounded_iter(db_map).count()
                                 }
+                                SallyColumn::Redb((redb_map, typed_store::sally::SallyConfig { mode: typed_store::sally::SallyRunMode::FallbackToDB })) => {
+                                    typed_store::traits::Map::unbounded_iter(redb_map).count()
+                                }
                                 _ => unimplemented!(),
                             }
                         }
@@ -9,6 +9,8 @@ use thiserror::Error;
 pub enum TypedStoreError {
     #[error("rocksdb error: {0}")]
     RocksDBError(String),
+    #[error("redb error: {0}")]
+    RedbError(String),
     #[error("(de)serialization error: {0}")]
     SerializationError(String),
     #[error("the column family {0} was not registered with the database")]
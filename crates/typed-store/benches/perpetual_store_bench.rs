@@ -0,0 +1,233 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compares the RocksDB and redb backends on the access patterns of the authority perpetual
+//! store: atomic per-checkpoint writes of new object versions together with transaction effects,
+//! lookups of the latest version of an object, and multi gets of effects by digest.
+//!
+//! Note that redb fsyncs on every commit while RocksDB writes to its WAL without syncing, so the
+//! write numbers compare the default durability of each engine.
+
+use std::path::PathBuf;
+
+use criterion::*;
+use rand::{seq::SliceRandom, Rng};
+use typed_store::redb_store::{open_redb, RedbMap};
+use typed_store::rocks::{open_cf, DBMap, MetricConf, ReadWriteOptions};
+use typed_store::{Map, TypedStoreError};
+
+type ObjectID = [u8; 32];
+type Digest = [u8; 32];
+type ObjectKey = (ObjectID, u64);
+
+const NUM_OBJECTS: usize = 10_000;
+const VERSIONS_PER_OBJECT: u64 = 3;
+const BATCH_SIZE: usize = 100;
+const OBJECT_SIZE: usize = 512;
+const EFFECTS_SIZE: usize = 256;
+
+trait PerpetualStore {
+    fn write_checkpoint(
+        &self,
+        objects: &[(ObjectKey, Vec<u8>)],
+        effects: &[(Digest, Vec<u8>)],
+    ) -> Result<(), TypedStoreError>;
+
+    fn latest_object_version(&self, id: &ObjectID) -> Result<Option<u64>, TypedStoreError>;
+
+    fn multi_get_effects(&self, digests: &[Digest]) -> Result<usize, TypedStoreError>;
+}
+
+struct RocksDBTables {
+    objects: DBMap<ObjectKey, Vec<u8>>,
+    effects: DBMap<Digest, Vec<u8>>,
+}
+
+impl RocksDBTables {
+    fn open(path: PathBuf) -> Self {
+        let db = open_cf(path, None, MetricConf::default(), &["objects", "effects"])
+            .expect("Failed to open rocksdb");
+        Self {
+            objects: DBMap::reopen(&db, Some("objects"), &ReadWriteOptions::default())
+                .expect("Failed to open objects"),
+            effects: DBMap::reopen(&db, Some("effects"), &ReadWriteOptions::default())
+                .expect("Failed to open effects"),
+        }
+    }
+}
+
+impl PerpetualStore for RocksDBTables {
+    fn write_checkpoint(
+        &self,
+        objects: &[(ObjectKey, Vec<u8>)],
+        effects: &[(Digest, Vec<u8>)],
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = self.objects.batch();
+        batch.insert_batch(&self.objects, objects.iter().map(|(k, v)| (k, v)))?;
+        batch.insert_batch(&self.effects, effects.iter().map(|(k, v)| (k, v)))?;
+        batch.write()
+    }
+
+    fn latest_object_version(&self, id: &ObjectID) -> Result<Option<u64>, TypedStoreError> {
+        let mut iter = self
+            .objects
+            .unbounded_iter()
+            .skip_prior_to(&(*id, u64::MAX))?;
+        Ok(iter
+            .next()
+            .filter(|((object_id, _), _)| object_id == id)
+            .map(|((_, version), _)| version))
+    }
+
+    fn multi_get_effects(&self, digests: &[Digest]) -> Result<usize, TypedStoreError> {
+        Ok(self
+            .effects
+            .multi_get(digests)?
+            .into_iter()
+            .flatten()
+            .count())
+    }
+}
+
+struct RedbTables {
+    objects: RedbMap<ObjectKey, Vec<u8>>,
+    effects: RedbMap<Digest, Vec<u8>>,
+}
+
+impl RedbTables {
+    fn open(path: PathBuf) -> Self {
+        let db = open_redb(path, &["objects", "effects"]).expect("Failed to open redb");
+        Self {
+            objects: RedbMap::reopen(&db, "objects").expect("Failed to open objects"),
+            effects: RedbMap::reopen(&db, "effects").expect("Failed to open effects"),
+        }
+    }
+}
+
+impl PerpetualStore for RedbTables {
+    fn write_checkpoint(
+        &self,
+        objects: &[(ObjectKey, Vec<u8>)],
+        effects: &[(Digest, Vec<u8>)],
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = self.objects.batch();
+        batch.insert_batch(&self.objects, objects.iter().map(|(k, v)| (k, v)))?;
+        batch.insert_batch(&self.effects, effects.iter().map(|(k, v)| (k, v)))?;
+        batch.write()
+    }
+
+    fn latest_object_version(&self, id: &ObjectID) -> Result<Option<u64>, TypedStoreError> {
+        let mut iter = self
+            .objects
+            .unbounded_iter()
+            .skip_prior_to(&(*id, u64::MAX))?;
+        Ok(iter
+            .next()
+            .filter(|((object_id, _), _)| object_id == id)
+            .map(|((_, version), _)| version))
+    }
+
+    fn multi_get_effects(&self, digests: &[Digest]) -> Result<usize, TypedStoreError> {
+        Ok(self
+            .effects
+            .multi_get(digests)?
+            .into_iter()
+            .flatten()
+            .count())
+    }
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut rng = rand::thread_rng();
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+fn temp_dir() -> PathBuf {
+    tempfile::tempdir()
+        .expect("Failed to open temporary directory")
+        .into_path()
+}
+
+/// Populates the store with a few versions of every object and one effects entry per version.
+fn populate(store: &impl PerpetualStore, ids: &[ObjectID]) -> Vec<Digest> {
+    let mut digests = vec![];
+    for version in 1..=VERSIONS_PER_OBJECT {
+        for chunk in ids.chunks(BATCH_SIZE) {
+            let objects: Vec<_> = chunk
+                .iter()
+                .map(|id| ((*id, version), random_bytes(OBJECT_SIZE)))
+                .collect();
+            let effects: Vec<_> = chunk
+                .iter()
+                .map(|_| (rand::random::<Digest>(), random_bytes(EFFECTS_SIZE)))
+                .collect();
+            digests.extend(effects.iter().map(|(digest, _)| *digest));
+            store
+                .write_checkpoint(&objects, &effects)
+                .expect("Failed to populate store");
+        }
+    }
+    digests
+}
+
+fn bench_store(
+    group: &mut BenchmarkGroup<'_, measurement::WallTime>,
+    name: &str,
+    store: &impl PerpetualStore,
+) {
+    let ids: Vec<ObjectID> = (0..NUM_OBJECTS).map(|_| rand::random()).collect();
+    let digests = populate(store, &ids);
+    let mut rng = rand::thread_rng();
+    let mut next_version = VERSIONS_PER_OBJECT + 1;
+
+    group.bench_function(BenchmarkId::new("write_checkpoint", name), |b| {
+        b.iter_batched(
+            || {
+                let objects: Vec<_> = ids
+                    .choose_multiple(&mut rng, BATCH_SIZE)
+                    .map(|id| ((*id, next_version), random_bytes(OBJECT_SIZE)))
+                    .collect();
+                let effects: Vec<_> = (0..BATCH_SIZE)
+                    .map(|_| (rand::random::<Digest>(), random_bytes(EFFECTS_SIZE)))
+                    .collect();
+                next_version += 1;
+                (objects, effects)
+            },
+            |(objects, effects)| store.write_checkpoint(&objects, &effects).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut rng = rand::thread_rng();
+    group.bench_function(BenchmarkId::new("latest_object_version", name), |b| {
+        b.iter_batched(
+            || *ids.choose(&mut rng).unwrap(),
+            |id| store.latest_object_version(&id).unwrap().unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut rng = rand::thread_rng();
+    group.bench_function(BenchmarkId::new("multi_get_effects", name), |b| {
+        b.iter_batched(
+            || {
+                digests
+                    .choose_multiple(&mut rng, BATCH_SIZE)
+                    .copied()
+                    .collect::<Vec<_>>()
+            },
+            |keys| store.multi_get_effects(&keys).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn perpetual_store_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("perpetual_store");
+    bench_store(&mut group, "rocksdb", &RocksDBTables::open(temp_dir()));
+    bench_store(&mut group, "redb", &RedbTables::open(temp_dir()));
+    group.finish();
+}
+
+criterion_group!(benches, perpetual_store_bench);
+criterion_main!(benches);
@@ -11,6 +11,7 @@
 pub mod traits;
 pub use traits::Map;
 pub mod metrics;
+pub mod redb_store;
 pub mod rocks;
 pub use typed_store_error::TypedStoreError;
 pub mod sally;
@@ -0,0 +1,1221 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A persistent sally backend built on [redb](https://docs.rs/redb), a pure-Rust embedded
+//! copy-on-write B-tree. All columns of a database live as separate redb tables in a single file,
+//! so a write batch spanning several columns is committed in one redb write transaction and is
+//! atomic across them. Keys are encoded exactly like `DBMap` keys (big endian, fixed int bincode),
+//! so iteration order matches the RocksDB backend.
+
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, VecDeque},
+    fmt,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bincode::Options;
+use collectable::TryExtend;
+use redb::{
+    AccessGuard, Database, ReadOnlyTable, ReadableTable, StorageError, Table, TableDefinition,
+    TableError, TableHandle,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    rocks::{
+        be_fix_int_ser,
+        errors::{typed_store_err_from_bcs_err, typed_store_err_from_bincode_err},
+    },
+    traits::{Map, TableSummary},
+    TypedStoreError,
+};
+
+/// Name of the redb file created inside the database directory.
+pub const REDB_FILE_NAME: &str = "store.redb";
+
+/// Number of entries an iterator reads from a table at a time.
+const ITER_PAGE_SIZE: usize = 256;
+
+type RawTableDefinition<'a> = TableDefinition<'a, &'static [u8], &'static [u8]>;
+type RawReadOnlyTable = ReadOnlyTable<&'static [u8], &'static [u8]>;
+type RawEntry = (Vec<u8>, Vec<u8>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Reverse,
+}
+
+fn table_definition(name: &str) -> RawTableDefinition<'_> {
+    TableDefinition::new(name)
+}
+
+pub fn typed_store_err_from_redb_err<E: Into<redb::Error>>(err: E) -> TypedStoreError {
+    TypedStoreError::RedbError(format!("{}", err.into()))
+}
+
+fn typed_store_err_from_table_err(err: TableError) -> TypedStoreError {
+    match err {
+        TableError::TableDoesNotExist(name) => TypedStoreError::UnregisteredColumn(name),
+        err => typed_store_err_from_redb_err(err),
+    }
+}
+
+/// A redb database holding the tables of one sally db instance.
+pub struct RedbDB {
+    database: Database,
+    path: PathBuf,
+}
+
+impl RedbDB {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Names of all the tables in the database.
+    pub fn list_tables(&self) -> Result<Vec<String>, TypedStoreError> {
+        let txn = self
+            .database
+            .begin_read()
+            .map_err(typed_store_err_from_redb_err)?;
+        Ok(txn
+            .list_tables()
+            .map_err(typed_store_err_from_redb_err)?
+            .map(|table| table.name().to_owned())
+            .collect())
+    }
+}
+
+impl fmt::Debug for RedbDB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedbDB").field("path", &self.path).finish()
+    }
+}
+
+/// Opens (or creates) the redb database in the directory at `path` and makes sure every table in
+/// `tables` exists. Unlike RocksDB, redb locks its file, so a database can only be opened by one
+/// process at a time.
+pub fn open_redb<P: AsRef<Path>>(path: P, tables: &[&str]) -> Result<Arc<RedbDB>, TypedStoreError> {
+    let path = path.as_ref();
+    std::fs::create_dir_all(path).map_err(|e| TypedStoreError::RedbError(e.to_string()))?;
+    let database =
+        Database::create(path.join(REDB_FILE_NAME)).map_err(typed_store_err_from_redb_err)?;
+    let txn = database
+        .begin_write()
+        .map_err(typed_store_err_from_redb_err)?;
+    for table in tables {
+        txn.open_table(table_definition(table))
+            .map_err(typed_store_err_from_redb_err)?;
+    }
+    txn.commit().map_err(typed_store_err_from_redb_err)?;
+    Ok(Arc::new(RedbDB {
+        database,
+        path: path.to_path_buf(),
+    }))
+}
+
+/// Opens the existing redb database in the directory at `path` without creating it or any of its
+/// tables, for handles that only read. redb has no read-only mode, so the file is still locked
+/// against other processes while it is open.
+pub fn open_redb_read_only<P: AsRef<Path>>(path: P) -> Result<Arc<RedbDB>, TypedStoreError> {
+    let path = path.as_ref();
+    let database =
+        Database::open(path.join(REDB_FILE_NAME)).map_err(typed_store_err_from_redb_err)?;
+    Ok(Arc::new(RedbDB {
+        database,
+        path: path.to_path_buf(),
+    }))
+}
+
+/// An interface to a redb table, the counterpart of `DBMap` for the redb backend.
+pub struct RedbMap<K, V> {
+    db: Arc<RedbDB>,
+    table: String,
+    _phantom: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V> Clone for RedbMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            table: self.table.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for RedbMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedbMap")
+            .field("db", &self.db)
+            .field("table", &self.table)
+            .finish()
+    }
+}
+
+impl<K, V> RedbMap<K, V> {
+    /// Returns a map over the table `table` of an already opened database. The table must have
+    /// been created by `open_redb`.
+    pub fn reopen(db: &Arc<RedbDB>, table: &str) -> Result<Self, TypedStoreError> {
+        let txn = db
+            .database
+            .begin_read()
+            .map_err(typed_store_err_from_redb_err)?;
+        txn.open_table(table_definition(table))
+            .map_err(typed_store_err_from_table_err)?;
+        Ok(Self {
+            db: db.clone(),
+            table: table.to_owned(),
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn table_name(&self) -> &str {
+        &self.table
+    }
+
+    pub fn batch(&self) -> RedbWriteBatch {
+        RedbWriteBatch::new(self.db.clone())
+    }
+
+    /// Like `Map::is_empty`, but returns the error if the table cannot be read.
+    pub fn try_is_empty(&self) -> Result<bool, TypedStoreError> {
+        let first = self
+            .read_table()?
+            .first()
+            .map_err(typed_store_err_from_redb_err)?;
+        Ok(first.is_none())
+    }
+
+    fn read_table(&self) -> Result<RawReadOnlyTable, TypedStoreError> {
+        let txn = self
+            .db
+            .database
+            .begin_read()
+            .map_err(typed_store_err_from_redb_err)?;
+        txn.open_table(table_definition(&self.table))
+            .map_err(typed_store_err_from_table_err)
+    }
+
+    fn raw_iter(&self, lower: Bound<Vec<u8>>, upper: Bound<Vec<u8>>) -> RawRedbIter {
+        match self.read_table() {
+            Ok(table) => RawRedbIter::new(Some(table), (lower, upper), None),
+            Err(err) => RawRedbIter::new(None, (lower, upper), Some(err)),
+        }
+    }
+
+    fn raw_range_iter(&self, range: impl RangeBounds<K>) -> RawRedbIter
+    where
+        K: Serialize,
+    {
+        let serialize_bound = |bound: Bound<&K>| match bound {
+            Bound::Included(key) => {
+                Bound::Included(be_fix_int_ser(key).expect("Serialization must not fail"))
+            }
+            Bound::Excluded(key) => {
+                Bound::Excluded(be_fix_int_ser(key).expect("Serialization must not fail"))
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        self.raw_iter(
+            serialize_bound(range.start_bound()),
+            serialize_bound(range.end_bound()),
+        )
+    }
+
+    fn raw_iter_with_bounds(&self, lower_bound: Option<K>, upper_bound: Option<K>) -> RawRedbIter
+    where
+        K: Serialize,
+    {
+        // Same semantics as `DBMap`: the lower bound is inclusive and the upper bound exclusive.
+        let lower = lower_bound.map_or(Bound::Unbounded, |key| {
+            Bound::Included(be_fix_int_ser(&key).expect("Serialization must not fail"))
+        });
+        let upper = upper_bound.map_or(Bound::Unbounded, |key| {
+            Bound::Excluded(be_fix_int_ser(&key).expect("Serialization must not fail"))
+        });
+        self.raw_iter(lower, upper)
+    }
+
+    pub fn table_summary(&self) -> eyre::Result<TableSummary> {
+        let mut num_keys = 0;
+        let mut key_bytes_total = 0;
+        let mut value_bytes_total = 0;
+        let mut key_hist = hdrhistogram::Histogram::<u64>::new_with_max(100000, 2).unwrap();
+        let mut value_hist = hdrhistogram::Histogram::<u64>::new_with_max(100000, 2).unwrap();
+        for entry in self.raw_iter(Bound::Unbounded, Bound::Unbounded) {
+            let (key, value) = entry?;
+            num_keys += 1;
+            key_bytes_total += key.len();
+            value_bytes_total += value.len();
+            key_hist.record(key.len() as u64)?;
+            value_hist.record(value.len() as u64)?;
+        }
+        Ok(TableSummary {
+            num_keys,
+            key_bytes_total,
+            value_bytes_total,
+            key_hist,
+            value_hist,
+        })
+    }
+}
+
+fn get_raw(table: &RawReadOnlyTable, raw_key: &[u8]) -> Result<Option<Vec<u8>>, TypedStoreError> {
+    Ok(table
+        .get(raw_key)
+        .map_err(typed_store_err_from_redb_err)?
+        .map(|value| value.value().to_vec()))
+}
+
+fn deserialize_key<K: DeserializeOwned>(raw_key: &[u8]) -> Result<K, TypedStoreError> {
+    bincode::DefaultOptions::new()
+        .with_big_endian()
+        .with_fixint_encoding()
+        .deserialize(raw_key)
+        .map_err(typed_store_err_from_bincode_err)
+}
+
+fn deserialize_value<V: DeserializeOwned>(raw_value: &[u8]) -> Result<V, TypedStoreError> {
+    bcs::from_bytes(raw_value).map_err(typed_store_err_from_bcs_err)
+}
+
+impl<'a, K, V> Map<'a, K, V> for RedbMap<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    type Error = TypedStoreError;
+    type Iterator = RedbIter<K, V>;
+    type SafeIterator = RedbSafeIter<K, V>;
+    type Keys = RedbKeys<K>;
+    type Values = RedbValues<V>;
+
+    fn contains_key(&self, key: &K) -> Result<bool, Self::Error> {
+        let raw_key = be_fix_int_ser(key)?;
+        let table = self.read_table()?;
+        Ok(table
+            .get(raw_key.as_slice())
+            .map_err(typed_store_err_from_redb_err)?
+            .is_some())
+    }
+
+    fn get(&self, key: &K) -> Result<Option<V>, Self::Error> {
+        self.get_raw_bytes(key)?
+            .map(|raw_value| deserialize_value(&raw_value))
+            .transpose()
+    }
+
+    fn get_raw_bytes(&self, key: &K) -> Result<Option<Vec<u8>>, Self::Error> {
+        let raw_key = be_fix_int_ser(key)?;
+        get_raw(&self.read_table()?, &raw_key)
+    }
+
+    fn insert(&self, key: &K, value: &V) -> Result<(), Self::Error> {
+        let mut batch = self.batch();
+        batch.insert_batch(self, [(key, value)])?;
+        batch.write()
+    }
+
+    fn remove(&self, key: &K) -> Result<(), Self::Error> {
+        let mut batch = self.batch();
+        batch.delete_batch(self, [key])?;
+        batch.write()
+    }
+
+    fn unsafe_clear(&self) -> Result<(), Self::Error> {
+        let txn = self
+            .db
+            .database
+            .begin_write()
+            .map_err(typed_store_err_from_redb_err)?;
+        txn.delete_table(table_definition(&self.table))
+            .map_err(typed_store_err_from_redb_err)?;
+        txn.open_table(table_definition(&self.table))
+            .map_err(typed_store_err_from_redb_err)?;
+        txn.commit().map_err(typed_store_err_from_redb_err)
+    }
+
+    fn schedule_delete_all(&self) -> Result<(), TypedStoreError> {
+        self.unsafe_clear()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.try_is_empty().expect("Failed to read from redb table")
+    }
+
+    fn unbounded_iter(&'a self) -> Self::Iterator {
+        RedbIter::new(self.safe_iter())
+    }
+
+    fn iter_with_bounds(
+        &'a self,
+        lower_bound: Option<K>,
+        upper_bound: Option<K>,
+    ) -> Self::Iterator {
+        RedbIter::new(self.safe_iter_with_bounds(lower_bound, upper_bound))
+    }
+
+    fn range_iter(&'a self, range: impl RangeBounds<K>) -> Self::Iterator {
+        RedbIter::new(self.safe_range_iter(range))
+    }
+
+    fn safe_iter(&'a self) -> Self::SafeIterator {
+        RedbSafeIter::new(self.raw_iter(Bound::Unbounded, Bound::Unbounded))
+    }
+
+    fn safe_iter_with_bounds(
+        &'a self,
+        lower_bound: Option<K>,
+        upper_bound: Option<K>,
+    ) -> Self::SafeIterator {
+        RedbSafeIter::new(self.raw_iter_with_bounds(lower_bound, upper_bound))
+    }
+
+    fn safe_range_iter(&'a self, range: impl RangeBounds<K>) -> Self::SafeIterator {
+        RedbSafeIter::new(self.raw_range_iter(range))
+    }
+
+    fn keys(&'a self) -> Self::Keys {
+        RedbKeys {
+            iter: self.raw_iter(Bound::Unbounded, Bound::Unbounded),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn values(&'a self) -> Self::Values {
+        RedbValues {
+            iter: self.raw_iter(Bound::Unbounded, Bound::Unbounded),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Reads all the keys from a single snapshot of the table.
+    fn multi_get<J>(&self, keys: impl IntoIterator<Item = J>) -> Result<Vec<Option<V>>, Self::Error>
+    where
+        J: Borrow<K>,
+    {
+        self.multi_get_raw_bytes(keys)?
+            .into_iter()
+            .map(|raw_value| raw_value.map(|v| deserialize_value(&v)).transpose())
+            .collect()
+    }
+
+    /// Reads all the keys from a single snapshot of the table.
+    fn multi_get_raw_bytes<J>(
+        &self,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<Vec<u8>>>, Self::Error>
+    where
+        J: Borrow<K>,
+    {
+        let table = self.read_table()?;
+        keys.into_iter()
+            .map(|key| get_raw(&table, &be_fix_int_ser(key.borrow())?))
+            .collect()
+    }
+
+    fn chunked_multi_get<J>(
+        &self,
+        keys: impl IntoIterator<Item = J>,
+        _chunk_size: usize,
+    ) -> Result<Vec<Option<V>>, Self::Error>
+    where
+        J: Borrow<K>,
+    {
+        self.multi_get(keys)
+    }
+
+    /// Inserts key-value pairs in a single transaction.
+    fn multi_insert<J, U>(
+        &self,
+        key_val_pairs: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<(), Self::Error>
+    where
+        J: Borrow<K>,
+        U: Borrow<V>,
+    {
+        let mut batch = self.batch();
+        batch.insert_batch(self, key_val_pairs)?;
+        batch.write()
+    }
+
+    /// Removes keys in a single transaction.
+    fn multi_remove<J>(&self, keys: impl IntoIterator<Item = J>) -> Result<(), Self::Error>
+    where
+        J: Borrow<K>,
+    {
+        let mut batch = self.batch();
+        batch.delete_batch(self, keys)?;
+        batch.write()
+    }
+
+    fn try_catch_up_with_primary(&self) -> Result<(), Self::Error> {
+        // redb has no secondary instances, every reader sees the latest committed transaction.
+        Ok(())
+    }
+}
+
+impl<J, K, U, V> TryExtend<(J, U)> for RedbMap<K, V>
+where
+    J: Borrow<K>,
+    U: Borrow<V>,
+    K: Serialize,
+    V: Serialize,
+{
+    type Error = TypedStoreError;
+
+    fn try_extend<T>(&mut self, iter: &mut T) -> Result<(), Self::Error>
+    where
+        T: Iterator<Item = (J, U)>,
+    {
+        let mut batch = self.batch();
+        batch.insert_batch(self, iter)?;
+        batch.write()
+    }
+
+    fn try_extend_from_slice(&mut self, slice: &[(J, U)]) -> Result<(), Self::Error> {
+        let slice_of_refs = slice.iter().map(|(k, v)| (k.borrow(), v.borrow()));
+        let mut batch = self.batch();
+        batch.insert_batch(self, slice_of_refs)?;
+        batch.write()
+    }
+}
+
+enum RedbBatchOp {
+    Insert(String, Vec<RawEntry>),
+    Delete(String, Vec<Vec<u8>>),
+    DeleteRange(String, (Vec<u8>, Vec<u8>)),
+}
+
+/// A collection of mutations over one or more tables of the same redb database, applied
+/// atomically in a single write transaction.
+pub struct RedbWriteBatch {
+    db: Arc<RedbDB>,
+    ops: Vec<RedbBatchOp>,
+}
+
+impl RedbWriteBatch {
+    fn new(db: Arc<RedbDB>) -> Self {
+        Self {
+            db,
+            ops: Vec::new(),
+        }
+    }
+
+    fn check_db<K, V>(&self, map: &RedbMap<K, V>) -> Result<(), TypedStoreError> {
+        if !Arc::ptr_eq(&self.db, &map.db) {
+            return Err(TypedStoreError::CrossDBBatch);
+        }
+        Ok(())
+    }
+
+    pub fn write(self) -> Result<(), TypedStoreError> {
+        let txn = self
+            .db
+            .database
+            .begin_write()
+            .map_err(typed_store_err_from_redb_err)?;
+        {
+            let mut tables: HashMap<&str, Table<'_, &'static [u8], &'static [u8]>> = HashMap::new();
+            for op in &self.ops {
+                let name = match op {
+                    RedbBatchOp::Insert(name, _)
+                    | RedbBatchOp::Delete(name, _)
+                    | RedbBatchOp::DeleteRange(name, _) => name.as_str(),
+                };
+                if !tables.contains_key(name) {
+                    let table = txn
+                        .open_table(table_definition(name))
+                        .map_err(typed_store_err_from_redb_err)?;
+                    tables.insert(name, table);
+                }
+                let table = tables.get_mut(name).expect("table was just opened");
+                match op {
+                    RedbBatchOp::Insert(_, entries) => {
+                        for (key, value) in entries {
+                            table
+                                .insert(key.as_slice(), value.as_slice())
+                                .map_err(typed_store_err_from_redb_err)?;
+                        }
+                    }
+                    RedbBatchOp::Delete(_, keys) => {
+                        for key in keys {
+                            table
+                                .remove(key.as_slice())
+                                .map_err(typed_store_err_from_redb_err)?;
+                        }
+                    }
+                    RedbBatchOp::DeleteRange(_, (from, to)) => {
+                        if from >= to {
+                            continue;
+                        }
+                        let keys = table
+                            .range::<&[u8]>(from.as_slice()..to.as_slice())
+                            .map_err(typed_store_err_from_redb_err)?
+                            .map(|entry| entry.map(|(key, _)| key.value().to_vec()))
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(typed_store_err_from_redb_err)?;
+                        for key in keys {
+                            table
+                                .remove(key.as_slice())
+                                .map_err(typed_store_err_from_redb_err)?;
+                        }
+                    }
+                }
+            }
+        }
+        txn.commit().map_err(typed_store_err_from_redb_err)
+    }
+
+    /// Deletes a set of keys given as an iterator
+    pub fn delete_batch<J: Borrow<K>, K: Serialize, V>(
+        &mut self,
+        db: &RedbMap<K, V>,
+        purged_vals: impl IntoIterator<Item = J>,
+    ) -> Result<(), TypedStoreError> {
+        self.check_db(db)?;
+        let keys = purged_vals
+            .into_iter()
+            .map(|key| be_fix_int_ser(key.borrow()))
+            .collect::<Result<_, _>>()?;
+        self.ops.push(RedbBatchOp::Delete(db.table.clone(), keys));
+        Ok(())
+    }
+
+    /// Deletes a range of keys between `from` (inclusive) and `to` (non-inclusive)
+    pub fn delete_range<K: Serialize, V>(
+        &mut self,
+        db: &RedbMap<K, V>,
+        from: &K,
+        to: &K,
+    ) -> Result<(), TypedStoreError> {
+        self.check_db(db)?;
+        let range = (be_fix_int_ser(from)?, be_fix_int_ser(to)?);
+        self.ops
+            .push(RedbBatchOp::DeleteRange(db.table.clone(), range));
+        Ok(())
+    }
+
+    /// inserts a range of (key, value) pairs given as an iterator
+    pub fn insert_batch<J: Borrow<K>, K: Serialize, U: Borrow<V>, V: Serialize>(
+        &mut self,
+        db: &RedbMap<K, V>,
+        new_vals: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<(), TypedStoreError> {
+        self.check_db(db)?;
+        let entries = new_vals
+            .into_iter()
+            .map(|(key, value)| {
+                Ok((
+                    be_fix_int_ser(key.borrow())?,
+                    bcs::to_bytes(value.borrow()).map_err(typed_store_err_from_bcs_err)?,
+                ))
+            })
+            .collect::<Result<_, TypedStoreError>>()?;
+        self.ops
+            .push(RedbBatchOp::Insert(db.table.clone(), entries));
+        Ok(())
+    }
+}
+
+fn bound_as_slice(bound: &Bound<Vec<u8>>) -> Bound<&[u8]> {
+    match bound {
+        Bound::Included(key) => Bound::Included(key.as_slice()),
+        Bound::Excluded(key) => Bound::Excluded(key.as_slice()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+/// Returns the more restrictive of two lower bounds.
+fn max_lower_bound(a: &Bound<Vec<u8>>, b: &Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+    match (a, b) {
+        (Bound::Unbounded, bound) | (bound, Bound::Unbounded) => bound.clone(),
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y))
+            if x != y =>
+        {
+            if x > y {
+                a.clone()
+            } else {
+                b.clone()
+            }
+        }
+        (Bound::Excluded(_), _) => a.clone(),
+        _ => b.clone(),
+    }
+}
+
+/// Returns the more restrictive of two upper bounds.
+fn min_upper_bound(a: &Bound<Vec<u8>>, b: &Bound<Vec<u8>>) -> Bound<Vec<u8>> {
+    match (a, b) {
+        (Bound::Unbounded, bound) | (bound, Bound::Unbounded) => bound.clone(),
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y))
+            if x != y =>
+        {
+            if x < y {
+                a.clone()
+            } else {
+                b.clone()
+            }
+        }
+        (Bound::Excluded(_), _) => a.clone(),
+        _ => b.clone(),
+    }
+}
+
+/// Whether no key can satisfy both bounds. redb, like `BTreeMap`, rejects such ranges.
+fn is_empty_range(lower: &Bound<Vec<u8>>, upper: &Bound<Vec<u8>>) -> bool {
+    match (lower, upper) {
+        (Bound::Included(l), Bound::Included(u)) => l > u,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Included(u) | Bound::Excluded(u)) => {
+            l >= u
+        }
+        _ => false,
+    }
+}
+
+fn collect_page<'a>(
+    entries: impl Iterator<
+        Item = Result<
+            (
+                AccessGuard<'a, &'static [u8]>,
+                AccessGuard<'a, &'static [u8]>,
+            ),
+            StorageError,
+        >,
+    >,
+) -> Result<VecDeque<RawEntry>, StorageError> {
+    entries
+        .take(ITER_PAGE_SIZE)
+        .map(|entry| entry.map(|(key, value)| (key.value().to_vec(), value.value().to_vec())))
+        .collect()
+}
+
+/// Untyped iterator over a snapshot of a redb table. Entries are read a page at a time so that
+/// no borrow of the table outlives a call to `next`, which lets the iterator be repositioned
+/// like a RocksDB raw iterator.
+struct RawRedbIter {
+    table: Option<RawReadOnlyTable>,
+    bounds: (Bound<Vec<u8>>, Bound<Vec<u8>>),
+    // Next key to read from in the current direction: a lower bound when iterating forward and
+    // an upper bound when iterating in reverse.
+    position: Bound<Vec<u8>>,
+    direction: Direction,
+    buffer: VecDeque<RawEntry>,
+    exhausted: bool,
+    error: Option<TypedStoreError>,
+}
+
+impl RawRedbIter {
+    fn new(
+        table: Option<RawReadOnlyTable>,
+        bounds: (Bound<Vec<u8>>, Bound<Vec<u8>>),
+        error: Option<TypedStoreError>,
+    ) -> Self {
+        Self {
+            exhausted: table.is_none(),
+            table,
+            bounds,
+            position: Bound::Unbounded,
+            direction: Direction::Forward,
+            buffer: VecDeque::new(),
+            error,
+        }
+    }
+
+    fn current_range(&self) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
+        match self.direction {
+            Direction::Forward => (
+                max_lower_bound(&self.bounds.0, &self.position),
+                self.bounds.1.clone(),
+            ),
+            Direction::Reverse => (
+                self.bounds.0.clone(),
+                min_upper_bound(&self.bounds.1, &self.position),
+            ),
+        }
+    }
+
+    fn read_page(
+        &self,
+        lower: &Bound<Vec<u8>>,
+        upper: &Bound<Vec<u8>>,
+        direction: Direction,
+    ) -> Result<VecDeque<RawEntry>, TypedStoreError> {
+        let Some(table) = &self.table else {
+            return Ok(VecDeque::new());
+        };
+        if is_empty_range(lower, upper) {
+            return Ok(VecDeque::new());
+        }
+        let entries = table
+            .range::<&[u8]>((bound_as_slice(lower), bound_as_slice(upper)))
+            .map_err(typed_store_err_from_redb_err)?;
+        match direction {
+            Direction::Forward => collect_page(entries),
+            Direction::Reverse => collect_page(entries.rev()),
+        }
+        .map_err(typed_store_err_from_redb_err)
+    }
+
+    fn fill_buffer(&mut self) {
+        if self.exhausted || !self.buffer.is_empty() {
+            return;
+        }
+        let (lower, upper) = self.current_range();
+        match self.read_page(&lower, &upper, self.direction) {
+            Ok(page) => {
+                if page.len() < ITER_PAGE_SIZE {
+                    self.exhausted = true;
+                }
+                if let Some((last_key, _)) = page.back() {
+                    self.position = Bound::Excluded(last_key.clone());
+                }
+                self.buffer = page;
+            }
+            Err(err) => {
+                self.exhausted = true;
+                self.error = Some(err);
+            }
+        }
+    }
+
+    fn reposition(&mut self, position: Option<Vec<u8>>) {
+        self.buffer.clear();
+        match position {
+            Some(key) => {
+                self.position = Bound::Included(key);
+                self.exhausted = self.table.is_none();
+            }
+            None => self.exhausted = true,
+        }
+    }
+
+    /// Returns the last key within the iterator bounds that is not greater than `upper`.
+    fn last_key_up_to(&self, upper: Bound<Vec<u8>>) -> Result<Option<Vec<u8>>, TypedStoreError> {
+        let Some(table) = &self.table else {
+            return Ok(None);
+        };
+        let upper = min_upper_bound(&self.bounds.1, &upper);
+        if is_empty_range(&self.bounds.0, &upper) {
+            return Ok(None);
+        }
+        let last = table
+            .range::<&[u8]>((bound_as_slice(&self.bounds.0), bound_as_slice(&upper)))
+            .map_err(typed_store_err_from_redb_err)?
+            .next_back()
+            .transpose()
+            .map_err(typed_store_err_from_redb_err)?;
+        Ok(last.map(|(key, _)| key.value().to_vec()))
+    }
+
+    fn seek(&mut self, key: Vec<u8>) {
+        self.reposition(Some(key));
+    }
+
+    fn seek_for_prev(&mut self, key: Vec<u8>) -> Result<(), TypedStoreError> {
+        let prior = self.last_key_up_to(Bound::Included(key))?;
+        self.reposition(prior);
+        Ok(())
+    }
+
+    fn seek_to_last(&mut self) {
+        match self.last_key_up_to(Bound::Unbounded) {
+            Ok(last) => self.reposition(last),
+            Err(err) => {
+                self.reposition(None);
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Turns the iterator around, starting from the entry it currently points at.
+    fn reverse(&mut self) {
+        self.fill_buffer();
+        let current = self.buffer.front().map(|(key, _)| key.clone());
+        self.direction = Direction::Reverse;
+        self.reposition(current);
+    }
+}
+
+impl Iterator for RawRedbIter {
+    type Item = Result<RawEntry, TypedStoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fill_buffer();
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
+
+/// An iterator over all key-value pairs in a redb table.
+pub struct RedbSafeIter<K, V> {
+    iter: RawRedbIter,
+    _phantom: PhantomData<(K, V)>,
+}
+
+impl<K, V> RedbSafeIter<K, V> {
+    fn new(iter: RawRedbIter) -> Self {
+        Self {
+            iter,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for RedbSafeIter<K, V> {
+    type Item = Result<(K, V), TypedStoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|entry| {
+            let (raw_key, raw_value) = entry?;
+            Ok((deserialize_key(&raw_key)?, deserialize_value(&raw_value)?))
+        })
+    }
+}
+
+impl<K: Serialize, V> RedbSafeIter<K, V> {
+    /// Skips all the elements that are smaller than the given key,
+    /// and either lands on the key or the first one greater than
+    /// the key.
+    pub fn skip_to(mut self, key: &K) -> Result<Self, TypedStoreError> {
+        self.iter.seek(be_fix_int_ser(key)?);
+        Ok(self)
+    }
+
+    /// Moves the iterator the element given or
+    /// the one prior to it if it does not exist. If there is
+    /// no element prior to it, it returns an empty iterator.
+    pub fn skip_prior_to(mut self, key: &K) -> Result<Self, TypedStoreError> {
+        self.iter.seek_for_prev(be_fix_int_ser(key)?)?;
+        Ok(self)
+    }
+
+    /// Seeks to the last key in the table.
+    pub fn skip_to_last(mut self) -> Self {
+        self.iter.seek_to_last();
+        self
+    }
+
+    /// Will make the direction of the iteration reverse and will
+    /// create a new `RevIter` to consume. Every call to `next` method
+    /// will give the next element from the end.
+    pub fn reverse(mut self) -> RedbSafeRevIter<K, V> {
+        self.iter.reverse();
+        RedbSafeRevIter { iter: self }
+    }
+}
+
+/// An iterator with a reverted direction to the original. The `RevIter`
+/// is hosting an iteration which is consuming in the opposing direction.
+/// It's not possible to do further manipulation (ex re-reverse) to the
+/// iterator.
+pub struct RedbSafeRevIter<K, V> {
+    iter: RedbSafeIter<K, V>,
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for RedbSafeRevIter<K, V> {
+    type Item = Result<(K, V), TypedStoreError>;
+
+    /// Will give the next item backwards
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+/// Same as `RedbSafeIter` but panics on storage and deserialization errors, backing the
+/// unchecked iteration methods of `Map`.
+pub struct RedbIter<K, V> {
+    iter: RedbSafeIter<K, V>,
+}
+
+impl<K, V> RedbIter<K, V> {
+    fn new(iter: RedbSafeIter<K, V>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<K: DeserializeOwned, V: DeserializeOwned> Iterator for RedbIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|entry| entry.expect("Failed to read from redb table"))
+    }
+}
+
+impl<K: Serialize, V> RedbIter<K, V> {
+    /// Skips all the elements that are smaller than the given key,
+    /// and either lands on the key or the first one greater than
+    /// the key.
+    pub fn skip_to(self, key: &K) -> Result<Self, TypedStoreError> {
+        Ok(Self::new(self.iter.skip_to(key)?))
+    }
+
+    /// Moves the iterator the element given or
+    /// the one prior to it if it does not exist. If there is
+    /// no element prior to it, it returns an empty iterator.
+    pub fn skip_prior_to(self, key: &K) -> Result<Self, TypedStoreError> {
+        Ok(Self::new(self.iter.skip_prior_to(key)?))
+    }
+
+    /// Seeks to the last key in the table.
+    pub fn skip_to_last(self) -> Self {
+        Self::new(self.iter.skip_to_last())
+    }
+}
+
+/// An iterator over the keys of a redb table.
+pub struct RedbKeys<K> {
+    iter: RawRedbIter,
+    _phantom: PhantomData<K>,
+}
+
+impl<K: DeserializeOwned> Iterator for RedbKeys<K> {
+    type Item = Result<K, TypedStoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|entry| deserialize_key(&entry?.0))
+    }
+}
+
+/// An iterator over the values of a redb table.
+pub struct RedbValues<V> {
+    iter: RawRedbIter,
+    _phantom: PhantomData<V>,
+}
+
+impl<V: DeserializeOwned> Iterator for RedbValues<V> {
+    type Item = Result<V, TypedStoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|entry| deserialize_value(&entry?.1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        redb_store::{open_redb, open_redb_read_only, RedbDB, RedbMap},
+        Map, TypedStoreError,
+    };
+
+    fn open_db(tables: &[&str]) -> Arc<RedbDB> {
+        let path = tempfile::tempdir()
+            .expect("Failed to open temporary directory")
+            .into_path();
+        open_redb(path, tables).expect("Failed to open redb")
+    }
+
+    fn open_map<K, V>() -> RedbMap<K, V> {
+        RedbMap::reopen(&open_db(&["table"]), "table").expect("Failed to open table")
+    }
+
+    #[test]
+    fn test_reopen_unknown_table() {
+        let db = open_db(&["table"]);
+        assert_eq!(
+            RedbMap::<i32, String>::reopen(&db, "missing").unwrap_err(),
+            TypedStoreError::UnregisteredColumn("missing".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_and_remove() {
+        let db = open_map();
+        db.insert(&123456789, &"123456789".to_string())
+            .expect("Failed to insert");
+        assert!(db
+            .contains_key(&123456789)
+            .expect("Failed to call contains key"));
+        assert_eq!(
+            Some("123456789".to_string()),
+            db.get(&123456789).expect("Failed to get")
+        );
+        assert_eq!(
+            Some(bcs::to_bytes(&"123456789".to_string()).unwrap()),
+            db.get_raw_bytes(&123456789)
+                .expect("Failed to get_raw_bytes")
+        );
+        assert_eq!(None, db.get(&000000000).expect("Failed to get"));
+
+        db.remove(&123456789).expect("Failed to remove");
+        assert!(db.get(&123456789).expect("Failed to get").is_none());
+        assert!(db.is_empty());
+    }
+
+    #[test]
+    fn test_persists_across_reopen() {
+        let path = tempfile::tempdir()
+            .expect("Failed to open temporary directory")
+            .into_path();
+        {
+            let db = open_redb(&path, &["table"]).expect("Failed to open redb");
+            let map = RedbMap::reopen(&db, "table").expect("Failed to open table");
+            map.multi_insert((0..10).map(|i| (i, i.to_string())))
+                .expect("Failed to multi-insert");
+        }
+        let db = open_redb(&path, &["table"]).expect("Failed to reopen redb");
+        let map: RedbMap<i32, String> =
+            RedbMap::reopen(&db, "table").expect("Failed to open table");
+        assert_eq!(map.safe_iter().count(), 10);
+        assert_eq!(
+            map.multi_get([0, 9, 10]).expect("Failed to multi get"),
+            vec![Some("0".to_string()), Some("9".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_open_read_only() {
+        let path = tempfile::tempdir()
+            .expect("Failed to open temporary directory")
+            .into_path();
+        assert!(open_redb_read_only(&path).is_err());
+        {
+            let db = open_redb(&path, &["table"]).expect("Failed to open redb");
+            let map = RedbMap::reopen(&db, "table").expect("Failed to open table");
+            map.insert(&1, &"1".to_string()).expect("Failed to insert");
+        }
+        let db = open_redb_read_only(&path).expect("Failed to reopen redb");
+        let map: RedbMap<i32, String> =
+            RedbMap::reopen(&db, "table").expect("Failed to open table");
+        assert_eq!(map.try_is_empty(), Ok(false));
+        assert_eq!(
+            RedbMap::<i32, String>::reopen(&db, "missing").unwrap_err(),
+            TypedStoreError::UnregisteredColumn("missing".to_string())
+        );
+        assert_eq!(db.list_tables().unwrap(), vec!["table".to_string()]);
+    }
+
+    #[test]
+    fn test_batch_across_tables() {
+        let db = open_db(&["first", "second"]);
+        let first: RedbMap<i32, String> = RedbMap::reopen(&db, "first").unwrap();
+        let second: RedbMap<i32, String> = RedbMap::reopen(&db, "second").unwrap();
+
+        let mut wb = first.batch();
+        wb.insert_batch(&first, (0..101).map(|i| (i, i.to_string())))
+            .expect("Failed to batch insert");
+        wb.insert_batch(&second, (1000..1100).map(|i| (i, i.to_string())))
+            .expect("Failed to batch insert");
+        wb.delete_batch(&first, (1..101).step_by(2))
+            .expect("Failed to batch delete");
+        wb.delete_range(&second, &1050, &1100)
+            .expect("Failed to delete range");
+
+        // Nothing is visible until the batch is written.
+        assert!(first.is_empty());
+        assert!(second.is_empty());
+        wb.write().expect("Failed to execute batch");
+
+        for k in first.keys() {
+            assert_eq!(k.unwrap() % 2, 0);
+        }
+        assert_eq!(first.safe_iter().count(), 51);
+        assert_eq!(
+            second.keys().collect::<Result<Vec<_>, _>>().unwrap(),
+            (1000..1050).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_batch_across_databases() {
+        let first: RedbMap<i32, String> = open_map();
+        let second: RedbMap<i32, String> = open_map();
+        let mut wb = first.batch();
+        assert_eq!(
+            wb.insert_batch(&second, [(1, "1".to_string())]),
+            Err(TypedStoreError::CrossDBBatch)
+        );
+    }
+
+    #[test]
+    fn test_bounded_iter() {
+        let db: RedbMap<i32, String> = open_map();
+        // More entries than an iterator page, so iteration crosses page boundaries.
+        db.multi_insert((0..1000).map(|i| (i, i.to_string())))
+            .expect("Failed to multi-insert");
+
+        let keys = |iter: super::RedbSafeIter<i32, String>| {
+            iter.map(|entry| entry.unwrap().0).collect::<Vec<_>>()
+        };
+        assert_eq!(keys(db.safe_iter()), (0..1000).collect::<Vec<_>>());
+        assert_eq!(
+            keys(db.safe_iter_with_bounds(Some(100), Some(700))),
+            (100..700).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            keys(db.safe_range_iter(100..=700)),
+            (100..=700).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            keys(db.safe_range_iter(..300).skip_to(&250).unwrap()),
+            (250..300).collect::<Vec<_>>()
+        );
+        assert_eq!(keys(db.safe_range_iter(300..200)), Vec::<i32>::new());
+        assert_eq!(
+            db.iter_with_bounds(Some(998), None).collect::<Vec<_>>(),
+            vec![(998, "998".to_string()), (999, "999".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_reverse_iter() {
+        let db: RedbMap<i32, String> = open_map();
+        db.multi_insert((0..1000).step_by(2).map(|i| (i, i.to_string())))
+            .expect("Failed to multi-insert");
+
+        let rev_keys = db
+            .safe_iter()
+            .skip_to_last()
+            .reverse()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(rev_keys, (0..1000).step_by(2).rev().collect::<Vec<_>>());
+
+        // Lands on the key prior to the one that is missing and iterates backwards from it.
+        let mut iter = db.safe_iter().skip_prior_to(&501).unwrap().reverse();
+        assert_eq!(iter.next(), Some(Ok((500, "500".to_string()))));
+        assert_eq!(iter.next(), Some(Ok((498, "498".to_string()))));
+
+        // Reverse iteration respects the lower bound.
+        let rev_keys = db
+            .safe_iter_with_bounds(Some(100), Some(200))
+            .skip_prior_to(&1000)
+            .unwrap()
+            .reverse()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(rev_keys, (100..200).step_by(2).rev().collect::<Vec<_>>());
+
+        // Nothing is prior to the lower bound.
+        assert!(db
+            .safe_iter_with_bounds(Some(10), None)
+            .skip_prior_to(&5)
+            .unwrap()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_clear() {
+        let db: RedbMap<i32, String> = open_map();
+        db.unsafe_clear().expect("Failed to clear empty table");
+        db.multi_insert((0..101).map(|i| (i, i.to_string())))
+            .expect("Failed to multi-insert");
+        assert_eq!(db.values().count(), 101);
+        db.unsafe_clear().expect("Failed to clear");
+        assert!(db.is_empty());
+        db.insert(&1, &"e".to_string()).expect("Failed to insert");
+        assert_eq!(db.safe_iter().count(), 1);
+    }
+}
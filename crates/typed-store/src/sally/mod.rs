@@ -20,7 +20,7 @@
 //! use crate::typed_store::Map;
 //!
 //! // `ExampleTable` is a sally db instance where each column is first initialized with TestDB
-//! // (btree map) backend and later switched to a RocksDB column family
+//! // (btree map) backend and later switched to a RocksDB column family and to a redb table
 //!
 //! #[derive(SallyDB)]
 //! pub struct ExampleTable {
@@ -46,10 +46,15 @@
 //!     let primary_path = tempfile::tempdir().expect("Failed to open db path").into_path();
 //!     table = ExampleTable::init(SallyDBOptions::RocksDB((primary_path, MetricConf::default(), RocksDBAccessType::Primary, None, None)));
 //!     insert_key_vals(&table).await;
+//!     // switch to redb backend
+//!     let redb_path = tempfile::tempdir().expect("Failed to open db path").into_path();
+//!     table = ExampleTable::init(SallyDBOptions::Redb(redb_path));
+//!     insert_key_vals(&table).await;
 //!     Ok(())
 //! }
 //! ```
 use crate::{
+    redb_store::{RedbKeys, RedbMap, RedbValues, RedbWriteBatch},
     rocks::{
         default_db_options, keys::Keys, values::Values, DBBatch, DBMap, DBOptions,
         RocksDBAccessType,
@@ -59,6 +64,7 @@ use crate::{
     TypedStoreError,
 };
 
+use crate::redb_store::{RedbSafeIter, RedbSafeRevIter};
 use crate::rocks::safe_iter::{SafeIter as RocksDBIter, SafeRevIter};
 use crate::rocks::{DBMapTableConfigMap, MetricConf};
 use crate::test_db::{TestDBIter, TestDBRevIter};
@@ -95,6 +101,7 @@ impl Default for SallyConfig {
 pub enum SallyColumn<K, V> {
     RocksDB((DBMap<K, V>, SallyConfig)),
     TestDB((TestDB<K, V>, SallyConfig)),
+    Redb((RedbMap<K, V>, SallyConfig)),
 }
 
 impl<K, V> SallyColumn<K, V> {
@@ -106,6 +113,11 @@ impl<K, V> SallyColumn<K, V> {
     pub fn new_testdb(db: TestDB<K, V>) -> Self {
         SallyColumn::TestDB((db, SallyConfig::default()))
     }
+    pub fn new_single_redb(db: RedbMap<K, V>) -> Self {
+        // All columns of a redb backed db share one redb database, so its transactions already
+        // give atomic commits across columns
+        SallyColumn::Redb((db, SallyConfig::default()))
+    }
     pub fn batch(&self) -> SallyWriteBatch {
         match self {
             SallyColumn::RocksDB((
@@ -120,6 +132,12 @@ impl<K, V> SallyColumn<K, V> {
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyWriteBatch::TestDB(test_db.batch()),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyWriteBatch::Redb(redb_map.batch()),
         }
     }
 }
@@ -149,6 +167,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.contains_key(key),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.contains_key(key),
         }
     }
     async fn get(&self, key: &K) -> Result<Option<V>, TypedStoreError> {
@@ -165,6 +189,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.get(key),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.get(key),
         }
     }
     async fn get_raw_bytes(&self, key: &K) -> Result<Option<Vec<u8>>, TypedStoreError> {
@@ -181,6 +211,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.get_raw_bytes(key),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.get_raw_bytes(key),
         }
     }
     async fn is_empty(&self) -> bool {
@@ -197,6 +233,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.is_empty(),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.is_empty(),
         }
     }
     async fn iter(&'a self) -> Self::Iterator {
@@ -213,6 +255,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyIter::TestDB(test_db.safe_iter()),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyIter::Redb(redb_map.safe_iter()),
         }
     }
     async fn keys(&'a self) -> Self::Keys {
@@ -229,6 +277,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyKeys::TestDB(test_db.keys()),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyKeys::Redb(redb_map.keys()),
         }
     }
     async fn values(&'a self) -> Self::Values {
@@ -245,6 +299,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => SallyValues::TestDB(test_db.values()),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => SallyValues::Redb(redb_map.values()),
         }
     }
     async fn multi_get<J>(
@@ -267,6 +327,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.multi_get(keys),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.multi_get(keys),
         }
     }
     async fn try_catch_up_with_primary(&self) -> Result<(), Self::Error> {
@@ -283,6 +349,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => Ok(test_db.try_catch_up_with_primary()?),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => Ok(redb_map.try_catch_up_with_primary()?),
         }
     }
 }
@@ -313,6 +385,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.try_extend(iter),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.try_extend(iter),
         }
     }
     fn try_extend_from_slice(&mut self, slice: &[(J, U)]) -> Result<(), Self::Error> {
@@ -329,6 +407,12 @@ where
                     mode: SallyRunMode::FallbackToDB,
                 },
             )) => test_db.try_extend_from_slice(slice),
+            SallyColumn::Redb((
+                redb_map,
+                SallyConfig {
+                    mode: SallyRunMode::FallbackToDB,
+                },
+            )) => redb_map.try_extend_from_slice(slice),
         }
     }
 }
@@ -341,6 +425,8 @@ pub enum SallyWriteBatch {
     RocksDB(DBBatch),
     // Write batch for btree map based backend
     TestDB(TestDBWriteBatch),
+    // Write batch for redb backend, committed in a single redb write transaction
+    Redb(RedbWriteBatch),
 }
 
 impl SallyWriteBatch {
@@ -348,6 +434,7 @@ impl SallyWriteBatch {
         match self {
             SallyWriteBatch::RocksDB(db_batch) => db_batch.write(),
             SallyWriteBatch::TestDB(write_batch) => write_batch.write(),
+            SallyWriteBatch::Redb(write_batch) => write_batch.write(),
         }
     }
     /// Deletes a set of keys given as an iterator
@@ -363,6 +450,9 @@ impl SallyWriteBatch {
             (SallyWriteBatch::TestDB(write_batch), SallyColumn::TestDB((test_db, _))) => {
                 write_batch.delete_batch(test_db, purged_vals)
             }
+            (SallyWriteBatch::Redb(write_batch), SallyColumn::Redb((redb_map, _))) => {
+                write_batch.delete_batch(redb_map, purged_vals)
+            }
             _ => unimplemented!(),
         }
     }
@@ -380,6 +470,9 @@ impl SallyWriteBatch {
             (SallyWriteBatch::TestDB(write_batch), SallyColumn::TestDB((test_db, _))) => {
                 write_batch.delete_range(test_db, from, to)
             }
+            (SallyWriteBatch::Redb(write_batch), SallyColumn::Redb((redb_map, _))) => {
+                write_batch.delete_range(redb_map, from, to)
+            }
             _ => unimplemented!(),
         }
     }
@@ -398,6 +491,10 @@ impl SallyWriteBatch {
                 write_batch.insert_batch(test_db, new_vals)?;
                 Ok(())
             }
+            (SallyWriteBatch::Redb(write_batch), SallyColumn::Redb((redb_map, _))) => {
+                write_batch.insert_batch(redb_map, new_vals)?;
+                Ok(())
+            }
             _ => unimplemented!(),
         }
     }
@@ -408,6 +505,7 @@ pub enum SallyIter<'a, K, V> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(RocksDBIter<'a, K, V>),
     TestDB(TestDBIter<'a, K, V>),
+    Redb(RedbSafeIter<K, V>),
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyIter<'a, K, V> {
@@ -416,6 +514,7 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyIter<'a, K,
         match self {
             SallyIter::RocksDB(iter) => iter.next(),
             SallyIter::TestDB(iter) => iter.next(),
+            SallyIter::Redb(iter) => iter.next(),
         }
     }
 }
@@ -428,6 +527,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         let iter = match self {
             SallyIter::RocksDB(iter) => SallyIter::RocksDB(iter.skip_to(key)?),
             SallyIter::TestDB(iter) => SallyIter::TestDB(iter.skip_to(key)?),
+            SallyIter::Redb(iter) => SallyIter::Redb(iter.skip_to(key)?),
         };
         Ok(iter)
     }
@@ -439,6 +539,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         let iter = match self {
             SallyIter::RocksDB(iter) => SallyIter::RocksDB(iter.skip_prior_to(key)?),
             SallyIter::TestDB(iter) => SallyIter::TestDB(iter.skip_prior_to(key)?),
+            SallyIter::Redb(iter) => SallyIter::Redb(iter.skip_prior_to(key)?),
         };
         Ok(iter)
     }
@@ -448,6 +549,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         match self {
             SallyIter::RocksDB(iter) => SallyIter::RocksDB(iter.skip_to_last()),
             SallyIter::TestDB(iter) => SallyIter::TestDB(iter.skip_to_last()),
+            SallyIter::Redb(iter) => SallyIter::Redb(iter.skip_to_last()),
         }
     }
 
@@ -458,6 +560,7 @@ impl<'a, K: Serialize, V> SallyIter<'a, K, V> {
         match self {
             SallyIter::RocksDB(iter) => SallyRevIter::RocksDB(iter.reverse()),
             SallyIter::TestDB(iter) => SallyRevIter::TestDB(iter.reverse()),
+            SallyIter::Redb(iter) => SallyRevIter::Redb(iter.reverse()),
         }
     }
 }
@@ -466,6 +569,7 @@ pub enum SallyRevIter<'a, K, V> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(SafeRevIter<'a, K, V>),
     TestDB(TestDBRevIter<'a, K, V>),
+    Redb(RedbSafeRevIter<K, V>),
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyRevIter<'a, K, V> {
@@ -476,6 +580,7 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SallyRevIter<'a,
         match self {
             SallyRevIter::RocksDB(rev_iter) => rev_iter.next(),
             SallyRevIter::TestDB(rev_iter) => rev_iter.next(),
+            SallyRevIter::Redb(rev_iter) => rev_iter.next(),
         }
     }
 }
@@ -485,6 +590,7 @@ pub enum SallyKeys<'a, K> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(Keys<'a, K>),
     TestDB(TestDBKeys<'a, K>),
+    Redb(RedbKeys<K>),
 }
 
 impl<'a, K: DeserializeOwned> Iterator for SallyKeys<'a, K> {
@@ -494,6 +600,7 @@ impl<'a, K: DeserializeOwned> Iterator for SallyKeys<'a, K> {
         match self {
             SallyKeys::RocksDB(keys) => keys.next(),
             SallyKeys::TestDB(iter) => iter.next(),
+            SallyKeys::Redb(iter) => iter.next(),
         }
     }
 }
@@ -503,6 +610,7 @@ pub enum SallyValues<'a, V> {
     // Iter for a rocksdb backed sally column when `fallback_to_db` is true
     RocksDB(Values<'a, V>),
     TestDB(TestDBValues<'a, V>),
+    Redb(RedbValues<V>),
 }
 
 impl<'a, V: DeserializeOwned> Iterator for SallyValues<'a, V> {
@@ -512,6 +620,7 @@ impl<'a, V: DeserializeOwned> Iterator for SallyValues<'a, V> {
         match self {
             SallyValues::RocksDB(values) => values.next(),
             SallyValues::TestDB(iter) => iter.next(),
+            SallyValues::Redb(iter) => iter.next(),
         }
     }
 }
@@ -529,6 +638,8 @@ pub enum SallyDBOptions {
         ),
     ),
     TestDB,
+    // Options when sally db instance is backed by a redb database in the given directory
+    Redb(PathBuf),
 }
 
 /// Options to configure a sally db instance for performing read only operations at the global level
@@ -536,6 +647,9 @@ pub enum SallyReadOnlyDBOptions {
     // Options when sally db instance is backed by a single rocksdb instance
     RocksDB(Box<(PathBuf, MetricConf, Option<PathBuf>, Option<Options>)>),
     TestDB,
+    // redb has no secondary mode: the database is opened directly, so no other process may have
+    // it open at the same time
+    Redb(PathBuf),
 }
 
 /// Options to configure an individual column in a sally db instance
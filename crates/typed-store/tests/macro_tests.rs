@@ -18,6 +18,7 @@ use typed_store::rocks::{be_fix_int_ser, MetricConf};
 use typed_store::sally::SallyColumn;
 use typed_store::sally::SallyDBOptions;
 use typed_store::sally::SallyReadOnlyDBOptions;
+use typed_store::traits::AsyncMap;
 use typed_store::traits::Map;
 use typed_store::traits::TableSummary;
 use typed_store::traits::TypedStoreDebug;
@@ -315,6 +316,50 @@ async fn test_sallydb() {
     assert_eq!(format!("\"8\""), *m.get(&"\"8\"".to_string()).unwrap());
 }
 
+#[tokio::test]
+async fn test_sallydb_redb() {
+    let primary_path = temp_dir();
+    let example_db = SallyDBExample::init(SallyDBOptions::Redb(primary_path.clone()));
+
+    // Write to both columns atomically
+    let keys_vals_1 = (1..10).map(|i| (i.to_string(), i.to_string()));
+    let mut wb = example_db.col1.batch();
+    wb.insert_batch(&example_db.col1, keys_vals_1.clone())
+        .expect("Failed to insert");
+    let keys_vals_2 = (3..10).map(|i| (i, i.to_string()));
+    wb.insert_batch(&example_db.col2, keys_vals_2.clone())
+        .expect("Failed to insert");
+    wb.delete_range(&example_db.col2, &8, &10)
+        .expect("Failed to delete range");
+    wb.write().await.expect("Failed to commit write batch");
+
+    assert_eq!(
+        AsyncMap::get(&example_db.col1, &"1".to_string()).await,
+        Ok(Some("1".to_string()))
+    );
+    assert_eq!(AsyncMap::get(&example_db.col2, &8).await, Ok(None));
+
+    // redb allows a single open instance, so close the primary before the read only handle
+    drop(example_db);
+    let example_db_read_only =
+        SallyDBExample::get_read_only_handle(SallyReadOnlyDBOptions::Redb(primary_path));
+
+    assert_eq!(9, example_db_read_only.count_keys("col1").unwrap());
+    assert_eq!(5, example_db_read_only.count_keys("col2").unwrap());
+
+    let m = example_db_read_only.dump("col1", 100, 0).unwrap();
+    for (k, v) in keys_vals_1 {
+        assert_eq!(format!("\"{v}\""), *m.get(&format!("\"{k}\"")).unwrap());
+    }
+    let m = example_db_read_only.dump("col2", 3, 1).unwrap();
+    assert_eq!(2, m.len());
+    assert_eq!("\"6\"", *m.get("6").unwrap());
+    assert_eq!("\"7\"", *m.get("7").unwrap());
+
+    let summary = example_db_read_only.table_summary("col2").unwrap();
+    assert_eq!(5, summary.num_keys);
+}
+
 #[tokio::test]
 async fn macro_transactional_test() {
     let key = "key".to_string();